@@ -296,6 +296,8 @@ fn create_benchmark_athlete() -> models::AthleteProfile {
         preferred_units: models::Units::Metric,
         created_at: Utc::now(),
         updated_at: Utc::now(),
+        rowing_ftp: None,
+        css_pace: None,
     }
 }
 
@@ -366,6 +368,11 @@ fn create_simple_workout(sport: models::Sport, duration: u32, seed: usize) -> mo
         notes: Some(format!("Benchmark Workout {}", seed)),
         source: None,
         raw_data: None,
+        swim_lengths: Vec::new(),
+        start_time: None,
+        laps: Vec::new(),
+        rpe: None,
+        strength_sets: Vec::new(),
     }
 }
 
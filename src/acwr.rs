@@ -0,0 +1,538 @@
+#![allow(dead_code)]
+
+//! Acute:Chronic Workload Ratio (ACWR) and Foster monotony/strain
+//!
+//! Computes workload ratios from daily TSS so that `TrainingDecision::assess`
+//! and `OvertrainingRisk` can be fed from workout history instead of hand-supplied
+//! numbers.
+//!
+//! # Variants
+//!
+//! - **Rolling coupled**: 7-day average divided by a 28-day average that includes
+//!   the acute week (Hulin et al., 2014).
+//! - **Rolling uncoupled**: the chronic average excludes the acute week (days 8-28),
+//!   which removes the mathematical coupling between numerator and denominator
+//!   (Lolli et al., 2019).
+//! - **EWMA**: exponentially weighted averages with decay `2 / (N + 1)`
+//!   (Williams et al., 2017). Responds to load changes without the hard window edge.
+//!
+//! # Monotony and Strain (Foster, 1998)
+//!
+//! - **Monotony** = mean daily load over 7 days / standard deviation of that load
+//! - **Strain** = weekly load × monotony
+//!
+//! Monotony above 2.0 combined with high strain is associated with illness and
+//! overreaching.
+
+use crate::pmc::DailyTss;
+use chrono::NaiveDate;
+use rust_decimal::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use thiserror::Error;
+
+/// ACWR calculation errors
+#[derive(Error, Debug)]
+pub enum AcwrError {
+    #[error("Invalid date range: {0}")]
+    InvalidDateRange(String),
+    #[error("Configuration error: {0}")]
+    ConfigurationError(String),
+}
+
+/// ACWR calculation method
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AcwrMethod {
+    /// Rolling averages, chronic window includes the acute window
+    RollingCoupled,
+    /// Rolling averages, chronic window excludes the acute window
+    RollingUncoupled,
+    /// Exponentially weighted moving averages
+    Ewma,
+}
+
+/// ACWR risk zone
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AcwrZone {
+    /// Below the lower danger threshold (default < 0.8) - detraining, spike exposure on return
+    Undertraining,
+    /// Between thresholds (default 0.8-1.3) - "sweet spot"
+    Optimal,
+    /// Elevated but not dangerous (default 1.3-1.5)
+    Caution,
+    /// Above the upper danger threshold (default > 1.5) - injury risk
+    Danger,
+}
+
+impl AcwrZone {
+    /// Classify a ratio using the configured thresholds
+    pub fn from_ratio(ratio: f64, config: &AcwrConfig) -> Self {
+        if ratio > config.danger_high {
+            AcwrZone::Danger
+        } else if ratio > config.caution_high {
+            AcwrZone::Caution
+        } else if ratio < config.danger_low {
+            AcwrZone::Undertraining
+        } else {
+            AcwrZone::Optimal
+        }
+    }
+
+    /// True for zones that should be surfaced as warnings
+    pub fn is_danger(&self) -> bool {
+        matches!(self, AcwrZone::Danger | AcwrZone::Undertraining)
+    }
+
+    /// Get zone description
+    pub fn description(&self) -> &'static str {
+        match self {
+            AcwrZone::Undertraining => "Undertraining (load well below chronic level)",
+            AcwrZone::Optimal => "Optimal loading",
+            AcwrZone::Caution => "Elevated load (monitor closely)",
+            AcwrZone::Danger => "Danger zone (acute load spike)",
+        }
+    }
+}
+
+/// ACWR configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AcwrConfig {
+    /// Acute window in days (default: 7)
+    pub acute_days: u16,
+
+    /// Chronic window in days (default: 28)
+    pub chronic_days: u16,
+
+    /// Method used for the headline ratio and zone classification
+    pub method: AcwrMethod,
+
+    /// Upper danger threshold (default: 1.5)
+    pub danger_high: f64,
+
+    /// Upper bound of the optimal range (default: 1.3)
+    pub caution_high: f64,
+
+    /// Lower danger threshold (default: 0.8)
+    pub danger_low: f64,
+}
+
+impl Default for AcwrConfig {
+    fn default() -> Self {
+        AcwrConfig {
+            acute_days: 7,
+            chronic_days: 28,
+            method: AcwrMethod::Ewma,
+            danger_high: 1.5,
+            caution_high: 1.3,
+            danger_low: 0.8,
+        }
+    }
+}
+
+/// Workload ratios and Foster metrics for a single day
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AcwrMetrics {
+    /// Date these metrics are calculated for
+    pub date: NaiveDate,
+
+    /// Daily load (TSS) on this date
+    pub daily_load: f64,
+
+    /// Rolling average load over the acute window
+    pub acute_load: f64,
+
+    /// Rolling average load over the chronic window
+    pub chronic_load: f64,
+
+    /// Rolling ACWR, chronic window includes the acute window
+    pub rolling_coupled: Option<f64>,
+
+    /// Rolling ACWR, chronic window excludes the acute window
+    pub rolling_uncoupled: Option<f64>,
+
+    /// EWMA-based ACWR
+    pub ewma: Option<f64>,
+
+    /// Foster monotony over the acute window (mean / SD)
+    pub monotony: Option<f64>,
+
+    /// Foster strain over the acute window (weekly load × monotony)
+    pub strain: Option<f64>,
+
+    /// Zone of the ratio selected by `AcwrConfig::method`
+    pub zone: Option<AcwrZone>,
+}
+
+impl AcwrMetrics {
+    /// Get the ratio for a specific method
+    pub fn ratio(&self, method: AcwrMethod) -> Option<f64> {
+        match method {
+            AcwrMethod::RollingCoupled => self.rolling_coupled,
+            AcwrMethod::RollingUncoupled => self.rolling_uncoupled,
+            AcwrMethod::Ewma => self.ewma,
+        }
+    }
+}
+
+/// ACWR calculation engine
+pub struct AcwrCalculator {
+    config: AcwrConfig,
+}
+
+impl AcwrCalculator {
+    /// Create new ACWR calculator with default configuration
+    pub fn new() -> Self {
+        AcwrCalculator {
+            config: AcwrConfig::default(),
+        }
+    }
+
+    /// Create new ACWR calculator with custom configuration
+    pub fn with_config(config: AcwrConfig) -> Result<Self, AcwrError> {
+        if config.acute_days == 0 || config.chronic_days <= config.acute_days {
+            return Err(AcwrError::ConfigurationError(
+                "Chronic window must be longer than a non-empty acute window".to_string(),
+            ));
+        }
+        Ok(AcwrCalculator { config })
+    }
+
+    /// Get the active configuration
+    pub fn config(&self) -> &AcwrConfig {
+        &self.config
+    }
+
+    /// Calculate ACWR metrics for a date range
+    ///
+    /// Days before `start_date` are used to warm up the rolling windows and EWMAs,
+    /// in the same way `PmcCalculator::calculate_pmc_series` seeds CTL.
+    pub fn calculate_series(
+        &self,
+        daily_tss: &BTreeMap<NaiveDate, DailyTss>,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Result<Vec<AcwrMetrics>, AcwrError> {
+        if start_date > end_date {
+            return Err(AcwrError::InvalidDateRange(
+                "Start date must be before end date".to_string(),
+            ));
+        }
+
+        let acute_days = self.config.acute_days as usize;
+        let chronic_days = self.config.chronic_days as usize;
+        let acute_lambda = 2.0 / (acute_days as f64 + 1.0);
+        let chronic_lambda = 2.0 / (chronic_days as f64 + 1.0);
+
+        // Three chronic windows of warm-up leaves <1% residual weight on the zero seed
+        let calculation_start = start_date
+            .checked_sub_days(chrono::Days::new(chronic_days as u64 * 3))
+            .unwrap_or(start_date);
+
+        let mut loads: Vec<f64> = Vec::new();
+        let mut acute_ewma = 0.0;
+        let mut chronic_ewma = 0.0;
+        let mut series = Vec::new();
+        let mut current_date = calculation_start;
+
+        while current_date <= end_date {
+            let load = daily_tss
+                .get(&current_date)
+                .and_then(|d| d.total_tss.to_f64())
+                .unwrap_or(0.0);
+            loads.push(load);
+
+            acute_ewma = load * acute_lambda + (1.0 - acute_lambda) * acute_ewma;
+            chronic_ewma = load * chronic_lambda + (1.0 - chronic_lambda) * chronic_ewma;
+
+            if current_date >= start_date {
+                let acute_window = Self::tail(&loads, acute_days);
+                let chronic_window = Self::tail(&loads, chronic_days);
+                let acute_load = Self::mean(acute_window);
+                let chronic_load = Self::mean(chronic_window);
+
+                let uncoupled_window =
+                    &chronic_window[..chronic_window.len() - acute_window.len()];
+                let rolling_coupled = Self::ratio(acute_load, chronic_load);
+                let rolling_uncoupled = Self::ratio(acute_load, Self::mean(uncoupled_window));
+                let ewma = Self::ratio(acute_ewma, chronic_ewma);
+
+                let (monotony, strain) = if acute_window.len() == acute_days {
+                    Self::foster(acute_window)
+                } else {
+                    (None, None)
+                };
+
+                let mut metrics = AcwrMetrics {
+                    date: current_date,
+                    daily_load: load,
+                    acute_load,
+                    chronic_load,
+                    rolling_coupled,
+                    rolling_uncoupled,
+                    ewma,
+                    monotony,
+                    strain,
+                    zone: None,
+                };
+                metrics.zone = metrics
+                    .ratio(self.config.method)
+                    .map(|r| AcwrZone::from_ratio(r, &self.config));
+
+                series.push(metrics);
+            }
+
+            current_date = current_date.succ_opt().unwrap();
+        }
+
+        Ok(series)
+    }
+
+    /// Get the ACWR metrics for a single date (last day of the series)
+    pub fn latest(
+        &self,
+        daily_tss: &BTreeMap<NaiveDate, DailyTss>,
+        as_of_date: NaiveDate,
+    ) -> Result<Option<AcwrMetrics>, AcwrError> {
+        Ok(self
+            .calculate_series(daily_tss, as_of_date, as_of_date)?
+            .into_iter()
+            .last())
+    }
+
+    /// Collect warnings for days in the danger zones
+    ///
+    /// Consecutive days in the same zone are reported once, with the first date and
+    /// the peak (or trough) ratio of the run.
+    pub fn danger_warnings(&self, series: &[AcwrMetrics]) -> Vec<AcwrWarning> {
+        let mut warnings: Vec<AcwrWarning> = Vec::new();
+        let mut previous_zone: Option<AcwrZone> = None;
+
+        for metrics in series {
+            let (zone, ratio) = match (metrics.zone, metrics.ratio(self.config.method)) {
+                (Some(zone), Some(ratio)) if zone.is_danger() => (zone, ratio),
+                _ => {
+                    previous_zone = None;
+                    continue;
+                }
+            };
+
+            if previous_zone == Some(zone) {
+                if let Some(last) = warnings.last_mut() {
+                    last.days += 1;
+                    let worse = match zone {
+                        AcwrZone::Danger => ratio > last.ratio,
+                        _ => ratio < last.ratio,
+                    };
+                    if worse {
+                        last.ratio = ratio;
+                    }
+                }
+            } else {
+                warnings.push(AcwrWarning {
+                    start_date: metrics.date,
+                    days: 1,
+                    zone,
+                    ratio,
+                });
+            }
+            previous_zone = Some(zone);
+        }
+
+        warnings
+    }
+
+    fn tail(values: &[f64], n: usize) -> &[f64] {
+        &values[values.len().saturating_sub(n)..]
+    }
+
+    fn mean(values: &[f64]) -> f64 {
+        if values.is_empty() {
+            0.0
+        } else {
+            values.iter().sum::<f64>() / values.len() as f64
+        }
+    }
+
+    fn ratio(acute: f64, chronic: f64) -> Option<f64> {
+        if chronic > 0.0 {
+            Some(acute / chronic)
+        } else {
+            None
+        }
+    }
+
+    /// Foster monotony and strain for a window of daily loads
+    fn foster(window: &[f64]) -> (Option<f64>, Option<f64>) {
+        let mean = Self::mean(window);
+        let variance =
+            window.iter().map(|l| (l - mean).powi(2)).sum::<f64>() / window.len() as f64;
+        let sd = variance.sqrt();
+
+        if sd <= f64::EPSILON {
+            // Identical loads every day: monotony is undefined (infinite)
+            return (None, None);
+        }
+
+        let monotony = mean / sd;
+        let weekly_load: f64 = window.iter().sum();
+        (Some(monotony), Some(weekly_load * monotony))
+    }
+}
+
+impl Default for AcwrCalculator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A run of consecutive days in an ACWR danger zone
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AcwrWarning {
+    /// First day of the run
+    pub start_date: NaiveDate,
+
+    /// Number of consecutive days in the zone
+    pub days: u16,
+
+    /// Zone the run belongs to
+    pub zone: AcwrZone,
+
+    /// Peak ratio for danger runs, lowest ratio for undertraining runs
+    pub ratio: f64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::Decimal;
+
+    fn daily(date: NaiveDate, tss: i64) -> DailyTss {
+        DailyTss {
+            date,
+            total_tss: Decimal::from(tss),
+            workout_count: 1,
+            has_workouts: tss > 0,
+            workout_tss_values: vec![Decimal::from(tss)],
+        }
+    }
+
+    /// Build `days` of history ending on `end`, using `load(day_index)` for TSS
+    fn history(end: NaiveDate, days: i64, load: impl Fn(i64) -> i64) -> BTreeMap<NaiveDate, DailyTss> {
+        (0..days)
+            .map(|i| {
+                let date = end - chrono::Duration::days(days - 1 - i);
+                (date, daily(date, load(i)))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_steady_load_gives_ratio_of_one() {
+        let end = NaiveDate::from_ymd_opt(2024, 9, 30).unwrap();
+        let tss = history(end, 60, |_| 80);
+        let calculator = AcwrCalculator::new();
+
+        let metrics = calculator.latest(&tss, end).unwrap().unwrap();
+
+        assert!((metrics.rolling_coupled.unwrap() - 1.0).abs() < 1e-9);
+        assert!((metrics.rolling_uncoupled.unwrap() - 1.0).abs() < 1e-9);
+        assert!((metrics.ewma.unwrap() - 1.0).abs() < 0.05);
+        assert_eq!(metrics.zone, Some(AcwrZone::Optimal));
+        // No variation in load - monotony undefined
+        assert!(metrics.monotony.is_none());
+    }
+
+    #[test]
+    fn test_load_spike_enters_danger_zone() {
+        let end = NaiveDate::from_ymd_opt(2024, 9, 30).unwrap();
+        // 50 TSS/day, then a week at 150 TSS/day
+        let tss = history(end, 60, |i| if i >= 53 { 150 } else { 50 });
+        let calculator = AcwrCalculator::new();
+
+        let metrics = calculator.latest(&tss, end).unwrap().unwrap();
+
+        // Coupled: 150 / ((21 * 50 + 7 * 150) / 28) = 2.0
+        assert!((metrics.rolling_coupled.unwrap() - 2.0).abs() < 1e-9);
+        // Uncoupled: 150 / 50 = 3.0
+        assert!((metrics.rolling_uncoupled.unwrap() - 3.0).abs() < 1e-9);
+        assert!(metrics.ewma.unwrap() > 1.5);
+        assert_eq!(metrics.zone, Some(AcwrZone::Danger));
+    }
+
+    #[test]
+    fn test_detraining_enters_undertraining_zone() {
+        let end = NaiveDate::from_ymd_opt(2024, 9, 30).unwrap();
+        let tss = history(end, 60, |i| if i >= 53 { 10 } else { 100 });
+        let calculator = AcwrCalculator::new();
+
+        let metrics = calculator.latest(&tss, end).unwrap().unwrap();
+
+        assert!(metrics.ewma.unwrap() < 0.8);
+        assert_eq!(metrics.zone, Some(AcwrZone::Undertraining));
+    }
+
+    #[test]
+    fn test_no_history_has_no_ratio() {
+        let end = NaiveDate::from_ymd_opt(2024, 9, 30).unwrap();
+        let calculator = AcwrCalculator::new();
+
+        // With no load at all there is no chronic base to compare against
+        let empty = BTreeMap::new();
+        let metrics = calculator.latest(&empty, end).unwrap().unwrap();
+        assert!(metrics.rolling_coupled.is_none());
+        assert!(metrics.zone.is_none());
+    }
+
+    #[test]
+    fn test_foster_monotony_and_strain() {
+        let end = NaiveDate::from_ymd_opt(2024, 9, 30).unwrap();
+        // Alternating hard/easy days over the acute week
+        let tss = history(end, 35, |i| if i % 2 == 0 { 100 } else { 50 });
+        let calculator = AcwrCalculator::new();
+
+        let metrics = calculator.latest(&tss, end).unwrap().unwrap();
+
+        let window = [100.0, 50.0, 100.0, 50.0, 100.0, 50.0, 100.0];
+        let mean = window.iter().sum::<f64>() / 7.0;
+        let sd = (window.iter().map(|l| (l - mean).powi(2)).sum::<f64>() / 7.0).sqrt();
+
+        assert!((metrics.monotony.unwrap() - mean / sd).abs() < 1e-9);
+        assert!((metrics.strain.unwrap() - 550.0 * mean / sd).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_danger_warnings_group_consecutive_days() {
+        let end = NaiveDate::from_ymd_opt(2024, 9, 30).unwrap();
+        let tss = history(end, 60, |i| if i >= 53 { 150 } else { 50 });
+        let calculator = AcwrCalculator::with_config(AcwrConfig {
+            method: AcwrMethod::RollingCoupled,
+            ..AcwrConfig::default()
+        })
+        .unwrap();
+
+        let start = end - chrono::Duration::days(13);
+        let series = calculator.calculate_series(&tss, start, end).unwrap();
+        let warnings = calculator.danger_warnings(&series);
+
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].zone, AcwrZone::Danger);
+        assert!((warnings[0].ratio - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_invalid_config_and_range() {
+        assert!(AcwrCalculator::with_config(AcwrConfig {
+            acute_days: 28,
+            chronic_days: 7,
+            ..AcwrConfig::default()
+        })
+        .is_err());
+
+        let calculator = AcwrCalculator::new();
+        let start = NaiveDate::from_ymd_opt(2024, 9, 30).unwrap();
+        let end = NaiveDate::from_ymd_opt(2024, 9, 1).unwrap();
+        assert!(calculator
+            .calculate_series(&BTreeMap::new(), start, end)
+            .is_err());
+    }
+}
@@ -1,12 +1,12 @@
 use anyhow::{Context, Result};
-use chrono::{DateTime, NaiveDate, Utc};
+use chrono::{DateTime, FixedOffset, NaiveDate, Utc};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
-use crate::models::{Sport, Units};
+use crate::models::{LocalTimezone, Sport, Units};
 use crate::pmc::PmcConfig;
 
 /// Main application configuration
@@ -95,6 +95,10 @@ pub struct ZoneSettings {
     pub custom_hr_zones: Option<Vec<Decimal>>,
     pub custom_power_zones: Option<Vec<Decimal>>,
     pub custom_pace_zones: Option<Vec<Decimal>>,
+
+    /// User-defined zone models, selectable per sport by ID
+    #[serde(default)]
+    pub custom_models: Vec<crate::zones::ZoneModel>,
 }
 
 /// Heart rate zone calculation methods
@@ -239,6 +243,12 @@ pub struct AthleteProfile {
     /// Threshold pace for running (minutes per km) - default sport
     pub threshold_pace: Option<Decimal>,
 
+    /// Local timezone, an IANA name (e.g. "Europe/Berlin") or a UTC offset
+    /// (e.g. "+01:00"); training days are counted in it. Without one, each
+    /// workout's recorded local time is used.
+    #[serde(default)]
+    pub timezone: Option<String>,
+
     /// Creation timestamp
     pub created_at: DateTime<Utc>,
 
@@ -330,8 +340,8 @@ pub struct ThresholdChange {
     /// Date of the change
     pub date: NaiveDate,
 
-    /// Sport this change applies to
-    pub sport: Sport,
+    /// Sport profile this change applies to; `None` for athlete-wide thresholds
+    pub sport: Option<Sport>,
 
     /// Type of threshold that changed
     pub threshold_type: ThresholdType,
@@ -388,6 +398,85 @@ pub enum ThresholdSource {
     Import,
 }
 
+/// Threshold values and zone model selection, athlete-wide or for one sport
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ThresholdValues {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ftp: Option<u16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lthr: Option<u16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_hr: Option<u16>,
+    /// Minutes per km
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub threshold_pace: Option<Decimal>,
+    /// Minutes per 100m
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub threshold_swim_pace: Option<Decimal>,
+    /// Zone model IDs, see `ZoneModel::find`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub zone_models: Vec<String>,
+}
+
+impl ThresholdValues {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Thresholds of one sport in a zone file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SportThresholds {
+    pub sport: Sport,
+    #[serde(flatten)]
+    pub values: ThresholdValues,
+}
+
+/// Thresholds and zone models exchanged by `zones import` and `zones export`
+///
+/// ```toml
+/// effective_date = "2025-03-01"
+/// ftp = 265
+/// lthr = 168
+///
+/// [[sports]]
+/// sport = "Running"
+/// threshold_pace = 4.5
+/// zone_models = ["friel-hr", "friel-run-pace"]
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ZoneFile {
+    /// Date the thresholds took effect; defaults to the import date
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub effective_date: Option<NaiveDate>,
+    #[serde(flatten)]
+    pub athlete: ThresholdValues,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sports: Vec<SportThresholds>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub custom_models: Vec<crate::zones::ZoneModel>,
+}
+
+impl ZoneFile {
+    /// Parse TOML or JSON, chosen by the file extension
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        match path.extension().and_then(|e| e.to_str()).map(str::to_lowercase).as_deref() {
+            Some("json") => serde_json::from_str(&content).with_context(|| format!("Invalid zone file {}", path.display())),
+            _ => toml::from_str(&content).with_context(|| format!("Invalid zone file {}", path.display())),
+        }
+    }
+
+    /// Write TOML or JSON, chosen by the file extension
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let content = match path.extension().and_then(|e| e.to_str()).map(str::to_lowercase).as_deref() {
+            Some("json") => serde_json::to_string_pretty(self)?,
+            _ => toml::to_string_pretty(self)?,
+        };
+        fs::write(path, content).with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
+
 impl Default for AppConfig {
     fn default() -> Self {
         let now = Utc::now();
@@ -439,6 +528,7 @@ impl Default for ZoneSettings {
             custom_hr_zones: None,
             custom_power_zones: None,
             custom_pace_zones: None,
+            custom_models: Vec::new(),
         }
     }
 }
@@ -612,6 +702,27 @@ impl AppConfig {
     }
 }
 
+impl SportProfile {
+    /// Empty profile that inherits every threshold from the athlete
+    pub fn new(sport: Sport) -> Self {
+        SportProfile {
+            sport,
+            ftp: None,
+            lthr: None,
+            threshold_pace: None,
+            threshold_swim_pace: None,
+            critical_power: None,
+            awc: None,
+            zones: None,
+            last_test_date: None,
+            max_hr: None,
+            zone_method: None,
+            last_updated: Utc::now(),
+            notes: None,
+        }
+    }
+}
+
 #[allow(dead_code)]
 impl AthleteConfig {
     /// Create a new athlete configuration
@@ -633,6 +744,7 @@ impl AthleteConfig {
                 ftp: None,
                 lthr: None,
                 threshold_pace: None,
+                timezone: None,
                 created_at: now,
                 updated_at: now,
                 active: true,
@@ -666,15 +778,323 @@ impl AthleteConfig {
         self.profile.updated_at = Utc::now();
     }
 
+    /// Convert to the calculation-level athlete profile used by the analyzers
+    pub fn to_athlete_profile(&self) -> crate::models::AthleteProfile {
+        crate::models::AthleteProfile {
+            id: self.id.clone(),
+            name: self.profile.name.clone(),
+            date_of_birth: self.profile.date_of_birth,
+            weight: self.profile.weight,
+            height: self.profile.height,
+            ftp: self.profile.ftp,
+            lthr: self.profile.lthr,
+            threshold_pace: self.profile.threshold_pace,
+            rowing_ftp: self.get_sport_profile(&Sport::Rowing).and_then(|p| p.ftp),
+            css_pace: self.get_sport_profile(&Sport::Swimming).and_then(|p| p.threshold_swim_pace),
+            max_hr: self.profile.max_hr,
+            resting_hr: self.profile.resting_hr,
+            training_zones: crate::models::TrainingZones::default(),
+            preferred_units: self.profile.preferred_units.clone(),
+            created_at: self.profile.created_at,
+            updated_at: self.profile.updated_at,
+        }
+    }
+
+    /// Thresholds for zone calculations in a sport
+    ///
+    /// Sport profile values override the athlete-wide ones; swimming uses the
+    /// threshold swim pace and rowing only its own FTP.
+    pub fn zone_thresholds(&self, sport: &Sport) -> crate::zones::ZoneThresholds {
+        let sport_profile = self.get_sport_profile(sport);
+        let threshold_pace = match sport {
+            Sport::Swimming => sport_profile.and_then(|p| p.threshold_swim_pace),
+            _ => sport_profile.and_then(|p| p.threshold_pace).or(self.profile.threshold_pace),
+        };
+        let ftp = match sport {
+            Sport::Rowing => sport_profile.and_then(|p| p.ftp),
+            _ => sport_profile.and_then(|p| p.ftp).or(self.profile.ftp),
+        };
+
+        crate::zones::ZoneThresholds {
+            ftp,
+            lthr: sport_profile.and_then(|p| p.lthr).or(self.profile.lthr),
+            max_hr: sport_profile.and_then(|p| p.max_hr).or(self.profile.max_hr),
+            threshold_pace,
+        }
+    }
+
+    /// Apply new thresholds, recording a `ThresholdChange` for every value that changed
+    ///
+    /// With a `sport` the values go to that sport's profile, otherwise to the
+    /// athlete-wide profile. A change dated before a newer recorded change of
+    /// the same threshold is kept in the history without replacing the current
+    /// value. Returns the recorded changes.
+    pub fn apply_thresholds(
+        &mut self,
+        sport: Option<&Sport>,
+        values: &ThresholdValues,
+        effective_date: NaiveDate,
+        source: ThresholdSource,
+        notes: Option<String>,
+    ) -> Vec<ThresholdChange> {
+        let mut changes = Vec::new();
+
+        let updates: [(ThresholdType, Option<Decimal>); 5] = [
+            (ThresholdType::Ftp, values.ftp.map(Decimal::from)),
+            (ThresholdType::Lthr, values.lthr.map(Decimal::from)),
+            (ThresholdType::MaxHr, values.max_hr.map(Decimal::from)),
+            (ThresholdType::ThresholdPace, values.threshold_pace),
+            (ThresholdType::ThresholdSwimPace, values.threshold_swim_pace),
+        ];
+
+        for (threshold_type, new_value) in updates {
+            let Some(new_value) = new_value else { continue };
+
+            // A backdated change replaces the value in effect on its date and
+            // becomes the previous value of the change that followed it
+            let next_change = self
+                .threshold_history
+                .iter()
+                .enumerate()
+                .filter(|(_, c)| c.sport.as_ref() == sport && c.threshold_type == threshold_type && c.date > effective_date)
+                .min_by_key(|(_, c)| c.date)
+                .map(|(i, _)| i);
+            let old_value = match next_change {
+                Some(_) => self.threshold_value_on(sport, &threshold_type, effective_date),
+                None => self.threshold_value(sport, &threshold_type),
+            };
+            if old_value == Some(new_value) {
+                continue;
+            }
+            match next_change {
+                Some(i) => self.threshold_history[i].old_value = Some(new_value),
+                None => self.set_threshold_value(sport, &threshold_type, new_value),
+            }
+
+            changes.push(ThresholdChange {
+                date: effective_date,
+                sport: sport.cloned(),
+                threshold_type,
+                old_value,
+                new_value,
+                source: source.clone(),
+                notes: notes.clone(),
+            });
+        }
+
+        if !values.zone_models.is_empty() {
+            let profile = self.sport_profile_mut(sport.cloned().unwrap_or_else(|| self.primary_sport.clone()));
+            profile.zone_method = Some(values.zone_models.join(","));
+        }
+
+        for change in &changes {
+            self.add_threshold_change(change.clone());
+        }
+        changes
+    }
+
+    /// Value of a threshold in effect on a date according to the history
+    ///
+    /// Falls back to the current value when the history has no change of the
+    /// threshold.
+    pub fn threshold_value_on(&self, sport: Option<&Sport>, threshold_type: &ThresholdType, date: NaiveDate) -> Option<Decimal> {
+        let changes = || {
+            self.threshold_history
+                .iter()
+                .filter(move |c| c.sport.as_ref() == sport && c.threshold_type == *threshold_type)
+        };
+        if let Some(change) = changes().filter(|c| c.date <= date).max_by_key(|c| c.date) {
+            return Some(change.new_value);
+        }
+        match changes().filter(|c| c.date > date).min_by_key(|c| c.date) {
+            Some(change) => change.old_value,
+            None => self.threshold_value(sport, threshold_type),
+        }
+    }
+
+    /// Current value of a threshold, from the sport profile when given
+    pub fn threshold_value(&self, sport: Option<&Sport>, threshold_type: &ThresholdType) -> Option<Decimal> {
+        match sport {
+            Some(sport) => {
+                let profile = self.get_sport_profile(sport)?;
+                match threshold_type {
+                    ThresholdType::Ftp => profile.ftp.map(Decimal::from),
+                    ThresholdType::Lthr => profile.lthr.map(Decimal::from),
+                    ThresholdType::MaxHr => profile.max_hr.map(Decimal::from),
+                    ThresholdType::ThresholdPace => profile.threshold_pace,
+                    ThresholdType::ThresholdSwimPace => profile.threshold_swim_pace,
+                    ThresholdType::CriticalPower => profile.critical_power.map(Decimal::from),
+                    ThresholdType::Awc => profile.awc,
+                }
+            }
+            None => match threshold_type {
+                ThresholdType::Ftp => self.profile.ftp.map(Decimal::from),
+                ThresholdType::Lthr => self.profile.lthr.map(Decimal::from),
+                ThresholdType::MaxHr => self.profile.max_hr.map(Decimal::from),
+                ThresholdType::ThresholdPace => self.profile.threshold_pace,
+                ThresholdType::ThresholdSwimPace => self.get_sport_profile(&Sport::Swimming)?.threshold_swim_pace,
+                ThresholdType::CriticalPower | ThresholdType::Awc => None,
+            },
+        }
+    }
+
+    fn set_threshold_value(&mut self, sport: Option<&Sport>, threshold_type: &ThresholdType, value: Decimal) {
+        use rust_decimal::prelude::ToPrimitive;
+
+        match sport {
+            Some(sport) => {
+                let profile = self.sport_profile_mut(sport.clone());
+                match threshold_type {
+                    ThresholdType::Ftp => profile.ftp = value.to_u16(),
+                    ThresholdType::Lthr => profile.lthr = value.to_u16(),
+                    ThresholdType::MaxHr => profile.max_hr = value.to_u16(),
+                    ThresholdType::ThresholdPace => profile.threshold_pace = Some(value),
+                    ThresholdType::ThresholdSwimPace => profile.threshold_swim_pace = Some(value),
+                    ThresholdType::CriticalPower => profile.critical_power = value.to_u16(),
+                    ThresholdType::Awc => profile.awc = Some(value),
+                }
+                profile.last_updated = Utc::now();
+            }
+            None => match threshold_type {
+                ThresholdType::Ftp => self.profile.ftp = value.to_u16(),
+                ThresholdType::Lthr => self.profile.lthr = value.to_u16(),
+                ThresholdType::MaxHr => self.profile.max_hr = value.to_u16(),
+                ThresholdType::ThresholdPace => self.profile.threshold_pace = Some(value),
+                // Swim pace only exists per sport
+                ThresholdType::ThresholdSwimPace => self.sport_profile_mut(Sport::Swimming).threshold_swim_pace = Some(value),
+                ThresholdType::CriticalPower | ThresholdType::Awc => {}
+            },
+        }
+    }
+
+    /// Sport profile, created empty when missing
+    pub fn sport_profile_mut(&mut self, sport: Sport) -> &mut SportProfile {
+        self.sport_profiles.entry(sport.clone()).or_insert_with(|| SportProfile::new(sport))
+    }
+
+    /// Recalculate the stored zones of the primary sport and every sport profile
+    ///
+    /// Zones that cannot be calculated (missing thresholds) are cleared.
+    pub fn refresh_zones(&mut self, settings: &ZoneSettings) {
+        use crate::zones::{ZoneCalculator, ZoneMetric};
+
+        let primary = self.primary_sport.clone();
+        self.sport_profile_mut(primary);
+
+        let sports: Vec<Sport> = self.sport_profiles.keys().cloned().collect();
+        for sport in sports {
+            let thresholds = self.zone_thresholds(&sport);
+            let selection = self.sport_profiles[&sport].zone_method.clone();
+            let resolve = |metric| {
+                ZoneCalculator::zones_for_sport(&thresholds, &sport, metric, selection.as_deref(), settings)
+                    .ok()
+                    .map(|zones| zones.boundaries)
+            };
+            let zones = SportZones {
+                heart_rate: resolve(ZoneMetric::HeartRate),
+                power: resolve(ZoneMetric::Power),
+                pace: resolve(ZoneMetric::Pace),
+            };
+            self.sport_profile_mut(sport).zones = Some(zones);
+        }
+    }
+
+    /// Current thresholds and zone selections as a zone file
+    pub fn zone_file(&self) -> ZoneFile {
+        let mut sports: Vec<SportThresholds> = self
+            .sport_profiles
+            .values()
+            .map(|profile| SportThresholds {
+                sport: profile.sport.clone(),
+                values: ThresholdValues {
+                    ftp: profile.ftp,
+                    lthr: profile.lthr,
+                    max_hr: profile.max_hr,
+                    threshold_pace: profile.threshold_pace,
+                    threshold_swim_pace: profile.threshold_swim_pace,
+                    zone_models: profile
+                        .zone_method
+                        .iter()
+                        .flat_map(|m| m.split(','))
+                        .map(|id| id.trim().to_string())
+                        .filter(|id| !id.is_empty())
+                        .collect(),
+                },
+            })
+            .filter(|entry| !entry.values.is_empty())
+            .collect();
+        sports.sort_by_key(|entry| format!("{:?}", entry.sport));
+
+        ZoneFile {
+            effective_date: Some(Utc::now().date_naive()),
+            athlete: ThresholdValues {
+                ftp: self.profile.ftp,
+                lthr: self.profile.lthr,
+                max_hr: self.profile.max_hr,
+                threshold_pace: self.profile.threshold_pace,
+                threshold_swim_pace: None,
+                zone_models: Vec::new(),
+            },
+            sports,
+            custom_models: Vec::new(),
+        }
+    }
+
+    /// Timezone the athlete's training days are counted in
+    pub fn local_timezone(&self) -> Option<LocalTimezone> {
+        self.profile.timezone.as_deref().and_then(parse_timezone)
+    }
+
     /// Get threshold history for a specific sport and threshold type
-    pub fn get_threshold_history(&self, sport: &Sport, threshold_type: &ThresholdType) -> Vec<&ThresholdChange> {
+    pub fn get_threshold_history(&self, sport: Option<&Sport>, threshold_type: &ThresholdType) -> Vec<&ThresholdChange> {
         self.threshold_history
             .iter()
-            .filter(|change| change.sport == *sport && change.threshold_type == *threshold_type)
+            .filter(|change| change.sport.as_ref() == sport && change.threshold_type == *threshold_type)
             .collect()
     }
 }
 
+/// Parse an IANA timezone name such as "Europe/Berlin", or a UTC offset
+pub fn parse_timezone(text: &str) -> Option<LocalTimezone> {
+    match text.trim().parse::<chrono_tz::Tz>() {
+        Ok(tz) => Some(LocalTimezone::Named(tz)),
+        Err(_) => parse_utc_offset(text).map(LocalTimezone::Fixed),
+    }
+}
+
+/// Parse a UTC offset such as "UTC", "Z", "+02:00", "-0530" or "UTC+1"
+pub fn parse_utc_offset(text: &str) -> Option<FixedOffset> {
+    let text = text.trim();
+    let offset = text
+        .strip_prefix("UTC")
+        .or_else(|| text.strip_prefix("GMT"))
+        .unwrap_or(text);
+    if offset.is_empty() || offset == "Z" {
+        return FixedOffset::east_opt(0);
+    }
+
+    let (sign, digits) = match (offset.strip_prefix('+'), offset.strip_prefix('-')) {
+        (Some(rest), _) => (1, rest),
+        (_, Some(rest)) => (-1, rest),
+        _ => return None,
+    };
+    if !digits.chars().all(|c| c.is_ascii_digit() || c == ':') {
+        return None;
+    }
+    let (hours, minutes) = match digits.split_once(':') {
+        Some((hours, minutes)) => (hours, minutes),
+        None if digits.len() > 2 => digits.split_at(digits.len() - 2),
+        None => (digits, "0"),
+    };
+    let hours: i32 = hours.parse().ok()?;
+    let minutes: i32 = minutes.parse().ok()?;
+    if hours > 14 || minutes >= 60 {
+        return None;
+    }
+
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+}
+
 // Add the dirs dependency for getting home directory
 #[cfg(test)]
 mod tests {
@@ -721,4 +1141,104 @@ mod tests {
         assert_eq!(loaded_config.athletes.len(), 1);
         assert!(loaded_config.default_athlete_id.is_some());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_apply_thresholds_records_history() {
+        let mut athlete = AthleteConfig::new("Test Athlete".to_string(), None);
+        let march = NaiveDate::from_ymd_opt(2025, 3, 1).unwrap();
+        let values = ThresholdValues {
+            ftp: Some(250),
+            lthr: Some(165),
+            ..Default::default()
+        };
+
+        let changes = athlete.apply_thresholds(None, &values, march, ThresholdSource::Test, None);
+        assert_eq!(changes.len(), 2);
+        assert_eq!(athlete.profile.ftp, Some(250));
+
+        // Unchanged values are not recorded again
+        assert!(athlete.apply_thresholds(None, &values, march, ThresholdSource::Test, None).is_empty());
+
+        // An older change goes into the history without replacing the newer value
+        let january = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let older = ThresholdValues { ftp: Some(240), ..Default::default() };
+        let changes = athlete.apply_thresholds(None, &older, january, ThresholdSource::Import, None);
+        assert_eq!(changes[0].old_value, None);
+        assert_eq!(changes[0].sport, None);
+        assert_eq!(athlete.profile.ftp, Some(250));
+        assert_eq!(athlete.threshold_history.len(), 3);
+        assert_eq!(athlete.threshold_history.last().unwrap().date, january);
+        let ftp_history = athlete.get_threshold_history(None, &ThresholdType::Ftp);
+        assert_eq!(ftp_history[0].old_value, Some(Decimal::from(240)));
+
+        // Between the two the January value was in effect
+        let february = NaiveDate::from_ymd_opt(2025, 2, 1).unwrap();
+        let between = ThresholdValues { ftp: Some(245), ..Default::default() };
+        let changes = athlete.apply_thresholds(None, &between, february, ThresholdSource::Import, None);
+        assert_eq!(changes[0].old_value, Some(Decimal::from(240)));
+        assert_eq!(athlete.threshold_value_on(None, &ThresholdType::Ftp, february), Some(Decimal::from(245)));
+        assert_eq!(athlete.profile.ftp, Some(250));
+
+        let running = ThresholdValues {
+            lthr: Some(172),
+            zone_models: vec!["seiler-hr".to_string()],
+            ..Default::default()
+        };
+        athlete.apply_thresholds(Some(&Sport::Running), &running, march, ThresholdSource::Manual, None);
+        athlete.refresh_zones(&ZoneSettings::default());
+        let profile = athlete.get_sport_profile(&Sport::Running).unwrap();
+        assert_eq!(profile.lthr, Some(172));
+        assert_eq!(profile.zones.as_ref().unwrap().heart_rate.as_ref().unwrap().len(), 3);
+        assert_eq!(athlete.profile.lthr, Some(165));
+    }
+
+    #[test]
+    fn test_zone_file_round_trip() {
+        let temp_dir = tempdir().unwrap();
+        let mut athlete = AthleteConfig::new("Test Athlete".to_string(), None);
+        let date = NaiveDate::from_ymd_opt(2025, 3, 1).unwrap();
+        athlete.apply_thresholds(None, &ThresholdValues { ftp: Some(280), ..Default::default() }, date, ThresholdSource::Manual, None);
+        let swim = ThresholdValues {
+            threshold_swim_pace: Some(Decimal::new(175, 2)),
+            ..Default::default()
+        };
+        athlete.apply_thresholds(Some(&Sport::Swimming), &swim, date, ThresholdSource::Manual, None);
+        assert_eq!(athlete.to_athlete_profile().css_pace, Some(Decimal::new(175, 2)));
+
+        for name in ["zones.toml", "zones.json"] {
+            let path = temp_dir.path().join(name);
+            athlete.zone_file().save(&path).unwrap();
+            let loaded = ZoneFile::load(&path).unwrap();
+            assert_eq!(loaded, athlete.zone_file());
+            assert_eq!(loaded.athlete.ftp, Some(280));
+            assert_eq!(loaded.sports[0].sport, Sport::Swimming);
+        }
+
+        let parsed: ZoneFile = toml::from_str("ftp = 300\nthreshold_pace = 4.25\n").unwrap();
+        assert_eq!(parsed.athlete.threshold_pace, Some(Decimal::new(425, 2)));
+        assert!(parsed.effective_date.is_none());
+    }
+
+    #[test]
+    fn test_parse_utc_offset() {
+        assert_eq!(parse_utc_offset("UTC"), FixedOffset::east_opt(0));
+        assert_eq!(parse_utc_offset("+02:00"), FixedOffset::east_opt(7200));
+        assert_eq!(parse_utc_offset("-0530"), FixedOffset::west_opt(19800));
+        assert_eq!(parse_utc_offset("UTC+1"), FixedOffset::east_opt(3600));
+        assert_eq!(parse_utc_offset("Europe/Berlin"), None);
+        assert_eq!(parse_utc_offset("+15:00"), None);
+    }
+
+    #[test]
+    fn test_parse_timezone() {
+        assert_eq!(parse_timezone("Europe/Berlin"), Some(LocalTimezone::Named(chrono_tz::Europe::Berlin)));
+        assert_eq!(parse_timezone("+02:00"), FixedOffset::east_opt(7200).map(LocalTimezone::Fixed));
+        assert_eq!(parse_timezone("Mars/Olympus"), None);
+
+        // Stored values read back as the same timezone
+        for text in ["America/New_York", "-05:30"] {
+            let timezone = parse_timezone(text).unwrap();
+            assert_eq!(parse_timezone(&timezone.to_string()), Some(timezone));
+        }
+    }
+}
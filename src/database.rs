@@ -1,19 +1,244 @@
-use chrono::{DateTime, NaiveDate, Utc};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
 use flate2::{Compression, read::GzDecoder, write::GzEncoder};
 use rusqlite::{params, Connection, OptionalExtension, Row};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::io::{Read, Write};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use uuid::Uuid;
 
-use crate::models::{DataPoint, Sport, Workout, WorkoutSummary, WorkoutType, DataSource};
+use crate::import::fit_cache::FileFingerprint;
+use crate::models::{AthleteProfile, DataPoint, Lap, LapIntensity, LapTrigger, Sport, SetType, StrengthSet, SwimLength, SwimLengthType, Workout, WorkoutSummary, WorkoutType, DataSource};
+use crate::pmc::PmcMetrics;
+use crate::race_calendar::{Race, RacePriority};
+use crate::readiness::{DailyReadiness, WellnessQuestionnaire};
+use crate::training_effect::TrainingEffect;
+use crate::training_plan::{PlannedSessionKey, PlannedSessionLink, SessionCompliance, TrainingPlan};
+use crate::vo2max::{EnvironmentalConditions, Vo2MaxEstimate, Vo2MaxMethod, WorkoutVo2MaxEstimate};
+use crate::workout_merge::{Channel, ChannelProvenance, MergedWorkout};
 use crate::recovery::{
-    HrvMeasurement, HrvStatus, SleepSession, SleepMetrics, SleepStageSegment, SleepStage,
+    HrvMeasurement, HrvMetrics, HrvStatus, SleepSession, SleepMetrics,
     BodyBatteryData, PhysiologicalMetrics, RecoveryMetrics, RecoveryQuality,
 };
 
+/// Marker prefixed to serialized time series that include the `temperature` channel
+const TIME_SERIES_FORMAT_V2: &[u8] = b"TSv2";
+
+/// Marker prefixed to serialized time series that include the rowing stroke channels
+const TIME_SERIES_FORMAT_V3: &[u8] = b"TSv3";
+
+/// Decimals in stored time series are written as text
+///
+/// `Decimal`'s own `Deserialize` relies on `deserialize_any`, which bincode
+/// doesn't support; reading the text back explicitly keeps the stored bytes
+/// unchanged.
+mod decimal_text {
+    use rust_decimal::Decimal;
+    use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &Option<Decimal>, serializer: S) -> Result<S::Ok, S::Error> {
+        value.map(|d| d.to_string()).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Decimal>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|text| text.parse().map_err(D::Error::custom))
+            .transpose()
+    }
+}
+
+/// Stored layout of a [`DataPoint`]
+#[derive(Serialize, Deserialize)]
+struct StoredDataPoint {
+    timestamp: u32,
+    heart_rate: Option<u16>,
+    power: Option<u16>,
+    #[serde(with = "decimal_text")]
+    pace: Option<Decimal>,
+    elevation: Option<i16>,
+    cadence: Option<u16>,
+    #[serde(with = "decimal_text")]
+    speed: Option<Decimal>,
+    #[serde(with = "decimal_text")]
+    distance: Option<Decimal>,
+    left_power: Option<u16>,
+    right_power: Option<u16>,
+    ground_contact_time: Option<u16>,
+    vertical_oscillation: Option<u16>,
+    #[serde(with = "decimal_text")]
+    stride_length: Option<Decimal>,
+    stroke_count: Option<u16>,
+    stroke_type: Option<u8>,
+    lap_number: Option<u16>,
+    sport_transition: Option<bool>,
+    temperature: Option<i8>,
+    #[serde(with = "decimal_text")]
+    drive_length: Option<Decimal>,
+    stroke_work: Option<u16>,
+}
+
+impl From<&DataPoint> for StoredDataPoint {
+    fn from(p: &DataPoint) -> Self {
+        StoredDataPoint {
+            timestamp: p.timestamp,
+            heart_rate: p.heart_rate,
+            power: p.power,
+            pace: p.pace,
+            elevation: p.elevation,
+            cadence: p.cadence,
+            speed: p.speed,
+            distance: p.distance,
+            left_power: p.left_power,
+            right_power: p.right_power,
+            ground_contact_time: p.ground_contact_time,
+            vertical_oscillation: p.vertical_oscillation,
+            stride_length: p.stride_length,
+            stroke_count: p.stroke_count,
+            stroke_type: p.stroke_type,
+            lap_number: p.lap_number,
+            sport_transition: p.sport_transition,
+            temperature: p.temperature,
+            drive_length: p.drive_length,
+            stroke_work: p.stroke_work,
+        }
+    }
+}
+
+impl From<StoredDataPoint> for DataPoint {
+    fn from(p: StoredDataPoint) -> Self {
+        DataPoint {
+            timestamp: p.timestamp,
+            heart_rate: p.heart_rate,
+            power: p.power,
+            pace: p.pace,
+            elevation: p.elevation,
+            cadence: p.cadence,
+            speed: p.speed,
+            distance: p.distance,
+            left_power: p.left_power,
+            right_power: p.right_power,
+            ground_contact_time: p.ground_contact_time,
+            vertical_oscillation: p.vertical_oscillation,
+            stride_length: p.stride_length,
+            stroke_count: p.stroke_count,
+            stroke_type: p.stroke_type,
+            lap_number: p.lap_number,
+            sport_transition: p.sport_transition,
+            temperature: p.temperature,
+            drive_length: p.drive_length,
+            stroke_work: p.stroke_work,
+        }
+    }
+}
+
+/// Data point layout stored before the rowing stroke channels were added
+#[derive(Deserialize)]
+struct StoredDataPointV2 {
+    timestamp: u32,
+    heart_rate: Option<u16>,
+    power: Option<u16>,
+    #[serde(with = "decimal_text")]
+    pace: Option<Decimal>,
+    elevation: Option<i16>,
+    cadence: Option<u16>,
+    #[serde(with = "decimal_text")]
+    speed: Option<Decimal>,
+    #[serde(with = "decimal_text")]
+    distance: Option<Decimal>,
+    left_power: Option<u16>,
+    right_power: Option<u16>,
+    ground_contact_time: Option<u16>,
+    vertical_oscillation: Option<u16>,
+    #[serde(with = "decimal_text")]
+    stride_length: Option<Decimal>,
+    stroke_count: Option<u16>,
+    stroke_type: Option<u8>,
+    lap_number: Option<u16>,
+    sport_transition: Option<bool>,
+    temperature: Option<i8>,
+}
+
+impl From<StoredDataPointV2> for DataPoint {
+    fn from(p: StoredDataPointV2) -> Self {
+        DataPoint {
+            timestamp: p.timestamp,
+            heart_rate: p.heart_rate,
+            power: p.power,
+            pace: p.pace,
+            elevation: p.elevation,
+            cadence: p.cadence,
+            speed: p.speed,
+            distance: p.distance,
+            left_power: p.left_power,
+            right_power: p.right_power,
+            ground_contact_time: p.ground_contact_time,
+            vertical_oscillation: p.vertical_oscillation,
+            stride_length: p.stride_length,
+            stroke_count: p.stroke_count,
+            stroke_type: p.stroke_type,
+            lap_number: p.lap_number,
+            sport_transition: p.sport_transition,
+            temperature: p.temperature,
+            drive_length: None,
+            stroke_work: None,
+        }
+    }
+}
+
+/// Data point layout stored before the `temperature` channel was added
+#[derive(Deserialize)]
+struct LegacyDataPoint {
+    timestamp: u32,
+    heart_rate: Option<u16>,
+    power: Option<u16>,
+    #[serde(with = "decimal_text")]
+    pace: Option<Decimal>,
+    elevation: Option<i16>,
+    cadence: Option<u16>,
+    #[serde(with = "decimal_text")]
+    speed: Option<Decimal>,
+    #[serde(with = "decimal_text")]
+    distance: Option<Decimal>,
+    left_power: Option<u16>,
+    right_power: Option<u16>,
+    ground_contact_time: Option<u16>,
+    vertical_oscillation: Option<u16>,
+    #[serde(with = "decimal_text")]
+    stride_length: Option<Decimal>,
+    stroke_count: Option<u16>,
+    stroke_type: Option<u8>,
+    lap_number: Option<u16>,
+    sport_transition: Option<bool>,
+}
+
+impl From<LegacyDataPoint> for DataPoint {
+    fn from(p: LegacyDataPoint) -> Self {
+        DataPoint {
+            timestamp: p.timestamp,
+            heart_rate: p.heart_rate,
+            power: p.power,
+            pace: p.pace,
+            elevation: p.elevation,
+            cadence: p.cadence,
+            speed: p.speed,
+            distance: p.distance,
+            left_power: p.left_power,
+            right_power: p.right_power,
+            ground_contact_time: p.ground_contact_time,
+            vertical_oscillation: p.vertical_oscillation,
+            stride_length: p.stride_length,
+            stroke_count: p.stroke_count,
+            stroke_type: p.stroke_type,
+            lap_number: p.lap_number,
+            sport_transition: p.sport_transition,
+            temperature: None,
+            drive_length: None,
+            stroke_work: None,
+        }
+    }
+}
+
 /// Database error types
 #[allow(dead_code)]
 #[derive(Debug, thiserror::Error)]
@@ -44,9 +269,8 @@ pub struct CompressedTimeSeriesData {
 impl CompressedTimeSeriesData {
     /// Compress a vector of data points
     pub fn compress(data_points: &[DataPoint]) -> Result<Self, DatabaseError> {
-        // Serialize the data points using bincode
-        let serialized = bincode::serialize(data_points)
-            .map_err(|e| DatabaseError::SerializationError(e.to_string()))?;
+        // Serialize the data points using bincode behind the format marker
+        let serialized = Self::serialize_points(data_points)?;
 
         let original_size = serialized.len();
 
@@ -69,11 +293,32 @@ impl CompressedTimeSeriesData {
         let mut decompressed = Vec::new();
         decoder.read_to_end(&mut decompressed)?;
 
-        // Deserialize using bincode
-        let data_points: Vec<DataPoint> = bincode::deserialize(&decompressed)
+        Self::deserialize_points(&decompressed)
+    }
+
+    /// Serialize data points with bincode behind the format marker
+    fn serialize_points(data_points: &[DataPoint]) -> Result<Vec<u8>, DatabaseError> {
+        let stored: Vec<StoredDataPoint> = data_points.iter().map(StoredDataPoint::from).collect();
+        let mut serialized = TIME_SERIES_FORMAT_V3.to_vec();
+        bincode::serialize_into(&mut serialized, &stored)
             .map_err(|e| DatabaseError::SerializationError(e.to_string()))?;
+        Ok(serialized)
+    }
 
-        Ok(data_points)
+    /// Deserialize data points; data without a format marker was written
+    /// before ambient temperature was recorded
+    fn deserialize_points(serialized: &[u8]) -> Result<Vec<DataPoint>, DatabaseError> {
+        if let Some(payload) = serialized.strip_prefix(TIME_SERIES_FORMAT_V3) {
+            bincode::deserialize::<Vec<StoredDataPoint>>(payload)
+                .map(|points| points.into_iter().map(DataPoint::from).collect())
+        } else if let Some(payload) = serialized.strip_prefix(TIME_SERIES_FORMAT_V2) {
+            bincode::deserialize::<Vec<StoredDataPointV2>>(payload)
+                .map(|points| points.into_iter().map(DataPoint::from).collect())
+        } else {
+            bincode::deserialize::<Vec<LegacyDataPoint>>(serialized)
+                .map(|points| points.into_iter().map(DataPoint::from).collect())
+        }
+        .map_err(|e| DatabaseError::SerializationError(e.to_string()))
     }
 
     /// Get compression ratio (original size / compressed size)
@@ -108,9 +353,10 @@ impl Database {
     /// Initialize database schema with tables and indexes
     fn init_schema(&mut self) -> Result<(), DatabaseError> {
         // Enable WAL mode for better concurrent access
-        self.conn.execute("PRAGMA journal_mode=WAL", [])?;
-        self.conn.execute("PRAGMA synchronous=NORMAL", [])?;
-        self.conn.execute("PRAGMA cache_size=10000", [])?;
+        // journal_mode returns the resulting mode as a row, so use execute_batch
+        self.conn.execute_batch(
+            "PRAGMA journal_mode=WAL; PRAGMA synchronous=NORMAL; PRAGMA cache_size=10000;",
+        )?;
 
         // Athletes table
         self.conn.execute(
@@ -137,6 +383,7 @@ impl Database {
                 id TEXT PRIMARY KEY,
                 athlete_id TEXT,
                 date DATE NOT NULL,
+                start_time TEXT, -- RFC 3339 with the recording's UTC offset
                 sport TEXT NOT NULL,
                 duration_seconds INTEGER NOT NULL,
                 workout_type TEXT NOT NULL,
@@ -154,6 +401,7 @@ impl Database {
                 elevation_gain INTEGER,
                 avg_cadence INTEGER,
                 calories INTEGER,
+                rpe INTEGER,
 
                 -- Metadata
                 notes TEXT,
@@ -167,6 +415,8 @@ impl Database {
             "#,
             [],
         )?;
+        self.add_missing_column("workouts", "start_time", "TEXT")?;
+        self.add_missing_column("workouts", "rpe", "INTEGER")?;
 
         // Time series data table (stores compressed time-series data)
         self.conn.execute(
@@ -186,6 +436,69 @@ impl Database {
             [],
         )?;
 
+        // Pool lengths of swim workouts
+        self.conn.execute(
+            r#"
+            CREATE TABLE IF NOT EXISTS swim_lengths (
+                workout_id TEXT NOT NULL,
+                length_index INTEGER NOT NULL,
+                start_offset INTEGER NOT NULL,
+                duration_seconds REAL NOT NULL,
+                distance REAL NOT NULL,
+                strokes INTEGER,
+                stroke_type INTEGER,
+                length_type TEXT NOT NULL CHECK(length_type IN ('Active', 'Idle')),
+
+                PRIMARY KEY (workout_id, length_index),
+                FOREIGN KEY (workout_id) REFERENCES workouts (id) ON DELETE CASCADE
+            )
+            "#,
+            [],
+        )?;
+
+        // Device laps
+        self.conn.execute(
+            r#"
+            CREATE TABLE IF NOT EXISTS workout_laps (
+                workout_id TEXT NOT NULL,
+                lap_index INTEGER NOT NULL,
+                start_offset INTEGER NOT NULL,
+                duration_seconds INTEGER NOT NULL,
+                distance REAL,
+                avg_heart_rate INTEGER,
+                max_heart_rate INTEGER,
+                avg_power INTEGER,
+                max_power INTEGER,
+                trigger TEXT NOT NULL,
+                intensity TEXT NOT NULL,
+
+                PRIMARY KEY (workout_id, lap_index),
+                FOREIGN KEY (workout_id) REFERENCES workouts (id) ON DELETE CASCADE
+            )
+            "#,
+            [],
+        )?;
+
+        // Sets of strength workouts
+        self.conn.execute(
+            r#"
+            CREATE TABLE IF NOT EXISTS strength_sets (
+                workout_id TEXT NOT NULL,
+                set_index INTEGER NOT NULL,
+                start_offset INTEGER NOT NULL,
+                duration_seconds INTEGER NOT NULL,
+                exercise TEXT,
+                reps INTEGER,
+                weight_kg REAL,
+                set_type TEXT NOT NULL CHECK(set_type IN ('Active', 'Rest')),
+
+                PRIMARY KEY (workout_id, set_index),
+                FOREIGN KEY (workout_id) REFERENCES workouts (id) ON DELETE CASCADE
+            )
+            "#,
+            [],
+        )?;
+
         // Create indexes for fast queries
         self.conn.execute(
             "CREATE INDEX IF NOT EXISTS idx_workouts_date ON workouts (date)",
@@ -416,6 +729,265 @@ impl Database {
             [],
         )?;
 
+        // Subjective wellness questionnaire (1-5 per item, 5 = best)
+        self.conn.execute(
+            r#"
+            CREATE TABLE IF NOT EXISTS wellness_entries (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                athlete_id TEXT,
+                date DATE NOT NULL,
+                soreness INTEGER NOT NULL CHECK(soreness BETWEEN 1 AND 5),
+                mood INTEGER NOT NULL CHECK(mood BETWEEN 1 AND 5),
+                stress INTEGER NOT NULL CHECK(stress BETWEEN 1 AND 5),
+                sleep_quality INTEGER NOT NULL CHECK(sleep_quality BETWEEN 1 AND 5),
+                notes TEXT,
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+
+                FOREIGN KEY (athlete_id) REFERENCES athletes(id),
+                UNIQUE(date, athlete_id)
+            )
+            "#,
+            [],
+        )?;
+
+        // Daily composite readiness and the decision for the planned workout
+        self.conn.execute(
+            r#"
+            CREATE TABLE IF NOT EXISTS daily_readiness (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                athlete_id TEXT,
+                date DATE NOT NULL,
+                tsb REAL,
+                acwr REAL,
+                hrv_deviation REAL,
+                recovery_readiness INTEGER CHECK(recovery_readiness BETWEEN 0 AND 100),
+                objective_score INTEGER CHECK(objective_score BETWEEN 0 AND 100),
+                wellness_score INTEGER CHECK(wellness_score BETWEEN 0 AND 100),
+                composite_score INTEGER CHECK(composite_score BETWEEN 0 AND 100),
+                planned_tss REAL,
+                decision TEXT,
+                limiting_factors TEXT,
+                updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+
+                FOREIGN KEY (athlete_id) REFERENCES athletes(id),
+                UNIQUE(date, athlete_id)
+            )
+            "#,
+            [],
+        )?;
+
+        self.conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_wellness_athlete_date ON wellness_entries(athlete_id, date)",
+            [],
+        )?;
+        self.conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_daily_readiness_athlete_date ON daily_readiness(athlete_id, date)",
+            [],
+        )?;
+
+        // Training effect, EPOC and recovery time per workout
+        self.conn.execute(
+            r#"
+            CREATE TABLE IF NOT EXISTS workout_training_effects (
+                workout_id TEXT PRIMARY KEY,
+                athlete_id TEXT,
+                date DATE NOT NULL,
+                aerobic_te REAL NOT NULL,
+                anaerobic_te REAL NOT NULL,
+                epoc REAL NOT NULL,
+                recovery_time_hours INTEGER NOT NULL,
+                end_time TEXT, -- local wall-clock time the workout ended
+                updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+
+                FOREIGN KEY (workout_id) REFERENCES workouts(id) ON DELETE CASCADE,
+                FOREIGN KEY (athlete_id) REFERENCES athletes(id)
+            )
+            "#,
+            [],
+        )?;
+
+        self.conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_training_effects_athlete_date ON workout_training_effects(athlete_id, date)",
+            [],
+        )?;
+        self.add_missing_column("workout_training_effects", "end_time", "TEXT")?;
+
+        // Per-workout VO2max estimates with method, confidence and conditions
+        self.conn.execute(
+            r#"
+            CREATE TABLE IF NOT EXISTS vo2max_estimates (
+                workout_id TEXT PRIMARY KEY,
+                athlete_id TEXT,
+                date DATE NOT NULL,
+                method TEXT NOT NULL,
+                vo2max REAL NOT NULL,
+                uncorrected_vo2max REAL NOT NULL,
+                confidence REAL NOT NULL CHECK(confidence BETWEEN 0 AND 1),
+                temperature_c REAL,
+                altitude_m REAL,
+                effort_duration_seconds INTEGER NOT NULL,
+                updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+
+                FOREIGN KEY (workout_id) REFERENCES workouts(id) ON DELETE CASCADE,
+                FOREIGN KEY (athlete_id) REFERENCES athletes(id)
+            )
+            "#,
+            [],
+        )?;
+
+        self.conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_vo2max_athlete_date ON vo2max_estimates(athlete_id, date)",
+            [],
+        )?;
+
+        // Season race calendar, optionally linked to the workout recording the race
+        self.conn.execute(
+            r#"
+            CREATE TABLE IF NOT EXISTS races (
+                id TEXT PRIMARY KEY,
+                athlete_id TEXT,
+                name TEXT NOT NULL,
+                date DATE NOT NULL,
+                sport TEXT NOT NULL,
+                distance_meters REAL,
+                priority TEXT NOT NULL CHECK(priority IN ('A', 'B', 'C')),
+                goal_time_seconds INTEGER,
+                workout_id TEXT,
+                result_time_seconds INTEGER,
+                notes TEXT,
+                updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+
+                FOREIGN KEY (athlete_id) REFERENCES athletes(id),
+                FOREIGN KEY (workout_id) REFERENCES workouts(id) ON DELETE SET NULL
+            )
+            "#,
+            [],
+        )?;
+
+        self.conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_races_athlete_date ON races(athlete_id, date)",
+            [],
+        )?;
+
+        // Training plans, stored whole as JSON
+        self.conn.execute(
+            r#"
+            CREATE TABLE IF NOT EXISTS training_plans (
+                id TEXT PRIMARY KEY,
+                athlete_id TEXT,
+                name TEXT NOT NULL,
+                start_date DATE NOT NULL,
+                target_date DATE,
+                plan_json TEXT NOT NULL,
+                created_at DATETIME NOT NULL,
+                updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+
+                FOREIGN KEY (athlete_id) REFERENCES athletes(id)
+            )
+            "#,
+            [],
+        )?;
+
+        self.conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_training_plans_athlete ON training_plans(athlete_id, created_at)",
+            [],
+        )?;
+
+        // Planned sessions linked to the workouts that completed them. Links
+        // used to be keyed by date and position, which breaks when sessions
+        // move; they are recomputed by `plan monitor`, so the old table is dropped
+        let keyed_by_position: bool = self.conn.query_row(
+            "SELECT COUNT(*) > 0 FROM pragma_table_info('planned_session_links') WHERE name = 'session_index'",
+            [],
+            |row| row.get(0),
+        )?;
+        if keyed_by_position {
+            self.conn.execute("DROP TABLE planned_session_links", [])?;
+        }
+        self.conn.execute(
+            r#"
+            CREATE TABLE IF NOT EXISTS planned_session_links (
+                plan_id TEXT NOT NULL,
+                planned_workout_id TEXT NOT NULL,
+                planned_date DATE NOT NULL,
+                workout_id TEXT NOT NULL,
+                duration_score REAL NOT NULL,
+                tss_score REAL,
+                zone_score REAL,
+                compliance REAL NOT NULL,
+                matched_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+
+                PRIMARY KEY (plan_id, planned_workout_id),
+                FOREIGN KEY (plan_id) REFERENCES training_plans(id) ON DELETE CASCADE,
+                FOREIGN KEY (workout_id) REFERENCES workouts(id) ON DELETE CASCADE
+            )
+            "#,
+            [],
+        )?;
+
+        self.conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_planned_session_links_workout ON planned_session_links(workout_id)",
+            [],
+        )?;
+
+        // Daily PMC metrics, extended incrementally as workouts are imported
+        self.conn.execute(
+            r#"
+            CREATE TABLE IF NOT EXISTS pmc_metrics (
+                athlete_id TEXT,
+                date DATE NOT NULL,
+                ctl REAL NOT NULL,
+                atl REAL NOT NULL,
+                tsb REAL NOT NULL,
+                daily_tss REAL NOT NULL,
+                ctl_ramp_rate REAL,
+                atl_spike BOOLEAN NOT NULL DEFAULT 0,
+                updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+
+                FOREIGN KEY (athlete_id) REFERENCES athletes(id),
+                UNIQUE(date, athlete_id)
+            )
+            "#,
+            [],
+        )?;
+
+        // Source recording of each channel of a merged workout
+        self.conn.execute(
+            r#"
+            CREATE TABLE IF NOT EXISTS workout_channel_sources (
+                workout_id TEXT NOT NULL,
+                channel TEXT NOT NULL,
+                source_workout_id TEXT NOT NULL,
+                source TEXT,
+
+                PRIMARY KEY (workout_id, channel),
+                FOREIGN KEY (workout_id) REFERENCES workouts(id) ON DELETE CASCADE
+            )
+            "#,
+            [],
+        )?;
+
+        // Files seen by folder imports, identified by their content fingerprint
+        self.conn.execute(
+            r#"
+            CREATE TABLE IF NOT EXISTS imported_files (
+                file_path TEXT PRIMARY KEY,
+                hash TEXT NOT NULL,
+                file_size INTEGER NOT NULL,
+                modified_timestamp INTEGER NOT NULL,
+                workout_count INTEGER NOT NULL DEFAULT 0,
+                error TEXT,
+                imported_at DATETIME DEFAULT CURRENT_TIMESTAMP
+            )
+            "#,
+            [],
+        )?;
+
+        self.conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_imported_files_hash ON imported_files(hash)",
+            [],
+        )?;
+
         // Create duplicate detection view
         self.conn.execute(
             r#"
@@ -442,6 +1014,47 @@ impl Database {
         Ok(())
     }
 
+    /// Store or update an athlete and their thresholds
+    pub fn store_athlete(&mut self, athlete: &AthleteProfile) -> Result<(), DatabaseError> {
+        self.conn.execute(
+            r#"
+            INSERT INTO athletes (
+                id, name, birth_date, max_heart_rate, lactate_threshold_heart_rate,
+                functional_threshold_power, threshold_pace_per_km
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+            ON CONFLICT(id) DO UPDATE SET
+                name = excluded.name,
+                birth_date = excluded.birth_date,
+                max_heart_rate = excluded.max_heart_rate,
+                lactate_threshold_heart_rate = excluded.lactate_threshold_heart_rate,
+                functional_threshold_power = excluded.functional_threshold_power,
+                threshold_pace_per_km = excluded.threshold_pace_per_km,
+                updated_at = CURRENT_TIMESTAMP
+            "#,
+            params![
+                athlete.id,
+                athlete.name,
+                athlete.date_of_birth.map(|d| d.to_string()),
+                athlete.max_hr,
+                athlete.lthr,
+                athlete.ftp,
+                athlete.threshold_pace.map(|p| p.to_string()),
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    /// Make sure an athlete row exists so athlete-scoped rows satisfy their foreign keys
+    pub fn ensure_athlete(&mut self, athlete_id: &str) -> Result<(), DatabaseError> {
+        self.conn.execute(
+            "INSERT OR IGNORE INTO athletes (id, name) VALUES (?1, ?1)",
+            params![athlete_id],
+        )?;
+
+        Ok(())
+    }
+
     /// Store a workout with optional time-series data
     pub fn store_workout(&mut self, workout: &Workout) -> Result<(), DatabaseError> {
         let tx = self.conn.transaction()?;
@@ -455,6 +1068,14 @@ impl Database {
             )));
         }
 
+        Self::insert_workout(&tx, workout)?;
+
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Insert a workout with its time series and pool lengths
+    fn insert_workout(tx: &rusqlite::Transaction, workout: &Workout) -> Result<(), DatabaseError> {
         // Insert workout summary
         tx.execute(
             r#"
@@ -462,9 +1083,9 @@ impl Database {
                 id, athlete_id, date, sport, duration_seconds, workout_type, data_source,
                 avg_heart_rate, max_heart_rate, avg_power, normalized_power, avg_pace,
                 intensity_factor, tss, total_distance, elevation_gain, avg_cadence, calories,
-                notes, source, has_time_series, updated_at
+                notes, source, has_time_series, start_time, rpe, updated_at
             ) VALUES (
-                ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, CURRENT_TIMESTAMP
+                ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23, CURRENT_TIMESTAMP
             )
             "#,
             params![
@@ -489,6 +1110,8 @@ impl Database {
                 workout.notes,
                 workout.source,
                 workout.raw_data.is_some(),
+                workout.start_time.map(|t| t.to_rfc3339()),
+                workout.rpe,
             ],
         )?;
 
@@ -514,11 +1137,269 @@ impl Database {
             )?;
         }
 
-        tx.commit()?;
-        Ok(())
-    }
+        tx.execute("DELETE FROM swim_lengths WHERE workout_id = ?1", params![workout.id])?;
+        for (index, length) in workout.swim_lengths.iter().enumerate() {
+            tx.execute(
+                r#"
+                INSERT INTO swim_lengths (
+                    workout_id, length_index, start_offset, duration_seconds, distance,
+                    strokes, stroke_type, length_type
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+                "#,
+                params![
+                    workout.id,
+                    index as u32,
+                    length.start_offset,
+                    length.duration_seconds.to_string(),
+                    length.distance.to_string(),
+                    length.strokes,
+                    length.stroke_type,
+                    format!("{:?}", length.length_type),
+                ],
+            )?;
+        }
 
-    /// Check if a workout is a duplicate based on athlete, date, duration, and sport
+        tx.execute("DELETE FROM workout_laps WHERE workout_id = ?1", params![workout.id])?;
+        for (index, lap) in workout.laps.iter().enumerate() {
+            tx.execute(
+                r#"
+                INSERT INTO workout_laps (
+                    workout_id, lap_index, start_offset, duration_seconds, distance,
+                    avg_heart_rate, max_heart_rate, avg_power, max_power, trigger, intensity
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
+                "#,
+                params![
+                    workout.id,
+                    index as u32,
+                    lap.start_offset,
+                    lap.duration_seconds,
+                    lap.distance.map(|d| d.to_string()),
+                    lap.avg_heart_rate,
+                    lap.max_heart_rate,
+                    lap.avg_power,
+                    lap.max_power,
+                    format!("{:?}", lap.trigger),
+                    format!("{:?}", lap.intensity),
+                ],
+            )?;
+        }
+
+        tx.execute("DELETE FROM strength_sets WHERE workout_id = ?1", params![workout.id])?;
+        for (index, set) in workout.strength_sets.iter().enumerate() {
+            tx.execute(
+                r#"
+                INSERT INTO strength_sets (
+                    workout_id, set_index, start_offset, duration_seconds, exercise, reps,
+                    weight_kg, set_type
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+                "#,
+                params![
+                    workout.id,
+                    index as u32,
+                    set.start_offset,
+                    set.duration_seconds,
+                    set.exercise,
+                    set.reps,
+                    set.weight.map(|w| w.to_string()),
+                    format!("{:?}", set.set_type),
+                ],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Replace duplicate recordings with their merged workout
+    ///
+    /// Race and planned-session links move to the merged workout; the channel
+    /// provenance is stored alongside it.
+    pub fn store_merged_workout(
+        &mut self,
+        merged: &MergedWorkout,
+        replaced_ids: &[String],
+    ) -> Result<(), DatabaseError> {
+        let tx = self.conn.transaction()?;
+
+        Self::insert_workout(&tx, &merged.workout)?;
+
+        // Move races and plan links over before the cascading deletes remove them
+        for id in replaced_ids {
+            tx.execute("UPDATE races SET workout_id = ?1 WHERE workout_id = ?2", params![merged.workout.id, id])?;
+            // A workout completes one session per plan; drop the links of later recordings
+            tx.execute(
+                r#"
+                DELETE FROM planned_session_links
+                WHERE workout_id = ?2
+                  AND plan_id IN (SELECT plan_id FROM planned_session_links WHERE workout_id = ?1)
+                "#,
+                params![merged.workout.id, id],
+            )?;
+            tx.execute(
+                "UPDATE planned_session_links SET workout_id = ?1 WHERE workout_id = ?2",
+                params![merged.workout.id, id],
+            )?;
+        }
+
+        for id in replaced_ids {
+            for table in [
+                "time_series_data",
+                "swim_lengths",
+                "workout_laps",
+                "strength_sets",
+                "workout_training_effects",
+                "vo2max_estimates",
+                "workout_channel_sources",
+            ] {
+                tx.execute(&format!("DELETE FROM {} WHERE workout_id = ?1", table), params![id])?;
+            }
+            tx.execute("DELETE FROM workouts WHERE id = ?1", params![id])?;
+        }
+
+        for provenance in &merged.provenance {
+            tx.execute(
+                r#"
+                INSERT OR REPLACE INTO workout_channel_sources (workout_id, channel, source_workout_id, source)
+                VALUES (?1, ?2, ?3, ?4)
+                "#,
+                params![merged.workout.id, provenance.channel.name(), provenance.workout_id, provenance.source],
+            )?;
+        }
+
+        tx.commit()?;
+
+        for id in replaced_ids {
+            self.cache.remove(&format!("time_series_{}", id));
+        }
+        Ok(())
+    }
+
+    /// Sources of the channels of a merged workout
+    pub fn get_channel_provenance(&self, workout_id: &str) -> Result<Vec<ChannelProvenance>, DatabaseError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT channel, source_workout_id, source FROM workout_channel_sources WHERE workout_id = ?1",
+        )?;
+
+        let rows = stmt.query_map(params![workout_id], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?, row.get::<_, Option<String>>(2)?))
+        })?;
+
+        let mut provenance = Vec::new();
+        for row in rows {
+            let (channel, workout_id, source) = row?;
+            if let Some(channel) = Channel::from_name(&channel) {
+                provenance.push(ChannelProvenance { channel, workout_id, source });
+            }
+        }
+        provenance.sort_by_key(|p| p.channel);
+
+        Ok(provenance)
+    }
+
+    /// Load the pool lengths of a swim workout in order
+    pub fn load_swim_lengths(&self, workout_id: &str) -> Result<Vec<SwimLength>, DatabaseError> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT start_offset, duration_seconds, distance, strokes, stroke_type, length_type
+            FROM swim_lengths
+            WHERE workout_id = ?1
+            ORDER BY length_index
+            "#,
+        )?;
+
+        let lengths = stmt
+            .query_map(params![workout_id], |row| {
+                Ok(SwimLength {
+                    start_offset: row.get("start_offset")?,
+                    duration_seconds: Self::decimal_column(row, "duration_seconds")?.unwrap_or_default(),
+                    distance: Self::decimal_column(row, "distance")?.unwrap_or_default(),
+                    strokes: row.get("strokes")?,
+                    stroke_type: row.get("stroke_type")?,
+                    length_type: match row.get::<_, String>("length_type")?.as_str() {
+                        "Idle" => SwimLengthType::Idle,
+                        _ => SwimLengthType::Active,
+                    },
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(lengths)
+    }
+
+    /// Load the device laps of a workout in order
+    pub fn load_laps(&self, workout_id: &str) -> Result<Vec<Lap>, DatabaseError> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT start_offset, duration_seconds, distance, avg_heart_rate, max_heart_rate,
+                   avg_power, max_power, trigger, intensity
+            FROM workout_laps
+            WHERE workout_id = ?1
+            ORDER BY lap_index
+            "#,
+        )?;
+
+        let laps = stmt
+            .query_map(params![workout_id], |row| {
+                Ok(Lap {
+                    start_offset: row.get("start_offset")?,
+                    duration_seconds: row.get("duration_seconds")?,
+                    distance: Self::decimal_column(row, "distance")?,
+                    avg_heart_rate: row.get("avg_heart_rate")?,
+                    max_heart_rate: row.get("max_heart_rate")?,
+                    avg_power: row.get("avg_power")?,
+                    max_power: row.get("max_power")?,
+                    trigger: match row.get::<_, String>("trigger")?.as_str() {
+                        "Manual" => LapTrigger::Manual,
+                        "Time" => LapTrigger::Time,
+                        "Distance" => LapTrigger::Distance,
+                        "Position" => LapTrigger::Position,
+                        "SessionEnd" => LapTrigger::SessionEnd,
+                        "FitnessEquipment" => LapTrigger::FitnessEquipment,
+                        _ => LapTrigger::Unknown,
+                    },
+                    intensity: match row.get::<_, String>("intensity")?.as_str() {
+                        "Rest" => LapIntensity::Rest,
+                        "Warmup" => LapIntensity::Warmup,
+                        "Cooldown" => LapIntensity::Cooldown,
+                        _ => LapIntensity::Active,
+                    },
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(laps)
+    }
+
+    /// Load the sets of a strength workout in order
+    pub fn load_strength_sets(&self, workout_id: &str) -> Result<Vec<StrengthSet>, DatabaseError> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT start_offset, duration_seconds, exercise, reps, weight_kg, set_type
+            FROM strength_sets
+            WHERE workout_id = ?1
+            ORDER BY set_index
+            "#,
+        )?;
+
+        let sets = stmt
+            .query_map(params![workout_id], |row| {
+                Ok(StrengthSet {
+                    start_offset: row.get("start_offset")?,
+                    duration_seconds: row.get("duration_seconds")?,
+                    exercise: row.get("exercise")?,
+                    reps: row.get("reps")?,
+                    weight: Self::decimal_column(row, "weight_kg")?,
+                    set_type: match row.get::<_, String>("set_type")?.as_str() {
+                        "Rest" => SetType::Rest,
+                        _ => SetType::Active,
+                    },
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(sets)
+    }
+
+    /// Check if a workout is a duplicate based on athlete, date, duration, and sport
     fn is_duplicate_workout(tx: &rusqlite::Transaction, workout: &Workout) -> Result<bool, DatabaseError> {
         let date_str = workout.date.to_string();
         let sport_str = workout.sport.to_string();
@@ -548,7 +1429,7 @@ impl Database {
                 id, athlete_id, date, sport, duration_seconds, workout_type, data_source,
                 avg_heart_rate, max_heart_rate, avg_power, normalized_power, avg_pace,
                 intensity_factor, tss, total_distance, elevation_gain, avg_cadence, calories,
-                notes, source, has_time_series
+                notes, source, has_time_series, start_time, rpe
             FROM workouts
             WHERE id = ?1
             "#,
@@ -556,7 +1437,17 @@ impl Database {
             |row| self.workout_from_row(row),
         ).optional()?;
 
-        Ok(workout)
+        match workout {
+            Some(mut workout) => {
+                if workout.sport == Sport::Swimming {
+                    workout.swim_lengths = self.load_swim_lengths(&workout.id)?;
+                }
+                workout.laps = self.load_laps(&workout.id)?;
+                workout.strength_sets = self.load_strength_sets(&workout.id)?;
+                Ok(Some(workout))
+            }
+            None => Ok(None),
+        }
     }
 
     /// Load time-series data for a workout (lazy loading)
@@ -564,9 +1455,7 @@ impl Database {
         // Check cache first
         let cache_key = format!("time_series_{}", workout_id);
         if let Some(cached_data) = self.cache.get(&cache_key) {
-            let data_points: Vec<DataPoint> = bincode::deserialize(cached_data)
-                .map_err(|e| DatabaseError::SerializationError(e.to_string()))?;
-            return Ok(Some(data_points));
+            return CompressedTimeSeriesData::deserialize_points(cached_data).map(Some);
         }
 
         // Load from database
@@ -589,7 +1478,7 @@ impl Database {
             let data_points = compressed_ts.decompress()?;
 
             // Cache the decompressed data
-            if let Ok(serialized) = bincode::serialize(&data_points) {
+            if let Ok(serialized) = CompressedTimeSeriesData::serialize_points(&data_points) {
                 self.cache.insert(cache_key, serialized);
             }
 
@@ -611,9 +1500,9 @@ impl Database {
                     id, athlete_id, date, sport, duration_seconds, workout_type, data_source,
                     avg_heart_rate, max_heart_rate, avg_power, normalized_power, avg_pace,
                     intensity_factor, tss, total_distance, elevation_gain, avg_cadence, calories,
-                    notes, source, has_time_series
+                    notes, source, has_time_series, start_time, rpe
                 FROM workouts
-                ORDER BY date DESC
+                ORDER BY date DESC, start_time DESC
                 LIMIT {}
             ", limit)
         } else {
@@ -622,9 +1511,9 @@ impl Database {
                     id, athlete_id, date, sport, duration_seconds, workout_type, data_source,
                     avg_heart_rate, max_heart_rate, avg_power, normalized_power, avg_pace,
                     intensity_factor, tss, total_distance, elevation_gain, avg_cadence, calories,
-                    notes, source, has_time_series
+                    notes, source, has_time_series, start_time, rpe
                 FROM workouts
-                ORDER BY date DESC
+                ORDER BY date DESC, start_time DESC
             ")
         };
 
@@ -633,7 +1522,7 @@ impl Database {
 
         let mut workouts = Vec::new();
         for workout_result in workout_iter {
-            let workout = workout_result?;
+            let mut workout = workout_result?;
 
             // Apply filters in memory (not optimal but works for now)
             let mut matches = true;
@@ -663,6 +1552,11 @@ impl Database {
             }
 
             if matches {
+                if workout.sport == Sport::Swimming {
+                    workout.swim_lengths = self.load_swim_lengths(&workout.id)?;
+                }
+                workout.laps = self.load_laps(&workout.id)?;
+                workout.strength_sets = self.load_strength_sets(&workout.id)?;
                 workouts.push(workout);
             }
         }
@@ -670,6 +1564,19 @@ impl Database {
         Ok(workouts)
     }
 
+    /// Add a column introduced after the table was first created
+    fn add_missing_column(&self, table: &str, column: &str, definition: &str) -> Result<(), DatabaseError> {
+        let exists: bool = self.conn.query_row(
+            &format!("SELECT COUNT(*) > 0 FROM pragma_table_info('{}') WHERE name = ?1", table),
+            params![column],
+            |row| row.get(0),
+        )?;
+        if !exists {
+            self.conn.execute(&format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition), [])?;
+        }
+        Ok(())
+    }
+
     /// Helper to convert database row to Workout struct
     fn workout_from_row(&self, row: &Row) -> rusqlite::Result<Workout> {
         Ok(Workout {
@@ -685,14 +1592,10 @@ impl Database {
                 max_heart_rate: row.get("max_heart_rate")?,
                 avg_power: row.get("avg_power")?,
                 normalized_power: row.get("normalized_power")?,
-                avg_pace: row.get::<_, Option<String>>("avg_pace")?
-                    .and_then(|s| s.parse::<Decimal>().ok()),
-                intensity_factor: row.get::<_, Option<String>>("intensity_factor")?
-                    .and_then(|s| s.parse::<Decimal>().ok()),
-                tss: row.get::<_, Option<String>>("tss")?
-                    .and_then(|s| s.parse::<Decimal>().ok()),
-                total_distance: row.get::<_, Option<String>>("total_distance")?
-                    .and_then(|s| s.parse::<Decimal>().ok()),
+                avg_pace: Self::decimal_column(row, "avg_pace")?,
+                intensity_factor: Self::decimal_column(row, "intensity_factor")?,
+                tss: Self::decimal_column(row, "tss")?,
+                total_distance: Self::decimal_column(row, "total_distance")?,
                 elevation_gain: row.get("elevation_gain")?,
                 avg_cadence: row.get("avg_cadence")?,
                 calories: row.get("calories")?,
@@ -700,6 +1603,28 @@ impl Database {
             notes: row.get("notes")?,
             athlete_id: row.get("athlete_id")?,
             source: row.get("source")?,
+            swim_lengths: Vec::new(),
+            start_time: row
+                .get::<_, Option<String>>("start_time")?
+                .and_then(|t| DateTime::parse_from_rfc3339(&t).ok()),
+            laps: Vec::new(),
+            rpe: row.get("rpe")?,
+            strength_sets: Vec::new(),
+        })
+    }
+
+    /// Read a decimal stored in a REAL column
+    ///
+    /// Decimals are bound as text, but SQLite's REAL affinity converts them on
+    /// insert, so accept both representations when reading back.
+    fn decimal_column(row: &Row, name: &str) -> rusqlite::Result<Option<Decimal>> {
+        use rusqlite::types::ValueRef;
+
+        Ok(match row.get_ref(name)? {
+            ValueRef::Real(v) => Decimal::from_f64_retain(v).map(|d| d.round_dp(6).normalize()),
+            ValueRef::Integer(v) => Some(Decimal::from(v)),
+            ValueRef::Text(t) => std::str::from_utf8(t).ok().and_then(|s| s.parse::<Decimal>().ok()),
+            _ => None,
         })
     }
 
@@ -1091,18 +2016,100 @@ impl Database {
         let date_str: String = row.get("date")?;
         let date = NaiveDate::parse_from_str(&date_str, "%Y-%m-%d")
             .map_err(|e| rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(e)))?;
+        let timestamp = date.and_hms_opt(0, 0, 0).unwrap().and_utc();
+
+        // Rebuild the summary-level components stored in the row; per-measurement
+        // detail lives in the dedicated HRV/sleep/body battery tables
+        let hrv_rmssd: Option<f64> = row.get("hrv_rmssd")?;
+        let hrv_score: Option<i64> = row.get("hrv_score")?;
+        let hrv_metrics = if hrv_rmssd.is_some() || hrv_score.is_some() {
+            Some(HrvMetrics {
+                rmssd: hrv_rmssd,
+                status: row.get::<_, Option<String>>("hrv_status")?
+                    .and_then(|s| Self::parse_hrv_status(&s)),
+                baseline: row.get("hrv_baseline")?,
+                score: hrv_score.map(|s| s as u8),
+                measurement_time: None,
+                measurement_context: None,
+            })
+        } else {
+            None
+        };
+
+        let total_sleep: Option<i64> = row.get("total_sleep_minutes")?;
+        let sleep_data = match total_sleep {
+            Some(total) => Some(SleepMetrics {
+                total_sleep: total as u16,
+                deep_sleep: row.get::<_, Option<i64>>("deep_sleep_minutes")?.unwrap_or(0) as u16,
+                light_sleep: row.get::<_, Option<i64>>("light_sleep_minutes")?.unwrap_or(0) as u16,
+                rem_sleep: row.get::<_, Option<i64>>("rem_sleep_minutes")?.unwrap_or(0) as u16,
+                awake_time: row.get::<_, Option<i64>>("awake_minutes")?.unwrap_or(0) as u16,
+                sleep_score: row.get::<_, Option<i64>>("sleep_score")?.map(|s| s as u8),
+                sleep_efficiency: row.get("sleep_efficiency")?,
+                sleep_onset: None,
+                interruptions: None,
+            }),
+            None => None,
+        };
+
+        let bb_start: Option<i64> = row.get("body_battery_start")?;
+        let bb_end: Option<i64> = row.get("body_battery_end")?;
+        let body_battery = match (bb_start, bb_end) {
+            (Some(start), Some(end)) => Some(BodyBatteryData {
+                start_level: start as u8,
+                end_level: end as u8,
+                drain_rate: None,
+                charge_rate: None,
+                lowest_level: row.get::<_, Option<i64>>("body_battery_lowest")?.map(|l| l as u8),
+                highest_level: row.get::<_, Option<i64>>("body_battery_highest")?.map(|h| h as u8),
+                timestamp,
+            }),
+            _ => None,
+        };
+
+        let resting_hr: Option<i64> = row.get("resting_hr")?;
+        let respiration_rate: Option<f64> = row.get("respiration_rate")?;
+        let stress_score: Option<i64> = row.get("stress_score")?;
+        let recovery_time: Option<i64> = row.get("recovery_time_hours")?;
+        let physiological = if resting_hr.is_some() || respiration_rate.is_some()
+            || stress_score.is_some() || recovery_time.is_some()
+        {
+            Some(PhysiologicalMetrics {
+                resting_hr: resting_hr.map(|h| h as u8),
+                respiration_rate,
+                pulse_ox: None,
+                stress_score: stress_score.map(|s| s as u8),
+                recovery_time: recovery_time.map(|t| t as u16),
+                timestamp,
+            })
+        } else {
+            None
+        };
+
+        let training_readiness = row.get::<_, Option<i64>>("training_readiness")?.map(|r| r as u8);
 
         Ok(RecoveryMetrics {
             date,
-            hrv_metrics: None, // Simplified - would need full reconstruction
-            sleep_data: None,
-            body_battery: None,
-            physiological: None,
-            training_readiness: row.get::<_, Option<i64>>("training_readiness")?.map(|r| r as u8),
-            recovery_quality: None,
+            hrv_metrics,
+            sleep_data,
+            body_battery,
+            physiological,
+            training_readiness,
+            recovery_quality: training_readiness.map(RecoveryQuality::from_readiness),
         })
     }
 
+    /// Parse an HRV status stored with `format!("{:?}")`
+    fn parse_hrv_status(s: &str) -> Option<HrvStatus> {
+        match s {
+            "Balanced" => Some(HrvStatus::Balanced),
+            "Unbalanced" => Some(HrvStatus::Unbalanced),
+            "Poor" => Some(HrvStatus::Poor),
+            "NoReading" => Some(HrvStatus::NoReading),
+            _ => None,
+        }
+    }
+
     /// Get 7-day recovery trend
     pub fn get_recovery_trend_7day(&self, athlete_id: Option<&str>, end_date: NaiveDate) -> Result<Vec<RecoveryMetrics>, DatabaseError> {
         let start_date = end_date - chrono::Duration::days(6);
@@ -1114,6 +2121,584 @@ impl Database {
         let start_date = end_date - chrono::Duration::days(29);
         self.get_recovery_metrics(athlete_id, start_date, end_date)
     }
+
+    // ============================================================================
+    // Wellness and Daily Readiness
+    // ============================================================================
+
+    /// Store or replace the wellness questionnaire for a date
+    pub fn store_wellness_entry(&mut self, entry: &WellnessQuestionnaire, athlete_id: Option<&str>) -> Result<(), DatabaseError> {
+        entry.validate()
+            .map_err(|e| DatabaseError::IntegrityError(e.to_string()))?;
+
+        self.conn.execute(
+            r#"
+            INSERT OR REPLACE INTO wellness_entries (
+                athlete_id, date, soreness, mood, stress, sleep_quality, notes
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+            "#,
+            params![
+                athlete_id,
+                entry.date.to_string(),
+                entry.soreness,
+                entry.mood,
+                entry.stress,
+                entry.sleep_quality,
+                entry.notes,
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    /// Get wellness questionnaires for a date range
+    pub fn get_wellness_entries(&self, athlete_id: Option<&str>, start_date: NaiveDate, end_date: NaiveDate) -> Result<Vec<WellnessQuestionnaire>, DatabaseError> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT date, soreness, mood, stress, sleep_quality, notes FROM wellness_entries
+            WHERE athlete_id IS ?1 AND date BETWEEN ?2 AND ?3
+            ORDER BY date
+            "#,
+        )?;
+
+        let entries = stmt.query_map(params![athlete_id, start_date.to_string(), end_date.to_string()], |row| {
+            Ok(WellnessQuestionnaire {
+                date: NaiveDate::parse_from_str(&row.get::<_, String>("date")?, "%Y-%m-%d").unwrap(),
+                soreness: row.get("soreness")?,
+                mood: row.get("mood")?,
+                stress: row.get("stress")?,
+                sleep_quality: row.get("sleep_quality")?,
+                notes: row.get("notes")?,
+            })
+        })?;
+
+        Ok(entries.collect::<Result<Vec<_>, _>>()?)
+    }
+
+    /// Store or replace the composite readiness for a date
+    pub fn store_daily_readiness(&mut self, readiness: &DailyReadiness, athlete_id: Option<&str>) -> Result<(), DatabaseError> {
+        let decision = readiness.decision.as_ref()
+            .map(serde_json::to_string)
+            .transpose()
+            .map_err(|e| DatabaseError::SerializationError(e.to_string()))?;
+        let limiting_factors = serde_json::to_string(&readiness.limiting_factors)
+            .map_err(|e| DatabaseError::SerializationError(e.to_string()))?;
+
+        self.conn.execute(
+            r#"
+            INSERT OR REPLACE INTO daily_readiness (
+                athlete_id, date, tsb, acwr, hrv_deviation, recovery_readiness, objective_score,
+                wellness_score, composite_score, planned_tss, decision, limiting_factors, updated_at
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, CURRENT_TIMESTAMP)
+            "#,
+            params![
+                athlete_id,
+                readiness.date.to_string(),
+                readiness.tsb.map(|t| t.to_string()),
+                readiness.acwr,
+                readiness.hrv_deviation,
+                readiness.recovery_readiness,
+                readiness.objective_score,
+                readiness.wellness_score,
+                readiness.composite_score,
+                readiness.planned_tss.map(|t| t.to_string()),
+                decision,
+                limiting_factors,
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    /// Get stored daily readiness for a date range
+    pub fn get_daily_readiness(&self, athlete_id: Option<&str>, start_date: NaiveDate, end_date: NaiveDate) -> Result<Vec<DailyReadiness>, DatabaseError> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT * FROM daily_readiness
+            WHERE athlete_id IS ?1 AND date BETWEEN ?2 AND ?3
+            ORDER BY date
+            "#,
+        )?;
+
+        let rows = stmt.query_map(params![athlete_id, start_date.to_string(), end_date.to_string()], |row| {
+            let decision: Option<String> = row.get("decision")?;
+            let limiting_factors: Option<String> = row.get("limiting_factors")?;
+            let composite_score = row.get::<_, Option<i64>>("composite_score")?.map(|s| s as u8);
+
+            Ok(DailyReadiness {
+                date: NaiveDate::parse_from_str(&row.get::<_, String>("date")?, "%Y-%m-%d").unwrap(),
+                tsb: Self::decimal_column(row, "tsb")?,
+                acwr: row.get("acwr")?,
+                hrv_deviation: row.get("hrv_deviation")?,
+                recovery_readiness: row.get::<_, Option<i64>>("recovery_readiness")?.map(|s| s as u8),
+                objective_score: row.get::<_, Option<i64>>("objective_score")?.map(|s| s as u8),
+                wellness_score: row.get::<_, Option<i64>>("wellness_score")?.map(|s| s as u8),
+                composite_score,
+                recovery_quality: composite_score.map(RecoveryQuality::from_readiness),
+                planned_tss: Self::decimal_column(row, "planned_tss")?,
+                decision: decision.and_then(|d| serde_json::from_str(&d).ok()),
+                limiting_factors: limiting_factors
+                    .and_then(|f| serde_json::from_str(&f).ok())
+                    .unwrap_or_default(),
+            })
+        })?;
+
+        Ok(rows.collect::<Result<Vec<_>, _>>()?)
+    }
+
+    // ============================================================================
+    // Training Effect
+    // ============================================================================
+
+    /// Store or replace the training effect for a workout
+    pub fn store_training_effect(&mut self, effect: &TrainingEffect, athlete_id: Option<&str>) -> Result<(), DatabaseError> {
+        self.conn.execute(
+            r#"
+            INSERT OR REPLACE INTO workout_training_effects (
+                workout_id, athlete_id, date, aerobic_te, anaerobic_te, epoc, recovery_time_hours, end_time, updated_at
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, CURRENT_TIMESTAMP)
+            "#,
+            params![
+                effect.workout_id,
+                athlete_id,
+                effect.date.to_string(),
+                effect.aerobic_te,
+                effect.anaerobic_te,
+                effect.epoc,
+                effect.recovery_time_hours,
+                effect.end_time.map(|t| t.format("%Y-%m-%dT%H:%M:%S").to_string()),
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    /// Get the training effect for a workout
+    pub fn get_training_effect(&self, workout_id: &str) -> Result<Option<TrainingEffect>, DatabaseError> {
+        let effect = self.conn.query_row(
+            "SELECT * FROM workout_training_effects WHERE workout_id = ?1",
+            params![workout_id],
+            Self::training_effect_from_row,
+        ).optional()?;
+
+        Ok(effect)
+    }
+
+    /// Get training effects for a date range, oldest first
+    pub fn get_training_effects(&self, athlete_id: Option<&str>, start_date: NaiveDate, end_date: NaiveDate) -> Result<Vec<TrainingEffect>, DatabaseError> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT * FROM workout_training_effects
+            WHERE athlete_id IS ?1 AND date BETWEEN ?2 AND ?3
+            ORDER BY date
+            "#,
+        )?;
+
+        let effects = stmt.query_map(
+            params![athlete_id, start_date.to_string(), end_date.to_string()],
+            Self::training_effect_from_row,
+        )?;
+
+        Ok(effects.collect::<Result<Vec<_>, _>>()?)
+    }
+
+    /// Get the training effects of the last `limit` workouts, oldest first
+    pub fn get_recent_training_effects(&self, athlete_id: Option<&str>, limit: usize) -> Result<Vec<TrainingEffect>, DatabaseError> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT * FROM workout_training_effects
+            WHERE athlete_id IS ?1
+            ORDER BY date DESC
+            LIMIT ?2
+            "#,
+        )?;
+
+        let mut effects = stmt
+            .query_map(params![athlete_id, limit as i64], Self::training_effect_from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+        effects.reverse();
+
+        Ok(effects)
+    }
+
+    /// Helper to convert a database row to a TrainingEffect
+    fn training_effect_from_row(row: &Row) -> rusqlite::Result<TrainingEffect> {
+        Ok(TrainingEffect::from_stored(
+            row.get("workout_id")?,
+            NaiveDate::parse_from_str(&row.get::<_, String>("date")?, "%Y-%m-%d").unwrap(),
+            row.get("aerobic_te")?,
+            row.get("anaerobic_te")?,
+            row.get("epoc")?,
+            row.get("recovery_time_hours")?,
+        )
+        .with_end_time(
+            row.get::<_, Option<String>>("end_time")?
+                .and_then(|t| NaiveDateTime::parse_from_str(&t, "%Y-%m-%dT%H:%M:%S").ok()),
+        ))
+    }
+
+    // ============================================================================
+    // VO2max
+    // ============================================================================
+
+    /// Store or replace the VO2max estimate for a workout
+    pub fn store_vo2max_estimate(&mut self, estimate: &WorkoutVo2MaxEstimate, athlete_id: Option<&str>) -> Result<(), DatabaseError> {
+        self.conn.execute(
+            r#"
+            INSERT OR REPLACE INTO vo2max_estimates (
+                workout_id, athlete_id, date, method, vo2max, uncorrected_vo2max, confidence,
+                temperature_c, altitude_m, effort_duration_seconds, updated_at
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, CURRENT_TIMESTAMP)
+            "#,
+            params![
+                estimate.workout_id,
+                athlete_id,
+                estimate.estimate.date.to_string(),
+                estimate.estimate.estimation_method.to_string(),
+                estimate.estimate.vo2max_ml_kg_min,
+                estimate.uncorrected_vo2max,
+                estimate.estimate.confidence.clamp(0.0, 1.0),
+                estimate.conditions.temperature_c,
+                estimate.conditions.altitude_m,
+                estimate.effort_duration_seconds,
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    /// Get VO2max estimates for a date range, oldest first
+    pub fn get_vo2max_estimates(&self, athlete_id: Option<&str>, start_date: NaiveDate, end_date: NaiveDate) -> Result<Vec<WorkoutVo2MaxEstimate>, DatabaseError> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT * FROM vo2max_estimates
+            WHERE athlete_id IS ?1 AND date BETWEEN ?2 AND ?3
+            ORDER BY date
+            "#,
+        )?;
+
+        let rows = stmt.query_map(params![athlete_id, start_date.to_string(), end_date.to_string()], |row| {
+            let workout_id: String = row.get("workout_id")?;
+            Ok(WorkoutVo2MaxEstimate {
+                estimate: Vo2MaxEstimate {
+                    vo2max_ml_kg_min: row.get("vo2max")?,
+                    estimation_method: row.get::<_, String>("method")?.parse::<Vo2MaxMethod>().unwrap(),
+                    confidence: row.get("confidence")?,
+                    date: NaiveDate::parse_from_str(&row.get::<_, String>("date")?, "%Y-%m-%d").unwrap(),
+                    contributing_workouts: vec![workout_id.clone()],
+                },
+                workout_id,
+                uncorrected_vo2max: row.get("uncorrected_vo2max")?,
+                conditions: EnvironmentalConditions {
+                    temperature_c: row.get("temperature_c")?,
+                    altitude_m: row.get("altitude_m")?,
+                },
+                effort_duration_seconds: row.get("effort_duration_seconds")?,
+            })
+        })?;
+
+        Ok(rows.collect::<Result<Vec<_>, _>>()?)
+    }
+    // ============================================================================
+    // Race calendar
+    // ============================================================================
+
+    /// Store or replace a race on the calendar
+    pub fn store_race(&mut self, race: &Race, athlete_id: Option<&str>) -> Result<(), DatabaseError> {
+        self.conn.execute(
+            r#"
+            INSERT OR REPLACE INTO races (
+                id, athlete_id, name, date, sport, distance_meters, priority,
+                goal_time_seconds, workout_id, result_time_seconds, notes, updated_at
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, CURRENT_TIMESTAMP)
+            "#,
+            params![
+                race.id,
+                athlete_id,
+                race.name,
+                race.date.to_string(),
+                race.sport.to_string(),
+                race.distance_meters.map(|d| d.to_string()),
+                race.priority.to_string(),
+                race.goal_time_seconds,
+                race.workout_id,
+                race.result_time_seconds,
+                race.notes,
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    /// Get all races for an athlete in date order
+    pub fn get_races(&self, athlete_id: Option<&str>) -> Result<Vec<Race>, DatabaseError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT * FROM races WHERE athlete_id IS ?1 ORDER BY date",
+        )?;
+
+        let rows = stmt.query_map(params![athlete_id], |row| {
+            Ok(Race {
+                id: row.get("id")?,
+                name: row.get("name")?,
+                date: NaiveDate::parse_from_str(&row.get::<_, String>("date")?, "%Y-%m-%d").unwrap(),
+                sport: Sport::from_str(&row.get::<_, String>("sport")?).unwrap(),
+                distance_meters: Self::decimal_column(row, "distance_meters")?,
+                priority: row.get::<_, String>("priority")?.parse::<RacePriority>().unwrap(),
+                goal_time_seconds: row.get("goal_time_seconds")?,
+                workout_id: row.get("workout_id")?,
+                result_time_seconds: row.get("result_time_seconds")?,
+                notes: row.get("notes")?,
+            })
+        })?;
+
+        Ok(rows.collect::<Result<Vec<_>, _>>()?)
+    }
+
+    /// Delete a race by ID, returning whether it existed
+    pub fn delete_race(&mut self, race_id: &str) -> Result<bool, DatabaseError> {
+        let deleted = self.conn.execute("DELETE FROM races WHERE id = ?1", params![race_id])?;
+        Ok(deleted > 0)
+    }
+
+    // ============================================================================
+    // Training plans
+    // ============================================================================
+
+    /// Store or replace a training plan
+    pub fn store_training_plan(&mut self, plan: &TrainingPlan, athlete_id: Option<&str>) -> Result<(), DatabaseError> {
+        let plan_json = serde_json::to_string(plan)
+            .map_err(|e| DatabaseError::SerializationError(e.to_string()))?;
+
+        // Upsert so replacing a plan keeps its session links
+        self.conn.execute(
+            r#"
+            INSERT INTO training_plans (id, athlete_id, name, start_date, target_date, plan_json, created_at, updated_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, CURRENT_TIMESTAMP)
+            ON CONFLICT(id) DO UPDATE SET
+                name = excluded.name,
+                start_date = excluded.start_date,
+                target_date = excluded.target_date,
+                plan_json = excluded.plan_json,
+                updated_at = CURRENT_TIMESTAMP
+            "#,
+            params![
+                plan.id,
+                athlete_id,
+                plan.name,
+                plan.start_date.to_string(),
+                plan.target_date.map(|d| d.to_string()),
+                plan_json,
+                plan.created_at.to_rfc3339(),
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    /// Get a training plan by ID
+    pub fn get_training_plan(&self, plan_id: &str) -> Result<Option<TrainingPlan>, DatabaseError> {
+        let plan_json: Option<String> = self.conn
+            .query_row(
+                "SELECT plan_json FROM training_plans WHERE id = ?1",
+                params![plan_id],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        plan_json
+            .map(|json| serde_json::from_str(&json).map_err(|e| DatabaseError::SerializationError(e.to_string())))
+            .transpose()
+    }
+
+    /// Get all training plans for an athlete, newest first
+    pub fn list_training_plans(&self, athlete_id: Option<&str>) -> Result<Vec<TrainingPlan>, DatabaseError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT plan_json FROM training_plans WHERE athlete_id IS ?1 ORDER BY created_at DESC",
+        )?;
+
+        let rows = stmt.query_map(params![athlete_id], |row| row.get::<_, String>(0))?;
+
+        rows.map(|json| {
+            serde_json::from_str(&json?).map_err(|e| DatabaseError::SerializationError(e.to_string()))
+        })
+        .collect()
+    }
+
+    /// Replace the session links of a plan
+    pub fn store_plan_links(&mut self, plan_id: &str, links: &[PlannedSessionLink]) -> Result<(), DatabaseError> {
+        let tx = self.conn.transaction()?;
+        tx.execute("DELETE FROM planned_session_links WHERE plan_id = ?1", params![plan_id])?;
+
+        for link in links {
+            tx.execute(
+                r#"
+                INSERT INTO planned_session_links (
+                    plan_id, planned_workout_id, planned_date, workout_id,
+                    duration_score, tss_score, zone_score, compliance
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+                "#,
+                params![
+                    plan_id,
+                    link.session.id,
+                    link.session.date.to_string(),
+                    link.workout_id,
+                    link.compliance.duration_score.to_string(),
+                    link.compliance.tss_score.map(|d| d.to_string()),
+                    link.compliance.zone_score.map(|d| d.to_string()),
+                    link.compliance.overall.to_string(),
+                ],
+            )?;
+        }
+
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Get the session links of a plan in date order
+    pub fn get_plan_links(&self, plan_id: &str) -> Result<Vec<PlannedSessionLink>, DatabaseError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT * FROM planned_session_links WHERE plan_id = ?1 ORDER BY planned_date, planned_workout_id",
+        )?;
+
+        let rows = stmt.query_map(params![plan_id], |row| {
+            Ok(PlannedSessionLink {
+                plan_id: row.get("plan_id")?,
+                session: PlannedSessionKey {
+                    id: row.get("planned_workout_id")?,
+                    date: NaiveDate::parse_from_str(&row.get::<_, String>("planned_date")?, "%Y-%m-%d").unwrap(),
+                },
+                workout_id: row.get("workout_id")?,
+                compliance: SessionCompliance {
+                    duration_score: Self::decimal_column(row, "duration_score")?.unwrap_or_default(),
+                    tss_score: Self::decimal_column(row, "tss_score")?,
+                    zone_score: Self::decimal_column(row, "zone_score")?,
+                    overall: Self::decimal_column(row, "compliance")?.unwrap_or_default(),
+                },
+            })
+        })?;
+
+        Ok(rows.collect::<Result<Vec<_>, _>>()?)
+    }
+
+    // ============================================================================
+    // Performance Management Chart
+    // ============================================================================
+
+    /// Store or replace daily PMC metrics
+    pub fn store_pmc_metrics(&mut self, metrics: &[PmcMetrics], athlete_id: Option<&str>) -> Result<(), DatabaseError> {
+        let tx = self.conn.transaction()?;
+        {
+            // UNIQUE(date, athlete_id) doesn't conflict on NULL athletes, so replace explicitly
+            let mut delete = tx.prepare("DELETE FROM pmc_metrics WHERE athlete_id IS ?1 AND date = ?2")?;
+            let mut insert = tx.prepare(
+                r#"
+                INSERT INTO pmc_metrics (
+                    athlete_id, date, ctl, atl, tsb, daily_tss, ctl_ramp_rate, atl_spike, updated_at
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, CURRENT_TIMESTAMP)
+                "#,
+            )?;
+
+            for day in metrics {
+                delete.execute(params![athlete_id, day.date.to_string()])?;
+                insert.execute(params![
+                    athlete_id,
+                    day.date.to_string(),
+                    day.ctl.to_string(),
+                    day.atl.to_string(),
+                    day.tsb.to_string(),
+                    day.daily_tss.to_string(),
+                    day.ctl_ramp_rate.map(|r| r.to_string()),
+                    day.atl_spike,
+                ])?;
+            }
+        }
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    /// Get stored PMC metrics for a date range, oldest first
+    pub fn get_pmc_metrics(&self, athlete_id: Option<&str>, start_date: NaiveDate, end_date: NaiveDate) -> Result<Vec<PmcMetrics>, DatabaseError> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT * FROM pmc_metrics
+            WHERE athlete_id IS ?1 AND date BETWEEN ?2 AND ?3
+            ORDER BY date
+            "#,
+        )?;
+
+        let rows = stmt.query_map(params![athlete_id, start_date.to_string(), end_date.to_string()], |row| {
+            Ok(PmcMetrics {
+                date: NaiveDate::parse_from_str(&row.get::<_, String>("date")?, "%Y-%m-%d").unwrap(),
+                ctl: Self::decimal_column(row, "ctl")?.unwrap_or_default(),
+                atl: Self::decimal_column(row, "atl")?.unwrap_or_default(),
+                tsb: Self::decimal_column(row, "tsb")?.unwrap_or_default(),
+                daily_tss: Self::decimal_column(row, "daily_tss")?.unwrap_or_default(),
+                ctl_ramp_rate: Self::decimal_column(row, "ctl_ramp_rate")?,
+                atl_spike: row.get("atl_spike")?,
+            })
+        })?;
+
+        Ok(rows.collect::<Result<Vec<_>, _>>()?)
+    }
+
+    // ============================================================================
+    // Imported files
+    // ============================================================================
+
+    /// Record a file processed by a folder import
+    ///
+    /// `workout_count` is `None` when the file was only touched (same content,
+    /// new modification time); its earlier outcome is kept.
+    pub fn record_imported_file(
+        &mut self,
+        file_path: &Path,
+        fingerprint: &FileFingerprint,
+        workout_count: Option<usize>,
+        error: Option<&str>,
+    ) -> Result<(), DatabaseError> {
+        self.conn.execute(
+            r#"
+            INSERT INTO imported_files (file_path, hash, file_size, modified_timestamp, workout_count, error)
+            VALUES (?1, ?2, ?3, ?4, COALESCE(?5, 0), ?6)
+            ON CONFLICT(file_path) DO UPDATE SET
+                hash = excluded.hash,
+                file_size = excluded.file_size,
+                modified_timestamp = excluded.modified_timestamp,
+                workout_count = CASE WHEN ?5 IS NULL THEN workout_count ELSE excluded.workout_count END,
+                error = CASE WHEN ?5 IS NULL THEN error ELSE excluded.error END,
+                imported_at = CASE WHEN ?5 IS NULL THEN imported_at ELSE CURRENT_TIMESTAMP END
+            "#,
+            params![
+                file_path.to_string_lossy(),
+                fingerprint.hash,
+                fingerprint.file_size as i64,
+                fingerprint.modified_timestamp,
+                workout_count.map(|c| c as i64),
+                error,
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    /// Fingerprints of all files processed by folder imports
+    pub fn get_imported_files(&self) -> Result<HashMap<PathBuf, FileFingerprint>, DatabaseError> {
+        let mut stmt = self.conn.prepare("SELECT file_path, hash, file_size, modified_timestamp FROM imported_files")?;
+
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                PathBuf::from(row.get::<_, String>(0)?),
+                FileFingerprint {
+                    hash: row.get(1)?,
+                    file_size: row.get::<_, i64>(2)? as u64,
+                    modified_timestamp: row.get(3)?,
+                },
+            ))
+        })?;
+
+        Ok(rows.collect::<Result<HashMap<_, _>, _>>()?)
+    }
 }
 
 /// Workout query filters
@@ -1228,4 +2813,479 @@ impl DataSource {
             DataSource::Rpe => "Rpe".to_string(),
         }
     }
-}
\ No newline at end of file
+}
+
+impl std::str::FromStr for Vo2MaxMethod {
+    type Err = DatabaseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "AcsmRunning" => Ok(Vo2MaxMethod::AcsmRunning),
+            "AcsmCycling" => Ok(Vo2MaxMethod::AcsmCycling),
+            "PowerBased" => Ok(Vo2MaxMethod::PowerBased),
+            "HeartRateReserve" => Ok(Vo2MaxMethod::HeartRateReserve),
+            "Combined" => Ok(Vo2MaxMethod::Combined),
+            _ => Err(DatabaseError::SerializationError(format!("Unknown VO2max method: {}", s))),
+        }
+    }
+}
+
+impl std::fmt::Display for Vo2MaxMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Vo2MaxMethod::AcsmRunning => "AcsmRunning",
+            Vo2MaxMethod::AcsmCycling => "AcsmCycling",
+            Vo2MaxMethod::PowerBased => "PowerBased",
+            Vo2MaxMethod::HeartRateReserve => "HeartRateReserve",
+            Vo2MaxMethod::Combined => "Combined",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn legacy_point(timestamp: u32) -> LegacyDataPoint {
+        LegacyDataPoint {
+            timestamp,
+            heart_rate: Some(150),
+            power: Some(200),
+            pace: None,
+            elevation: Some(120),
+            cadence: Some(90),
+            speed: None,
+            distance: None,
+            left_power: None,
+            right_power: None,
+            ground_contact_time: None,
+            vertical_oscillation: None,
+            stride_length: None,
+            stroke_count: None,
+            stroke_type: None,
+            lap_number: Some(1),
+            sport_transition: None,
+        }
+    }
+
+    #[test]
+    fn test_time_series_round_trip_keeps_temperature() {
+        let mut point = DataPoint::from(legacy_point(0));
+        point.temperature = Some(-3);
+        point.speed = Some(dec!(8.25));
+        point.distance = Some(dec!(1204.5));
+
+        let compressed = CompressedTimeSeriesData::compress(&[point.clone()]).unwrap();
+        assert_eq!(compressed.decompress().unwrap(), vec![point]);
+    }
+
+    #[test]
+    fn test_time_series_reads_legacy_layout() {
+        #[derive(Serialize)]
+        struct Legacy(u32, Option<u16>, Option<u16>, Option<Decimal>, Option<i16>, Option<u16>,
+            Option<Decimal>, Option<Decimal>, Option<u16>, Option<u16>, Option<u16>, Option<u16>,
+            Option<Decimal>, Option<u16>, Option<u8>, Option<u16>, Option<bool>);
+
+        let legacy: Vec<Legacy> = (0..3)
+            .map(|t| {
+                let p = legacy_point(t);
+                Legacy(p.timestamp, p.heart_rate, p.power, p.pace, p.elevation, p.cadence, p.speed,
+                    p.distance, p.left_power, p.right_power, p.ground_contact_time, p.vertical_oscillation,
+                    p.stride_length, p.stroke_count, p.stroke_type, p.lap_number, p.sport_transition)
+            })
+            .collect();
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&bincode::serialize(&legacy).unwrap()).unwrap();
+        let stored = CompressedTimeSeriesData {
+            compressed_data: encoder.finish().unwrap(),
+            original_size: 0,
+            point_count: 3,
+        };
+
+        let points = stored.decompress().unwrap();
+        assert_eq!(points.len(), 3);
+        assert_eq!(points[2].timestamp, 2);
+        assert_eq!(points[2].heart_rate, Some(150));
+        assert_eq!(points[2].temperature, None);
+    }
+
+    #[test]
+    fn test_time_series_round_trip_keeps_rowing_strokes() {
+        let mut point = DataPoint::from(legacy_point(0));
+        point.drive_length = Some(dec!(1.42));
+        point.stroke_work = Some(712);
+
+        let compressed = CompressedTimeSeriesData::compress(&[point.clone()]).unwrap();
+        assert_eq!(compressed.decompress().unwrap(), vec![point]);
+    }
+
+    #[test]
+    fn test_time_series_reads_v2_layout() {
+        #[derive(Serialize)]
+        struct V2(u32, Option<u16>, Option<u16>, Option<Decimal>, Option<i16>, Option<u16>,
+            Option<Decimal>, Option<Decimal>, Option<u16>, Option<u16>, Option<u16>, Option<u16>,
+            Option<Decimal>, Option<u16>, Option<u8>, Option<u16>, Option<bool>, Option<i8>);
+
+        let v2: Vec<V2> = (0..2)
+            .map(|t| {
+                let p = legacy_point(t);
+                V2(p.timestamp, p.heart_rate, p.power, p.pace, p.elevation, p.cadence, p.speed,
+                    p.distance, p.left_power, p.right_power, p.ground_contact_time, p.vertical_oscillation,
+                    p.stride_length, p.stroke_count, p.stroke_type, p.lap_number, p.sport_transition, Some(21))
+            })
+            .collect();
+
+        let mut serialized = TIME_SERIES_FORMAT_V2.to_vec();
+        bincode::serialize_into(&mut serialized, &v2).unwrap();
+        let points = CompressedTimeSeriesData::deserialize_points(&serialized).unwrap();
+        assert_eq!(points.len(), 2);
+        assert_eq!(points[1].temperature, Some(21));
+        assert_eq!(points[1].drive_length, None);
+        assert_eq!(points[1].stroke_work, None);
+    }
+
+    #[test]
+    fn test_training_plan_and_session_links_round_trip() {
+        use crate::training_plan::{PeriodizationModel, RecoveryPattern, TrainingGoal, TrainingWeek};
+
+        let dir = tempfile::tempdir().unwrap();
+        let mut db = Database::new(dir.path().join("plans.db")).unwrap();
+        let date = NaiveDate::from_ymd_opt(2025, 3, 3).unwrap();
+
+        db.store_workout(&Workout {
+            id: "w1".to_string(),
+            date,
+            sport: Sport::Cycling,
+            duration_seconds: 3600,
+            workout_type: WorkoutType::Endurance,
+            data_source: DataSource::Power,
+            raw_data: None,
+            summary: WorkoutSummary::default(),
+            notes: None,
+            athlete_id: None,
+            source: None,
+            swim_lengths: Vec::new(),
+            start_time: None,
+            laps: Vec::new(),
+            rpe: None,
+            strength_sets: Vec::new(),
+        }).unwrap();
+
+        let mut plan = TrainingPlan {
+            id: "plan_1".to_string(),
+            name: "Spring Build".to_string(),
+            goal: TrainingGoal::Century,
+            periodization_model: PeriodizationModel::Traditional,
+            recovery_pattern: RecoveryPattern::ThreeToOne,
+            start_date: date,
+            target_date: None,
+            total_weeks: 1,
+            weeks: vec![TrainingWeek {
+                week_number: 1,
+                start_date: date,
+                end_date: date + chrono::Duration::days(6),
+                phase: "Base".to_string(),
+                focus: "Aerobic".to_string(),
+                planned_tss: Decimal::from(300),
+                planned_hours: Decimal::from(5),
+                is_recovery_week: false,
+                workouts: Vec::new(),
+            }],
+            total_planned_tss: Decimal::from(300),
+            total_planned_hours: Decimal::from(5),
+            target_distribution: None,
+            created_at: Utc::now(),
+        };
+        db.store_training_plan(&plan, None).unwrap();
+
+        let link = PlannedSessionLink {
+            plan_id: plan.id.clone(),
+            session: PlannedSessionKey { id: "session_1".to_string(), date },
+            workout_id: "w1".to_string(),
+            compliance: SessionCompliance {
+                duration_score: Decimal::from(92),
+                tss_score: None,
+                zone_score: Some(Decimal::from(75)),
+                overall: Decimal::from(84),
+            },
+        };
+        db.store_plan_links(&plan.id, std::slice::from_ref(&link)).unwrap();
+
+        // Replacing the plan keeps its links
+        plan.name = "Spring Build v2".to_string();
+        db.store_training_plan(&plan, None).unwrap();
+
+        assert_eq!(db.get_training_plan("plan_1").unwrap().unwrap().name, "Spring Build v2");
+        assert_eq!(db.list_training_plans(None).unwrap().len(), 1);
+        assert_eq!(db.get_plan_links("plan_1").unwrap(), vec![link]);
+        assert!(db.get_training_plan("missing").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_swim_lengths_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut db = Database::new(dir.path().join("swim.db")).unwrap();
+        let lengths = vec![
+            SwimLength {
+                start_offset: 0,
+                duration_seconds: dec!(22.5),
+                distance: dec!(25),
+                strokes: Some(15),
+                stroke_type: Some(0),
+                length_type: SwimLengthType::Active,
+            },
+            SwimLength {
+                start_offset: 23,
+                duration_seconds: dec!(30),
+                distance: Decimal::ZERO,
+                strokes: None,
+                stroke_type: None,
+                length_type: SwimLengthType::Idle,
+            },
+        ];
+
+        db.store_workout(&Workout {
+            id: "swim".to_string(),
+            date: NaiveDate::from_ymd_opt(2025, 3, 3).unwrap(),
+            sport: Sport::Swimming,
+            duration_seconds: 53,
+            workout_type: WorkoutType::Endurance,
+            data_source: DataSource::Pace,
+            raw_data: None,
+            summary: WorkoutSummary::default(),
+            notes: None,
+            athlete_id: None,
+            source: None,
+            swim_lengths: lengths.clone(),
+            start_time: None,
+            laps: Vec::new(),
+            rpe: None,
+            strength_sets: Vec::new(),
+        }).unwrap();
+
+        assert_eq!(db.load_swim_lengths("swim").unwrap(), lengths);
+        assert_eq!(db.load_workout("swim").unwrap().unwrap().swim_lengths, lengths);
+    }
+
+    #[test]
+    fn test_laps_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut db = Database::new(dir.path().join("laps.db")).unwrap();
+        let laps = vec![
+            Lap {
+                start_offset: 0,
+                duration_seconds: 300,
+                distance: Some(dec!(2400.5)),
+                avg_heart_rate: Some(165),
+                max_heart_rate: Some(178),
+                avg_power: Some(310),
+                max_power: Some(420),
+                trigger: LapTrigger::Manual,
+                intensity: LapIntensity::Active,
+            },
+            Lap {
+                start_offset: 300,
+                duration_seconds: 120,
+                distance: None,
+                avg_heart_rate: Some(130),
+                max_heart_rate: None,
+                avg_power: None,
+                max_power: None,
+                trigger: LapTrigger::Time,
+                intensity: LapIntensity::Rest,
+            },
+        ];
+
+        db.store_workout(&Workout {
+            id: "intervals".to_string(),
+            date: NaiveDate::from_ymd_opt(2025, 3, 4).unwrap(),
+            sport: Sport::Cycling,
+            duration_seconds: 420,
+            workout_type: WorkoutType::Interval,
+            data_source: DataSource::Power,
+            raw_data: None,
+            summary: WorkoutSummary::default(),
+            notes: None,
+            athlete_id: None,
+            source: None,
+            swim_lengths: Vec::new(),
+            start_time: None,
+            laps: laps.clone(),
+            rpe: Some(7),
+            strength_sets: Vec::new(),
+        }).unwrap();
+
+        assert_eq!(db.load_laps("intervals").unwrap(), laps);
+        let stored = db.load_workout("intervals").unwrap().unwrap();
+        assert_eq!(stored.laps, laps);
+        assert_eq!(stored.rpe, Some(7));
+        assert_eq!(db.query_workouts(WorkoutFilters::default()).unwrap()[0].laps, laps);
+    }
+
+    #[test]
+    fn test_daily_readiness_leaves_recovery_input_alone() {
+        use crate::readiness::DailyReadiness;
+        use crate::recovery::RecoveryMetrics;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mut db = Database::new(dir.path().join("readiness.db")).unwrap();
+        let date = NaiveDate::from_ymd_opt(2025, 3, 6).unwrap();
+
+        let mut recovery = RecoveryMetrics::new(date);
+        recovery.training_readiness = Some(62);
+        db.store_recovery_metrics(&recovery, None).unwrap();
+
+        let readiness = DailyReadiness {
+            date,
+            tsb: Some(dec!(-12.5)),
+            acwr: Some(1.1),
+            hrv_deviation: None,
+            recovery_readiness: Some(62),
+            objective_score: Some(70),
+            wellness_score: Some(40),
+            composite_score: Some(55),
+            recovery_quality: None,
+            planned_tss: None,
+            decision: None,
+            limiting_factors: vec!["wellness".to_string()],
+        };
+        db.store_daily_readiness(&readiness, None).unwrap();
+
+        let stored = db.get_daily_readiness(None, date, date).unwrap();
+        assert_eq!(stored[0].composite_score, Some(55));
+        let recovery = db.get_recovery_metrics(None, date, date).unwrap();
+        assert_eq!(recovery[0].training_readiness, Some(62));
+    }
+
+    #[test]
+    fn test_strength_sets_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut db = Database::new(dir.path().join("strength.db")).unwrap();
+        let sets = vec![
+            StrengthSet {
+                start_offset: 0,
+                duration_seconds: 40,
+                exercise: Some("squat".to_string()),
+                reps: Some(5),
+                weight: Some(dec!(102.5)),
+                set_type: SetType::Active,
+            },
+            StrengthSet {
+                start_offset: 40,
+                duration_seconds: 180,
+                exercise: None,
+                reps: None,
+                weight: None,
+                set_type: SetType::Rest,
+            },
+        ];
+
+        db.store_workout(&Workout {
+            id: "gym".to_string(),
+            date: NaiveDate::from_ymd_opt(2025, 3, 5).unwrap(),
+            sport: Sport::CrossTraining,
+            duration_seconds: 220,
+            workout_type: WorkoutType::Strength,
+            data_source: DataSource::Rpe,
+            raw_data: None,
+            summary: WorkoutSummary::default(),
+            notes: None,
+            athlete_id: None,
+            source: None,
+            swim_lengths: Vec::new(),
+            start_time: None,
+            laps: Vec::new(),
+            rpe: Some(8),
+            strength_sets: sets.clone(),
+        }).unwrap();
+
+        assert_eq!(db.load_strength_sets("gym").unwrap(), sets);
+        assert_eq!(db.load_workout("gym").unwrap().unwrap().strength_sets, sets);
+        assert_eq!(db.query_workouts(WorkoutFilters::default()).unwrap()[0].strength_sets, sets);
+    }
+
+    #[test]
+    fn test_merged_workout_replaces_recordings() {
+        use crate::workout_merge::{Channel, ChannelProvenance, MergedWorkout};
+
+        let dir = tempfile::tempdir().unwrap();
+        let mut db = Database::new(dir.path().join("merge.db")).unwrap();
+        let recording = |id: &str, source: &str| Workout {
+            id: id.to_string(),
+            date: NaiveDate::from_ymd_opt(2025, 5, 4).unwrap(),
+            sport: Sport::Cycling,
+            duration_seconds: 3600,
+            workout_type: WorkoutType::Endurance,
+            data_source: DataSource::Power,
+            raw_data: Some(vec![DataPoint::from(legacy_point(0))]),
+            summary: WorkoutSummary::default(),
+            notes: None,
+            athlete_id: None,
+            source: Some(source.to_string()),
+            swim_lengths: Vec::new(),
+            start_time: None,
+            laps: Vec::new(),
+            rpe: None,
+            strength_sets: Vec::new(),
+        };
+        db.store_workout(&recording("edge", "edge.fit")).unwrap();
+        db.store_workout(&recording("watch", "watch.fit")).unwrap();
+        for plan in ["base", "build"] {
+            db.conn
+                .execute(
+                    "INSERT INTO training_plans (id, name, start_date, plan_json, created_at) VALUES (?1, ?1, '2025-05-01', '{}', '2025-05-01')",
+                    params![plan],
+                )
+                .unwrap();
+        }
+        for (plan, session, workout) in [("base", "ride", "edge"), ("base", "run", "watch"), ("build", "ride", "watch")] {
+            db.conn
+                .execute(
+                    r#"
+                    INSERT INTO planned_session_links (plan_id, planned_workout_id, planned_date, workout_id, duration_score, compliance)
+                    VALUES (?1, ?2, '2025-05-04', ?3, 1.0, 1.0)
+                    "#,
+                    params![plan, session, workout],
+                )
+                .unwrap();
+        }
+
+        let merged = MergedWorkout {
+            workout: Workout {
+                source: Some("edge.fit + watch.fit".to_string()),
+                start_time: DateTime::parse_from_rfc3339("2025-05-04T07:15:00+02:00").ok(),
+                ..recording("merged", "")
+            },
+            provenance: vec![
+                ChannelProvenance { channel: Channel::HeartRate, workout_id: "watch".to_string(), source: Some("watch.fit".to_string()) },
+                ChannelProvenance { channel: Channel::Power, workout_id: "edge".to_string(), source: Some("edge.fit".to_string()) },
+            ],
+        };
+        db.store_merged_workout(&merged, &["edge".to_string(), "watch".to_string()]).unwrap();
+
+        assert!(db.load_workout("edge").unwrap().is_none());
+        assert!(db.load_time_series_data("watch").unwrap().is_none());
+        assert!(db.load_time_series_data("merged").unwrap().is_some());
+        assert_eq!(db.load_workout("merged").unwrap().unwrap().start_time, merged.workout.start_time);
+        assert_eq!(db.get_channel_provenance("merged").unwrap(), merged.provenance);
+
+        let links: Vec<(String, String, String)> = db
+            .conn
+            .prepare("SELECT plan_id, planned_workout_id, workout_id FROM planned_session_links ORDER BY plan_id, planned_workout_id")
+            .unwrap()
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(
+            links,
+            vec![
+                ("base".to_string(), "ride".to_string(), "merged".to_string()),
+                ("build".to_string(), "ride".to_string(), "merged".to_string()),
+            ]
+        );
+    }
+}
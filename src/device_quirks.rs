@@ -8,7 +8,7 @@ use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use crate::models::{DataPoint, Workout};
 
@@ -146,6 +146,9 @@ impl DeviceQuirk {
             QuirkType::RunningDynamicsScaling { gct_scale, vo_scale } => {
                 self.apply_running_dynamics_scaling(workout, *gct_scale, *vo_scale)
             }
+            QuirkType::MissingData { field_name } => {
+                self.apply_missing_data(workout, field_name)
+            }
             _ => Ok(format!("Quirk type {:?} not yet implemented", self.quirk_type)),
         }
     }
@@ -219,6 +222,31 @@ impl DeviceQuirk {
         }
     }
 
+    fn apply_missing_data(&self, workout: &mut Workout, field_name: &str) -> Result<String> {
+        let clear: fn(&mut DataPoint) -> bool = match field_name {
+            "power_balance" => |p| {
+                let had = p.left_power.is_some() || p.right_power.is_some();
+                p.left_power = None;
+                p.right_power = None;
+                had
+            },
+            "cadence" => |p| p.cadence.take().is_some(),
+            "heart_rate" => |p| p.heart_rate.take().is_some(),
+            "temperature" => |p| p.temperature.take().is_some(),
+            "ground_contact_time" => |p| p.ground_contact_time.take().is_some(),
+            "vertical_oscillation" => |p| p.vertical_oscillation.take().is_some(),
+            "stride_length" => |p| p.stride_length.take().is_some(),
+            _ => return Ok(format!("Unknown field '{}' for missing data quirk", field_name)),
+        };
+
+        if let Some(ref mut data_points) = workout.raw_data {
+            let cleared = data_points.iter_mut().map(clear).filter(|&cleared| cleared).count();
+            Ok(format!("Marked {} {} values as missing", cleared, field_name))
+        } else {
+            Ok("No raw data to mark as missing".to_string())
+        }
+    }
+
     fn apply_running_dynamics_scaling(&self, workout: &mut Workout, gct_scale: Option<f64>, vo_scale: Option<f64>) -> Result<String> {
         if let Some(ref mut data_points) = workout.raw_data {
             let mut gct_fixed = 0;
@@ -259,6 +287,229 @@ impl DeviceQuirk {
     }
 }
 
+/// Quirk suspected from a workout's data streams
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuirkSuggestion {
+    /// What the data shows
+    pub description: String,
+
+    /// Quirk that would correct it
+    pub quirk_type: QuirkType,
+
+    /// Share of the examined samples showing the pattern (0.0 - 1.0)
+    pub confidence: f64,
+}
+
+impl QuirkSuggestion {
+    /// Turn the suggestion into a permanent quirk for the recording device
+    ///
+    /// The quirk is limited to the device's firmware version when known, as
+    /// firmware updates often fix such issues.
+    pub fn to_quirk(&self, device: &DeviceInfo) -> DeviceQuirk {
+        DeviceQuirk {
+            manufacturer_id: device.manufacturer_id,
+            product_id: device.product_id,
+            firmware_version_range: device.firmware_version.map(|fw| (fw, fw)),
+            description: self.description.clone(),
+            quirk_type: self.quirk_type.clone(),
+            enabled_by_default: true,
+        }
+    }
+}
+
+/// Heuristics flagging likely device quirks from the data streams themselves
+///
+/// Complements the ID-keyed registry, which only knows hand-entered devices.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuirkDetector {
+    /// Samples a stream needs before it is judged
+    pub min_samples: usize,
+
+    /// Share of samples that must show a pattern
+    pub min_share: f64,
+
+    /// Plausible cycling cadence range in rpm
+    pub cycling_cadence: (u16, u16),
+
+    /// Plausible running cadence range in steps per minute
+    pub running_cadence: (u16, u16),
+
+    /// Plausible ground contact time range in milliseconds
+    pub ground_contact_time: (u16, u16),
+
+    /// Seconds from the start checked for power spikes
+    pub spike_window_seconds: u32,
+
+    /// Start power above this multiple of the later peak counts as a spike
+    pub spike_factor: f64,
+}
+
+impl Default for QuirkDetector {
+    fn default() -> Self {
+        Self {
+            min_samples: 30,
+            min_share: 0.8,
+            cycling_cadence: (40, 130),
+            running_cadence: (140, 220),
+            ground_contact_time: (150, 400),
+            spike_window_seconds: 10,
+            spike_factor: 2.0,
+        }
+    }
+}
+
+impl QuirkDetector {
+    /// Run every detector on a workout
+    pub fn detect(&self, workout: &Workout) -> Vec<QuirkSuggestion> {
+        let Some(points) = workout.raw_data.as_deref() else {
+            return Vec::new();
+        };
+
+        [
+            self.detect_doubled_cadence(workout, points),
+            self.detect_start_power_spikes(points),
+            self.detect_fixed_power_balance(points),
+            self.detect_ground_contact_scaling(points),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+
+    /// Cadence consistently about twice the plausible range for the sport
+    fn detect_doubled_cadence(&self, workout: &Workout, points: &[DataPoint]) -> Option<QuirkSuggestion> {
+        let (min, max) = match workout.sport {
+            crate::models::Sport::Cycling => self.cycling_cadence,
+            crate::models::Sport::Running => self.running_cadence,
+            _ => return None,
+        };
+
+        let cadences: Vec<u16> = points.iter().filter_map(|p| p.cadence).filter(|&c| c > 0).collect();
+        if cadences.len() < self.min_samples {
+            return None;
+        }
+
+        let doubled = cadences
+            .iter()
+            .filter(|&&c| c > max && (min..=max).contains(&(c / 2)))
+            .count();
+        let share = doubled as f64 / cadences.len() as f64;
+
+        (share >= self.min_share).then(|| QuirkSuggestion {
+            description: format!(
+                "Cadence is about twice the plausible {}-{} range in {:.0}% of samples",
+                min,
+                max,
+                share * 100.0
+            ),
+            quirk_type: QuirkType::CadenceScaling { factor: 0.5 },
+            confidence: share,
+        })
+    }
+
+    /// Power in the opening seconds far above anything recorded later
+    fn detect_start_power_spikes(&self, points: &[DataPoint]) -> Option<QuirkSuggestion> {
+        let (start, rest): (Vec<&DataPoint>, Vec<&DataPoint>) = points
+            .iter()
+            .filter(|p| p.power.is_some())
+            .partition(|p| p.timestamp <= self.spike_window_seconds);
+        if start.is_empty() || rest.len() < self.min_samples {
+            return None;
+        }
+
+        let peak = rest.iter().filter_map(|p| p.power).max()?;
+        let threshold = (peak as f64 * self.spike_factor).min(u16::MAX as f64).round() as u16;
+        let spikes = start.iter().filter(|p| p.power.is_some_and(|w| w > threshold)).count();
+        if spikes == 0 {
+            return None;
+        }
+
+        Some(QuirkSuggestion {
+            description: format!(
+                "{} power sample(s) in the first {}s exceed {}W, {}x the later peak",
+                spikes, self.spike_window_seconds, threshold, self.spike_factor
+            ),
+            quirk_type: QuirkType::PowerSpikeStart {
+                threshold,
+                window_seconds: self.spike_window_seconds,
+            },
+            confidence: spikes as f64 / start.len() as f64,
+        })
+    }
+
+    /// Left/right balance pinned at exactly 50%, as single-sided meters report it
+    fn detect_fixed_power_balance(&self, points: &[DataPoint]) -> Option<QuirkSuggestion> {
+        let sides: Vec<(u16, u16)> = points
+            .iter()
+            .filter_map(|p| Some((p.left_power?, p.right_power?)))
+            .filter(|&(left, right)| left + right > 0)
+            .collect();
+        if sides.len() < self.min_samples {
+            return None;
+        }
+
+        // Splitting an odd total leaves the sides one watt apart
+        let even = sides.iter().filter(|&&(left, right)| left.abs_diff(right) <= 1).count();
+        let share = even as f64 / sides.len() as f64;
+
+        (share >= self.min_share).then(|| QuirkSuggestion {
+            description: format!(
+                "Left/right balance is exactly 50% in {:.0}% of samples, likely a single-sided meter",
+                share * 100.0
+            ),
+            quirk_type: QuirkType::MissingData {
+                field_name: "power_balance".to_string(),
+            },
+            confidence: share,
+        })
+    }
+
+    /// Ground contact time consistently ten times off the plausible range
+    fn detect_ground_contact_scaling(&self, points: &[DataPoint]) -> Option<QuirkSuggestion> {
+        let (min, max) = self.ground_contact_time;
+        let values: Vec<u16> = points
+            .iter()
+            .filter_map(|p| p.ground_contact_time)
+            .filter(|&gct| gct > 0)
+            .collect();
+        if values.len() < self.min_samples {
+            return None;
+        }
+
+        let plausible = |gct: u32| (min as u32..=max as u32).contains(&gct);
+        let too_large = values.iter().filter(|&&gct| gct > max && plausible(gct as u32 / 10)).count();
+        let too_small = values.iter().filter(|&&gct| gct < min && plausible(gct as u32 * 10)).count();
+        let (count, scale) = if too_large >= too_small {
+            (too_large, 0.1)
+        } else {
+            (too_small, 10.0)
+        };
+        let share = count as f64 / values.len() as f64;
+
+        (share >= self.min_share).then(|| QuirkSuggestion {
+            description: format!(
+                "Ground contact time is {}x off the plausible {}-{}ms range in {:.0}% of samples",
+                if scale < 1.0 { 10.0 } else { 0.1 },
+                min,
+                max,
+                share * 100.0
+            ),
+            quirk_type: QuirkType::RunningDynamicsScaling {
+                gct_scale: Some(scale),
+                vo_scale: None,
+            },
+            confidence: share,
+        })
+    }
+}
+
+/// Quirks accepted from detector suggestions, as stored in TOML
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct AcceptedQuirks {
+    #[serde(default, rename = "quirk")]
+    quirks: Vec<DeviceQuirk>,
+}
+
 /// Registry of known device quirks
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct QuirkRegistry {
@@ -302,10 +553,11 @@ impl QuirkRegistry {
     /// Add default product mappings
     fn add_default_products(&mut self) {
         // Garmin products
-        self.products.insert((1, 2697), "Edge 520".to_string());
+        self.products.insert((1, 2067), "Edge 520".to_string());
+        self.products.insert((1, 2697), "fenix 5".to_string());
         self.products.insert((1, 2713), "Edge 1030".to_string());
-        self.products.insert((1, 3122), "Edge 130".to_string());
-        self.products.insert((1, 2697), "Forerunner 945".to_string());
+        self.products.insert((1, 2909), "Edge 130".to_string());
+        self.products.insert((1, 3113), "Forerunner 945".to_string());
         self.products.insert((1, 3589), "Forerunner 255".to_string());
 
         // Wahoo products
@@ -318,7 +570,7 @@ impl QuirkRegistry {
         // Garmin Edge 520: Cadence doubled
         self.quirks.push(DeviceQuirk {
             manufacturer_id: 1,
-            product_id: 2697,
+            product_id: 2067,
             firmware_version_range: None,
             description: "Edge 520 reports cadence doubled".to_string(),
             quirk_type: QuirkType::CadenceScaling { factor: 0.5 },
@@ -351,7 +603,7 @@ impl QuirkRegistry {
         // Garmin FR 945: Running dynamics scaling
         self.quirks.push(DeviceQuirk {
             manufacturer_id: 1,
-            product_id: 2697,
+            product_id: 3113,
             firmware_version_range: Some((0, 1000)), // Older firmware versions
             description: "FR 945 running dynamics field scaling issues".to_string(),
             quirk_type: QuirkType::RunningDynamicsScaling {
@@ -429,6 +681,65 @@ impl QuirkRegistry {
     pub fn add_quirk(&mut self, quirk: DeviceQuirk) {
         self.quirks.push(quirk);
     }
+
+    /// Default location of quirks accepted from detector suggestions
+    pub fn accepted_quirks_path() -> PathBuf {
+        dirs::home_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join(".trainrs")
+            .join("device_quirks.toml")
+    }
+
+    /// Add previously accepted quirks, returning how many were loaded
+    pub fn load_accepted<P: AsRef<Path>>(&mut self, path: P) -> Result<usize> {
+        if !path.as_ref().exists() {
+            return Ok(0);
+        }
+
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read accepted quirks file: {}", path.as_ref().display()))?;
+        let accepted: AcceptedQuirks = toml::from_str(&content)
+            .with_context(|| "Failed to parse TOML accepted quirks")?;
+
+        let count = accepted.quirks.len();
+        self.quirks.extend(accepted.quirks);
+        Ok(count)
+    }
+
+    /// Persist a quirk accepted from a suggestion
+    ///
+    /// Returns false when the device already has an accepted quirk of the
+    /// same type for the same firmware range.
+    pub fn save_accepted<P: AsRef<Path>>(path: P, quirk: &DeviceQuirk) -> Result<bool> {
+        let mut accepted: AcceptedQuirks = if path.as_ref().exists() {
+            let content = fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read accepted quirks file: {}", path.as_ref().display()))?;
+            toml::from_str(&content).with_context(|| "Failed to parse TOML accepted quirks")?
+        } else {
+            AcceptedQuirks::default()
+        };
+
+        let duplicate = accepted.quirks.iter().any(|existing| {
+            existing.manufacturer_id == quirk.manufacturer_id
+                && existing.product_id == quirk.product_id
+                && existing.firmware_version_range == quirk.firmware_version_range
+                && std::mem::discriminant(&existing.quirk_type) == std::mem::discriminant(&quirk.quirk_type)
+        });
+        if duplicate {
+            return Ok(false);
+        }
+        accepted.quirks.push(quirk.clone());
+
+        if let Some(parent) = path.as_ref().parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let toml_content = toml::to_string_pretty(&accepted)
+            .with_context(|| "Failed to serialize accepted quirks to TOML")?;
+        fs::write(&path, toml_content)
+            .with_context(|| format!("Failed to write accepted quirks file: {}", path.as_ref().display()))?;
+
+        Ok(true)
+    }
 }
 
 impl Default for QuirkRegistry {
@@ -464,6 +775,9 @@ mod tests {
                 stroke_type: None,
                 lap_number: Some(1),
                 sport_transition: None,
+                temperature: None,
+                drive_length: None,
+                stroke_work: None,
             },
             DataPoint {
                 timestamp: 1,
@@ -483,6 +797,9 @@ mod tests {
                 stroke_type: None,
                 lap_number: Some(1),
                 sport_transition: None,
+                temperature: None,
+                drive_length: None,
+                stroke_work: None,
             },
         ];
 
@@ -502,6 +819,11 @@ mod tests {
             notes: None,
             athlete_id: None,
             source: None,
+            swim_lengths: Vec::new(),
+            start_time: None,
+            laps: Vec::new(),
+            rpe: None,
+            strength_sets: Vec::new(),
         }
     }
 
@@ -662,7 +984,7 @@ mod tests {
         let registry = QuirkRegistry::with_defaults();
 
         // Edge 520 should have cadence scaling quirk
-        let device = DeviceInfo::new(1, 2697);
+        let device = DeviceInfo::new(1, 2067);
         let quirks = registry.get_applicable_quirks(&device);
 
         assert!(!quirks.is_empty());
@@ -674,7 +996,7 @@ mod tests {
         let registry = QuirkRegistry::with_defaults();
         let mut workout = create_test_workout();
 
-        let device = DeviceInfo::new(1, 2697); // Edge 520
+        let device = DeviceInfo::new(1, 2067); // Edge 520
         let messages = registry.apply_quirks(&mut workout, &device, false).unwrap();
 
         assert!(!messages.is_empty());
@@ -687,10 +1009,129 @@ mod tests {
         let registry = QuirkRegistry::with_defaults();
         let mut workout = create_test_workout();
 
-        let device = DeviceInfo::new(1, 2697);
+        let device = DeviceInfo::new(1, 2067);
         let messages = registry.apply_quirks(&mut workout, &device, true).unwrap();
 
         assert_eq!(messages.len(), 1);
         assert!(messages[0].contains("disabled"));
     }
+
+    fn stream_workout(sport: Sport, seconds: u32, fill: impl Fn(u32, &mut DataPoint)) -> Workout {
+        let mut workout = create_test_workout();
+        workout.sport = sport;
+        let template = workout.raw_data.as_ref().unwrap()[0].clone();
+        workout.raw_data = Some(
+            (0..seconds)
+                .map(|t| {
+                    let mut point = DataPoint {
+                        timestamp: t,
+                        cadence: None,
+                        power: None,
+                        left_power: None,
+                        right_power: None,
+                        ground_contact_time: None,
+                        vertical_oscillation: None,
+                        ..template.clone()
+                    };
+                    fill(t, &mut point);
+                    point
+                })
+                .collect(),
+        );
+        workout
+    }
+
+    #[test]
+    fn test_detects_doubled_cadence() {
+        let detector = QuirkDetector::default();
+
+        let doubled = stream_workout(Sport::Cycling, 120, |t, p| p.cadence = Some(170 + (t % 10) as u16));
+        let suggestions = detector.detect(&doubled);
+        assert_eq!(suggestions.len(), 1);
+        assert!(matches!(suggestions[0].quirk_type, QuirkType::CadenceScaling { factor } if factor == 0.5));
+
+        let normal = stream_workout(Sport::Cycling, 120, |t, p| p.cadence = Some(85 + (t % 10) as u16));
+        assert!(detector.detect(&normal).is_empty());
+
+        // Running cadence around 180 is plausible as it is
+        let running = stream_workout(Sport::Running, 120, |_, p| p.cadence = Some(180));
+        assert!(detector.detect(&running).is_empty());
+    }
+
+    #[test]
+    fn test_detects_start_power_spike() {
+        let workout = stream_workout(Sport::Cycling, 120, |t, p| {
+            p.power = Some(if t == 2 { 1900 } else { 200 + (t % 50) as u16 });
+        });
+
+        let suggestions = QuirkDetector::default().detect(&workout);
+        assert_eq!(suggestions.len(), 1);
+        match suggestions[0].quirk_type {
+            QuirkType::PowerSpikeStart { threshold, window_seconds } => {
+                assert_eq!(threshold, 498);
+                assert_eq!(window_seconds, 10);
+            }
+            ref other => panic!("unexpected quirk {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_detects_fixed_power_balance() {
+        let fixed = stream_workout(Sport::Cycling, 60, |t, p| {
+            let power = 201 + t as u16;
+            p.power = Some(power);
+            p.left_power = Some(power / 2);
+            p.right_power = Some(power - power / 2);
+        });
+        let suggestions = QuirkDetector::default().detect(&fixed);
+        assert_eq!(suggestions.len(), 1);
+        assert!(matches!(&suggestions[0].quirk_type, QuirkType::MissingData { field_name } if field_name == "power_balance"));
+
+        let mut corrected = fixed.clone();
+        suggestions[0].to_quirk(&DeviceInfo::new(69, 7)).apply(&mut corrected).unwrap();
+        let points = corrected.raw_data.unwrap();
+        assert!(points.iter().all(|p| p.left_power.is_none() && p.right_power.is_none()));
+        assert_eq!(points[0].power, Some(201));
+
+        let measured = stream_workout(Sport::Cycling, 60, |t, p| {
+            p.left_power = Some(100 + (t % 7) as u16);
+            p.right_power = Some(104 - (t % 5) as u16);
+        });
+        assert!(QuirkDetector::default().detect(&measured).is_empty());
+    }
+
+    #[test]
+    fn test_detects_ground_contact_time_scaling() {
+        let workout = stream_workout(Sport::Running, 60, |t, p| p.ground_contact_time = Some(2400 + t as u16));
+        let suggestions = QuirkDetector::default().detect(&workout);
+        assert_eq!(suggestions.len(), 1);
+        assert!(matches!(
+            suggestions[0].quirk_type,
+            QuirkType::RunningDynamicsScaling { gct_scale: Some(scale), vo_scale: None } if scale == 0.1
+        ));
+
+        let plausible = stream_workout(Sport::Running, 60, |_, p| p.ground_contact_time = Some(245));
+        assert!(QuirkDetector::default().detect(&plausible).is_empty());
+    }
+
+    #[test]
+    fn test_accepted_quirks_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("device_quirks.toml");
+        let workout = stream_workout(Sport::Cycling, 120, |_, p| p.cadence = Some(176));
+        let suggestion = QuirkDetector::default().detect(&workout).remove(0);
+        let device = DeviceInfo::new(1, 2697).with_firmware(410);
+
+        assert!(QuirkRegistry::save_accepted(&path, &suggestion.to_quirk(&device)).unwrap());
+        assert!(!QuirkRegistry::save_accepted(&path, &suggestion.to_quirk(&device)).unwrap());
+
+        let mut registry = QuirkRegistry::with_defaults();
+        assert_eq!(registry.load_accepted(&path).unwrap(), 1);
+        assert_eq!(registry.get_applicable_quirks(&device).len(), 1);
+        assert!(registry.get_applicable_quirks(&DeviceInfo::new(1, 2697).with_firmware(420)).is_empty());
+
+        let mut corrected = workout.clone();
+        registry.apply_quirks(&mut corrected, &device, false).unwrap();
+        assert_eq!(corrected.raw_data.unwrap()[0].cadence, Some(88));
+    }
 }
@@ -314,6 +314,11 @@ mod tests {
             notes: Some("Test workout".to_string()),
             athlete_id: Some("test_athlete".to_string()),
             source: Some("test_source".to_string()),
+            swim_lengths: Vec::new(),
+            start_time: None,
+            laps: Vec::new(),
+            rpe: None,
+            strength_sets: Vec::new(),
         }
     }
 
@@ -0,0 +1,115 @@
+//! ERG and MRC course file export
+//!
+//! Both formats describe a power profile as (minutes, value) points: ERG in
+//! absolute watts for a given FTP, MRC in percent of FTP. Each step adds a
+//! start and an end point so steady steps stay flat and ramps interpolate.
+
+use std::fmt::Write as _;
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+
+use crate::models::AthleteProfile;
+use crate::structured_workout::StructuredWorkout;
+
+use super::planned::step_power;
+use super::ExportError;
+
+pub struct ErgExporter;
+
+impl ErgExporter {
+    /// Course file in absolute watts; requires the athlete's FTP
+    pub fn generate_erg(workout: &StructuredWorkout, name: &str, athlete: &AthleteProfile) -> Result<String, ExportError> {
+        let ftp = athlete
+            .ftp
+            .ok_or_else(|| ExportError::InsufficientData("FTP is required for ERG export".to_string()))?;
+        let ftp = Decimal::from(ftp);
+
+        let points = Self::course_points(workout, athlete)
+            .into_iter()
+            .map(|(minutes, power)| (minutes, (power * ftp).round()))
+            .collect::<Vec<_>>();
+
+        Ok(Self::write_course(name, Some(ftp), "WATTS", &points))
+    }
+
+    /// Course file in percent of FTP
+    pub fn generate_mrc(workout: &StructuredWorkout, name: &str, athlete: &AthleteProfile) -> Result<String, ExportError> {
+        let points = Self::course_points(workout, athlete)
+            .into_iter()
+            .map(|(minutes, power)| (minutes, (power * dec!(100)).round_dp(1)))
+            .collect::<Vec<_>>();
+
+        Ok(Self::write_course(name, None, "PERCENT", &points))
+    }
+
+    /// (elapsed minutes, fraction of FTP) points for every step
+    fn course_points(workout: &StructuredWorkout, athlete: &AthleteProfile) -> Vec<(Decimal, Decimal)> {
+        let mut points = Vec::new();
+        let mut elapsed = 0u32;
+
+        for step in workout.flatten() {
+            let seconds = step.estimated_seconds(&workout.sport, athlete);
+            let (start, end) = step_power(step, athlete);
+            points.push((Decimal::from(elapsed) / dec!(60), start));
+            elapsed += seconds;
+            points.push((Decimal::from(elapsed) / dec!(60), end));
+        }
+
+        points
+    }
+
+    fn write_course(name: &str, ftp: Option<Decimal>, unit: &str, points: &[(Decimal, Decimal)]) -> String {
+        let mut out = String::new();
+        out.push_str("[COURSE HEADER]\r\n");
+        out.push_str("VERSION = 2\r\n");
+        out.push_str("UNITS = ENGLISH\r\n");
+        let _ = write!(out, "DESCRIPTION = {}\r\n", name);
+        let _ = write!(out, "FILE NAME = {}\r\n", name);
+        if let Some(ftp) = ftp {
+            let _ = write!(out, "FTP = {}\r\n", ftp);
+        }
+        let _ = write!(out, "MINUTES {}\r\n", unit);
+        out.push_str("[END COURSE HEADER]\r\n");
+
+        out.push_str("[COURSE DATA]\r\n");
+        for (minutes, value) in points {
+            let _ = write!(out, "{:.2}\t{}\r\n", minutes, value.normalize());
+        }
+        out.push_str("[END COURSE DATA]\r\n");
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::planned::tests::{athlete, threshold_session};
+
+    #[test]
+    fn test_erg_in_watts() {
+        let erg = ErgExporter::generate_erg(&threshold_session(), "Threshold", &athlete()).unwrap();
+
+        assert!(erg.contains("FTP = 250\r\n"));
+        assert!(erg.contains("MINUTES WATTS\r\n"));
+        // Warmup ramps 50% → 65% over 10 minutes, first interval holds 100%
+        assert!(erg.contains("0.00\t125\r\n"));
+        assert!(erg.contains("10.00\t162\r\n"));
+        assert!(erg.contains("10.00\t250\r\n18.00\t250\r\n"));
+        // 10 + 4 × 12 + 10 minutes
+        assert!(erg.contains("68.00\t125\r\n[END COURSE DATA]"));
+    }
+
+    #[test]
+    fn test_mrc_in_percent_and_erg_needs_ftp() {
+        let mut athlete = athlete();
+        athlete.ftp = None;
+
+        let mrc = ErgExporter::generate_mrc(&threshold_session(), "Threshold", &athlete).unwrap();
+        assert!(mrc.contains("MINUTES PERCENT\r\n"));
+        assert!(!mrc.contains("FTP ="));
+        assert!(mrc.contains("18.00\t100\r\n"));
+
+        assert!(ErgExporter::generate_erg(&threshold_session(), "Threshold", &athlete).is_err());
+    }
+}
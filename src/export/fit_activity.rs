@@ -0,0 +1,360 @@
+//! FIT activity file export
+//!
+//! Encodes a recorded workout as a FIT file of type `activity`: a `file_id`,
+//! one `record` per data point, one `lap` per device lap (a single lap over
+//! the whole workout when none were recorded), a `session` and an `activity`
+//! message. The activity's `local_timestamp` carries the UTC offset of the
+//! start time, so the file imports back on the same local day.
+
+use std::fs;
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+
+use crate::models::{Lap, LapIntensity, LapTrigger, Workout};
+
+use super::fit_workout::{
+    finish_file, fit_sport, write_definition, BASE_ENUM, BASE_UINT16, BASE_UINT32, FIT_EPOCH_OFFSET,
+    INTENSITY_ACTIVE, INTENSITY_COOLDOWN, INTENSITY_REST, INTENSITY_WARMUP, MESG_FILE_ID,
+};
+use super::ExportError;
+
+const MESG_SESSION: u16 = 18;
+const MESG_LAP: u16 = 19;
+const MESG_RECORD: u16 = 20;
+const MESG_ACTIVITY: u16 = 34;
+
+const BASE_SINT8: u8 = 0x01;
+const BASE_UINT8: u8 = 0x02;
+
+const INVALID_UINT8: u8 = 0xFF;
+const INVALID_SINT8: i8 = 0x7F;
+const INVALID_UINT16: u16 = 0xFFFF;
+const INVALID_UINT32: u32 = 0xFFFF_FFFF;
+
+// event enums for lap, session and activity messages
+const EVENT_LAP: u8 = 9;
+const EVENT_SESSION: u8 = 8;
+const EVENT_ACTIVITY: u8 = 26;
+const EVENT_TYPE_STOP: u8 = 1;
+
+pub struct FitActivityExporter;
+
+impl FitActivityExporter {
+    pub fn export_workout(workout: &Workout, path: &Path) -> Result<(), ExportError> {
+        let data = Self::generate_fit(workout)?;
+        fs::write(path, data)?;
+        Ok(())
+    }
+
+    pub fn generate_fit(workout: &Workout) -> Result<Vec<u8>, ExportError> {
+        let start = workout.start_time.unwrap_or_else(|| {
+            workout.date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc().fixed_offset()
+        });
+        let start_timestamp = fit_timestamp(start.with_timezone(&Utc))?;
+        let end_timestamp = start_timestamp + workout.duration_seconds;
+
+        let laps = if workout.laps.is_empty() {
+            vec![Lap {
+                start_offset: 0,
+                duration_seconds: workout.duration_seconds,
+                distance: workout.summary.total_distance,
+                avg_heart_rate: workout.summary.avg_heart_rate,
+                max_heart_rate: workout.summary.max_heart_rate,
+                avg_power: workout.summary.avg_power,
+                max_power: None,
+                trigger: LapTrigger::SessionEnd,
+                intensity: LapIntensity::Active,
+            }]
+        } else {
+            workout.laps.clone()
+        };
+        let lap_count = u16::try_from(laps.len())
+            .map_err(|_| ExportError::SerializationError("Too many laps for FIT".to_string()))?;
+
+        let mut data = Vec::new();
+
+        // file_id
+        write_definition(&mut data, 0, MESG_FILE_ID, &[(0, 1, BASE_ENUM), (1, 2, BASE_UINT16), (2, 2, BASE_UINT16), (4, 4, BASE_UINT32)]);
+        data.push(0);
+        data.push(4); // file type: activity
+        data.extend_from_slice(&255u16.to_le_bytes()); // manufacturer: development
+        data.extend_from_slice(&0u16.to_le_bytes());
+        data.extend_from_slice(&start_timestamp.to_le_bytes());
+
+        // record
+        write_definition(
+            &mut data,
+            1,
+            MESG_RECORD,
+            &[
+                (253, 4, BASE_UINT32),
+                (2, 2, BASE_UINT16),
+                (3, 1, BASE_UINT8),
+                (4, 1, BASE_UINT8),
+                (5, 4, BASE_UINT32),
+                (6, 2, BASE_UINT16),
+                (7, 2, BASE_UINT16),
+                (13, 1, BASE_SINT8),
+            ],
+        );
+        for point in workout.raw_data.as_deref().unwrap_or_default() {
+            data.push(1);
+            data.extend_from_slice(&(start_timestamp + point.timestamp).to_le_bytes());
+            // altitude: (m + 500) × 5
+            let altitude = point.elevation.map_or(INVALID_UINT16, |e| ((i32::from(e) + 500) * 5).clamp(0, 0xFFFE) as u16);
+            data.extend_from_slice(&altitude.to_le_bytes());
+            data.push(point.heart_rate.map_or(INVALID_UINT8, |hr| hr.min(254) as u8));
+            data.push(point.cadence.map_or(INVALID_UINT8, |c| c.min(254) as u8));
+            data.extend_from_slice(&scaled_u32(point.distance, 100).to_le_bytes());
+            data.extend_from_slice(&scaled_u16(point.speed, 1000).to_le_bytes());
+            data.extend_from_slice(&point.power.map_or(INVALID_UINT16, |p| p.min(0xFFFE)).to_le_bytes());
+            data.push(point.temperature.unwrap_or(INVALID_SINT8) as u8);
+        }
+
+        // lap
+        write_definition(
+            &mut data,
+            2,
+            MESG_LAP,
+            &[
+                (254, 2, BASE_UINT16),
+                (253, 4, BASE_UINT32),
+                (0, 1, BASE_ENUM),
+                (1, 1, BASE_ENUM),
+                (2, 4, BASE_UINT32),
+                (7, 4, BASE_UINT32),
+                (8, 4, BASE_UINT32),
+                (9, 4, BASE_UINT32),
+                (15, 1, BASE_UINT8),
+                (16, 1, BASE_UINT8),
+                (19, 2, BASE_UINT16),
+                (20, 2, BASE_UINT16),
+                (23, 1, BASE_ENUM),
+                (24, 1, BASE_ENUM),
+                (25, 1, BASE_ENUM),
+            ],
+        );
+        for (index, lap) in laps.iter().enumerate() {
+            let lap_start = start_timestamp + lap.start_offset;
+            data.push(2);
+            data.extend_from_slice(&(index as u16).to_le_bytes());
+            data.extend_from_slice(&(lap_start + lap.duration_seconds).to_le_bytes());
+            data.push(EVENT_LAP);
+            data.push(EVENT_TYPE_STOP);
+            data.extend_from_slice(&lap_start.to_le_bytes());
+            data.extend_from_slice(&(lap.duration_seconds * 1000).to_le_bytes());
+            data.extend_from_slice(&(lap.duration_seconds * 1000).to_le_bytes());
+            data.extend_from_slice(&scaled_u32(lap.distance, 100).to_le_bytes());
+            data.push(lap.avg_heart_rate.map_or(INVALID_UINT8, |hr| hr.min(254) as u8));
+            data.push(lap.max_heart_rate.map_or(INVALID_UINT8, |hr| hr.min(254) as u8));
+            data.extend_from_slice(&lap.avg_power.unwrap_or(INVALID_UINT16).to_le_bytes());
+            data.extend_from_slice(&lap.max_power.unwrap_or(INVALID_UINT16).to_le_bytes());
+            data.push(fit_intensity(lap.intensity));
+            data.push(fit_lap_trigger(lap.trigger));
+            data.push(fit_sport(&workout.sport));
+        }
+
+        // session
+        write_definition(
+            &mut data,
+            3,
+            MESG_SESSION,
+            &[
+                (254, 2, BASE_UINT16),
+                (253, 4, BASE_UINT32),
+                (0, 1, BASE_ENUM),
+                (1, 1, BASE_ENUM),
+                (2, 4, BASE_UINT32),
+                (5, 1, BASE_ENUM),
+                (7, 4, BASE_UINT32),
+                (8, 4, BASE_UINT32),
+                (9, 4, BASE_UINT32),
+                (16, 1, BASE_UINT8),
+                (17, 1, BASE_UINT8),
+                (20, 2, BASE_UINT16),
+                (25, 2, BASE_UINT16),
+                (26, 2, BASE_UINT16),
+            ],
+        );
+        let summary = &workout.summary;
+        data.push(3);
+        data.extend_from_slice(&0u16.to_le_bytes());
+        data.extend_from_slice(&end_timestamp.to_le_bytes());
+        data.push(EVENT_SESSION);
+        data.push(EVENT_TYPE_STOP);
+        data.extend_from_slice(&start_timestamp.to_le_bytes());
+        data.push(fit_sport(&workout.sport));
+        data.extend_from_slice(&(workout.duration_seconds * 1000).to_le_bytes());
+        data.extend_from_slice(&(workout.duration_seconds * 1000).to_le_bytes());
+        data.extend_from_slice(&scaled_u32(summary.total_distance, 100).to_le_bytes());
+        data.push(summary.avg_heart_rate.map_or(INVALID_UINT8, |hr| hr.min(254) as u8));
+        data.push(summary.max_heart_rate.map_or(INVALID_UINT8, |hr| hr.min(254) as u8));
+        data.extend_from_slice(&summary.avg_power.unwrap_or(INVALID_UINT16).to_le_bytes());
+        data.extend_from_slice(&0u16.to_le_bytes());
+        data.extend_from_slice(&lap_count.to_le_bytes());
+
+        // activity
+        write_definition(
+            &mut data,
+            4,
+            MESG_ACTIVITY,
+            &[
+                (253, 4, BASE_UINT32),
+                (0, 4, BASE_UINT32),
+                (1, 2, BASE_UINT16),
+                (2, 1, BASE_ENUM),
+                (3, 1, BASE_ENUM),
+                (4, 1, BASE_ENUM),
+                (5, 4, BASE_UINT32),
+            ],
+        );
+        let local_end = u32::try_from(i64::from(end_timestamp) + i64::from(start.offset().local_minus_utc()))
+            .unwrap_or(INVALID_UINT32);
+        data.push(4);
+        data.extend_from_slice(&end_timestamp.to_le_bytes());
+        data.extend_from_slice(&(workout.duration_seconds * 1000).to_le_bytes());
+        data.extend_from_slice(&1u16.to_le_bytes());
+        data.push(0); // type: manual
+        data.push(EVENT_ACTIVITY);
+        data.push(EVENT_TYPE_STOP);
+        data.extend_from_slice(&local_end.to_le_bytes());
+
+        Ok(finish_file(data))
+    }
+}
+
+/// Seconds since the FIT epoch
+fn fit_timestamp(time: DateTime<Utc>) -> Result<u32, ExportError> {
+    u32::try_from(time.timestamp() - FIT_EPOCH_OFFSET)
+        .map_err(|_| ExportError::SerializationError(format!("Start time {} is outside the FIT range", time)))
+}
+
+fn scaled_u32(value: Option<Decimal>, scale: u32) -> u32 {
+    value
+        .and_then(|v| (v * Decimal::from(scale)).round().to_u32())
+        .filter(|v| *v < INVALID_UINT32)
+        .unwrap_or(INVALID_UINT32)
+}
+
+fn scaled_u16(value: Option<Decimal>, scale: u16) -> u16 {
+    value
+        .and_then(|v| (v * Decimal::from(scale)).round().to_u16())
+        .filter(|v| *v < INVALID_UINT16)
+        .unwrap_or(INVALID_UINT16)
+}
+
+fn fit_intensity(intensity: LapIntensity) -> u8 {
+    match intensity {
+        LapIntensity::Active => INTENSITY_ACTIVE,
+        LapIntensity::Rest => INTENSITY_REST,
+        LapIntensity::Warmup => INTENSITY_WARMUP,
+        LapIntensity::Cooldown => INTENSITY_COOLDOWN,
+    }
+}
+
+fn fit_lap_trigger(trigger: LapTrigger) -> u8 {
+    match trigger {
+        LapTrigger::Manual => 0,
+        LapTrigger::Time => 1,
+        LapTrigger::Distance => 2,
+        LapTrigger::Position => 4,
+        LapTrigger::SessionEnd | LapTrigger::Unknown => 7,
+        LapTrigger::FitnessEquipment => 8,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::import::fit::FitImporter;
+    use crate::import::ImportFormat;
+    use crate::models::{DataPoint, DataSource, Sport, WorkoutSummary, WorkoutType};
+    use chrono::NaiveDate;
+    use rust_decimal_macros::dec;
+
+    fn point(timestamp: u32, power: u16, heart_rate: u16) -> DataPoint {
+        DataPoint {
+            timestamp,
+            heart_rate: Some(heart_rate),
+            power: Some(power),
+            pace: None,
+            elevation: Some(120),
+            cadence: Some(90),
+            speed: Some(dec!(9.5)),
+            distance: Some(Decimal::from(timestamp) * dec!(9.5)),
+            left_power: None,
+            right_power: None,
+            ground_contact_time: None,
+            vertical_oscillation: None,
+            stride_length: None,
+            stroke_count: None,
+            stroke_type: None,
+            lap_number: None,
+            sport_transition: None,
+            temperature: Some(18),
+            drive_length: None,
+            stroke_work: None,
+        }
+    }
+
+    fn lap(start_offset: u32, power: u16, trigger: LapTrigger, intensity: LapIntensity) -> Lap {
+        Lap {
+            start_offset,
+            duration_seconds: 60,
+            distance: Some(dec!(570)),
+            avg_heart_rate: Some(150),
+            max_heart_rate: Some(160),
+            avg_power: Some(power),
+            max_power: Some(power + 20),
+            trigger,
+            intensity,
+        }
+    }
+
+    #[test]
+    fn test_laps_survive_a_round_trip() {
+        let start = DateTime::parse_from_rfc3339("2025-06-01T23:30:00-04:00").unwrap();
+        let workout = Workout {
+            id: "intervals".to_string(),
+            date: NaiveDate::from_ymd_opt(2025, 6, 1).unwrap(),
+            start_time: Some(start),
+            sport: Sport::Cycling,
+            duration_seconds: 120,
+            workout_type: WorkoutType::Interval,
+            data_source: DataSource::Power,
+            raw_data: Some((0..120).map(|t| point(t, if t < 60 { 300 } else { 120 }, 150)).collect()),
+            summary: WorkoutSummary {
+                avg_power: Some(210),
+                total_distance: Some(dec!(1140)),
+                ..WorkoutSummary::default()
+            },
+            notes: None,
+            athlete_id: None,
+            source: None,
+            swim_lengths: Vec::new(),
+            laps: vec![
+                lap(0, 300, LapTrigger::Manual, LapIntensity::Active),
+                lap(60, 120, LapTrigger::Time, LapIntensity::Rest),
+            ],
+            rpe: None,
+            strength_sets: Vec::new(),
+        };
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("intervals.fit");
+        FitActivityExporter::export_workout(&workout, &path).unwrap();
+
+        let imported = FitImporter::new().with_validation_enabled(false).import_file(&path).unwrap();
+        let imported = &imported[0];
+        assert_eq!(imported.start_time, Some(start));
+        assert_eq!(imported.date, workout.date);
+        assert_eq!(imported.duration_seconds, 120);
+        assert_eq!(imported.raw_data.as_ref().unwrap().len(), 120);
+        assert_eq!(imported.laps, workout.laps);
+        // Points are numbered by the lap they fall in
+        assert_eq!(imported.raw_data.as_ref().unwrap()[60].lap_number, Some(2));
+    }
+}
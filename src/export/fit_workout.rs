@@ -0,0 +1,348 @@
+//! FIT workout file export
+//!
+//! Encodes a structured workout as a FIT file of type `workout` with a
+//! `file_id`, a `workout` and one `workout_step` message per step. Repeat
+//! blocks become a "repeat until steps complete" step pointing back at the
+//! first step of the block.
+//!
+//! Targets use the FIT custom range encoding:
+//! - power: 0-1000 is %FTP
+//! - heart rate: values above 100 are bpm + 100
+//! - speed: m/s × 1000
+
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+
+use crate::models::{AthleteProfile, Sport};
+use crate::structured_workout::{StepDuration, StepKind, StepTarget, StructuredWorkout, WorkoutBlock, WorkoutStep};
+
+use super::ExportError;
+
+const FIT_PROTOCOL_VERSION: u8 = 0x20;
+const FIT_PROFILE_VERSION: u16 = 2132;
+/// Seconds between the Unix epoch and the FIT epoch (1989-12-31T00:00:00Z)
+pub(super) const FIT_EPOCH_OFFSET: i64 = 631_065_600;
+
+pub(super) const MESG_FILE_ID: u16 = 0;
+const MESG_WORKOUT: u16 = 26;
+const MESG_WORKOUT_STEP: u16 = 27;
+
+pub(super) const BASE_ENUM: u8 = 0x00;
+const BASE_STRING: u8 = 0x07;
+pub(super) const BASE_UINT16: u8 = 0x84;
+pub(super) const BASE_UINT32: u8 = 0x86;
+const BASE_UINT32Z: u8 = 0x8C;
+
+const WORKOUT_NAME_SIZE: u8 = 32;
+const STEP_NAME_SIZE: u8 = 16;
+
+// workout_step enums
+const DURATION_TIME: u8 = 0;
+const DURATION_DISTANCE: u8 = 1;
+const DURATION_REPEAT_UNTIL_STEPS_COMPLETE: u8 = 6;
+const TARGET_SPEED: u8 = 0;
+const TARGET_HEART_RATE: u8 = 1;
+const TARGET_OPEN: u8 = 2;
+const TARGET_POWER: u8 = 4;
+pub(super) const INTENSITY_ACTIVE: u8 = 0;
+pub(super) const INTENSITY_REST: u8 = 1;
+pub(super) const INTENSITY_WARMUP: u8 = 2;
+pub(super) const INTENSITY_COOLDOWN: u8 = 3;
+
+pub struct FitWorkoutExporter;
+
+/// Encoded values of one workout_step message
+struct FitStep {
+    name: String,
+    duration_type: u8,
+    duration_value: u32,
+    target_type: u8,
+    target_value: u32,
+    target_low: u32,
+    target_high: u32,
+    intensity: u8,
+}
+
+impl FitWorkoutExporter {
+    pub fn generate_fit(workout: &StructuredWorkout, name: &str, athlete: &AthleteProfile) -> Result<Vec<u8>, ExportError> {
+        let steps = Self::encode_steps(workout, athlete)?;
+        let step_count = u16::try_from(steps.len())
+            .map_err(|_| ExportError::SerializationError("Too many workout steps for FIT".to_string()))?;
+
+        let mut data = Vec::new();
+
+        // file_id
+        write_definition(&mut data, 0, MESG_FILE_ID, &[(0, 1, BASE_ENUM), (1, 2, BASE_UINT16), (2, 2, BASE_UINT16), (4, 4, BASE_UINT32)]);
+        data.push(0);
+        data.push(5); // file type: workout
+        data.extend_from_slice(&255u16.to_le_bytes()); // manufacturer: development
+        data.extend_from_slice(&0u16.to_le_bytes());
+        let created = (chrono::Utc::now().timestamp() - FIT_EPOCH_OFFSET).max(0) as u32;
+        data.extend_from_slice(&created.to_le_bytes());
+
+        // workout
+        write_definition(
+            &mut data,
+            1,
+            MESG_WORKOUT,
+            &[(4, 1, BASE_ENUM), (5, 4, BASE_UINT32Z), (6, 2, BASE_UINT16), (8, WORKOUT_NAME_SIZE, BASE_STRING)],
+        );
+        data.push(1);
+        data.push(fit_sport(&workout.sport));
+        data.extend_from_slice(&0x20u32.to_le_bytes()); // capabilities: tcx
+        data.extend_from_slice(&step_count.to_le_bytes());
+        write_string(&mut data, name, WORKOUT_NAME_SIZE);
+
+        // workout_step
+        write_definition(
+            &mut data,
+            2,
+            MESG_WORKOUT_STEP,
+            &[
+                (254, 2, BASE_UINT16),
+                (0, STEP_NAME_SIZE, BASE_STRING),
+                (1, 1, BASE_ENUM),
+                (2, 4, BASE_UINT32),
+                (3, 1, BASE_ENUM),
+                (4, 4, BASE_UINT32),
+                (5, 4, BASE_UINT32),
+                (6, 4, BASE_UINT32),
+                (7, 1, BASE_ENUM),
+            ],
+        );
+        for (index, step) in steps.iter().enumerate() {
+            data.push(2);
+            data.extend_from_slice(&(index as u16).to_le_bytes());
+            write_string(&mut data, &step.name, STEP_NAME_SIZE);
+            data.push(step.duration_type);
+            data.extend_from_slice(&step.duration_value.to_le_bytes());
+            data.push(step.target_type);
+            data.extend_from_slice(&step.target_value.to_le_bytes());
+            data.extend_from_slice(&step.target_low.to_le_bytes());
+            data.extend_from_slice(&step.target_high.to_le_bytes());
+            data.push(step.intensity);
+        }
+
+        Ok(finish_file(data))
+    }
+
+    fn encode_steps(workout: &StructuredWorkout, athlete: &AthleteProfile) -> Result<Vec<FitStep>, ExportError> {
+        let mut steps = Vec::new();
+
+        for block in &workout.blocks {
+            match block {
+                WorkoutBlock::Step(step) => steps.push(Self::encode_step(step, athlete)),
+                WorkoutBlock::Repeat { count, steps: group } => {
+                    let first_index = steps.len() as u32;
+                    steps.extend(group.iter().map(|step| Self::encode_step(step, athlete)));
+                    steps.push(FitStep {
+                        name: String::new(),
+                        duration_type: DURATION_REPEAT_UNTIL_STEPS_COMPLETE,
+                        duration_value: first_index,
+                        target_type: TARGET_OPEN,
+                        target_value: *count,
+                        target_low: 0,
+                        target_high: 0,
+                        intensity: INTENSITY_ACTIVE,
+                    });
+                }
+            }
+        }
+
+        if steps.is_empty() {
+            return Err(ExportError::InsufficientData("Workout has no steps".to_string()));
+        }
+        Ok(steps)
+    }
+
+    fn encode_step(step: &WorkoutStep, athlete: &AthleteProfile) -> FitStep {
+        let (duration_type, duration_value) = match step.duration {
+            StepDuration::Time { seconds } => (DURATION_TIME, seconds * 1000),
+            StepDuration::Distance { meters } => (DURATION_DISTANCE, meters * 100),
+        };
+
+        let (target_type, target_low, target_high) = match step.target {
+            StepTarget::PercentFtp { low, high } => (TARGET_POWER, u32::from(low), u32::from(high)),
+            StepTarget::PercentLthr { low, high } if athlete.lthr.is_some() => {
+                let lthr = u32::from(athlete.lthr.unwrap());
+                (TARGET_HEART_RATE, lthr * u32::from(low) / 100 + 100, lthr * u32::from(high) / 100 + 100)
+            }
+            StepTarget::Pace { fast, slow } => (TARGET_SPEED, speed_mm_per_s(slow), speed_mm_per_s(fast)),
+            _ => (TARGET_OPEN, 0, 0),
+        };
+
+        let mut name = match step.kind {
+            StepKind::Warmup => "Warmup".to_string(),
+            StepKind::Interval => "Interval".to_string(),
+            StepKind::Recovery => "Recovery".to_string(),
+            StepKind::Steady => "Steady".to_string(),
+            StepKind::Cooldown => "Cooldown".to_string(),
+        };
+        if let StepTarget::Rpe(rpe) = step.target {
+            name = format!("{} RPE {}", name, rpe);
+        }
+
+        let intensity = match step.kind {
+            StepKind::Warmup => INTENSITY_WARMUP,
+            StepKind::Cooldown => INTENSITY_COOLDOWN,
+            StepKind::Recovery => INTENSITY_REST,
+            StepKind::Interval | StepKind::Steady => INTENSITY_ACTIVE,
+        };
+
+        FitStep {
+            name,
+            duration_type,
+            duration_value,
+            target_type,
+            target_value: 0,
+            target_low,
+            target_high,
+            intensity,
+        }
+    }
+}
+
+pub(super) fn fit_sport(sport: &Sport) -> u8 {
+    match sport {
+        Sport::Running => 1,
+        Sport::Cycling => 2,
+        Sport::Swimming => 5,
+        Sport::Rowing => 15,
+        Sport::CrossTraining => 10,
+        Sport::Triathlon => 0,
+    }
+}
+
+/// Pace in min/km to speed in mm/s
+fn speed_mm_per_s(minutes_per_km: Decimal) -> u32 {
+    if minutes_per_km <= Decimal::ZERO {
+        return 0;
+    }
+    (dec!(1000000) / (minutes_per_km * dec!(60))).round().to_u32().unwrap_or(0)
+}
+
+pub(super) fn write_definition(data: &mut Vec<u8>, local_type: u8, global: u16, fields: &[(u8, u8, u8)]) {
+    data.push(0x40 | local_type);
+    data.push(0); // reserved
+    data.push(0); // little endian
+    data.extend_from_slice(&global.to_le_bytes());
+    data.push(fields.len() as u8);
+    for (number, size, base_type) in fields {
+        data.extend_from_slice(&[*number, *size, *base_type]);
+    }
+}
+
+/// Null-terminated, null-padded fixed-size string truncated on a char boundary
+fn write_string(data: &mut Vec<u8>, value: &str, size: u8) {
+    let max = size as usize - 1;
+    let mut end = value.len().min(max);
+    while !value.is_char_boundary(end) {
+        end -= 1;
+    }
+    data.extend_from_slice(&value.as_bytes()[..end]);
+    data.extend(std::iter::repeat_n(0u8, size as usize - end));
+}
+
+/// Prefix the 14-byte header and append the file CRC
+pub(super) fn finish_file(data: Vec<u8>) -> Vec<u8> {
+    let mut file = Vec::with_capacity(data.len() + 16);
+    file.push(14);
+    file.push(FIT_PROTOCOL_VERSION);
+    file.extend_from_slice(&FIT_PROFILE_VERSION.to_le_bytes());
+    file.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    file.extend_from_slice(b".FIT");
+    let header_crc = fit_crc(&file);
+    file.extend_from_slice(&header_crc.to_le_bytes());
+
+    file.extend_from_slice(&data);
+    let crc = fit_crc(&file);
+    file.extend_from_slice(&crc.to_le_bytes());
+    file
+}
+
+fn fit_crc(bytes: &[u8]) -> u16 {
+    const TABLE: [u16; 16] = [
+        0x0000, 0xCC01, 0xD801, 0x1400, 0xF001, 0x3C00, 0x2800, 0xE401,
+        0xA001, 0x6C00, 0x7800, 0xB401, 0x5000, 0x9C01, 0x8801, 0x4400,
+    ];
+
+    bytes.iter().fold(0u16, |mut crc, &byte| {
+        let mut tmp = TABLE[(crc & 0xF) as usize];
+        crc = (crc >> 4) & 0x0FFF;
+        crc = crc ^ tmp ^ TABLE[(byte & 0xF) as usize];
+        tmp = TABLE[(crc & 0xF) as usize];
+        crc = (crc >> 4) & 0x0FFF;
+        crc ^ tmp ^ TABLE[((byte >> 4) & 0xF) as usize]
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::planned::tests::{athlete, threshold_session};
+    use fitparser::profile::MesgNum;
+    use fitparser::Value;
+
+    fn field(record: &fitparser::FitDataRecord, name: &str) -> Value {
+        record
+            .fields()
+            .iter()
+            .find(|f| f.name() == name)
+            .map(|f| f.value().clone())
+            .unwrap_or(Value::String(String::new()))
+    }
+
+    /// Raw number of a field; fitparser shows custom power targets through the
+    /// workout_power type, so unnamed values come back as "unknown_variant_N"
+    fn number(value: Value) -> u32 {
+        match value {
+            Value::UInt32(v) => v,
+            Value::String(s) => s.trim_start_matches("unknown_variant_").parse().unwrap(),
+            other => panic!("unexpected value {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_fit_workout_decodes() {
+        let bytes = FitWorkoutExporter::generate_fit(&threshold_session(), "Threshold", &athlete()).unwrap();
+        let records = fitparser::from_bytes(&bytes).unwrap();
+
+        let workout = records.iter().find(|r| r.kind() == MesgNum::Workout).unwrap();
+        assert_eq!(field(workout, "wkt_name"), Value::String("Threshold".to_string()));
+        assert_eq!(field(workout, "num_valid_steps"), Value::UInt16(5));
+
+        let steps: Vec<_> = records.iter().filter(|r| r.kind() == MesgNum::WorkoutStep).collect();
+        assert_eq!(steps.len(), 5);
+
+        // Interval at 95-105% FTP for 8 minutes
+        assert_eq!(field(steps[1], "target_type"), Value::String("power".to_string()));
+        assert_eq!(number(field(steps[1], "custom_target_power_low")), 95);
+        assert_eq!(number(field(steps[1], "custom_target_power_high")), 105);
+        assert_eq!(field(steps[1], "duration_time"), Value::Float64(480.0));
+
+        // Repeat the interval/recovery pair four times
+        assert_eq!(field(steps[3], "duration_type"), Value::String("repeat_until_steps_cmplt".to_string()));
+        assert_eq!(number(field(steps[3], "duration_step")), 1);
+        assert_eq!(number(field(steps[3], "repeat_steps")), 4);
+    }
+
+    #[test]
+    fn test_pace_and_heart_rate_targets() {
+        let athlete = athlete();
+        let pace = WorkoutStep::new(
+            StepKind::Interval,
+            StepDuration::Distance { meters: 1000 },
+            StepTarget::Pace { fast: dec!(4.0), slow: dec!(4.5) },
+        );
+        let encoded = FitWorkoutExporter::encode_step(&pace, &athlete);
+        assert_eq!(encoded.target_type, TARGET_SPEED);
+        assert_eq!((encoded.target_low, encoded.target_high), (3704, 4167));
+        assert_eq!(encoded.duration_value, 100_000);
+
+        let hr = WorkoutStep::new(StepKind::Steady, StepDuration::minutes(30), StepTarget::PercentLthr { low: 80, high: 90 });
+        let encoded = FitWorkoutExporter::encode_step(&hr, &athlete);
+        assert_eq!(encoded.target_type, TARGET_HEART_RATE);
+        assert_eq!((encoded.target_low, encoded.target_high), (232, 248));
+    }
+}
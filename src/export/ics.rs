@@ -0,0 +1,324 @@
+//! iCalendar (.ics) export of training plans and the race calendar
+//!
+//! Each planned session becomes an all-day VEVENT whose body carries the
+//! description, planned duration and TSS and the step list. UIDs derive from
+//! the session ID (races use the race ID), so exporting the plan again after
+//! sessions were rescheduled updates the events already in a calendar instead
+//! of duplicating them; `SEQUENCE` carries the session's revision so clients
+//! accept the new date. Sessions of plans saved before IDs existed fall back
+//! to a UID built from the plan ID, date and position. `X-TRAINRS-*`
+//! properties keep the values the importer needs to rebuild the sessions
+//! exactly.
+
+use std::fmt::Write as _;
+
+use chrono::{Duration, NaiveDate, Utc};
+
+use crate::race_calendar::Race;
+use crate::structured_workout::{StructuredWorkout, WorkoutBlock};
+use crate::training_plan::{PlannedSessionKey, PlannedWorkout, TrainingPlan};
+
+use super::ExportError;
+
+/// Product identifier written to exported calendars
+const PRODID: &str = "-//trainrs//Training Plan//EN";
+
+pub struct IcsExporter;
+
+impl IcsExporter {
+    /// Calendar with every session of the plan and, optionally, the races
+    pub fn generate_ics(plan: Option<&TrainingPlan>, races: &[Race]) -> Result<String, ExportError> {
+        let stamp = Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+        let mut out = String::new();
+
+        push_line(&mut out, "BEGIN:VCALENDAR");
+        push_line(&mut out, "VERSION:2.0");
+        push_line(&mut out, &format!("PRODID:{}", PRODID));
+        push_line(&mut out, "CALSCALE:GREGORIAN");
+        push_line(&mut out, "METHOD:PUBLISH");
+
+        if let Some(plan) = plan {
+            push_line(&mut out, &format!("X-WR-CALNAME:{}", escape_text(&plan.name)));
+            push_line(&mut out, &format!("X-TRAINRS-PLAN-ID:{}", escape_text(&plan.id)));
+            let goal = serde_json::to_string(&plan.goal).map_err(|e| ExportError::SerializationError(e.to_string()))?;
+            push_line(&mut out, &format!("X-TRAINRS-GOAL:{}", escape_text(&goal)));
+
+            for week in &plan.weeks {
+                for (key, workout) in plan.sessions().into_iter().filter(|(key, _)| week.start_date <= key.date && key.date <= week.end_date) {
+                    Self::write_session(&mut out, plan, week.week_number, &week.phase, key, workout, &stamp)?;
+                }
+            }
+        } else {
+            push_line(&mut out, "X-WR-CALNAME:Race Calendar");
+        }
+
+        for race in races {
+            Self::write_race(&mut out, race, &stamp);
+        }
+
+        push_line(&mut out, "END:VCALENDAR");
+        Ok(out)
+    }
+
+    /// Stable UID of a planned session
+    pub fn session_uid(plan_id: &str, key: &PlannedSessionKey, workout: &PlannedWorkout) -> String {
+        if workout.id.is_empty() {
+            format!("{}-{}@trainrs", plan_id, key.id)
+        } else {
+            format!("session-{}@trainrs", workout.id)
+        }
+    }
+
+    /// Stable UID of a race
+    pub fn race_uid(race: &Race) -> String {
+        format!("race-{}@trainrs", race.id)
+    }
+
+    fn write_session(
+        out: &mut String,
+        plan: &TrainingPlan,
+        week_number: u32,
+        phase: &str,
+        key: PlannedSessionKey,
+        workout: &PlannedWorkout,
+        stamp: &str,
+    ) -> Result<(), ExportError> {
+        push_line(out, "BEGIN:VEVENT");
+        push_line(out, &format!("UID:{}", Self::session_uid(&plan.id, &key, workout)));
+        push_line(out, &format!("DTSTAMP:{}", stamp));
+        push_line(out, &format!("SEQUENCE:{}", workout.sequence));
+        write_all_day(out, workout.date);
+        push_line(out, &format!("SUMMARY:{}", escape_text(&format!("{} ({} min)", workout.label(), workout.planned_duration_minutes))));
+        push_line(out, &format!("DESCRIPTION:{}", escape_text(&session_body(workout))));
+        push_line(out, &format!("CATEGORIES:TRAINING,{}", workout.sport.to_string().to_uppercase()));
+        push_line(out, "TRANSP:TRANSPARENT");
+        push_line(out, &format!("X-TRAINRS-PLAN-ID:{}", escape_text(&plan.id)));
+        push_line(out, &format!("X-TRAINRS-WEEK:{}", week_number));
+        push_line(out, &format!("X-TRAINRS-PHASE:{}", escape_text(phase)));
+        push_line(out, &format!("X-TRAINRS-SPORT:{}", workout.sport.to_string()));
+        push_line(out, &format!("X-TRAINRS-TYPE:{}", workout.workout_type.to_string()));
+        push_line(out, &format!("X-TRAINRS-DURATION:{}", workout.planned_duration_minutes));
+        push_line(out, &format!("X-TRAINRS-TSS:{}", workout.planned_tss.round_dp(1)));
+        push_line(out, &format!("X-TRAINRS-IF:{}", workout.intensity_factor.round_dp(3)));
+        if let Some(structure) = &workout.structure {
+            let json = serde_json::to_string(structure).map_err(|e| ExportError::SerializationError(e.to_string()))?;
+            push_line(out, &format!("X-TRAINRS-STRUCTURE:{}", escape_text(&json)));
+        }
+        push_line(out, "END:VEVENT");
+        Ok(())
+    }
+
+    fn write_race(out: &mut String, race: &Race, stamp: &str) {
+        let mut body = format!("{} race\nSport: {}", race.priority, race.sport.to_string());
+        if let Some(distance) = race.distance_meters {
+            let _ = write!(body, "\nDistance: {} km", (distance / rust_decimal_macros::dec!(1000)).round_dp(2).normalize());
+        }
+        if let Some(goal) = race.goal_time_seconds {
+            let _ = write!(body, "\nGoal: {}", clock_time(goal));
+        }
+        if let Some(result) = race.result_time_seconds {
+            let _ = write!(body, "\nResult: {}", clock_time(result));
+        }
+        if let Some(notes) = &race.notes {
+            let _ = write!(body, "\n\n{}", notes);
+        }
+
+        push_line(out, "BEGIN:VEVENT");
+        push_line(out, &format!("UID:{}", Self::race_uid(race)));
+        push_line(out, &format!("DTSTAMP:{}", stamp));
+        write_all_day(out, race.date);
+        push_line(out, &format!("SUMMARY:{}", escape_text(&format!("{} ({} race)", race.name, race.priority))));
+        push_line(out, &format!("DESCRIPTION:{}", escape_text(&body)));
+        push_line(out, "CATEGORIES:RACE");
+        push_line(out, &format!("X-TRAINRS-RACE-PRIORITY:{}", race.priority));
+        push_line(out, &format!("X-TRAINRS-SPORT:{}", race.sport.to_string()));
+        if let Some(distance) = race.distance_meters {
+            push_line(out, &format!("X-TRAINRS-DISTANCE:{}", distance.normalize()));
+        }
+        if let Some(goal) = race.goal_time_seconds {
+            push_line(out, &format!("X-TRAINRS-GOAL-TIME:{}", goal));
+        }
+        push_line(out, "END:VEVENT");
+    }
+}
+
+/// Human-readable event body: description, load and steps
+fn session_body(workout: &PlannedWorkout) -> String {
+    let mut body = workout.description.clone();
+    let _ = write!(
+        body,
+        "\n\nSport: {}\nDuration: {} min\nPlanned TSS: {:.0}\nIF: {:.2}",
+        workout.sport.to_string(),
+        workout.planned_duration_minutes,
+        workout.planned_tss,
+        workout.intensity_factor
+    );
+    if let Some(structure) = &workout.structure {
+        body.push_str("\n\nSteps:");
+        for line in step_lines(structure) {
+            let _ = write!(body, "\n{}", line);
+        }
+    }
+    if let Some(notes) = &workout.notes {
+        let _ = write!(body, "\n\n{}", notes);
+    }
+    body
+}
+
+fn step_lines(structure: &StructuredWorkout) -> Vec<String> {
+    let mut lines = Vec::new();
+    for block in &structure.blocks {
+        match block {
+            WorkoutBlock::Step(step) => lines.push(format!("- {:?} {}", step.kind, step.label())),
+            WorkoutBlock::Repeat { count, steps } => {
+                lines.push(format!("- {}×", count));
+                for step in steps {
+                    lines.push(format!("  - {:?} {}", step.kind, step.label()));
+                }
+            }
+        }
+    }
+    lines
+}
+
+fn clock_time(seconds: u32) -> String {
+    format!("{}:{:02}:{:02}", seconds / 3600, seconds % 3600 / 60, seconds % 60)
+}
+
+fn write_all_day(out: &mut String, date: NaiveDate) {
+    push_line(out, &format!("DTSTART;VALUE=DATE:{}", date.format("%Y%m%d")));
+    push_line(out, &format!("DTEND;VALUE=DATE:{}", (date + Duration::days(1)).format("%Y%m%d")));
+}
+
+/// Escape a TEXT value (RFC 5545 §3.3.11)
+pub(crate) fn escape_text(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Append a content line, folded at 75 octets with CRLF line endings (RFC 5545 §3.1)
+fn push_line(out: &mut String, line: &str) {
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > 75 {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(c);
+        width += c.len_utf8();
+    }
+    out.push_str("\r\n");
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::export::planned::tests::{athlete, threshold_session};
+    use crate::models::{Sport, WorkoutType};
+    use crate::race_calendar::RacePriority;
+    use crate::training_plan::{PeriodizationModel, RecoveryPattern, TrainingGoal, TrainingWeek};
+    use rust_decimal_macros::dec;
+
+    pub(crate) fn plan() -> TrainingPlan {
+        let athlete = athlete();
+        let start = NaiveDate::from_ymd_opt(2025, 3, 3).unwrap();
+        let threshold = PlannedWorkout::from_structure(start + Duration::days(1), WorkoutType::Threshold, "Threshold intervals", threshold_session(), &athlete);
+        let group_ride = PlannedWorkout {
+            id: "group-ride".to_string(),
+            sequence: 0,
+            date: start + Duration::days(5),
+            sport: Sport::Cycling,
+            workout_type: WorkoutType::Endurance,
+            planned_duration_minutes: 120,
+            planned_tss: dec!(95),
+            description: "Group ride; stay in the bunch, no attacks".to_string(),
+            intensity_factor: dec!(0.70),
+            notes: None,
+            structure: None,
+        };
+
+        TrainingPlan {
+            id: "plan_42".to_string(),
+            name: "Spring Build".to_string(),
+            goal: TrainingGoal::Century,
+            periodization_model: PeriodizationModel::Traditional,
+            recovery_pattern: RecoveryPattern::ThreeToOne,
+            start_date: start,
+            target_date: None,
+            total_weeks: 1,
+            weeks: vec![TrainingWeek {
+                week_number: 1,
+                start_date: start,
+                end_date: start + Duration::days(6),
+                phase: "Build".to_string(),
+                focus: "Threshold".to_string(),
+                planned_tss: threshold.planned_tss + dec!(95),
+                planned_hours: dec!(3.5),
+                is_recovery_week: false,
+                workouts: vec![threshold, group_ride],
+            }],
+            total_planned_tss: dec!(0),
+            total_planned_hours: dec!(0),
+            target_distribution: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_sessions_become_all_day_events_with_stable_uids() {
+        let plan = plan();
+        let mut race = Race::new("Spring Classic".to_string(), NaiveDate::from_ymd_opt(2025, 4, 6).unwrap(), Sport::Cycling, RacePriority::A);
+        race.distance_meters = Some(dec!(160000));
+
+        let ics = IcsExporter::generate_ics(Some(&plan), std::slice::from_ref(&race)).unwrap();
+
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 3);
+        assert!(ics.contains("UID:session-group-ride@trainrs\r\nDTSTAMP:"));
+        assert!(ics.contains("SEQUENCE:0\r\n"));
+        assert!(ics.contains("DTSTART;VALUE=DATE:20250304\r\nDTEND;VALUE=DATE:20250305\r\n"));
+        assert!(ics.contains("SUMMARY:Threshold intervals (68 min)\r\n"));
+        assert!(ics.contains("SUMMARY:Group ride\\; stay in the bunch\\, no attacks (120 min)\r\n"));
+        assert!(ics.contains(&format!("UID:race-{}@trainrs\r\n", race.id)));
+        assert!(ics.contains("X-TRAINRS-RACE-PRIORITY:A\r\n"));
+
+        // A rescheduled session keeps its UID and announces the new revision
+        let mut moved = plan.clone();
+        let session = &mut moved.weeks[0].workouts[1];
+        session.date += Duration::days(1);
+        session.sequence += 1;
+        let again = IcsExporter::generate_ics(Some(&moved), &[]).unwrap();
+        assert!(again.contains(&format!("UID:session-{}@trainrs\r\n", plan.weeks[0].workouts[0].id)));
+        assert!(again.contains("UID:session-group-ride@trainrs\r\n"));
+        assert!(again.contains("SEQUENCE:1\r\nDTSTART;VALUE=DATE:20250309\r\n"));
+    }
+
+    #[test]
+    fn test_sessions_without_id_fall_back_to_plan_and_date() {
+        let mut plan = plan();
+        plan.weeks[0].workouts[0].id.clear();
+        let ics = IcsExporter::generate_ics(Some(&plan), &[]).unwrap();
+        assert!(ics.contains("UID:plan_42-20250304-0@trainrs\r\n"));
+        assert!(ics.contains("UID:session-group-ride@trainrs\r\n"));
+    }
+
+    #[test]
+    fn test_lines_are_folded_at_75_octets() {
+        let ics = IcsExporter::generate_ics(Some(&plan()), &[]).unwrap();
+        assert!(ics.split("\r\n").all(|line| line.len() <= 75));
+        // Steps are listed in the body
+        let unfolded = ics.replace("\r\n ", "");
+        assert!(unfolded.contains("Steps:\\n- Warmup 10min @ 50-65% FTP\\n- 4×\\n  - Interval 8min @ 95-105% FTP"));
+    }
+}
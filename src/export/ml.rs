@@ -553,6 +553,8 @@ mod tests {
             preferred_units: Units::Metric,
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
+            rowing_ftp: None,
+            css_pace: None,
         }
     }
 
@@ -586,6 +588,9 @@ mod tests {
                     stroke_type: None,
                     lap_number: Some(1),
                     sport_transition: Some(false),
+                    temperature: None,
+                    drive_length: None,
+                    stroke_work: None,
                 },
                 DataPoint {
                     timestamp: 1800,
@@ -605,6 +610,9 @@ mod tests {
                     stroke_type: None,
                     lap_number: Some(1),
                     sport_transition: Some(false),
+                    temperature: None,
+                    drive_length: None,
+                    stroke_work: None,
                 },
             ]),
             summary: WorkoutSummary {
@@ -622,6 +630,11 @@ mod tests {
             },
             notes: None,
             source: None,
+            swim_lengths: Vec::new(),
+            start_time: None,
+            laps: Vec::new(),
+            rpe: None,
+            strength_sets: Vec::new(),
         }
     }
 
@@ -8,10 +8,16 @@ use std::path::Path;
 use thiserror::Error;
 
 pub mod csv;
+pub mod erg;
+pub mod fit_activity;
+pub mod fit_workout;
+pub mod ics;
 pub mod json;
 pub mod ml;
+pub mod planned;
 pub mod pwx;
 pub mod text;
+pub mod zwo;
 
 /// Export format types
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -22,6 +28,7 @@ pub enum ExportFormat {
     Html,
     Pdf,
     Pwx,
+    Fit,
 }
 
 impl ExportFormat {
@@ -33,6 +40,7 @@ impl ExportFormat {
             "html" => Ok(ExportFormat::Html),
             "pdf" => Ok(ExportFormat::Pdf),
             "pwx" | "wko5" | "trainingpeaks" => Ok(ExportFormat::Pwx),
+            "fit" => Ok(ExportFormat::Fit),
             _ => Err(ExportError::UnsupportedFormat(s.to_string())),
         }
     }
@@ -279,6 +287,18 @@ impl ExportManager {
 
                 pwx::PwxExporter::export_workout(filtered_workouts[0], output)
             }
+            (ExportFormat::Fit, ExportType::TrainingPeaksFormat) => {
+                let output = output_path.as_ref();
+                match filtered_workouts.as_slice() {
+                    [workout] => fit_activity::FitActivityExporter::export_workout(workout, output),
+                    [] => Err(ExportError::InsufficientData(
+                        "FIT export requires at least one workout".to_string(),
+                    )),
+                    _ => Err(ExportError::ConfigurationError(
+                        "FIT export supports one workout per file".to_string(),
+                    )),
+                }
+            }
             _ => Err(ExportError::UnsupportedFormat(format!(
                 "{:?} format for {:?} export type not yet implemented",
                 options.format, options.export_type
@@ -647,6 +667,11 @@ mod tests {
             notes: None,
             athlete_id: Some("test_athlete".to_string()),
             source: None,
+            swim_lengths: Vec::new(),
+            start_time: None,
+            laps: Vec::new(),
+            rpe: None,
+            strength_sets: Vec::new(),
         }
     }
 
@@ -689,6 +714,7 @@ mod tests {
         assert_eq!(ExportFormat::from_str("JSON").unwrap(), ExportFormat::Json);
         assert_eq!(ExportFormat::from_str("text").unwrap(), ExportFormat::Text);
         assert_eq!(ExportFormat::from_str("txt").unwrap(), ExportFormat::Text);
+        assert_eq!(ExportFormat::from_str("FIT").unwrap(), ExportFormat::Fit);
 
         assert!(ExportFormat::from_str("invalid").is_err());
     }
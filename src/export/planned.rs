@@ -0,0 +1,346 @@
+//! Planned workout file export
+//!
+//! Writes structured planned workouts to trainer and device workout formats:
+//! Zwift `.zwo`, ERG/MRC course files and FIT workout files. Planned workouts
+//! without steps are exported as a single steady step at their planned IF.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+
+use crate::models::{AthleteProfile, Sport};
+use crate::structured_workout::{StepDuration, StepKind, StepTarget, StructuredWorkout, WorkoutStep};
+use crate::training_plan::{PlannedWorkout, TrainingPlan, TrainingWeek};
+
+use super::erg::ErgExporter;
+use super::fit_workout::FitWorkoutExporter;
+use super::zwo::ZwoExporter;
+use super::ExportError;
+
+/// Workout file formats for trainers and devices
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorkoutFileFormat {
+    /// Zwift workout XML
+    Zwo,
+    /// Course file in absolute watts
+    Erg,
+    /// Course file in percent of FTP
+    Mrc,
+    /// FIT workout file for Garmin and compatible devices
+    Fit,
+}
+
+impl FromStr for WorkoutFileFormat {
+    type Err = ExportError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "zwo" | "zwift" => Ok(WorkoutFileFormat::Zwo),
+            "erg" => Ok(WorkoutFileFormat::Erg),
+            "mrc" => Ok(WorkoutFileFormat::Mrc),
+            "fit" | "garmin" => Ok(WorkoutFileFormat::Fit),
+            _ => Err(ExportError::UnsupportedFormat(s.to_string())),
+        }
+    }
+}
+
+impl WorkoutFileFormat {
+    /// Whether the format can describe a session of `sport`
+    ///
+    /// ERG/MRC course files drive a cycling trainer and Zwift only has bike
+    /// and run workouts; FIT workouts cover every sport.
+    pub fn supports(&self, sport: &Sport) -> bool {
+        match self {
+            WorkoutFileFormat::Erg | WorkoutFileFormat::Mrc => *sport == Sport::Cycling,
+            WorkoutFileFormat::Zwo => matches!(sport, Sport::Cycling | Sport::Running),
+            WorkoutFileFormat::Fit => true,
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            WorkoutFileFormat::Zwo => "zwo",
+            WorkoutFileFormat::Erg => "erg",
+            WorkoutFileFormat::Mrc => "mrc",
+            WorkoutFileFormat::Fit => "fit",
+        }
+    }
+}
+
+pub struct PlannedWorkoutExporter;
+
+impl PlannedWorkoutExporter {
+    /// Steps for a planned workout, synthesizing a steady step when it has none
+    pub fn structure_for(workout: &PlannedWorkout, athlete: &AthleteProfile) -> StructuredWorkout {
+        if let Some(structure) = &workout.structure {
+            return structure.clone();
+        }
+
+        let intensity = workout.intensity_factor;
+        StructuredWorkout::new(&workout.description, workout.sport.clone()).step(
+            StepKind::Steady,
+            StepDuration::minutes(workout.planned_duration_minutes.max(1)),
+            StepTarget::for_sport(&workout.sport, athlete, intensity, intensity),
+        )
+    }
+
+    /// Render a planned workout in the given format
+    pub fn render(
+        workout: &PlannedWorkout,
+        athlete: &AthleteProfile,
+        format: WorkoutFileFormat,
+    ) -> Result<Vec<u8>, ExportError> {
+        let structure = Self::structure_for(workout, athlete);
+        let name = title(workout);
+
+        Ok(match format {
+            WorkoutFileFormat::Zwo => ZwoExporter::generate_zwo(&structure, &name, &workout.description, athlete)?.into_bytes(),
+            WorkoutFileFormat::Erg => ErgExporter::generate_erg(&structure, &name, athlete)?.into_bytes(),
+            WorkoutFileFormat::Mrc => ErgExporter::generate_mrc(&structure, &name, athlete)?.into_bytes(),
+            WorkoutFileFormat::Fit => FitWorkoutExporter::generate_fit(&structure, &name, athlete)?,
+        })
+    }
+
+    /// Write a single planned workout to a file
+    pub fn export_workout(
+        workout: &PlannedWorkout,
+        athlete: &AthleteProfile,
+        format: WorkoutFileFormat,
+        path: &Path,
+    ) -> Result<(), ExportError> {
+        fs::write(path, Self::render(workout, athlete, format)?)?;
+        Ok(())
+    }
+
+    /// Write every workout of a training week into a folder, one file per session
+    ///
+    /// Sessions of sports the format cannot describe are skipped.
+    pub fn export_week(
+        week: &TrainingWeek,
+        athlete: &AthleteProfile,
+        format: WorkoutFileFormat,
+        dir: &Path,
+    ) -> Result<Vec<PathBuf>, ExportError> {
+        fs::create_dir_all(dir)?;
+
+        let mut paths = Vec::new();
+        for (index, workout) in week.workouts.iter().enumerate() {
+            if !format.supports(&workout.sport) {
+                continue;
+            }
+            let file_name = format!(
+                "{}_{}_{}.{}",
+                workout.date.format("%Y-%m-%d"),
+                index + 1,
+                sanitize(&title(workout)),
+                format.extension()
+            );
+            let path = dir.join(file_name);
+            Self::export_workout(workout, athlete, format, &path)?;
+            paths.push(path);
+        }
+
+        Ok(paths)
+    }
+
+    /// Write a whole plan, one folder per training week
+    pub fn export_plan(
+        plan: &TrainingPlan,
+        athlete: &AthleteProfile,
+        format: WorkoutFileFormat,
+        dir: &Path,
+    ) -> Result<Vec<PathBuf>, ExportError> {
+        let mut paths = Vec::new();
+        for week in &plan.weeks {
+            let folder = dir.join(week_folder_name(week));
+            paths.extend(Self::export_week(week, athlete, format, &folder)?);
+        }
+        Ok(paths)
+    }
+}
+
+/// Folder name for a training week, e.g. "week_03_2025-01-20_build"
+pub fn week_folder_name(week: &TrainingWeek) -> String {
+    format!(
+        "week_{:02}_{}_{}",
+        week.week_number,
+        week.start_date.format("%Y-%m-%d"),
+        sanitize(&week.phase)
+    )
+}
+
+/// Short workout title from the part of the description before the step summary
+fn title(workout: &PlannedWorkout) -> String {
+    workout
+        .description
+        .split(": ")
+        .next()
+        .filter(|s| !s.is_empty())
+        .unwrap_or("Workout")
+        .to_string()
+}
+
+fn sanitize(name: &str) -> String {
+    let cleaned: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '_' })
+        .collect();
+    let parts: Vec<&str> = cleaned.split('_').filter(|p| !p.is_empty()).collect();
+    parts.join("_")
+}
+
+/// Step power as (start, end) fractions of FTP
+///
+/// Warmups ramp up and cooldowns ramp down across the target range; other
+/// steps hold the middle of the range. Non-power targets use their implied IF.
+pub(crate) fn step_power(step: &WorkoutStep, athlete: &AthleteProfile) -> (Decimal, Decimal) {
+    let (low, high) = match step.target {
+        StepTarget::PercentFtp { low, high } => (Decimal::from(low) / dec!(100), Decimal::from(high) / dec!(100)),
+        target => {
+            let intensity = target.intensity_factor(athlete);
+            (intensity, intensity)
+        }
+    };
+
+    match step.kind {
+        StepKind::Warmup => (low, high),
+        StepKind::Cooldown => (high, low),
+        _ => {
+            let mid = (low + high) / dec!(2);
+            (mid, mid)
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::models::{Sport, WorkoutType};
+    use chrono::NaiveDate;
+
+    pub(crate) fn athlete() -> AthleteProfile {
+        AthleteProfile {
+            id: "test".to_string(),
+            name: "Test".to_string(),
+            date_of_birth: None,
+            weight: Some(dec!(70)),
+            height: None,
+            ftp: Some(250),
+            lthr: Some(165),
+            threshold_pace: Some(dec!(4.5)),
+            max_hr: Some(185),
+            resting_hr: Some(50),
+            training_zones: Default::default(),
+            preferred_units: crate::models::Units::Metric,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            rowing_ftp: None,
+            css_pace: None,
+        }
+    }
+
+    pub(crate) fn threshold_session() -> StructuredWorkout {
+        let ftp = |low, high| StepTarget::PercentFtp { low, high };
+        StructuredWorkout::new("Threshold", Sport::Cycling)
+            .step(StepKind::Warmup, StepDuration::minutes(10), ftp(50, 65))
+            .repeat(
+                4,
+                vec![
+                    WorkoutStep::new(StepKind::Interval, StepDuration::minutes(8), ftp(95, 105)),
+                    WorkoutStep::new(StepKind::Recovery, StepDuration::minutes(4), ftp(50, 60)),
+                ],
+            )
+            .step(StepKind::Cooldown, StepDuration::minutes(10), ftp(50, 60))
+    }
+
+    fn week() -> TrainingWeek {
+        let athlete = athlete();
+        let start = NaiveDate::from_ymd_opt(2025, 3, 3).unwrap();
+        let threshold = PlannedWorkout::from_structure(start + chrono::Duration::days(1), WorkoutType::Threshold, "Threshold intervals", threshold_session(), &athlete);
+        let unstructured = PlannedWorkout {
+            id: uuid::Uuid::new_v4().to_string(),
+            sequence: 0,
+            date: start + chrono::Duration::days(5),
+            sport: Sport::Cycling,
+            workout_type: WorkoutType::Endurance,
+            planned_duration_minutes: 90,
+            planned_tss: dec!(70),
+            description: "Group ride".to_string(),
+            intensity_factor: dec!(0.70),
+            notes: None,
+            structure: None,
+        };
+
+        TrainingWeek {
+            week_number: 3,
+            start_date: start,
+            end_date: start + chrono::Duration::days(6),
+            phase: "Build".to_string(),
+            focus: "Threshold Development".to_string(),
+            planned_tss: dec!(300),
+            planned_hours: dec!(4.5),
+            is_recovery_week: false,
+            workouts: vec![threshold, unstructured],
+        }
+    }
+
+    #[test]
+    fn test_unstructured_workout_becomes_steady_step() {
+        let week = week();
+        let athlete = athlete();
+        let structure = PlannedWorkoutExporter::structure_for(&week.workouts[1], &athlete);
+        let steps = structure.flatten();
+        assert_eq!(steps.len(), 1);
+        assert_eq!(steps[0].duration, StepDuration::minutes(90));
+        assert_eq!(steps[0].target, StepTarget::PercentFtp { low: 70, high: 70 });
+
+        // Other sports get their own target rather than a power target
+        let mut run = week.workouts[1].clone();
+        run.sport = Sport::Running;
+        let structure = PlannedWorkoutExporter::structure_for(&run, &athlete);
+        let steps = structure.flatten();
+        assert!(matches!(steps[0].target, StepTarget::Pace { .. }));
+    }
+
+    #[test]
+    fn test_export_week_skips_sports_the_format_cannot_describe() {
+        let dir = tempfile::tempdir().unwrap();
+        let athlete = athlete();
+        let mut week = week();
+        let mut swim = week.workouts[1].clone();
+        swim.sport = Sport::Swimming;
+        let mut run = week.workouts[1].clone();
+        run.sport = Sport::Running;
+        week.workouts.extend([swim, run]);
+
+        let count = |format: WorkoutFileFormat| {
+            let folder = dir.path().join(format.extension());
+            PlannedWorkoutExporter::export_week(&week, &athlete, format, &folder).unwrap().len()
+        };
+        assert_eq!(count(WorkoutFileFormat::Erg), 2);
+        assert_eq!(count(WorkoutFileFormat::Mrc), 2);
+        assert_eq!(count(WorkoutFileFormat::Zwo), 3);
+        assert_eq!(count(WorkoutFileFormat::Fit), 4);
+    }
+
+    #[test]
+    fn test_export_week_writes_one_file_per_workout() {
+        let dir = tempfile::tempdir().unwrap();
+        let athlete = athlete();
+        let week = week();
+
+        for format in [WorkoutFileFormat::Zwo, WorkoutFileFormat::Erg, WorkoutFileFormat::Mrc, WorkoutFileFormat::Fit] {
+            let folder = dir.path().join(week_folder_name(&week)).join(format.extension());
+            let paths = PlannedWorkoutExporter::export_week(&week, &athlete, format, &folder).unwrap();
+
+            assert_eq!(paths.len(), 2);
+            assert!(paths[0].ends_with(format!("2025-03-04_1_threshold_intervals.{}", format.extension())));
+            assert!(paths.iter().all(|p| p.exists() && fs::metadata(p).unwrap().len() > 0));
+        }
+
+        assert_eq!(week_folder_name(&week), "week_03_2025-03-03_build");
+    }
+}
@@ -8,7 +8,7 @@ use quick_xml::events::{BytesDecl, BytesEnd, BytesStart, BytesText, Event};
 use quick_xml::Writer;
 use rust_decimal::Decimal;
 
-use crate::models::{DataSource, Sport, Workout};
+use crate::models::{DataSource, Lap, LapIntensity, Sport, Workout};
 use crate::power::MmpAnalyzer;
 
 use super::ExportError;
@@ -156,10 +156,20 @@ impl PwxExporter {
         Ok(())
     }
 
+    /// Device laps as segments with their summary; lap numbers of the samples otherwise
     fn write_segments<W: Write>(
         writer: &mut Writer<W>,
         workout: &Workout,
     ) -> Result<(), ExportError> {
+        if !workout.laps.is_empty() {
+            writer.write_event(Event::Start(BytesStart::new("segments")))?;
+            for (index, lap) in workout.laps.iter().enumerate() {
+                Self::write_lap_segment(writer, index + 1, lap)?;
+            }
+            writer.write_event(Event::End(BytesEnd::new("segments")))?;
+            return Ok(());
+        }
+
         let raw_data = match &workout.raw_data {
             Some(data) if !data.is_empty() => data,
             _ => return Ok(()),
@@ -181,6 +191,57 @@ impl PwxExporter {
         Ok(())
     }
 
+    fn write_lap_segment<W: Write>(
+        writer: &mut Writer<W>,
+        number: usize,
+        lap: &Lap,
+    ) -> Result<(), ExportError> {
+        writer.write_event(Event::Start(BytesStart::new("segment")))?;
+        Self::write_text_element(writer, "name", &format!("Lap {}", number))?;
+        let segment_type = match lap.intensity {
+            LapIntensity::Active => "Lap",
+            LapIntensity::Rest => "Rest",
+            LapIntensity::Warmup => "Warmup",
+            LapIntensity::Cooldown => "Cooldown",
+        };
+        Self::write_text_element(writer, "type", segment_type)?;
+
+        writer.write_event(Event::Start(BytesStart::new("summarydata")))?;
+        Self::write_text_element(writer, "beginning", &lap.start_offset.to_string())?;
+        Self::write_text_element(writer, "duration", &lap.duration_seconds.to_string())?;
+        Self::write_min_max_avg(writer, "hr", lap.max_heart_rate, lap.avg_heart_rate)?;
+        Self::write_min_max_avg(writer, "pwr", lap.max_power, lap.avg_power)?;
+        if let Some(distance) = lap.distance {
+            Self::write_text_element(writer, "dist", &decimal_to_string(distance))?;
+        }
+        writer.write_event(Event::End(BytesEnd::new("summarydata")))?;
+
+        writer.write_event(Event::End(BytesEnd::new("segment")))?;
+        Ok(())
+    }
+
+    /// PWX `minMaxAvg` element with the recorded attributes
+    fn write_min_max_avg<W: Write>(
+        writer: &mut Writer<W>,
+        name: &str,
+        max: Option<u16>,
+        avg: Option<u16>,
+    ) -> Result<(), ExportError> {
+        if max.is_none() && avg.is_none() {
+            return Ok(());
+        }
+
+        let mut element = BytesStart::new(name);
+        if let Some(max) = max {
+            element.push_attribute(("max", max.to_string().as_str()));
+        }
+        if let Some(avg) = avg {
+            element.push_attribute(("avg", avg.to_string().as_str()));
+        }
+        writer.write_event(Event::Empty(element))?;
+        Ok(())
+    }
+
     fn write_samples<W: Write>(
         writer: &mut Writer<W>,
         workout: &Workout,
@@ -360,6 +421,9 @@ mod tests {
             stroke_type: None,
             lap_number: Some(1),
             sport_transition: None,
+            temperature: None,
+            drive_length: None,
+            stroke_work: None,
         }
     }
 
@@ -392,6 +456,11 @@ mod tests {
             notes: Some("Morning ride".to_string()),
             athlete_id: Some("athlete_a".to_string()),
             source: Some("Garmin Edge".to_string()),
+            swim_lengths: Vec::new(),
+            start_time: None,
+            laps: Vec::new(),
+            rpe: None,
+            strength_sets: Vec::new(),
         }
     }
 
@@ -407,6 +476,43 @@ mod tests {
         assert!(xml.contains("<powerCurve>"));
     }
 
+    #[test]
+    fn writes_device_laps_as_segments() {
+        let mut workout = build_test_workout();
+        workout.laps = vec![
+            Lap {
+                start_offset: 0,
+                duration_seconds: 30,
+                distance: Some(dec!(250)),
+                avg_heart_rate: Some(148),
+                max_heart_rate: Some(150),
+                avg_power: Some(225),
+                max_power: Some(230),
+                trigger: crate::models::LapTrigger::Manual,
+                intensity: LapIntensity::Active,
+            },
+            Lap {
+                start_offset: 30,
+                duration_seconds: 30,
+                distance: None,
+                avg_heart_rate: None,
+                max_heart_rate: None,
+                avg_power: None,
+                max_power: None,
+                trigger: crate::models::LapTrigger::SessionEnd,
+                intensity: LapIntensity::Rest,
+            },
+        ];
+
+        let xml = PwxExporter::generate_pwx_xml(&workout).unwrap();
+        assert_eq!(xml.matches("<segment>").count(), 2);
+        assert!(xml.contains("<beginning>30</beginning>"));
+        assert!(xml.contains(r#"<hr max="150" avg="148"/>"#));
+        assert!(xml.contains(r#"<pwr max="230" avg="225"/>"#));
+        assert!(xml.contains("<dist>250</dist>"));
+        assert!(xml.contains("<type>Rest</type>"));
+    }
+
     #[test]
     fn exports_pwx_file_to_disk() {
         let workout = build_test_workout();
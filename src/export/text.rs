@@ -293,6 +293,11 @@ mod tests {
             notes: None,
             athlete_id: Some("test_athlete".to_string()),
             source: None,
+            swim_lengths: Vec::new(),
+            start_time: None,
+            laps: Vec::new(),
+            rpe: None,
+            strength_sets: Vec::new(),
         };
 
         let workouts = vec![&workout];
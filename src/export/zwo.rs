@@ -0,0 +1,129 @@
+//! Zwift workout (.zwo) export
+//!
+//! Power values are fractions of FTP. Work/recovery repeat pairs become
+//! `IntervalsT` blocks; warmups and cooldowns are ramps.
+
+use quick_xml::events::{BytesEnd, BytesStart, BytesText, Event};
+use quick_xml::Writer;
+use rust_decimal::Decimal;
+
+use crate::models::{AthleteProfile, Sport};
+use crate::structured_workout::{StepKind, StructuredWorkout, WorkoutBlock, WorkoutStep};
+
+use super::planned::step_power;
+use super::ExportError;
+
+pub struct ZwoExporter;
+
+impl ZwoExporter {
+    pub fn generate_zwo(
+        workout: &StructuredWorkout,
+        name: &str,
+        description: &str,
+        athlete: &AthleteProfile,
+    ) -> Result<String, ExportError> {
+        let mut writer = Writer::new_with_indent(Vec::new(), b' ', 2);
+
+        writer.write_event(Event::Start(BytesStart::new("workout_file")))?;
+        write_text_element(&mut writer, "author", "trainrs")?;
+        write_text_element(&mut writer, "name", name)?;
+        write_text_element(&mut writer, "description", description)?;
+        let sport_type = match workout.sport {
+            Sport::Running => "run",
+            _ => "bike",
+        };
+        write_text_element(&mut writer, "sportType", sport_type)?;
+
+        writer.write_event(Event::Start(BytesStart::new("workout")))?;
+        for block in &workout.blocks {
+            match block {
+                WorkoutBlock::Repeat { count, steps } if is_interval_pair(steps) => {
+                    let (on, off) = (&steps[0], &steps[1]);
+                    let mut element = BytesStart::new("IntervalsT");
+                    element.push_attribute(("Repeat", count.to_string().as_str()));
+                    element.push_attribute(("OnDuration", seconds(on, workout, athlete).as_str()));
+                    element.push_attribute(("OffDuration", seconds(off, workout, athlete).as_str()));
+                    element.push_attribute(("OnPower", fraction(step_power(on, athlete).0).as_str()));
+                    element.push_attribute(("OffPower", fraction(step_power(off, athlete).0).as_str()));
+                    writer.write_event(Event::Empty(element))?;
+                }
+                WorkoutBlock::Repeat { count, steps } => {
+                    for _ in 0..*count {
+                        for step in steps {
+                            write_step(&mut writer, step, workout, athlete)?;
+                        }
+                    }
+                }
+                WorkoutBlock::Step(step) => write_step(&mut writer, step, workout, athlete)?,
+            }
+        }
+        writer.write_event(Event::End(BytesEnd::new("workout")))?;
+
+        writer.write_event(Event::End(BytesEnd::new("workout_file")))?;
+
+        String::from_utf8(writer.into_inner()).map_err(|err| ExportError::SerializationError(err.to_string()))
+    }
+}
+
+fn is_interval_pair(steps: &[WorkoutStep]) -> bool {
+    steps.len() == 2 && steps[0].kind == StepKind::Interval && steps[1].kind == StepKind::Recovery
+}
+
+fn write_step<W: std::io::Write>(
+    writer: &mut Writer<W>,
+    step: &WorkoutStep,
+    workout: &StructuredWorkout,
+    athlete: &AthleteProfile,
+) -> Result<(), ExportError> {
+    let (start, end) = step_power(step, athlete);
+    let element = match step.kind {
+        StepKind::Warmup | StepKind::Cooldown => {
+            let tag = if step.kind == StepKind::Warmup { "Warmup" } else { "Cooldown" };
+            let mut element = BytesStart::new(tag);
+            element.push_attribute(("Duration", seconds(step, workout, athlete).as_str()));
+            // Zwift ramps from PowerLow to PowerHigh in both directions
+            element.push_attribute(("PowerLow", fraction(start).as_str()));
+            element.push_attribute(("PowerHigh", fraction(end).as_str()));
+            element
+        }
+        _ => {
+            let mut element = BytesStart::new("SteadyState");
+            element.push_attribute(("Duration", seconds(step, workout, athlete).as_str()));
+            element.push_attribute(("Power", fraction(start).as_str()));
+            element
+        }
+    };
+    writer.write_event(Event::Empty(element))?;
+    Ok(())
+}
+
+fn seconds(step: &WorkoutStep, workout: &StructuredWorkout, athlete: &AthleteProfile) -> String {
+    step.estimated_seconds(&workout.sport, athlete).to_string()
+}
+
+fn fraction(value: Decimal) -> String {
+    value.round_dp(3).normalize().to_string()
+}
+
+fn write_text_element<W: std::io::Write>(writer: &mut Writer<W>, name: &str, value: &str) -> Result<(), ExportError> {
+    writer.write_event(Event::Start(BytesStart::new(name)))?;
+    writer.write_event(Event::Text(BytesText::new(value)))?;
+    writer.write_event(Event::End(BytesEnd::new(name)))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::planned::tests::{athlete, threshold_session};
+
+    #[test]
+    fn test_zwo_uses_ramps_and_interval_blocks() {
+        let xml = ZwoExporter::generate_zwo(&threshold_session(), "Threshold", "4x8min", &athlete()).unwrap();
+
+        assert!(xml.contains("<sportType>bike</sportType>"));
+        assert!(xml.contains(r#"<Warmup Duration="600" PowerLow="0.5" PowerHigh="0.65"/>"#));
+        assert!(xml.contains(r#"<IntervalsT Repeat="4" OnDuration="480" OffDuration="240" OnPower="1" OffPower="0.55"/>"#));
+        assert!(xml.contains(r#"<Cooldown Duration="600" PowerLow="0.6" PowerHigh="0.5"/>"#));
+    }
+}
@@ -1,3 +1,5 @@
+#![allow(dead_code)]
+
 //! Configurable formula engine for metric calculations
 //!
 //! Provides a flexible system for customizing sports science calculations
@@ -194,6 +196,7 @@ impl Default for NormalizedPowerConfig {
 }
 
 /// Smoothing algorithms for normalized power
+#[allow(clippy::enum_variant_names)]
 #[derive(Debug, Clone, PartialEq)]
 pub enum SmoothingAlgorithm {
     /// Simple rolling average
@@ -0,0 +1,415 @@
+//! Bulk import of Strava and Garmin account exports
+//!
+//! Reads the export zip directly. Activity members (`.fit`, `.tcx`, `.gpx`,
+//! optionally gzip-compressed) are unpacked to a scratch directory and
+//! dispatched through [`ImportManager`]; nested zips, as in Garmin's GDPR
+//! export, are dispatched the same way and end up back here.
+//!
+//! Strava's `activities.csv` supplies each activity's name, description,
+//! type, gear, commute flag and perceived exertion. Rows without a file
+//! (manual entries) become summary-only workouts.
+
+use anyhow::{Context, Result};
+use chrono::NaiveDateTime;
+use flate2::read::GzDecoder;
+use rust_decimal::prelude::*;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::import::{ImportFormat, ImportManager};
+use crate::models::{DataSource, Sport, Workout, WorkoutSummary, WorkoutType};
+
+/// Strava's activity list inside the export
+const ACTIVITIES_CSV: &str = "activities.csv";
+
+/// Member extensions imported from an archive
+const ACTIVITY_EXTENSIONS: [&str; 4] = ["fit", "tcx", "gpx", "zip"];
+
+/// Importer for account export archives
+#[derive(Default)]
+pub struct ArchiveImporter;
+
+/// Metadata of one activity from Strava's `activities.csv`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ActivityMetadata {
+    pub name: Option<String>,
+    pub activity_type: Option<String>,
+    pub description: Option<String>,
+    pub gear: Option<String>,
+    pub commute: bool,
+    /// Perceived exertion (1-10)
+    pub perceived_exertion: Option<u8>,
+    /// Activity start in UTC
+    pub start: Option<NaiveDateTime>,
+    pub elapsed_seconds: Option<u32>,
+    pub distance_meters: Option<Decimal>,
+    /// Activity file inside the archive, empty for manual entries
+    pub filename: Option<String>,
+}
+
+/// Result of importing one archive
+#[derive(Debug, Default)]
+pub struct ArchiveImport {
+    pub workouts: Vec<Workout>,
+    /// Members that failed to import, with the reason
+    pub failures: Vec<(String, String)>,
+    /// Members that are not activity files
+    pub skipped: usize,
+}
+
+/// Scratch directory removed when dropped
+struct ScratchDir(PathBuf);
+
+impl Drop for ScratchDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+impl ArchiveImporter {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Import every activity of an export archive
+    pub fn import_archive(&self, path: &Path) -> Result<ArchiveImport> {
+        let file = File::open(path).with_context(|| format!("Failed to open archive: {}", path.display()))?;
+        let mut archive = zip::ZipArchive::new(file).with_context(|| format!("Not a zip archive: {}", path.display()))?;
+
+        let metadata = match archive.by_name(ACTIVITIES_CSV) {
+            Ok(mut member) => {
+                let mut text = String::new();
+                member.read_to_string(&mut text)?;
+                parse_activities_csv(&text)?
+            }
+            Err(_) => Vec::new(),
+        };
+        let by_file: HashMap<&str, &ActivityMetadata> = metadata
+            .iter()
+            .filter_map(|m| m.filename.as_deref().map(|f| (f, m)))
+            .collect();
+
+        let scratch = ScratchDir(std::env::temp_dir().join(format!("trainrs-archive-{}", Uuid::new_v4())));
+        std::fs::create_dir_all(&scratch.0)?;
+
+        let manager = ImportManager::new();
+        let archive_name = path.file_name().unwrap_or_default().to_string_lossy().into_owned();
+        let mut result = ArchiveImport::default();
+
+        for index in 0..archive.len() {
+            let mut member = archive.by_index(index)?;
+            let member_name = member.name().to_string();
+            if member.is_dir() || member_name == ACTIVITIES_CSV || member_name.starts_with("__MACOSX/") {
+                continue;
+            }
+
+            let (file_name, compressed) = match member_name.rsplit('/').next().unwrap_or(&member_name) {
+                name if name.to_lowercase().ends_with(".gz") => (name[..name.len() - 3].to_string(), true),
+                name => (name.to_string(), false),
+            };
+            let extension = Path::new(&file_name)
+                .extension()
+                .and_then(|e| e.to_str())
+                .map(str::to_lowercase)
+                .unwrap_or_default();
+            if !ACTIVITY_EXTENSIONS.contains(&extension.as_str()) {
+                result.skipped += 1;
+                continue;
+            }
+
+            let mut bytes = Vec::new();
+            let read = if compressed {
+                GzDecoder::new(&mut member).read_to_end(&mut bytes)
+            } else {
+                member.read_to_end(&mut bytes)
+            };
+            if let Err(e) = read {
+                result.failures.push((member_name, format!("Failed to decompress: {}", e)));
+                continue;
+            }
+
+            let member_dir = scratch.0.join(index.to_string());
+            std::fs::create_dir(&member_dir)?;
+            let member_path = member_dir.join(&file_name);
+            std::fs::write(&member_path, &bytes)?;
+
+            let source = format!("{}:{}", archive_name, member_name);
+            match manager.import_file(&member_path) {
+                Ok(workouts) => {
+                    let metadata = by_file.get(member_name.as_str()).copied();
+                    let nested_prefix = format!("{}:", file_name);
+                    for mut workout in workouts {
+                        // Workouts from nested archives name their member within the nested archive
+                        let member_source = match workout.source.as_deref() {
+                            Some(nested) if nested.starts_with(&nested_prefix) => {
+                                format!("{}:{}", source, &nested[nested_prefix.len()..])
+                            }
+                            _ => source.clone(),
+                        };
+                        if let Some(original) = workout.source.replace(member_source.clone()) {
+                            workout.notes = workout.notes.map(|notes| notes.replace(&original, &member_source));
+                        }
+                        if let Some(metadata) = metadata {
+                            metadata.apply(&mut workout);
+                        }
+                        result.workouts.push(workout);
+                    }
+                }
+                Err(e) => result.failures.push((member_name, e.to_string())),
+            }
+        }
+
+        for entry in metadata.iter().filter(|m| m.filename.is_none()) {
+            if let Some(workout) = entry.to_workout(&archive_name) {
+                result.workouts.push(workout);
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+impl ImportFormat for ArchiveImporter {
+    fn can_import(&self, file_path: &Path) -> bool {
+        file_path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.eq_ignore_ascii_case("zip"))
+            .unwrap_or(false)
+    }
+
+    fn import_file(&self, file_path: &Path) -> Result<Vec<Workout>> {
+        let result = self.import_archive(file_path)?;
+        for (member, error) in &result.failures {
+            warn!("Failed to import {} from {}: {}", member, file_path.display(), error);
+        }
+        if result.workouts.is_empty() && !result.failures.is_empty() {
+            anyhow::bail!("No activity in {} could be imported", file_path.display());
+        }
+        Ok(result.workouts)
+    }
+
+    fn get_format_name(&self) -> &'static str {
+        "account export archive"
+    }
+
+    fn sniff(&self, header: &[u8]) -> Option<bool> {
+        Some(header.starts_with(b"PK\x03\x04"))
+    }
+}
+
+impl ActivityMetadata {
+    /// Sport for a Strava activity type
+    pub fn sport(&self) -> Option<Sport> {
+        match self.activity_type.as_deref()? {
+            "Ride" | "VirtualRide" | "EBikeRide" | "MountainBikeRide" | "GravelRide" | "Velomobile" | "Handcycle" => {
+                Some(Sport::Cycling)
+            }
+            "Run" | "TrailRun" | "VirtualRun" => Some(Sport::Running),
+            "Swim" => Some(Sport::Swimming),
+            "Rowing" | "VirtualRow" => Some(Sport::Rowing),
+            "WeightTraining" | "Workout" | "Crossfit" | "Elliptical" | "StairStepper" | "Yoga" => Some(Sport::CrossTraining),
+            _ => None,
+        }
+    }
+
+    /// Notes built from the name, description, gear and commute flag
+    fn notes(&self, source: &str) -> String {
+        let mut lines: Vec<String> = [&self.name, &self.description].into_iter().flatten().cloned().collect();
+        if let Some(gear) = &self.gear {
+            lines.push(format!("Gear: {}", gear));
+        }
+        if self.commute {
+            lines.push("Commute".to_string());
+        }
+        lines.push(format!("Imported from {}", source));
+        lines.join("\n")
+    }
+
+    /// Apply the metadata to a workout imported from the activity's file
+    pub fn apply(&self, workout: &mut Workout) {
+        if let Some(sport) = self.sport() {
+            workout.sport = sport;
+        }
+        if self.activity_type.as_deref() == Some("WeightTraining") {
+            workout.workout_type = WorkoutType::Strength;
+        }
+        if self.perceived_exertion.is_some() {
+            workout.rpe = self.perceived_exertion;
+        }
+        let source = workout.source.clone().unwrap_or_default();
+        workout.notes = Some(self.notes(&source));
+    }
+
+    /// Summary-only workout for an activity entered without a file
+    fn to_workout(&self, archive_name: &str) -> Option<Workout> {
+        let start = self.start?;
+        let source = format!("{}:{}", archive_name, ACTIVITIES_CSV);
+        Some(Workout {
+            id: Uuid::new_v4().to_string(),
+            date: start.date(),
+            start_time: Some(start.and_utc().fixed_offset()),
+            sport: self.sport().unwrap_or(Sport::CrossTraining),
+            duration_seconds: self.elapsed_seconds?,
+            workout_type: if self.activity_type.as_deref() == Some("WeightTraining") {
+                WorkoutType::Strength
+            } else {
+                WorkoutType::Endurance
+            },
+            data_source: DataSource::Rpe,
+            raw_data: None,
+            summary: WorkoutSummary {
+                total_distance: self.distance_meters.filter(|d| !d.is_zero()),
+                ..WorkoutSummary::default()
+            },
+            notes: Some(self.notes(&source)),
+            athlete_id: None,
+            source: Some(source),
+            swim_lengths: Vec::new(),
+            laps: Vec::new(),
+            rpe: self.perceived_exertion,
+            strength_sets: Vec::new(),
+        })
+    }
+}
+
+/// Parse Strava's `activities.csv`
+///
+/// The export repeats some headers; the first `Distance` column is in the
+/// athlete's display unit and the last one in meters, so distance comes from
+/// the last one and every other field from its first column.
+pub fn parse_activities_csv(text: &str) -> Result<Vec<ActivityMetadata>> {
+    let mut reader = ::csv::ReaderBuilder::new().flexible(true).from_reader(text.as_bytes());
+    let headers: Vec<String> = reader.headers()?.iter().map(|h| h.trim().to_string()).collect();
+    let first = |name: &str| headers.iter().position(|h| h == name);
+    let last = |name: &str| headers.iter().rposition(|h| h == name);
+
+    let name = first("Activity Name");
+    let activity_type = first("Activity Type");
+    let description = first("Activity Description");
+    let gear = first("Activity Gear");
+    let commute = first("Commute");
+    let exertion = first("Perceived Exertion");
+    let date = first("Activity Date");
+    let elapsed = first("Elapsed Time");
+    let distance = last("Distance").filter(|i| first("Distance") != Some(*i));
+    let filename = first("Filename");
+
+    let mut activities = Vec::new();
+    for record in reader.records() {
+        let record = record?;
+        let text = |column: Option<usize>| {
+            column
+                .and_then(|i| record.get(i))
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+                .map(str::to_string)
+        };
+        let number = |column: Option<usize>| text(column).and_then(|v| v.parse::<f64>().ok());
+
+        activities.push(ActivityMetadata {
+            name: text(name),
+            activity_type: text(activity_type),
+            description: text(description),
+            gear: text(gear),
+            commute: text(commute).is_some_and(|v| matches!(v.to_lowercase().as_str(), "true" | "1" | "1.0")),
+            perceived_exertion: number(exertion).map(|e| e.round().clamp(1.0, 10.0) as u8),
+            start: text(date).and_then(|d| NaiveDateTime::parse_from_str(&d, "%b %d, %Y, %I:%M:%S %p").ok()),
+            elapsed_seconds: number(elapsed).map(|s| s.round() as u32),
+            distance_meters: number(distance).and_then(Decimal::from_f64).map(|d| d.round_dp(1)),
+            filename: text(filename),
+        });
+    }
+
+    Ok(activities)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use std::io::Write;
+
+    const ACTIVITIES: &str = "\
+Activity ID,Activity Date,Activity Name,Activity Type,Activity Description,Elapsed Time,Distance,Commute,Activity Gear,Filename,Elapsed Time,Distance,Perceived Exertion
+101,\"May 4, 2024, 4:30:00 AM\",Morning commute,Run,Easy along the river,20,0.18,true,Pegasus 40,activities/101.tcx.gz,20,180.0,3.0
+102,\"May 5, 2024, 6:00:00 PM\",Gym,WeightTraining,,2700,0,false,,,2700,0.0,7
+";
+
+    const RUN: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<TrainingCenterDatabase xmlns="http://www.garmin.com/xmlschemas/TrainingCenterDatabase/v2">
+  <Activities>
+    <Activity Sport="Other">
+      <Id>2024-05-04T06:30:00+02:00</Id>
+      <Lap StartTime="2024-05-04T04:30:00Z">
+        <TotalTimeSeconds>20.0</TotalTimeSeconds>
+        <Track>
+          <Trackpoint><Time>2024-05-04T04:30:00Z</Time><DistanceMeters>0.0</DistanceMeters><HeartRateBpm><Value>120</Value></HeartRateBpm></Trackpoint>
+          <Trackpoint><Time>2024-05-04T04:30:20Z</Time><DistanceMeters>180.0</DistanceMeters><HeartRateBpm><Value>130</Value></HeartRateBpm></Trackpoint>
+        </Track>
+      </Lap>
+    </Activity>
+  </Activities>
+</TrainingCenterDatabase>"#;
+
+    fn write_export(path: &Path) {
+        let mut zip = zip::ZipWriter::new(File::create(path).unwrap());
+        let options = zip::write::SimpleFileOptions::default();
+
+        zip.start_file(ACTIVITIES_CSV, options).unwrap();
+        zip.write_all(ACTIVITIES.as_bytes()).unwrap();
+
+        let mut gz = GzEncoder::new(Vec::new(), Compression::default());
+        gz.write_all(RUN.as_bytes()).unwrap();
+        zip.start_file("activities/101.tcx.gz", options).unwrap();
+        zip.write_all(&gz.finish().unwrap()).unwrap();
+
+        zip.start_file("profile.csv", options).unwrap();
+        zip.write_all(b"Email\nsam@example.com\n").unwrap();
+        zip.finish().unwrap();
+    }
+
+    #[test]
+    fn test_parses_repeated_strava_columns() {
+        let activities = parse_activities_csv(ACTIVITIES).unwrap();
+        assert_eq!(activities.len(), 2);
+        assert_eq!(activities[0].distance_meters, Some(Decimal::from(180)));
+        assert_eq!(activities[0].elapsed_seconds, Some(20));
+        assert_eq!(activities[0].filename.as_deref(), Some("activities/101.tcx.gz"));
+        assert!(activities[0].commute);
+        assert_eq!(activities[1].perceived_exertion, Some(7));
+        assert_eq!(activities[1].start, NaiveDateTime::parse_from_str("2024-05-05 18:00:00", "%Y-%m-%d %H:%M:%S").ok());
+    }
+
+    #[test]
+    fn test_imports_strava_export() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("export_12345.zip");
+        write_export(&path);
+
+        let result = ArchiveImporter::new().import_archive(&path).unwrap();
+        assert!(result.failures.is_empty(), "{:?}", result.failures);
+        assert_eq!(result.skipped, 1);
+        assert_eq!(result.workouts.len(), 2);
+
+        let run = &result.workouts[0];
+        assert_eq!(run.sport, Sport::Running);
+        assert_eq!(run.rpe, Some(3));
+        assert_eq!(run.duration_seconds, 20);
+        assert_eq!(run.source.as_deref(), Some("export_12345.zip:activities/101.tcx.gz"));
+        let notes = run.notes.as_deref().unwrap();
+        assert!(notes.starts_with("Morning commute\nEasy along the river\nGear: Pegasus 40\nCommute"), "{}", notes);
+
+        let gym = &result.workouts[1];
+        assert_eq!(gym.sport, Sport::CrossTraining);
+        assert_eq!(gym.workout_type, WorkoutType::Strength);
+        assert_eq!(gym.duration_seconds, 2700);
+        assert_eq!(gym.rpe, Some(7));
+        assert!(gym.raw_data.is_none());
+    }
+}
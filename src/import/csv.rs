@@ -2,16 +2,21 @@ use anyhow::Result;
 use chrono::{DateTime, NaiveDateTime, Utc};
 use csv::ReaderBuilder;
 use rust_decimal::Decimal;
-use rust_decimal_macros::dec;
 use std::collections::HashMap;
 use std::path::Path;
 
+use crate::import::csv_profile::{ColumnMapping, CsvField, CsvProfile, CsvProfileRegistry};
 use crate::import::{validation::WorkoutValidator, ImportFormat};
 use crate::models::{DataPoint, DataSource, Sport, Workout, WorkoutSummary, WorkoutType};
 
 /// CSV importer with flexible column mapping
+///
+/// Columns are mapped by an explicit [`CsvProfile`], by a profile detected
+/// from the header row, or else by matching common column names.
 pub struct CsvImporter {
     column_mapping: HashMap<String, String>,
+    profile: Option<CsvProfile>,
+    profiles: CsvProfileRegistry,
 }
 
 impl CsvImporter {
@@ -59,6 +64,21 @@ impl CsvImporter {
             "distance",
             &["distance", "dist", "total_distance", "cumulative_distance"],
         );
+        Self::add_mapping(
+            &mut column_mapping,
+            "temperature",
+            &["temperature", "temp", "air_temperature"],
+        );
+        Self::add_mapping(
+            &mut column_mapping,
+            "drive_length",
+            &["drive_length", "drive_length_m"],
+        );
+        Self::add_mapping(
+            &mut column_mapping,
+            "stroke_work",
+            &["stroke_work", "work_per_stroke", "work_per_stroke_j"],
+        );
         Self::add_mapping(
             &mut column_mapping,
             "latitude",
@@ -70,7 +90,23 @@ impl CsvImporter {
             &["longitude", "lng", "lon", "position_long"],
         );
 
-        Self { column_mapping }
+        Self {
+            column_mapping,
+            profile: None,
+            profiles: CsvProfileRegistry::with_builtins(),
+        }
+    }
+
+    /// Map every file with the given profile instead of detecting one
+    pub fn with_profile(mut self, profile: CsvProfile) -> Self {
+        self.profile = Some(profile);
+        self
+    }
+
+    /// Detect profiles from the given registry rather than the built-in one
+    pub fn with_profiles(mut self, profiles: CsvProfileRegistry) -> Self {
+        self.profiles = profiles;
+        self
     }
 
     fn add_mapping(mapping: &mut HashMap<String, String>, standard: &str, variations: &[&str]) {
@@ -108,6 +144,16 @@ impl CsvImporter {
         anyhow::bail!("Unable to parse datetime: {}", date_str);
     }
 
+    /// Parse a datetime with a profile's chrono format, UTC unless it carries an offset
+    fn parse_datetime_with(date_str: &str, format: &str) -> Result<DateTime<Utc>> {
+        if let Ok(dt) = DateTime::parse_from_str(date_str, format) {
+            return Ok(dt.with_timezone(&Utc));
+        }
+        let naive_dt = NaiveDateTime::parse_from_str(date_str, format)
+            .map_err(|e| anyhow::anyhow!("Unable to parse datetime '{}' as '{}': {}", date_str, format, e))?;
+        Ok(DateTime::from_naive_utc_and_offset(naive_dt, Utc))
+    }
+
     fn normalize_column_name(&self, name: &str) -> String {
         let normalized = name.to_lowercase().replace([' ', '-'], "_");
 
@@ -230,6 +276,11 @@ impl CsvImporter {
             )),
             athlete_id: None,
             source: Some(file_path.to_string_lossy().to_string()),
+            swim_lengths: Vec::new(),
+            start_time: None,
+            laps: Vec::new(),
+            rpe: None,
+            strength_sets: Vec::new(),
         };
 
         Ok(workout)
@@ -275,22 +326,29 @@ impl ImportFormat for CsvImporter {
     }
 
     fn import_file(&self, file_path: &Path) -> Result<Vec<Workout>> {
+        let delimiter = self.profile.as_ref().and_then(|p| p.delimiter).unwrap_or(',');
         let mut reader = ReaderBuilder::new()
             .has_headers(true)
+            .delimiter(delimiter as u8)
             .from_path(file_path)?;
 
-        let headers = reader.headers()?.clone();
+        let headers: Vec<String> = reader.headers()?.iter().map(str::to_string).collect();
+        let profile = self.profile.as_ref().or_else(|| self.profiles.detect(&headers));
         let mut data_points = Vec::new();
 
-        // Map headers to standard names
-        let header_mapping: HashMap<usize, String> = headers
+        // Map headers to data point fields
+        let column_mapping: Vec<Option<ColumnMapping>> = headers
             .iter()
-            .enumerate()
-            .map(|(i, header)| (i, self.normalize_column_name(header)))
+            .map(|header| match profile {
+                Some(profile) => profile.column(header).cloned(),
+                None => CsvField::from_name(&self.normalize_column_name(header))
+                    .map(|field| ColumnMapping { field, unit: None }),
+            })
             .collect();
+        let timestamp_format = profile.and_then(|p| p.timestamp_format.as_deref());
 
         // Track workout start time
-        let mut workout_start_time = None;
+        let mut workout_start_time: Option<DateTime<Utc>> = None;
 
         let mut current_timestamp = 0u32;
 
@@ -314,6 +372,9 @@ impl ImportFormat for CsvImporter {
                 stroke_type: None,
                 lap_number: None,
                 sport_transition: None,
+                temperature: None,
+                drive_length: None,
+                stroke_work: None,
             };
 
             // Parse each field based on column mapping
@@ -322,69 +383,39 @@ impl ImportFormat for CsvImporter {
                     continue;
                 }
 
-                if let Some(column_name) = header_mapping.get(&i) {
-                    match column_name.as_str() {
-                        "timestamp" => {
-                            match Self::parse_datetime(value) {
-                                Ok(dt) => {
-                                    if workout_start_time.is_none() {
-                                        workout_start_time = Some(dt);
-                                        data_point.timestamp = 0;
-                                    } else if let Some(start) = workout_start_time {
-                                        data_point.timestamp =
-                                            (dt - start).num_seconds().max(0) as u32;
-                                    }
-                                }
-                                Err(_) => {
-                                    // Try parsing as elapsed seconds
-                                    if let Ok(elapsed) = value.parse::<f64>() {
-                                        data_point.timestamp = elapsed as u32;
-                                        if workout_start_time.is_none() {
-                                            workout_start_time = Some(Utc::now());
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                        "heart_rate" => {
-                            if let Ok(hr) = value.parse::<u16>() {
-                                data_point.heart_rate = Some(hr);
-                            }
-                        }
-                        "power" => {
-                            if let Ok(power) = value.parse::<u16>() {
-                                data_point.power = Some(power);
-                            }
-                        }
-                        "pace" => {
-                            if let Ok(pace) = value.parse::<f64>() {
-                                data_point.pace =
-                                    Some(Decimal::try_from(pace).unwrap_or(dec!(0.0)));
-                            }
-                        }
-                        "speed" => {
-                            if let Ok(speed) = value.parse::<f64>() {
-                                data_point.speed =
-                                    Some(Decimal::try_from(speed).unwrap_or(dec!(0.0)));
-                            }
-                        }
-                        "elevation" => {
-                            if let Ok(elevation) = value.parse::<i16>() {
-                                data_point.elevation = Some(elevation);
-                            }
-                        }
-                        "cadence" => {
-                            if let Ok(cadence) = value.parse::<u16>() {
-                                data_point.cadence = Some(cadence);
-                            }
+                let Some(Some(mapping)) = column_mapping.get(i) else {
+                    continue;
+                };
+
+                if mapping.field != CsvField::Timestamp {
+                    if let Some(converted) = mapping.parse(value) {
+                        mapping.field.set(&mut data_point, converted);
+                    }
+                    continue;
+                }
+
+                // Profiles without a timestamp format record elapsed time
+                let datetime = match (timestamp_format, profile) {
+                    (Some(format), _) => Self::parse_datetime_with(value, format).ok(),
+                    (None, Some(_)) => None,
+                    (None, None) => Self::parse_datetime(value).ok(),
+                };
+                match datetime {
+                    Some(dt) => {
+                        if let Some(start) = workout_start_time {
+                            data_point.timestamp = (dt - start).num_seconds().max(0) as u32;
+                        } else {
+                            workout_start_time = Some(dt);
+                            data_point.timestamp = 0;
                         }
-                        "distance" => {
-                            if let Ok(distance) = value.parse::<f64>() {
-                                data_point.distance =
-                                    Some(Decimal::try_from(distance).unwrap_or(dec!(0.0)));
+                    }
+                    None => {
+                        if let Some(elapsed) = mapping.parse(value) {
+                            data_point.timestamp = elapsed.max(0.0) as u32;
+                            if workout_start_time.is_none() {
+                                workout_start_time = Some(Utc::now());
                             }
                         }
-                        _ => {} // Ignore unknown columns
                     }
                 }
             }
@@ -402,6 +433,10 @@ impl ImportFormat for CsvImporter {
 
         // Create workout from data points
         let mut workout = self.create_workout_from_csv(file_path, data_points, start_time)?;
+        workout.start_time = workout_start_time.map(|t| t.fixed_offset());
+        if let Some(sport) = profile.and_then(|p| p.sport.clone()) {
+            workout.sport = sport;
+        }
 
         // Validate and clean the workout data
         WorkoutValidator::validate_workout(&mut workout)?;
@@ -412,4 +447,102 @@ impl ImportFormat for CsvImporter {
     fn get_format_name(&self) -> &'static str {
         "CSV"
     }
+
+    fn sniff(&self, header: &[u8]) -> Option<bool> {
+        // A text header row naming at least one known column among several
+        let first_line = header.split(|&b| b == b'\n').next().unwrap_or_default();
+        let Ok(line) = std::str::from_utf8(first_line) else {
+            return Some(false);
+        };
+        let columns: Vec<String> = line
+            .trim_end_matches('\r')
+            .split(',')
+            .map(|column| column.trim().trim_matches('"').to_string())
+            .collect();
+        Some(
+            columns.len() >= 2
+                && (self.profiles.detect(&columns).is_some()
+                    || columns.iter().any(|column| {
+                        let normalized = self.normalize_column_name(column);
+                        self.column_mapping.values().any(|standard| *standard == normalized)
+                    })),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_imports_with_detected_profile() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ride.csv");
+        std::fs::write(
+            &path,
+            "Time,Heart Rate (bpm),Power (W),Speed (mph),Distance (mi),Altitude (ft)\n\
+             2024-06-01 07:00:00,120,180,20,0,328\n\
+             2024-06-01 07:00:10,130,200,22.5,0.06,331\n",
+        )
+        .unwrap();
+
+        let workout = CsvImporter::new().import_file(&path).unwrap().remove(0);
+        assert_eq!(workout.sport, Sport::Cycling);
+        assert_eq!(workout.duration_seconds, 10);
+
+        let points = workout.raw_data.unwrap();
+        assert_eq!(points[1].timestamp, 10);
+        assert_eq!(points[1].power, Some(200));
+        assert_eq!(points[0].speed, Some(Decimal::new(8941, 3)));
+        assert_eq!(points[1].distance, Some(Decimal::new(96561, 3)));
+        assert_eq!(points[0].elevation, Some(100));
+    }
+
+    #[test]
+    fn test_explicit_profile_overrides_generic_columns() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("erg.csv");
+        std::fs::write(&path, "elapsed;split;spm\n0:00;1:55.0;24\n0:30;1:50.0;26\n").unwrap();
+
+        let profile = CsvProfile::from_toml(
+            r#"
+name = "erg"
+sport = "Rowing"
+delimiter = ";"
+
+[columns]
+"elapsed" = { field = "timestamp" }
+"split" = { field = "pace", unit = "s/500m" }
+"spm" = { field = "cadence" }
+"#,
+        )
+        .unwrap();
+
+        let workout = CsvImporter::new().with_profile(profile).import_file(&path).unwrap().remove(0);
+        assert_eq!(workout.sport, Sport::Rowing);
+        let points = workout.raw_data.unwrap();
+        assert_eq!(points[1].timestamp, 30);
+        assert_eq!(points[1].pace, Some(Decimal::new(3667, 3)));
+        assert_eq!(points[1].cadence, Some(26));
+    }
+
+    #[test]
+    fn test_imports_concept2_stroke_data() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("logbook.csv");
+        std::fs::write(
+            &path,
+            "\"Number\",\"Time (seconds)\",\"Distance (meters)\",\"Pace (seconds)\",\"Watts\",\"Stroke Rate\",\"Heart Rate\",\"Drive Length (meters)\",\"Work Per Stroke (joules)\"\n\
+             1,0.0,0,115.0,230,24,120,1.38,690\n\
+             2,2.5,10.9,114.0,236,25,125,1.41,702\n",
+        )
+        .unwrap();
+
+        let workout = CsvImporter::new().import_file(&path).unwrap().remove(0);
+        assert_eq!(workout.sport, Sport::Rowing);
+        let points = workout.raw_data.unwrap();
+        assert_eq!(points[1].cadence, Some(25));
+        assert_eq!(points[1].drive_length, Some(Decimal::new(141, 2)));
+        assert_eq!(points[1].stroke_work, Some(702));
+    }
 }
@@ -0,0 +1,576 @@
+//! Column mapping profiles for CSV exports
+//!
+//! Every training tool lays out its CSV export differently: column names,
+//! units and timestamp formats all vary. A profile describes one tool's
+//! layout, naming the [`DataPoint`] field each column feeds and the unit it
+//! is recorded in:
+//!
+//! ```toml
+//! name = "wahoo"
+//! description = "Wahoo ELEMNT companion app export"
+//! sport = "Cycling"
+//! timestamp_format = "%Y-%m-%d %H:%M:%S"
+//!
+//! [columns]
+//! "Time" = { field = "timestamp" }
+//! "Speed (mph)" = { field = "speed", unit = "mph" }
+//! "Altitude (ft)" = { field = "elevation", unit = "ft" }
+//! ```
+//!
+//! Profiles for common tools are built in; further profiles are read from
+//! `~/.trainrs/csv_profiles/*.toml`.
+
+use anyhow::{Context, Result};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::models::{DataPoint, Sport};
+
+/// `DataPoint` field a CSV column feeds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CsvField {
+    Timestamp,
+    HeartRate,
+    Power,
+    Pace,
+    Speed,
+    Elevation,
+    Cadence,
+    Distance,
+    Temperature,
+    GroundContactTime,
+    VerticalOscillation,
+    StrideLength,
+    DriveLength,
+    StrokeWork,
+}
+
+/// Unit a CSV column is recorded in
+///
+/// Values are converted to the units `DataPoint` stores: seconds, m/s,
+/// meters, min/km and °C.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CsvUnit {
+    #[serde(rename = "s")]
+    Seconds,
+    #[serde(rename = "min")]
+    Minutes,
+    #[serde(rename = "ms")]
+    Milliseconds,
+    #[serde(rename = "m/s")]
+    MetersPerSecond,
+    #[serde(rename = "km/h")]
+    KilometersPerHour,
+    #[serde(rename = "mph")]
+    MilesPerHour,
+    #[serde(rename = "m")]
+    Meters,
+    #[serde(rename = "km")]
+    Kilometers,
+    #[serde(rename = "mi")]
+    Miles,
+    #[serde(rename = "ft")]
+    Feet,
+    #[serde(rename = "min/km")]
+    MinutesPerKilometer,
+    #[serde(rename = "min/mi")]
+    MinutesPerMile,
+    #[serde(rename = "s/500m")]
+    SecondsPer500Meters,
+    #[serde(rename = "c")]
+    Celsius,
+    #[serde(rename = "f")]
+    Fahrenheit,
+}
+
+/// Mapping of one CSV column
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ColumnMapping {
+    pub field: CsvField,
+    /// Unit of the column; the field's storage unit when absent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unit: Option<CsvUnit>,
+}
+
+/// Named CSV column mapping profile
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CsvProfile {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Sport assigned to imported workouts
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sport: Option<Sport>,
+    /// Field delimiter, a comma when absent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delimiter: Option<char>,
+    /// chrono format of absolute timestamps; timestamps are elapsed time when absent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp_format: Option<String>,
+    /// Column header → mapping
+    pub columns: BTreeMap<String, ColumnMapping>,
+}
+
+/// Collection of CSV profiles, built-in and user supplied
+#[derive(Debug, Clone)]
+pub struct CsvProfileRegistry {
+    profiles: Vec<CsvProfile>,
+    /// User profile files that failed to load, with the reason
+    invalid: Vec<(PathBuf, String)>,
+}
+
+const WAHOO: &str = r#"
+name = "wahoo"
+description = "Wahoo ELEMNT companion app export (imperial units)"
+sport = "Cycling"
+timestamp_format = "%Y-%m-%d %H:%M:%S"
+
+[columns]
+"Time" = { field = "timestamp" }
+"Heart Rate (bpm)" = { field = "heart_rate" }
+"Power (W)" = { field = "power" }
+"Cadence (rpm)" = { field = "cadence" }
+"Speed (mph)" = { field = "speed", unit = "mph" }
+"Distance (mi)" = { field = "distance", unit = "mi" }
+"Altitude (ft)" = { field = "elevation", unit = "ft" }
+"Temperature (F)" = { field = "temperature", unit = "f" }
+"#;
+
+const ZWIFT: &str = r#"
+name = "zwift"
+description = "Zwift ride data export"
+sport = "Cycling"
+
+[columns]
+"Elapsed Time" = { field = "timestamp" }
+"Watts" = { field = "power" }
+"HeartRate" = { field = "heart_rate" }
+"Cadence" = { field = "cadence" }
+"Speed (km/h)" = { field = "speed", unit = "km/h" }
+"Distance (km)" = { field = "distance", unit = "km" }
+"Altitude (m)" = { field = "elevation" }
+"#;
+
+const CONCEPT2: &str = r#"
+name = "concept2"
+description = "Concept2 Logbook stroke data export"
+sport = "Rowing"
+
+[columns]
+"Time (seconds)" = { field = "timestamp" }
+"Distance (meters)" = { field = "distance" }
+"Pace (seconds)" = { field = "pace", unit = "s/500m" }
+"Watts" = { field = "power" }
+"Stroke Rate" = { field = "cadence" }
+"Heart Rate" = { field = "heart_rate" }
+"Drive Length (meters)" = { field = "drive_length" }
+"Work Per Stroke (joules)" = { field = "stroke_work" }
+"#;
+
+const WKO: &str = r#"
+name = "wko"
+description = "WKO and PowerTap CSV export"
+sport = "Cycling"
+
+[columns]
+"Minutes" = { field = "timestamp", unit = "min" }
+"Km/h" = { field = "speed", unit = "km/h" }
+"Watts" = { field = "power" }
+"Km" = { field = "distance", unit = "km" }
+"Cadence" = { field = "cadence" }
+"Hrate" = { field = "heart_rate" }
+"Altitude (m)" = { field = "elevation" }
+"#;
+
+const GOLDEN_CHEETAH: &str = r#"
+name = "golden_cheetah"
+description = "Golden Cheetah ride export"
+
+[columns]
+"secs" = { field = "timestamp" }
+"cad" = { field = "cadence" }
+"hr" = { field = "heart_rate" }
+"km" = { field = "distance", unit = "km" }
+"kph" = { field = "speed", unit = "km/h" }
+"watts" = { field = "power" }
+"alt" = { field = "elevation" }
+"temp" = { field = "temperature" }
+"#;
+
+const BUILTIN_PROFILES: &[&str] = &[WAHOO, ZWIFT, CONCEPT2, WKO, GOLDEN_CHEETAH];
+
+impl CsvField {
+    /// Field for one of the generic importer's standard column names
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "timestamp" => Some(CsvField::Timestamp),
+            "heart_rate" => Some(CsvField::HeartRate),
+            "power" => Some(CsvField::Power),
+            "pace" => Some(CsvField::Pace),
+            "speed" => Some(CsvField::Speed),
+            "elevation" => Some(CsvField::Elevation),
+            "cadence" => Some(CsvField::Cadence),
+            "distance" => Some(CsvField::Distance),
+            "temperature" => Some(CsvField::Temperature),
+            "ground_contact_time" => Some(CsvField::GroundContactTime),
+            "vertical_oscillation" => Some(CsvField::VerticalOscillation),
+            "stride_length" => Some(CsvField::StrideLength),
+            "drive_length" => Some(CsvField::DriveLength),
+            "stroke_work" => Some(CsvField::StrokeWork),
+            _ => None,
+        }
+    }
+
+    /// Unit the field is stored in
+    fn storage_unit(self) -> Option<CsvUnit> {
+        match self {
+            CsvField::Timestamp => Some(CsvUnit::Seconds),
+            CsvField::Pace => Some(CsvUnit::MinutesPerKilometer),
+            CsvField::Speed => Some(CsvUnit::MetersPerSecond),
+            CsvField::Elevation | CsvField::Distance | CsvField::StrideLength | CsvField::DriveLength => {
+                Some(CsvUnit::Meters)
+            }
+            CsvField::Temperature => Some(CsvUnit::Celsius),
+            _ => None,
+        }
+    }
+
+    /// Store a converted value on a data point
+    ///
+    /// Timestamps are resolved by the importer and ignored here.
+    pub fn set(self, point: &mut DataPoint, value: f64) {
+        let decimal = || Decimal::try_from(value).ok().map(|d| d.round_dp(3));
+        match self {
+            CsvField::Timestamp => {}
+            CsvField::HeartRate => point.heart_rate = Some(value.round() as u16),
+            CsvField::Power => point.power = Some(value.round() as u16),
+            CsvField::Pace => point.pace = decimal(),
+            CsvField::Speed => point.speed = decimal(),
+            CsvField::Elevation => point.elevation = Some(value.round() as i16),
+            CsvField::Cadence => point.cadence = Some(value.round() as u16),
+            CsvField::Distance => point.distance = decimal(),
+            CsvField::Temperature => point.temperature = Some(value.round() as i8),
+            CsvField::GroundContactTime => point.ground_contact_time = Some(value.round() as u16),
+            CsvField::VerticalOscillation => point.vertical_oscillation = Some(value.round() as u16),
+            CsvField::StrideLength => point.stride_length = decimal(),
+            CsvField::DriveLength => point.drive_length = decimal(),
+            CsvField::StrokeWork => point.stroke_work = Some(value.round() as u16),
+        }
+    }
+}
+
+impl CsvUnit {
+    /// Convert a value to the matching `DataPoint` storage unit
+    pub fn to_storage(self, value: f64) -> f64 {
+        match self {
+            CsvUnit::Seconds
+            | CsvUnit::MetersPerSecond
+            | CsvUnit::Meters
+            | CsvUnit::MinutesPerKilometer
+            | CsvUnit::Celsius => value,
+            CsvUnit::Minutes => value * 60.0,
+            CsvUnit::Milliseconds => value / 1000.0,
+            CsvUnit::KilometersPerHour => value / 3.6,
+            CsvUnit::MilesPerHour => value * 0.44704,
+            CsvUnit::Kilometers => value * 1000.0,
+            CsvUnit::Miles => value * 1609.344,
+            CsvUnit::Feet => value * 0.3048,
+            CsvUnit::MinutesPerMile => value / 1.609344,
+            CsvUnit::SecondsPer500Meters => value * 2.0 / 60.0,
+            CsvUnit::Fahrenheit => (value - 32.0) * 5.0 / 9.0,
+        }
+    }
+
+    /// Whether clock values such as `8:30` count minutes rather than seconds
+    fn counts_minutes(self) -> bool {
+        matches!(
+            self,
+            CsvUnit::Minutes | CsvUnit::MinutesPerKilometer | CsvUnit::MinutesPerMile
+        )
+    }
+}
+
+impl ColumnMapping {
+    /// Parse a cell and convert it to the field's storage unit
+    ///
+    /// Clock values (`1:52.3`, `1:02:03`) are accepted for time, pace and
+    /// elapsed timestamp columns.
+    pub fn parse(&self, raw: &str) -> Option<f64> {
+        let raw = raw.trim();
+        let unit = self.unit.or_else(|| self.field.storage_unit());
+        let value = if raw.contains(':') {
+            let seconds = parse_clock(raw)?;
+            match unit {
+                Some(unit) if unit.counts_minutes() => seconds / 60.0,
+                _ => seconds,
+            }
+        } else {
+            raw.parse::<f64>().ok()?
+        };
+        if !value.is_finite() {
+            return None;
+        }
+        Some(unit.map_or(value, |unit| unit.to_storage(value)))
+    }
+}
+
+/// Seconds in an `h:mm:ss` or `m:ss` clock value
+fn parse_clock(raw: &str) -> Option<f64> {
+    let mut seconds = 0.0;
+    for part in raw.split(':') {
+        seconds = seconds * 60.0 + part.trim().parse::<f64>().ok()?;
+    }
+    Some(seconds)
+}
+
+/// Column headers compare case-insensitively, ignoring surrounding whitespace
+fn same_header(a: &str, b: &str) -> bool {
+    a.trim().eq_ignore_ascii_case(b.trim())
+}
+
+impl CsvProfile {
+    /// Parse a profile from TOML
+    pub fn from_toml(content: &str) -> Result<Self> {
+        toml::from_str(content).with_context(|| "Failed to parse TOML CSV profile")
+    }
+
+    /// Load a profile from a TOML file
+    pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read CSV profile: {}", path.as_ref().display()))?;
+        Self::from_toml(&content).with_context(|| format!("Invalid CSV profile: {}", path.as_ref().display()))
+    }
+
+    /// Mapping for a column header
+    pub fn column(&self, header: &str) -> Option<&ColumnMapping> {
+        self.columns
+            .iter()
+            .find(|(name, _)| same_header(name, header))
+            .map(|(_, mapping)| mapping)
+    }
+
+    /// Number of the profile's columns present among the headers
+    pub fn matched_columns(&self, headers: &[String]) -> usize {
+        self.columns
+            .keys()
+            .filter(|name| headers.iter().any(|header| same_header(name, header)))
+            .count()
+    }
+
+    /// Whether the headers look like this profile's export
+    ///
+    /// The timestamp column and at least half of the mapped columns must be
+    /// present.
+    pub fn matches(&self, headers: &[String]) -> bool {
+        let has_timestamp = self.columns.iter().any(|(name, mapping)| {
+            mapping.field == CsvField::Timestamp && headers.iter().any(|header| same_header(name, header))
+        });
+        let matched = self.matched_columns(headers);
+        has_timestamp && matched >= 2 && matched * 2 >= self.columns.len()
+    }
+}
+
+impl CsvProfileRegistry {
+    /// Registry holding only the built-in profiles
+    pub fn with_builtins() -> Self {
+        let profiles = BUILTIN_PROFILES
+            .iter()
+            .map(|content| CsvProfile::from_toml(content).expect("built-in CSV profile is valid"))
+            .collect();
+        Self { profiles, invalid: Vec::new() }
+    }
+
+    /// Directory user profiles are read from
+    pub fn default_dir() -> PathBuf {
+        dirs::home_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join(".trainrs")
+            .join("csv_profiles")
+    }
+
+    /// Add a profile, replacing any profile of the same name
+    pub fn add(&mut self, profile: CsvProfile) {
+        self.profiles.retain(|p| p.name != profile.name);
+        self.profiles.push(profile);
+    }
+
+    /// Load every `*.toml` profile in a directory, returning how many were read
+    ///
+    /// Files that fail to parse are skipped and listed by [`Self::invalid`],
+    /// so one broken profile does not stop imports with the others.
+    pub fn load_dir<P: AsRef<Path>>(&mut self, dir: P) -> Result<usize> {
+        let dir = dir.as_ref();
+        if !dir.is_dir() {
+            return Ok(0);
+        }
+
+        let mut paths: Vec<PathBuf> = fs::read_dir(dir)
+            .with_context(|| format!("Failed to read CSV profile directory: {}", dir.display()))?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+            .collect();
+        paths.sort();
+
+        let mut loaded = 0;
+        for path in paths {
+            match CsvProfile::load_from_file(&path) {
+                Ok(profile) => {
+                    self.add(profile);
+                    loaded += 1;
+                }
+                Err(e) => self.invalid.push((path, format!("{:#}", e))),
+            }
+        }
+        Ok(loaded)
+    }
+
+    /// Profile files skipped by [`Self::load_dir`], with the reason
+    pub fn invalid(&self) -> &[(PathBuf, String)] {
+        &self.invalid
+    }
+
+    /// Profile by name
+    pub fn get(&self, name: &str) -> Option<&CsvProfile> {
+        self.profiles.iter().find(|p| p.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Profile by name, or loaded from a TOML file path
+    ///
+    /// A name matching the file of a skipped profile reports why it failed.
+    pub fn resolve(&self, name_or_path: &str) -> Result<CsvProfile> {
+        if let Some(profile) = self.get(name_or_path) {
+            return Ok(profile.clone());
+        }
+        let path = Path::new(name_or_path);
+        if path.is_file() {
+            return CsvProfile::load_from_file(path);
+        }
+        let skipped = self.invalid.iter().find(|(path, _)| {
+            path.file_stem().and_then(|stem| stem.to_str()).is_some_and(|stem| stem.eq_ignore_ascii_case(name_or_path))
+        });
+        if let Some((_, reason)) = skipped {
+            anyhow::bail!("{}", reason);
+        }
+
+        let names: Vec<&str> = self.profiles.iter().map(|p| p.name.as_str()).collect();
+        anyhow::bail!(
+            "Unknown CSV profile '{}' (available: {})",
+            name_or_path,
+            names.join(", ")
+        )
+    }
+
+    /// Profile whose columns best match a header row
+    pub fn detect(&self, headers: &[String]) -> Option<&CsvProfile> {
+        self.profiles
+            .iter()
+            .filter(|p| p.matches(headers))
+            .max_by_key(|p| p.matched_columns(headers))
+    }
+}
+
+impl Default for CsvProfileRegistry {
+    fn default() -> Self {
+        Self::with_builtins()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(names: &[&str]) -> Vec<String> {
+        names.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_builtin_profiles_parse() {
+        let registry = CsvProfileRegistry::with_builtins();
+        for name in ["wahoo", "zwift", "concept2", "wko", "golden_cheetah"] {
+            assert!(registry.get(name).is_some(), "missing {}", name);
+        }
+        assert_eq!(registry.get("concept2").unwrap().sport, Some(Sport::Rowing));
+    }
+
+    #[test]
+    fn test_unit_conversions() {
+        let mapping = |field, unit| ColumnMapping { field, unit: Some(unit) };
+        let close = |a: f64, b: f64| (a - b).abs() < 1e-4;
+
+        assert!(close(mapping(CsvField::Speed, CsvUnit::MilesPerHour).parse("10").unwrap(), 4.4704));
+        assert!(close(mapping(CsvField::Elevation, CsvUnit::Feet).parse("1000").unwrap(), 304.8));
+        assert!(close(mapping(CsvField::Pace, CsvUnit::MinutesPerMile).parse("8:02.803").unwrap(), 5.0));
+        assert!(close(mapping(CsvField::Pace, CsvUnit::SecondsPer500Meters).parse("1:30").unwrap(), 3.0));
+        assert!(close(mapping(CsvField::Temperature, CsvUnit::Fahrenheit).parse("68").unwrap(), 20.0));
+
+        let elapsed = ColumnMapping { field: CsvField::Timestamp, unit: None };
+        assert_eq!(elapsed.parse("1:02:03"), Some(3723.0));
+        assert_eq!(elapsed.parse("n/a"), None);
+    }
+
+    #[test]
+    fn test_detects_profile_from_headers() {
+        let registry = CsvProfileRegistry::with_builtins();
+
+        let concept2 = headers(&[
+            "Number", "Time (seconds)", "Distance (meters)", "Pace (seconds)", "Watts", "Cal/Hr", "Stroke Rate",
+            "Heart Rate",
+        ]);
+        assert_eq!(registry.detect(&concept2).map(|p| p.name.as_str()), Some("concept2"));
+
+        let cheetah = headers(&["secs", "cad", "hr", "km", "kph", "nm", "watts", "alt", "lon", "lat"]);
+        assert_eq!(registry.detect(&cheetah).map(|p| p.name.as_str()), Some("golden_cheetah"));
+
+        let generic = headers(&["time", "hr", "power"]);
+        assert!(registry.detect(&generic).is_none());
+    }
+
+    #[test]
+    fn test_user_profile_replaces_builtin() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("wahoo.toml"),
+            r#"
+name = "wahoo"
+sport = "Running"
+delimiter = ";"
+
+[columns]
+"t" = { field = "timestamp", unit = "ms" }
+"#,
+        )
+        .unwrap();
+
+        let mut registry = CsvProfileRegistry::with_builtins();
+        assert_eq!(registry.load_dir(dir.path()).unwrap(), 1);
+        let wahoo = registry.resolve("wahoo").unwrap();
+        assert_eq!(wahoo.sport, Some(Sport::Running));
+        assert_eq!(wahoo.delimiter, Some(';'));
+        assert_eq!(registry.profiles.len(), BUILTIN_PROFILES.len());
+        assert!(registry.resolve("strava").is_err());
+    }
+
+    #[test]
+    fn test_invalid_user_profile_is_skipped() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("broken.toml"), "name = \"broken\"\n[columns\n").unwrap();
+        std::fs::write(
+            dir.path().join("rower.toml"),
+            "name = \"rower\"\n\n[columns]\n\"t\" = { field = \"timestamp\" }\n",
+        )
+        .unwrap();
+
+        let mut registry = CsvProfileRegistry::with_builtins();
+        assert_eq!(registry.load_dir(dir.path()).unwrap(), 1);
+        assert_eq!(registry.invalid().len(), 1);
+        assert!(registry.resolve("rower").is_ok());
+        assert!(registry.resolve("wahoo").is_ok());
+
+        let error = registry.resolve("broken").unwrap_err().to_string();
+        assert!(error.contains("broken.toml"), "{}", error);
+    }
+}
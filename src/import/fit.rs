@@ -1,7 +1,10 @@
 use anyhow::{Context, Result};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, FixedOffset, Utc};
 use fitparser::{FitDataRecord, Value};
+use rust_decimal::prelude::FromPrimitive;
 use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use std::collections::HashMap;
 use std::fs::File;
 use std::path::Path;
 use uuid::Uuid;
@@ -13,10 +16,13 @@ use crate::import::{
     validation_rules::DataValidator,
     ImportFormat
 };
-use crate::models::{DataPoint, DataSource, Sport, Workout, WorkoutSummary, WorkoutType};
+use crate::models::{
+    DataPoint, DataSource, Lap, LapIntensity, LapTrigger, SetType, Sport, StrengthSet, SwimLength, SwimLengthType,
+    Workout, WorkoutSummary, WorkoutType,
+};
 use crate::power::PowerAnalyzer;
 use crate::recovery::{BodyBatteryData, HrvMeasurement, PhysiologicalMetrics, SleepSession, SleepStage, SleepStageSegment};
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 
 /// FIT file importer for Garmin native format.
 ///
@@ -254,6 +260,17 @@ impl FitImporter {
     pub fn quirk_registry(&self) -> &QuirkRegistry {
         &self.quirk_registry
     }
+
+    /// Read the recording device of a FIT file
+    pub fn read_device_info(&self, file_path: &Path) -> Result<Option<DeviceInfo>> {
+        let mut file = File::open(file_path)
+            .with_context(|| format!("Failed to open FIT file: {}", file_path.display()))?;
+
+        let records: Vec<FitDataRecord> = fitparser::from_reader(&mut file)
+            .map_err(|e| anyhow::anyhow!("Failed to parse FIT file records: {:?}", e))?;
+
+        Ok(self.extract_device_info(&records))
+    }
     /// Parse HRV (Heart Rate Variability) data from FIT file monitoring messages
     ///
     /// Extracts HRV metrics from MonitoringInfo and StressLevel messages in FIT files.
@@ -956,20 +973,13 @@ impl FitImporter {
                 let mut workout_type = WorkoutType::Endurance;
                 let mut start_time = Utc::now();
                 let mut duration = 0u32;
+                let mut indoor_rowing = false;
 
                 for field in record.fields() {
                     match field.name() {
                         "sport" => {
-                            if let Value::Enum(sport_val) = field.value() {
-                                sport = match sport_val {
-                                    1 => Sport::Running,
-                                    2 => Sport::Cycling,
-                                    5 => Sport::Swimming,
-                                    6 => Sport::Rowing,
-                                    15 => Sport::Triathlon,
-                                    26 => Sport::CrossTraining,
-                                    _ => Sport::Cycling, // Default fallback
-                                };
+                            if let Some(session_sport) = fit_sport(field.value()) {
+                                sport = session_sport;
                             }
                         }
                         "start_time" => {
@@ -985,6 +995,9 @@ impl FitImporter {
                             }
                         }
                         "sub_sport" => {
+                            if fit_indoor_rowing(field.value()) {
+                                indoor_rowing = true;
+                            }
                             if let Value::Enum(sub_sport_val) = field.value() {
                                 workout_type = match sub_sport_val {
                                     1 => WorkoutType::Interval, // Track
@@ -998,6 +1011,10 @@ impl FitImporter {
                     }
                 }
 
+                if indoor_rowing {
+                    sport = Sport::Rowing;
+                }
+
                 return Ok((sport, workout_type, start_time, duration));
             }
         }
@@ -1006,6 +1023,42 @@ impl FitImporter {
         Ok((Sport::Cycling, WorkoutType::Endurance, Utc::now(), 0))
     }
 
+    /// Start of the activity in the local time of the device
+    ///
+    /// Uses `Session.start_time`, falling back to `FileId.time_created`. The
+    /// UTC offset comes from the `Activity` message, whose `local_timestamp`
+    /// is the device's wall-clock time at `timestamp`.
+    fn parse_local_start_time(&self, records: &[FitDataRecord]) -> Option<DateTime<FixedOffset>> {
+        let timestamp = |record: &FitDataRecord, name: &str| {
+            record.fields().iter().find(|f| f.name() == name).and_then(|f| match f.value() {
+                Value::Timestamp(ts) => Some(*ts),
+                _ => None,
+            })
+        };
+        let find = |kind: fitparser::profile::MesgNum, name: &str| {
+            records.iter().filter(|r| r.kind() == kind).find_map(|r| timestamp(r, name))
+        };
+
+        let start = find(fitparser::profile::MesgNum::Session, "start_time")
+            .or_else(|| find(fitparser::profile::MesgNum::FileId, "time_created"))?
+            .with_timezone(&Utc);
+
+        let offset = records
+            .iter()
+            .filter(|r| r.kind() == fitparser::profile::MesgNum::Activity)
+            .find_map(|r| {
+                let utc = timestamp(r, "timestamp")?.naive_utc();
+                // Local date-times decode as wall-clock time in the reader's timezone
+                let local = timestamp(r, "local_timestamp")?.naive_local();
+                // Offsets are whole quarter hours and at most 14 hours
+                let seconds = ((local - utc).num_seconds() as f64 / 900.0).round() as i32 * 900;
+                FixedOffset::east_opt(seconds).filter(|_| seconds.abs() <= 14 * 3600)
+            })
+            .unwrap_or_else(|| FixedOffset::east_opt(0).unwrap());
+
+        Some(start.with_timezone(&offset))
+    }
+
     /// Extract device information from FIT file
     fn extract_device_info(&self, records: &[FitDataRecord]) -> Option<DeviceInfo> {
         for record in records {
@@ -1017,14 +1070,10 @@ impl FitImporter {
                 for field in record.fields() {
                     match field.name() {
                         "manufacturer" => {
-                            if let Value::UInt16(id) = field.value() {
-                                manufacturer_id = Some(*id);
-                            }
+                            manufacturer_id = fit_manufacturer_id(field.value());
                         }
-                        "product" => {
-                            if let Value::UInt16(id) = field.value() {
-                                product_id = Some(*id);
-                            }
+                        "product" | "garmin_product" => {
+                            product_id = fit_product_id(field.value());
                         }
                         "product_name" => {
                             // Product name is in the file, we can use it later
@@ -1043,9 +1092,7 @@ impl FitImporter {
                         if dev_record.kind() == fitparser::profile::MesgNum::DeviceInfo {
                             for dev_field in dev_record.fields() {
                                 if dev_field.name() == "software_version" {
-                                    if let Value::UInt16(ver) = dev_field.value() {
-                                        firmware_version = Some(*ver);
-                                    }
+                                    firmware_version = firmware_version.or(fit_software_version(dev_field.value()));
                                 }
                             }
                         }
@@ -1089,17 +1136,7 @@ impl FitImporter {
                             }
                         }
                         "sport" => {
-                            if let Value::Enum(sport_val) = field.value() {
-                                let lap_sport = match sport_val {
-                                    1 => Sport::Running,
-                                    2 => Sport::Cycling,
-                                    5 => Sport::Swimming,
-                                    6 => Sport::Rowing,
-                                    15 => Sport::Triathlon,
-                                    26 => Sport::CrossTraining,
-                                    _ => Sport::Cycling,
-                                };
-
+                            if let Some(lap_sport) = fit_sport(field.value()) {
                                 // Detect sport transition
                                 if let Some(prev_sport) = current_sport {
                                     if prev_sport != lap_sport {
@@ -1137,6 +1174,9 @@ impl FitImporter {
                     stroke_type: None,
                     lap_number: Some(current_lap),
                     sport_transition: None,
+                    temperature: None,
+                    drive_length: None,
+                    stroke_work: None,
                 };
 
                 for field in record.fields() {
@@ -1170,6 +1210,11 @@ impl FitImporter {
                                 data_point.cadence = Some(*cad as u16);
                             }
                         }
+                        "temperature" => {
+                            if let Value::SInt8(temp) = field.value() {
+                                data_point.temperature = Some(*temp);
+                            }
+                        }
                         "speed" | "enhanced_speed" => {
                             if let Value::Float64(speed) = field.value() {
                                 data_point.speed = Some(Decimal::from_f64_retain(*speed).unwrap_or_default());
@@ -1221,9 +1266,7 @@ impl FitImporter {
                             }
                         }
                         "swim_stroke" => {
-                            if let Value::Enum(stroke_type) = field.value() {
-                                data_point.stroke_type = Some(*stroke_type);
-                            }
+                            data_point.stroke_type = fit_swim_stroke(field.value());
                         }
                         _ => {}
                     }
@@ -1236,8 +1279,163 @@ impl FitImporter {
         Ok(data_points)
     }
 
+    /// Parse pool lengths from FIT `length` messages
+    ///
+    /// Lengths carry no distance of their own; each covers the session's pool length.
+    fn parse_swim_lengths(&self, records: &[FitDataRecord], start_time: DateTime<Utc>) -> Vec<SwimLength> {
+        let pool_length = records
+            .iter()
+            .filter(|r| r.kind() == fitparser::profile::MesgNum::Session)
+            .flat_map(|r| r.fields())
+            .find(|f| f.name() == "pool_length")
+            .and_then(|f| match f.value() {
+                Value::Float64(length) => Decimal::from_f64(*length),
+                Value::UInt16(length) => Some(Decimal::from(*length) / dec!(100)),
+                _ => None,
+            })
+            .map(|length| length.round_dp(2));
+        let Some(pool_length) = pool_length.filter(|l| *l > Decimal::ZERO) else {
+            return Vec::new();
+        };
+
+        let mut lengths = Vec::new();
+        for record in records.iter().filter(|r| r.kind() == fitparser::profile::MesgNum::Length) {
+            let mut length = SwimLength {
+                start_offset: 0,
+                duration_seconds: Decimal::ZERO,
+                distance: pool_length,
+                strokes: None,
+                stroke_type: None,
+                length_type: SwimLengthType::Active,
+            };
+
+            for field in record.fields() {
+                match (field.name(), field.value()) {
+                    ("start_time", Value::Timestamp(time)) => {
+                        length.start_offset = (time.with_timezone(&Utc) - start_time).num_seconds().max(0) as u32;
+                    }
+                    ("total_elapsed_time", Value::Float64(seconds)) => {
+                        length.duration_seconds = Decimal::from_f64(*seconds).unwrap_or_default().round_dp(3);
+                    }
+                    ("total_strokes", Value::UInt16(strokes)) => length.strokes = Some(*strokes),
+                    ("swim_stroke", value) => length.stroke_type = fit_swim_stroke(value),
+                    ("length_type", Value::String(kind)) if kind == "idle" => length.length_type = SwimLengthType::Idle,
+                    ("length_type", Value::Enum(0)) => length.length_type = SwimLengthType::Idle,
+                    _ => {}
+                }
+            }
+
+            if length.length_type == SwimLengthType::Idle {
+                length.distance = Decimal::ZERO;
+            }
+            lengths.push(length);
+        }
+
+        lengths
+    }
+
+    /// Parse strength sets from FIT `set` messages
+    ///
+    /// Exercise categories are kept as their FIT profile names (`bench_press`),
+    /// which is also how manually logged exercises are normalized.
+    fn parse_strength_sets(&self, records: &[FitDataRecord], start_time: DateTime<Utc>) -> Vec<StrengthSet> {
+        let mut sets = Vec::new();
+        for record in records.iter().filter(|r| r.kind() == fitparser::profile::MesgNum::Set) {
+            let mut set = StrengthSet {
+                start_offset: 0,
+                duration_seconds: 0,
+                exercise: None,
+                reps: None,
+                weight: None,
+                set_type: SetType::Active,
+            };
+
+            for field in record.fields() {
+                match (field.name(), field.value()) {
+                    ("start_time", Value::Timestamp(time)) => {
+                        set.start_offset = (time.with_timezone(&Utc) - start_time).num_seconds().max(0) as u32;
+                    }
+                    ("duration", Value::Float64(seconds)) => set.duration_seconds = seconds.round() as u32,
+                    ("repetitions", Value::UInt16(reps)) => set.reps = Some(*reps),
+                    ("weight", Value::Float64(kg)) => set.weight = Decimal::from_f64(*kg).map(|w| w.round_dp(2)),
+                    ("set_type", Value::String(kind)) if kind == "rest" => set.set_type = SetType::Rest,
+                    ("set_type", Value::Enum(0)) => set.set_type = SetType::Rest,
+                    ("category", value) => set.exercise = fit_exercise_category(value),
+                    _ => {}
+                }
+            }
+
+            if set.set_type == SetType::Rest {
+                set.reps = None;
+                set.weight = None;
+            }
+            sets.push(set);
+        }
+
+        sets.sort_by_key(|set| set.start_offset);
+        sets
+    }
+
+    /// Parse device laps from FIT `lap` messages
+    fn parse_laps(&self, records: &[FitDataRecord], start_time: DateTime<Utc>) -> Vec<Lap> {
+        let mut laps = Vec::new();
+        for record in records.iter().filter(|r| r.kind() == fitparser::profile::MesgNum::Lap) {
+            let mut lap = Lap {
+                start_offset: 0,
+                duration_seconds: 0,
+                distance: None,
+                avg_heart_rate: None,
+                max_heart_rate: None,
+                avg_power: None,
+                max_power: None,
+                trigger: LapTrigger::Unknown,
+                intensity: LapIntensity::Active,
+            };
+            let mut elapsed = None;
+
+            for field in record.fields() {
+                match (field.name(), field.value()) {
+                    ("start_time", Value::Timestamp(time)) => {
+                        lap.start_offset = (time.with_timezone(&Utc) - start_time).num_seconds().max(0) as u32;
+                    }
+                    ("total_timer_time", Value::Float64(seconds)) => lap.duration_seconds = seconds.round() as u32,
+                    ("total_elapsed_time", Value::Float64(seconds)) => elapsed = Some(seconds.round() as u32),
+                    ("total_distance", Value::Float64(meters)) => {
+                        lap.distance = Decimal::from_f64(*meters).map(|d| d.round_dp(1));
+                    }
+                    ("avg_heart_rate", Value::UInt8(hr)) => lap.avg_heart_rate = Some(u16::from(*hr)),
+                    ("max_heart_rate", Value::UInt8(hr)) => lap.max_heart_rate = Some(u16::from(*hr)),
+                    ("avg_power", Value::UInt16(power)) => lap.avg_power = Some(*power),
+                    ("max_power", Value::UInt16(power)) => lap.max_power = Some(*power),
+                    ("lap_trigger", value) => lap.trigger = fit_lap_trigger(value),
+                    ("intensity", value) => lap.intensity = fit_lap_intensity(value).unwrap_or(LapIntensity::Active),
+                    _ => {}
+                }
+            }
+
+            if lap.duration_seconds == 0 {
+                lap.duration_seconds = elapsed.unwrap_or(0);
+            }
+            laps.push(lap);
+        }
+
+        laps.sort_by_key(|lap| lap.start_offset);
+        laps
+    }
+
+    /// Number the data points by the device lap they fall in
+    ///
+    /// Lap messages are written when a lap ends, so counting them while
+    /// reading records labels each lap's points with the previous lap.
+    fn assign_lap_numbers(data_points: &mut [DataPoint], laps: &[Lap]) {
+        for point in data_points {
+            let index = laps.iter().rposition(|lap| lap.start_offset <= point.timestamp).unwrap_or(0);
+            point.lap_number = Some(index as u16 + 1);
+        }
+    }
+
     /// Calculate workout summary from data points with advanced power metrics
-    fn calculate_summary(&self, data_points: &[DataPoint], sport: &Sport) -> WorkoutSummary {
+    pub(crate) fn calculate_summary(data_points: &[DataPoint], sport: &Sport) -> WorkoutSummary {
         if data_points.is_empty() {
             return WorkoutSummary {
                 avg_heart_rate: None,
@@ -1341,7 +1539,7 @@ impl FitImporter {
     }
 
     /// Determine primary data source based on available data and sport
-    fn determine_data_source(&self, data_points: &[DataPoint], sport: &Sport) -> DataSource {
+    pub(crate) fn determine_data_source(data_points: &[DataPoint], sport: &Sport) -> DataSource {
         let has_power = data_points.iter().any(|dp| dp.power.is_some());
         let has_heart_rate = data_points.iter().any(|dp| dp.heart_rate.is_some());
         let has_speed = data_points.iter().any(|dp| dp.speed.is_some());
@@ -2004,32 +2202,53 @@ impl ImportFormat for FitImporter {
             .with_context(|| "Failed to parse session information from FIT file")?;
 
         // Parse data points
-        let data_points = self.parse_data_points(&records, start_time)
+        let mut data_points = self.parse_data_points(&records, start_time)
             .with_context(|| "Failed to parse data points from FIT file")?;
 
-        if data_points.is_empty() {
+        // Strength sessions recorded without a heart rate strap have sets but no samples
+        let strength_sets = self.parse_strength_sets(&records, start_time);
+        if data_points.is_empty() && strength_sets.is_empty() {
             anyhow::bail!("FIT file contains no usable data points");
         }
+        let workout_type = if strength_sets.is_empty() { workout_type } else { WorkoutType::Strength };
+
+        let swim_lengths = if sport == Sport::Swimming {
+            self.parse_swim_lengths(&records, start_time)
+        } else {
+            Vec::new()
+        };
+
+        let laps = self.parse_laps(&records, start_time);
+        if !laps.is_empty() {
+            Self::assign_lap_numbers(&mut data_points, &laps);
+        }
 
         // Calculate summary metrics
-        let summary = self.calculate_summary(&data_points, &sport);
+        let summary = Self::calculate_summary(&data_points, &sport);
 
         // Determine primary data source
-        let data_source = self.determine_data_source(&data_points, &sport);
+        let data_source = Self::determine_data_source(&data_points, &sport);
+
+        let local_start = self.parse_local_start_time(&records);
 
         // Create workout object
         let mut workout = Workout {
             id: Uuid::new_v4().to_string(),
-            date: start_time.date_naive(),
+            date: local_start.map_or(start_time.date_naive(), |start| start.date_naive()),
+            start_time: local_start,
             sport,
             duration_seconds: duration,
             workout_type,
             data_source,
-            raw_data: Some(data_points),
+            raw_data: (!data_points.is_empty()).then_some(data_points),
             summary,
             notes: Some(format!("Imported from FIT file: {}", file_path.display())),
             athlete_id: None, // TODO: Extract from FIT file if available
             source: Some(file_path.to_string_lossy().to_string()),
+            swim_lengths,
+            laps,
+            rpe: None,
+            strength_sets,
         };
 
         // Apply device quirks if device info was extracted
@@ -2075,6 +2294,20 @@ impl ImportFormat for FitImporter {
                     ));
                 }
 
+                if !report.quirk_suggestions.is_empty() {
+                    let suggestions: Vec<&str> = report
+                        .quirk_suggestions
+                        .iter()
+                        .map(|suggestion| suggestion.description.as_str())
+                        .collect();
+                    let current_notes = workout.notes.unwrap_or_default();
+                    workout.notes = Some(format!(
+                        "{}\nSuspected device quirks: {}",
+                        current_notes,
+                        suggestions.join("; ")
+                    ));
+                }
+
                 // In strict mode, fail on errors
                 if self.strict_mode && !report.errors.is_empty() {
                     anyhow::bail!(
@@ -2100,6 +2333,164 @@ impl ImportFormat for FitImporter {
     fn get_format_name(&self) -> &'static str {
         "FIT"
     }
+
+    fn sniff(&self, header: &[u8]) -> Option<bool> {
+        // The ".FIT" data type signature follows the header size, protocol and
+        // profile versions and data size
+        Some(header.len() >= 12 && &header[8..12] == b".FIT")
+    }
+}
+
+/// Look up a FIT profile enum number from the name it decodes to
+///
+/// fitparser decodes known values to names and unknown ones to `unknown_variant_N`.
+fn fit_enum_number<T>(name: &str, table: &'static OnceLock<HashMap<String, u16>>) -> Option<u16>
+where
+    T: From<u16> + std::fmt::Display,
+{
+    if let Some(number) = name.strip_prefix("unknown_variant_") {
+        return number.parse().ok();
+    }
+    table
+        .get_or_init(|| (0..=u16::MAX).map(|id| (T::from(id).to_string(), id)).collect())
+        .get(name)
+        .copied()
+}
+
+/// FIT `manufacturer` as its number
+fn fit_manufacturer_id(value: &Value) -> Option<u16> {
+    static NAMES: OnceLock<HashMap<String, u16>> = OnceLock::new();
+    match value {
+        Value::UInt16(id) => Some(*id),
+        Value::String(name) => fit_enum_number::<fitparser::profile::field_types::Manufacturer>(name, &NAMES),
+        _ => None,
+    }
+}
+
+/// FIT `product`, or its `garmin_product` subfield, as its number
+fn fit_product_id(value: &Value) -> Option<u16> {
+    static NAMES: OnceLock<HashMap<String, u16>> = OnceLock::new();
+    match value {
+        Value::UInt16(id) => Some(*id),
+        Value::String(name) => fit_enum_number::<fitparser::profile::field_types::GarminProduct>(name, &NAMES),
+        _ => None,
+    }
+}
+
+/// FIT `software_version` in hundredths, as the profile stores it before scaling
+fn fit_software_version(value: &Value) -> Option<u16> {
+    match value {
+        Value::UInt16(version) => Some(*version),
+        Value::Float64(version) => Some((version * 100.0).round() as u16),
+        _ => None,
+    }
+}
+
+/// Map a FIT `sport` field, decoded either as its raw number or its profile name
+fn fit_sport(value: &Value) -> Option<Sport> {
+    match value {
+        Value::Enum(1) => Some(Sport::Running),
+        Value::Enum(2) => Some(Sport::Cycling),
+        Value::Enum(4) | Value::Enum(10) => Some(Sport::CrossTraining),
+        Value::Enum(5) => Some(Sport::Swimming),
+        Value::Enum(15) => Some(Sport::Rowing),
+        Value::Enum(18) => Some(Sport::Triathlon),
+        Value::Enum(_) => Some(Sport::Cycling),
+        Value::String(name) => match name.as_str() {
+            "running" => Some(Sport::Running),
+            "cycling" => Some(Sport::Cycling),
+            "swimming" => Some(Sport::Swimming),
+            "rowing" => Some(Sport::Rowing),
+            "multisport" => Some(Sport::Triathlon),
+            "training" | "fitness_equipment" => Some(Sport::CrossTraining),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Map a FIT `set` exercise category to its profile name
+///
+/// Devices write the category as an array; the first known entry names the exercise.
+fn fit_exercise_category(value: &Value) -> Option<String> {
+    match value {
+        Value::String(name) if name != "unknown" => Some(name.clone()),
+        Value::Array(values) => values.iter().find_map(fit_exercise_category),
+        _ => None,
+    }
+}
+
+/// Whether a FIT `sub_sport` is indoor rowing, which watches record under
+/// the `fitness_equipment` sport
+fn fit_indoor_rowing(value: &Value) -> bool {
+    match value {
+        Value::Enum(sub_sport) => *sub_sport == 14,
+        Value::String(name) => name == "indoor_rowing",
+        _ => false,
+    }
+}
+
+/// FIT `swim_stroke` as its enum number (see `SwimStrokeType`)
+fn fit_swim_stroke(value: &Value) -> Option<u8> {
+    match value {
+        Value::Enum(stroke) => Some(*stroke),
+        Value::String(name) => match name.as_str() {
+            "freestyle" => Some(0),
+            "backstroke" => Some(1),
+            "breaststroke" => Some(2),
+            "butterfly" => Some(3),
+            "drill" => Some(4),
+            "mixed" => Some(5),
+            "im" => Some(6),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Map a FIT `lap_trigger`, decoded either as its raw number or its profile name
+fn fit_lap_trigger(value: &Value) -> LapTrigger {
+    let name = match value {
+        Value::Enum(trigger) => match trigger {
+            0 => "manual",
+            1 => "time",
+            2 => "distance",
+            3..=6 => "position_start",
+            7 => "session_end",
+            8 => "fitness_equipment",
+            _ => "",
+        },
+        Value::String(name) => name.as_str(),
+        _ => "",
+    };
+
+    match name {
+        "manual" => LapTrigger::Manual,
+        "time" => LapTrigger::Time,
+        "distance" => LapTrigger::Distance,
+        "position_start" | "position_lap" | "position_waypoint" | "position_marked" => LapTrigger::Position,
+        "session_end" => LapTrigger::SessionEnd,
+        "fitness_equipment" => LapTrigger::FitnessEquipment,
+        _ => LapTrigger::Unknown,
+    }
+}
+
+/// Map a FIT `intensity`; intervals count as work and recovery as rest
+fn fit_lap_intensity(value: &Value) -> Option<LapIntensity> {
+    match value {
+        Value::Enum(0) | Value::Enum(5) => Some(LapIntensity::Active),
+        Value::Enum(1) | Value::Enum(4) => Some(LapIntensity::Rest),
+        Value::Enum(2) => Some(LapIntensity::Warmup),
+        Value::Enum(3) => Some(LapIntensity::Cooldown),
+        Value::String(name) => match name.as_str() {
+            "active" | "interval" => Some(LapIntensity::Active),
+            "rest" | "recovery" => Some(LapIntensity::Rest),
+            "warmup" => Some(LapIntensity::Warmup),
+            "cooldown" => Some(LapIntensity::Cooldown),
+            _ => None,
+        },
+        _ => None,
+    }
 }
 
 #[cfg(test)]
@@ -2129,6 +2520,9 @@ mod tests {
                 stroke_type: None,
                 lap_number: Some(1),
                 sport_transition: None,
+                temperature: None,
+                drive_length: None,
+                stroke_work: None,
             },
             DataPoint {
                 timestamp: 1,
@@ -2148,6 +2542,9 @@ mod tests {
                 stroke_type: None,
                 lap_number: Some(1),
                 sport_transition: None,
+                temperature: None,
+                drive_length: None,
+                stroke_work: None,
             },
             DataPoint {
                 timestamp: 2,
@@ -2167,6 +2564,9 @@ mod tests {
                 stroke_type: None,
                 lap_number: Some(1),
                 sport_transition: None,
+                temperature: None,
+                drive_length: None,
+                stroke_work: None,
             },
             // Add more data points to ensure we have enough for 30-second rolling average
             DataPoint {
@@ -2187,15 +2587,17 @@ mod tests {
                 stroke_type: None,
                 lap_number: Some(1),
                 sport_transition: None,
+                temperature: None,
+                drive_length: None,
+                stroke_work: None,
             },
         ]
     }
 
     #[test]
     fn test_calculate_summary_with_power_metrics() {
-        let importer = FitImporter::new();
         let data_points = create_test_power_data_points();
-        let summary = importer.calculate_summary(&data_points, &Sport::Cycling);
+        let summary = FitImporter::calculate_summary(&data_points, &Sport::Cycling);
 
         // Test basic metrics
         assert!(summary.avg_power.is_some());
@@ -2219,7 +2621,6 @@ mod tests {
 
     #[test]
     fn test_calculate_summary_with_running_data() {
-        let importer = FitImporter::new();
         let data_points = vec![
             DataPoint {
                 timestamp: 0,
@@ -2239,6 +2640,9 @@ mod tests {
                 stroke_type: None,
                 lap_number: Some(1),
                 sport_transition: None,
+                temperature: None,
+                drive_length: None,
+                stroke_work: None,
             },
             DataPoint {
                 timestamp: 1,
@@ -2258,10 +2662,13 @@ mod tests {
                 stroke_type: None,
                 lap_number: Some(1),
                 sport_transition: None,
+                temperature: None,
+                drive_length: None,
+                stroke_work: None,
             },
         ];
 
-        let summary = importer.calculate_summary(&data_points, &Sport::Running);
+        let summary = FitImporter::calculate_summary(&data_points, &Sport::Running);
 
         // Power metrics should be None for running
         assert!(summary.avg_power.is_none());
@@ -2280,9 +2687,8 @@ mod tests {
 
     #[test]
     fn test_calculate_summary_empty_data() {
-        let importer = FitImporter::new();
         let data_points = vec![];
-        let summary = importer.calculate_summary(&data_points, &Sport::Cycling);
+        let summary = FitImporter::calculate_summary(&data_points, &Sport::Cycling);
 
         // All metrics should be None for empty data
         assert!(summary.avg_power.is_none());
@@ -2317,6 +2723,9 @@ mod tests {
             stroke_type: None,
             lap_number: Some(1),
             sport_transition: None,
+            temperature: None,
+            drive_length: None,
+            stroke_work: None,
         };
 
         assert_eq!(data_point.left_power, Some(95));
@@ -2329,8 +2738,6 @@ mod tests {
 
     #[test]
     fn test_running_dynamics_and_swimming_metrics() {
-        let importer = FitImporter::new();
-
         // Test running data point with dynamics
         let running_data = vec![
             DataPoint {
@@ -2351,11 +2758,14 @@ mod tests {
                 stroke_type: None,
                 lap_number: Some(1),
                 sport_transition: None,
+                temperature: None,
+                drive_length: None,
+                stroke_work: None,
             },
         ];
 
         // Test data source determination for running with dynamics
-        let running_source = importer.determine_data_source(&running_data, &Sport::Running);
+        let running_source = FitImporter::determine_data_source(&running_data, &Sport::Running);
         assert_eq!(running_source, DataSource::Pace); // Should prefer pace for running dynamics
 
         // Test swimming data point
@@ -2378,11 +2788,14 @@ mod tests {
                 stroke_type: Some(1), // Freestyle
                 lap_number: Some(1),
                 sport_transition: None,
+                temperature: None,
+                drive_length: None,
+                stroke_work: None,
             },
         ];
 
         // Test data source determination for swimming
-        let swimming_source = importer.determine_data_source(&swimming_data, &Sport::Swimming);
+        let swimming_source = FitImporter::determine_data_source(&swimming_data, &Sport::Swimming);
         assert_eq!(swimming_source, DataSource::Pace); // Should use pace for swimming with stroke data
 
         // Test multisport data with transition
@@ -2405,6 +2818,9 @@ mod tests {
                 stroke_type: None,
                 lap_number: Some(1),
                 sport_transition: None,
+                temperature: None,
+                drive_length: None,
+                stroke_work: None,
             },
             DataPoint {
                 timestamp: 3600, // 1 hour later
@@ -2424,11 +2840,14 @@ mod tests {
                 stroke_type: None,
                 lap_number: Some(2),
                 sport_transition: Some(true), // Transition detected
+                temperature: None,
+                drive_length: None,
+                stroke_work: None,
             },
         ];
 
         // Test data source determination for triathlon
-        let triathlon_source = importer.determine_data_source(&multisport_data, &Sport::Triathlon);
+        let triathlon_source = FitImporter::determine_data_source(&multisport_data, &Sport::Triathlon);
         assert_eq!(triathlon_source, DataSource::Power); // Should prefer power for triathlon when available
 
         // Verify sport transition is marked
@@ -2437,8 +2856,6 @@ mod tests {
 
     #[test]
     fn test_enhanced_sport_mapping() {
-        let importer = FitImporter::new();
-
         // Test expanded sport mapping (this would require mock FIT data)
         // For now, just verify the data structures support the new sports
         let sports = vec![
@@ -2452,12 +2869,33 @@ mod tests {
 
         for sport in sports {
             let empty_data = vec![];
-            let source = importer.determine_data_source(&empty_data, &sport);
+            let source = FitImporter::determine_data_source(&empty_data, &sport);
             // All sports should have a default data source
             assert_eq!(source, DataSource::HeartRate);
         }
     }
 
+    #[test]
+    fn test_fit_sport_maps_rowing() {
+        assert_eq!(fit_sport(&Value::Enum(15)), Some(Sport::Rowing));
+        assert_eq!(fit_sport(&Value::String("rowing".to_string())), Some(Sport::Rowing));
+        assert_eq!(fit_sport(&Value::Enum(18)), Some(Sport::Triathlon));
+        assert!(fit_indoor_rowing(&Value::String("indoor_rowing".to_string())));
+        assert!(fit_indoor_rowing(&Value::Enum(14)));
+        assert!(!fit_indoor_rowing(&Value::String("indoor_cycling".to_string())));
+    }
+
+    #[test]
+    fn test_fit_exercise_category_names_set() {
+        let category = Value::Array(vec![
+            Value::String("unknown".to_string()),
+            Value::String("bench_press".to_string()),
+        ]);
+        assert_eq!(fit_exercise_category(&category), Some("bench_press".to_string()));
+        assert_eq!(fit_exercise_category(&Value::String("squat".to_string())), Some("squat".to_string()));
+        assert_eq!(fit_exercise_category(&Value::UInt16(65534)), None);
+    }
+
     #[test]
     fn test_developer_data_id_parsing() {
         use crate::models::DeveloperDataId;
@@ -417,6 +417,11 @@ mod tests {
                 notes: None,
                 source: None,
                 raw_data: None,
+                swim_lengths: Vec::new(),
+                start_time: None,
+                laps: Vec::new(),
+                rpe: None,
+                strength_sets: Vec::new(),
             }
         ];
 
@@ -459,6 +464,11 @@ mod tests {
                 notes: None,
                 source: None,
                 raw_data: None,
+                swim_lengths: Vec::new(),
+                start_time: None,
+                laps: Vec::new(),
+                rpe: None,
+                strength_sets: Vec::new(),
             }
         ];
 
@@ -515,6 +525,11 @@ mod tests {
                 notes: None,
                 source: None,
                 raw_data: None,
+                swim_lengths: Vec::new(),
+                start_time: None,
+                laps: Vec::new(),
+                rpe: None,
+                strength_sets: Vec::new(),
             }
         ];
 
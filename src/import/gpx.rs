@@ -1,16 +1,65 @@
-use anyhow::Result;
+//! GPX import for GPS tracks
+//!
+//! Each `trk` becomes a workout. The start time is the document's metadata
+//! `time`, falling back to the first trackpoint. Heart rate, cadence,
+//! temperature and power come from the Garmin `TrackPointExtension` (or a
+//! plain `power` element); distance and speed are derived from the positions.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, FixedOffset};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use rust_decimal::prelude::*;
 use std::path::Path;
+use uuid::Uuid;
 
+use crate::import::fit::FitImporter;
 use crate::import::ImportFormat;
-use crate::models::Workout;
+use crate::models::{DataPoint, Sport, Workout, WorkoutType};
+
+/// Mean Earth radius in meters
+const EARTH_RADIUS_METERS: f64 = 6_371_000.0;
 
-/// GPX importer for GPS track data (stub implementation)
+/// GPX importer for GPS track data
 pub struct GpxImporter;
 
+#[derive(Debug, Default)]
+struct GpxPoint {
+    latitude: f64,
+    longitude: f64,
+    elevation: Option<f64>,
+    time: Option<DateTime<FixedOffset>>,
+    heart_rate: Option<u16>,
+    cadence: Option<u16>,
+    temperature: Option<f64>,
+    power: Option<u16>,
+}
+
+#[derive(Debug, Default)]
+struct GpxTrack {
+    name: Option<String>,
+    activity_type: Option<String>,
+    points: Vec<GpxPoint>,
+}
+
 impl GpxImporter {
     pub fn new() -> Self {
         Self
     }
+
+    /// Parse the tracks of a GPX document
+    pub fn parse_str(&self, xml: &str, source: &str) -> Result<Vec<Workout>> {
+        let (metadata_time, tracks) = parse_tracks(xml)?;
+        let workouts: Vec<Workout> = tracks
+            .into_iter()
+            .filter_map(|track| track.into_workout(metadata_time, source))
+            .collect();
+
+        if workouts.is_empty() {
+            anyhow::bail!("GPX file contains no timed tracks");
+        }
+        Ok(workouts)
+    }
 }
 
 impl ImportFormat for GpxImporter {
@@ -22,13 +71,250 @@ impl ImportFormat for GpxImporter {
             .unwrap_or(false)
     }
 
-    fn import_file(&self, _file_path: &Path) -> Result<Vec<Workout>> {
-        // TODO: Implement GPX file parsing
-        // For now, return an error indicating GPX import is not yet implemented
-        anyhow::bail!("GPX file import is not yet implemented. Please use CSV format for now.")
+    fn import_file(&self, file_path: &Path) -> Result<Vec<Workout>> {
+        let xml = std::fs::read_to_string(file_path)
+            .with_context(|| format!("Failed to read GPX file: {}", file_path.display()))?;
+        self.parse_str(&xml, &file_path.to_string_lossy())
     }
 
     fn get_format_name(&self) -> &'static str {
         "GPX"
     }
+
+    fn sniff(&self, header: &[u8]) -> Option<bool> {
+        Some(header.windows(4).any(|w| w == b"<gpx"))
+    }
+}
+
+impl GpxTrack {
+    /// Store the text of the element at the end of `path`
+    fn set(&mut self, path: &[String], text: &str) {
+        let element = path.last().map(String::as_str).unwrap_or_default();
+        let parent = path.len().checked_sub(2).map_or("", |i| path[i].as_str());
+
+        if !path.iter().any(|p| p == "trkpt") {
+            match (parent, element) {
+                ("trk", "name") => self.name = Some(text.to_string()),
+                ("trk", "type") => self.activity_type = Some(text.to_string()),
+                _ => {}
+            }
+            return;
+        }
+
+        if let Some(point) = self.points.last_mut() {
+            match element {
+                "ele" => point.elevation = text.parse().ok(),
+                "time" => point.time = DateTime::parse_from_rfc3339(text).ok(),
+                "hr" => point.heart_rate = text.parse().ok(),
+                "cad" => point.cadence = text.parse().ok(),
+                "atemp" => point.temperature = text.parse().ok(),
+                "power" => point.power = text.parse().ok(),
+                _ => {}
+            }
+        }
+    }
+
+    fn into_workout(self, metadata_time: Option<DateTime<FixedOffset>>, source: &str) -> Option<Workout> {
+        let first_point_time = self.points.iter().find_map(|p| p.time);
+        let start_time = metadata_time.filter(|t| first_point_time.is_none_or(|first| *t <= first)).or(first_point_time)?;
+
+        let sport = match self.activity_type.as_deref().map(str::to_lowercase).as_deref() {
+            Some("cycling" | "biking" | "ride" | "road_biking" | "mountain_biking") => Sport::Cycling,
+            Some("running" | "run" | "trail_running") => Sport::Running,
+            Some("swimming" | "open_water_swimming") => Sport::Swimming,
+            Some("rowing") => Sport::Rowing,
+            _ => Sport::CrossTraining,
+        };
+
+        let mut distance = 0.0;
+        let mut previous: Option<(&GpxPoint, i64)> = None;
+        let mut data_points = Vec::new();
+        for point in &self.points {
+            let Some(time) = point.time else { continue };
+            let offset = (time - start_time).num_seconds().max(0);
+
+            let mut speed = None;
+            if let Some((last, last_offset)) = previous {
+                let step = haversine_meters(last, point);
+                distance += step;
+                if offset > last_offset {
+                    speed = Decimal::from_f64(step / (offset - last_offset) as f64).map(|s| s.round_dp(2));
+                }
+            }
+            previous = Some((point, offset));
+
+            data_points.push(DataPoint {
+                timestamp: offset as u32,
+                heart_rate: point.heart_rate,
+                power: point.power,
+                pace: None,
+                elevation: point.elevation.map(|e| e.round() as i16),
+                cadence: point.cadence,
+                speed,
+                distance: Decimal::from_f64(distance).map(|d| d.round_dp(1)),
+                left_power: None,
+                right_power: None,
+                ground_contact_time: None,
+                vertical_oscillation: None,
+                stride_length: None,
+                stroke_count: None,
+                stroke_type: None,
+                lap_number: None,
+                sport_transition: None,
+                temperature: point.temperature.map(|t| t.round().clamp(i8::MIN as f64, i8::MAX as f64) as i8),
+                drive_length: None,
+                stroke_work: None,
+            });
+        }
+        if data_points.is_empty() {
+            return None;
+        }
+
+        let notes = match &self.name {
+            Some(name) => format!("{} (imported from GPX file: {})", name, source),
+            None => format!("Imported from GPX file: {}", source),
+        };
+
+        Some(Workout {
+            id: Uuid::new_v4().to_string(),
+            date: start_time.date_naive(),
+            start_time: Some(start_time),
+            sport: sport.clone(),
+            duration_seconds: data_points.last().map_or(0, |p| p.timestamp),
+            workout_type: WorkoutType::Endurance,
+            data_source: FitImporter::determine_data_source(&data_points, &sport),
+            summary: FitImporter::calculate_summary(&data_points, &sport),
+            raw_data: Some(data_points),
+            notes: Some(notes),
+            athlete_id: None,
+            source: Some(source.to_string()),
+            swim_lengths: Vec::new(),
+            laps: Vec::new(),
+            rpe: None,
+            strength_sets: Vec::new(),
+        })
+    }
+}
+
+/// Collect the metadata time and the tracks of the document
+fn parse_tracks(xml: &str) -> Result<(Option<DateTime<FixedOffset>>, Vec<GpxTrack>)> {
+    let mut reader = Reader::from_str(xml);
+    reader.trim_text(true);
+
+    let mut path: Vec<String> = Vec::new();
+    let mut metadata_time = None;
+    let mut tracks: Vec<GpxTrack> = Vec::new();
+
+    loop {
+        match reader.read_event().context("Malformed GPX document")? {
+            Event::Start(element) => {
+                let name = String::from_utf8_lossy(element.local_name().as_ref()).into_owned();
+                match name.as_str() {
+                    "trk" => tracks.push(GpxTrack::default()),
+                    "trkpt" => {
+                        if let Some(track) = tracks.last_mut() {
+                            track.points.push(position(&element)?);
+                        }
+                    }
+                    _ => {}
+                }
+                path.push(name);
+            }
+            // Trackpoints without children carry a position only
+            Event::Empty(element) if element.local_name().as_ref() == b"trkpt" => {
+                if let Some(track) = tracks.last_mut() {
+                    track.points.push(position(&element)?);
+                }
+            }
+            Event::End(_) => {
+                path.pop();
+            }
+            Event::Text(text) => {
+                let text = text.unescape()?;
+                let text = text.trim();
+                if path.ends_with(&["metadata".to_string(), "time".to_string()]) {
+                    metadata_time = DateTime::parse_from_rfc3339(text).ok();
+                } else if let Some(track) = tracks.last_mut() {
+                    track.set(&path, text);
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok((metadata_time, tracks))
+}
+
+/// Latitude and longitude of a `trkpt`
+fn position(element: &BytesStart) -> Result<GpxPoint> {
+    let coordinate = |name: &str| -> Result<f64> {
+        let attribute = element
+            .try_get_attribute(name)?
+            .with_context(|| format!("GPX trackpoint without {}", name))?;
+        attribute.unescape_value()?.parse::<f64>().with_context(|| format!("Invalid {} in GPX trackpoint", name))
+    };
+
+    Ok(GpxPoint {
+        latitude: coordinate("lat")?,
+        longitude: coordinate("lon")?,
+        ..GpxPoint::default()
+    })
+}
+
+/// Great-circle distance between two points in meters
+fn haversine_meters(a: &GpxPoint, b: &GpxPoint) -> f64 {
+    let (lat_a, lat_b) = (a.latitude.to_radians(), b.latitude.to_radians());
+    let d_lat = lat_b - lat_a;
+    let d_lon = (b.longitude - a.longitude).to_radians();
+
+    let h = (d_lat / 2.0).sin().powi(2) + lat_a.cos() * lat_b.cos() * (d_lon / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS_METERS * h.sqrt().asin()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RUN: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<gpx version="1.1" creator="test" xmlns="http://www.topografix.com/GPX/1/1"
+    xmlns:gpxtpx="http://www.garmin.com/xmlschemas/TrackPointExtension/v1">
+  <metadata><time>2024-11-03T22:45:00-05:00</time></metadata>
+  <trk>
+    <name>Late run</name>
+    <type>running</type>
+    <trkseg>
+      <trkpt lat="40.7000" lon="-74.0000">
+        <ele>10.2</ele>
+        <time>2024-11-04T03:45:00Z</time>
+        <extensions><gpxtpx:TrackPointExtension><gpxtpx:hr>140</gpxtpx:hr><gpxtpx:cad>88</gpxtpx:cad></gpxtpx:TrackPointExtension></extensions>
+      </trkpt>
+      <trkpt lat="40.7009" lon="-74.0000">
+        <ele>11.0</ele>
+        <time>2024-11-04T03:45:30Z</time>
+        <extensions><gpxtpx:TrackPointExtension><gpxtpx:hr>150</gpxtpx:hr><gpxtpx:cad>90</gpxtpx:cad></gpxtpx:TrackPointExtension></extensions>
+      </trkpt>
+    </trkseg>
+  </trk>
+</gpx>"#;
+
+    #[test]
+    fn test_parses_track_with_local_start_time() {
+        let workouts = GpxImporter::new().parse_str(RUN, "run.gpx").unwrap();
+        assert_eq!(workouts.len(), 1);
+
+        let workout = &workouts[0];
+        assert_eq!(workout.sport, Sport::Running);
+        // The run started on the evening of the 3rd, local time
+        assert_eq!(workout.date, chrono::NaiveDate::from_ymd_opt(2024, 11, 3).unwrap());
+        assert_eq!(workout.start_time.unwrap().offset().local_minus_utc(), -5 * 3600);
+        assert_eq!(workout.duration_seconds, 30);
+
+        let data = workout.raw_data.as_ref().unwrap();
+        assert_eq!(data[1].heart_rate, Some(150));
+        // 0.0009 degrees of latitude is about 100 m
+        let distance = data[1].distance.unwrap().to_f64().unwrap();
+        assert!((distance - 100.1).abs() < 0.5, "distance {}", distance);
+        assert!(data[1].speed.unwrap() > Decimal::from(3));
+    }
 }
@@ -0,0 +1,557 @@
+//! iCalendar (.ics) import of planned sessions and races
+//!
+//! Events written by the `.ics` exporter carry `X-TRAINRS-*` properties and
+//! are rebuilt exactly. Events authored in other calendar tools are read from
+//! their summary, description and times: the sport and workout type come from
+//! keywords, the duration from `DURATION`, the event times or a "90 min" /
+//! "1.5h" mention, and TSS from the duration at the type's typical intensity.
+//! Events in the RACE category become races.
+
+use anyhow::{Context, Result};
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::models::{AthleteProfile, Sport, WorkoutType};
+use crate::race_calendar::{Race, RacePriority};
+use crate::structured_workout::StructuredWorkout;
+use crate::training_plan::{
+    PeriodizationModel, PlannedWorkout, RecoveryPattern, TrainingGoal, TrainingPlan, TrainingWeek,
+};
+
+/// Contents of an imported calendar
+#[derive(Debug, Clone)]
+pub struct IcsCalendar {
+    /// `X-WR-CALNAME`
+    pub name: Option<String>,
+    /// Plan ID of a calendar exported by trainrs
+    pub plan_id: Option<String>,
+    pub goal: Option<TrainingGoal>,
+    /// Planned sessions in date order
+    pub sessions: Vec<PlannedWorkout>,
+    /// Week number and phase of exported sessions, keyed by week start date
+    pub phases: BTreeMap<NaiveDate, (u32, String)>,
+    pub races: Vec<Race>,
+}
+
+impl IcsCalendar {
+    /// Group the sessions into Monday-based training weeks
+    ///
+    /// Returns None when the calendar has no training sessions. Exported
+    /// calendars keep their plan ID, so importing them replaces the plan.
+    pub fn to_plan(&self) -> Option<TrainingPlan> {
+        let first = self.sessions.first()?.date;
+        let last = self.sessions.last()?.date;
+        let start = week_start(first);
+
+        let mut weeks = Vec::new();
+        let mut week_start_date = start;
+        while week_start_date <= last {
+            let end = week_start_date + Duration::days(6);
+            let workouts: Vec<PlannedWorkout> = self
+                .sessions
+                .iter()
+                .filter(|w| week_start_date <= w.date && w.date <= end)
+                .cloned()
+                .collect();
+            let planned_tss: Decimal = workouts.iter().map(|w| w.planned_tss).sum();
+            let minutes: u32 = workouts.iter().map(|w| w.planned_duration_minutes).sum();
+            let (_, phase) = self
+                .phases
+                .get(&week_start_date)
+                .cloned()
+                .unwrap_or((0, "Imported".to_string()));
+
+            weeks.push(TrainingWeek {
+                week_number: weeks.len() as u32 + 1,
+                start_date: week_start_date,
+                end_date: end,
+                is_recovery_week: phase.contains("Recovery"),
+                focus: phase.clone(),
+                phase,
+                planned_tss,
+                planned_hours: (Decimal::from(minutes) / dec!(60)).round_dp(1),
+                workouts,
+            });
+            week_start_date += Duration::days(7);
+        }
+
+        Some(TrainingPlan {
+            id: self.plan_id.clone().unwrap_or_else(|| format!("ics_{}", chrono::Utc::now().timestamp())),
+            name: self.name.clone().unwrap_or_else(|| "Imported Calendar Plan".to_string()),
+            goal: self.goal.clone().unwrap_or(TrainingGoal::Maintenance),
+            periodization_model: PeriodizationModel::Traditional,
+            recovery_pattern: RecoveryPattern::ThreeToOne,
+            start_date: start,
+            target_date: Some(last),
+            total_weeks: weeks.len() as u32,
+            total_planned_tss: weeks.iter().map(|w| w.planned_tss).sum(),
+            total_planned_hours: weeks.iter().map(|w| w.planned_hours).sum(),
+            weeks,
+            target_distribution: None,
+            created_at: chrono::Utc::now(),
+        })
+    }
+}
+
+pub struct IcsImporter;
+
+impl IcsImporter {
+    pub fn import_file(path: &Path, athlete: &AthleteProfile) -> Result<IcsCalendar> {
+        let content = std::fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        Self::parse(&content, athlete)
+    }
+
+    pub fn parse(content: &str, athlete: &AthleteProfile) -> Result<IcsCalendar> {
+        let lines = unfold(content);
+        if !lines.iter().any(|l| l.eq_ignore_ascii_case("BEGIN:VCALENDAR")) {
+            anyhow::bail!("Not an iCalendar file: missing BEGIN:VCALENDAR");
+        }
+
+        let mut calendar = IcsCalendar {
+            name: None,
+            plan_id: None,
+            goal: None,
+            sessions: Vec::new(),
+            phases: BTreeMap::new(),
+            races: Vec::new(),
+        };
+
+        let mut event: Option<Vec<Property>> = None;
+        let mut nested = 0;
+        for line in &lines {
+            let Some(property) = Property::parse(line) else {
+                continue;
+            };
+
+            match (property.name.as_str(), property.value.to_ascii_uppercase().as_str()) {
+                ("BEGIN", "VEVENT") => event = Some(Vec::new()),
+                ("END", "VEVENT") => {
+                    if let Some(properties) = event.take() {
+                        Self::read_event(&properties, athlete, &mut calendar)?;
+                    }
+                }
+                // Skip alarms and other components nested in events
+                ("BEGIN", _) if event.is_some() => nested += 1,
+                ("END", _) if event.is_some() => nested -= 1,
+                _ => match event.as_mut() {
+                    Some(properties) if nested == 0 => properties.push(property),
+                    Some(_) => {}
+                    None => match property.name.as_str() {
+                        "X-WR-CALNAME" => calendar.name = Some(property.text()),
+                        "X-TRAINRS-PLAN-ID" => calendar.plan_id = Some(property.text()),
+                        "X-TRAINRS-GOAL" => calendar.goal = serde_json::from_str(&property.text()).ok(),
+                        _ => {}
+                    },
+                },
+            }
+        }
+
+        calendar.sessions.sort_by_key(|w| w.date);
+        calendar.races.sort_by_key(|r| r.date);
+        Ok(calendar)
+    }
+
+    fn read_event(properties: &[Property], athlete: &AthleteProfile, calendar: &mut IcsCalendar) -> Result<()> {
+        let get = |name: &str| properties.iter().find(|p| p.name == name);
+        let text = |name: &str| get(name).map(Property::text);
+
+        if text("STATUS").is_some_and(|s| s.eq_ignore_ascii_case("CANCELLED")) {
+            return Ok(());
+        }
+        let Some(start) = get("DTSTART") else {
+            return Ok(());
+        };
+        let date = parse_date(&start.value).with_context(|| format!("Invalid DTSTART: {}", start.value))?;
+
+        let summary = text("SUMMARY").unwrap_or_default();
+        // Exported summaries append "(A race)" or "(90 min)"; other calendars keep theirs
+        let exported = properties.iter().any(|p| p.name.starts_with("X-TRAINRS-"));
+        let title = if exported {
+            summary.rsplit_once(" (").map_or(summary.as_str(), |(title, _)| title).to_string()
+        } else {
+            summary.clone()
+        };
+        let description = text("DESCRIPTION").unwrap_or_default();
+        let keywords = format!("{} {}", summary, description).to_lowercase();
+        let sport = text("X-TRAINRS-SPORT")
+            .and_then(|s| Sport::from_str(&s).ok())
+            .or_else(|| sport_from_keywords(&keywords));
+
+        let is_race = text("X-TRAINRS-RACE-PRIORITY").is_some()
+            || text("CATEGORIES").is_some_and(|c| c.split(',').any(|c| c.trim().eq_ignore_ascii_case("RACE")));
+        if is_race {
+            let priority = text("X-TRAINRS-RACE-PRIORITY")
+                .and_then(|p| p.parse::<RacePriority>().ok())
+                .unwrap_or(RacePriority::B);
+            let mut race = Race::new(title, date, sport.unwrap_or(Sport::Running), priority);
+            if let Some(id) = text("UID").and_then(|uid| uid.strip_prefix("race-").and_then(|u| u.strip_suffix("@trainrs")).map(String::from)) {
+                race.id = id;
+            }
+            race.distance_meters = text("X-TRAINRS-DISTANCE").and_then(|d| d.parse().ok());
+            race.goal_time_seconds = text("X-TRAINRS-GOAL-TIME").and_then(|g| g.parse().ok());
+            calendar.races.push(race);
+            return Ok(());
+        }
+
+        let sport = sport.unwrap_or(Sport::Cycling);
+        let workout_type = text("X-TRAINRS-TYPE")
+            .and_then(|t| WorkoutType::from_str(&t).ok())
+            .unwrap_or_else(|| type_from_keywords(&keywords));
+        let structure: Option<StructuredWorkout> = text("X-TRAINRS-STRUCTURE").and_then(|json| serde_json::from_str(&json).ok());
+
+        let mut workout = match structure {
+            Some(structure) => PlannedWorkout::from_structure(date, workout_type.clone(), &title, structure, athlete),
+            None => {
+                let minutes = text("X-TRAINRS-DURATION")
+                    .and_then(|m| m.parse().ok())
+                    .or_else(|| event_minutes(properties))
+                    .or_else(|| minutes_from_text(&keywords))
+                    .unwrap_or(60);
+                let intensity_factor = typical_intensity(&workout_type);
+                PlannedWorkout {
+                    id: uuid::Uuid::new_v4().to_string(),
+                    sequence: 0,
+                    date,
+                    sport,
+                    workout_type: workout_type.clone(),
+                    planned_duration_minutes: minutes,
+                    planned_tss: (Decimal::from(minutes) / dec!(60) * intensity_factor * intensity_factor * dec!(100)).round_dp(1),
+                    description: title,
+                    intensity_factor,
+                    notes: (!description.is_empty() && text("X-TRAINRS-TYPE").is_none()).then_some(description.clone()),
+                    structure: None,
+                }
+            }
+        };
+
+        // Explicit values from the calendar win over derived ones
+        if let Some(tss) = text("X-TRAINRS-TSS").and_then(|t| t.parse().ok()) {
+            workout.planned_tss = tss;
+        }
+        if let Some(intensity) = text("X-TRAINRS-IF").and_then(|i| i.parse().ok()) {
+            workout.intensity_factor = intensity;
+        }
+        if let Some(minutes) = text("X-TRAINRS-DURATION").and_then(|m| m.parse().ok()) {
+            workout.planned_duration_minutes = minutes;
+        }
+        if let Some(id) = text("UID").and_then(|uid| uid.strip_prefix("session-").and_then(|u| u.strip_suffix("@trainrs")).map(String::from)) {
+            workout.id = id;
+        }
+        if let Some(sequence) = text("SEQUENCE").and_then(|s| s.parse().ok()) {
+            workout.sequence = sequence;
+        }
+
+        if let (Some(week), Some(phase)) = (text("X-TRAINRS-WEEK").and_then(|w| w.parse().ok()), text("X-TRAINRS-PHASE")) {
+            calendar.phases.insert(week_start(date), (week, phase));
+        }
+        calendar.sessions.push(workout);
+        Ok(())
+    }
+}
+
+/// Content line split into name, parameters and raw value
+#[derive(Debug, Clone)]
+struct Property {
+    name: String,
+    params: Vec<(String, String)>,
+    value: String,
+}
+
+impl Property {
+    fn parse(line: &str) -> Option<Self> {
+        // The value starts at the first colon outside a quoted parameter value
+        let mut in_quotes = false;
+        let split = line.char_indices().find(|(_, c)| {
+            if *c == '"' {
+                in_quotes = !in_quotes;
+            }
+            *c == ':' && !in_quotes
+        })?.0;
+        let (head, value) = (&line[..split], &line[split + 1..]);
+
+        let mut parts = head.split(';');
+        let name = parts.next()?.trim().to_ascii_uppercase();
+        let params = parts
+            .filter_map(|p| p.split_once('='))
+            .map(|(k, v)| (k.to_ascii_uppercase(), v.trim_matches('"').to_string()))
+            .collect();
+
+        Some(Self { name, params, value: value.to_string() })
+    }
+
+    fn param(&self, name: &str) -> Option<&str> {
+        self.params.iter().find(|(k, _)| k == name).map(|(_, v)| v.as_str())
+    }
+
+    /// Unescaped TEXT value (RFC 5545 §3.3.11)
+    fn text(&self) -> String {
+        let mut out = String::with_capacity(self.value.len());
+        let mut chars = self.value.chars();
+        while let Some(c) = chars.next() {
+            if c != '\\' {
+                out.push(c);
+                continue;
+            }
+            match chars.next() {
+                Some('n') | Some('N') => out.push('\n'),
+                Some(other) => out.push(other),
+                None => out.push('\\'),
+            }
+        }
+        out
+    }
+}
+
+/// Join folded lines (RFC 5545 §3.1)
+fn unfold(content: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for raw in content.split('\n') {
+        let line = raw.strip_suffix('\r').unwrap_or(raw);
+        match (line.strip_prefix(' ').or_else(|| line.strip_prefix('\t')), lines.last_mut()) {
+            (Some(continuation), Some(last)) => last.push_str(continuation),
+            _ if !line.is_empty() => lines.push(line.to_string()),
+            _ => {}
+        }
+    }
+    lines
+}
+
+/// Date of a DATE or DATE-TIME value
+fn parse_date(value: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(value.get(..8)?, "%Y%m%d").ok()
+}
+
+fn parse_datetime(value: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(value.trim_end_matches('Z'), "%Y%m%dT%H%M%S").ok()
+}
+
+/// Minutes from `DURATION` or timed `DTSTART`/`DTEND`; all-day events have none
+fn event_minutes(properties: &[Property]) -> Option<u32> {
+    let get = |name: &str| properties.iter().find(|p| p.name == name);
+
+    if let Some(duration) = get("DURATION") {
+        return parse_duration(&duration.value);
+    }
+
+    let start = get("DTSTART").filter(|p| p.param("VALUE") != Some("DATE"))?;
+    let end = get("DTEND")?;
+    let minutes = (parse_datetime(&end.value)? - parse_datetime(&start.value)?).num_minutes();
+    u32::try_from(minutes).ok().filter(|m| *m > 0)
+}
+
+/// ISO 8601 duration such as `PT1H30M` or `P1D`, in minutes
+fn parse_duration(value: &str) -> Option<u32> {
+    let value = value.trim_start_matches(['+', 'P']);
+    let mut minutes = 0u32;
+    let mut number = String::new();
+    let mut in_time = false;
+
+    for c in value.chars() {
+        match c {
+            'T' => in_time = true,
+            '0'..='9' => number.push(c),
+            unit => {
+                let n: u32 = number.parse().ok()?;
+                number.clear();
+                minutes += match (unit, in_time) {
+                    ('W', false) => n * 7 * 24 * 60,
+                    ('D', false) => n * 24 * 60,
+                    ('H', true) => n * 60,
+                    ('M', true) => n,
+                    ('S', true) => n / 60,
+                    _ => return None,
+                };
+            }
+        }
+    }
+    (minutes > 0).then_some(minutes)
+}
+
+/// "90 min", "90min" or "1.5h" / "2 hours" in free text
+fn minutes_from_text(text: &str) -> Option<u32> {
+    let words: Vec<&str> = text.split(|c: char| c.is_whitespace() || c == '(' || c == ')' || c == ',').filter(|w| !w.is_empty()).collect();
+
+    for (i, word) in words.iter().enumerate() {
+        let (number, unit) = match word.find(|c: char| !(c.is_ascii_digit() || c == '.')) {
+            Some(0) => continue,
+            Some(pos) => (&word[..pos], &word[pos..]),
+            None => (*word, words.get(i + 1).copied().unwrap_or_default()),
+        };
+        let Ok(value) = number.parse::<Decimal>() else {
+            continue;
+        };
+        if unit.starts_with("min") {
+            return value.to_u32();
+        }
+        if unit == "h" || unit.starts_with("hr") || unit.starts_with("hour") {
+            return (value * dec!(60)).round().to_u32();
+        }
+    }
+    None
+}
+
+fn sport_from_keywords(text: &str) -> Option<Sport> {
+    let has = |words: &[&str]| words.iter().any(|w| text.contains(w));
+    if has(&["swim", "pool", "open water"]) {
+        Some(Sport::Swimming)
+    } else if has(&["run", "jog", "tempo run", "strides", "track"]) {
+        Some(Sport::Running)
+    } else if has(&["ride", "bike", "cycling", "ftp", "zwift", "trainer"]) {
+        Some(Sport::Cycling)
+    } else if has(&["row", "erg"]) {
+        Some(Sport::Rowing)
+    } else {
+        None
+    }
+}
+
+fn type_from_keywords(text: &str) -> WorkoutType {
+    let has = |words: &[&str]| words.iter().any(|w| text.contains(w));
+    if has(&["vo2", "max effort"]) {
+        WorkoutType::VO2Max
+    } else if has(&["threshold", "sweet spot", "sweetspot", "ftp interval", "cruise"]) {
+        WorkoutType::Threshold
+    } else if has(&["interval", "repeats", "x "]) && has(&["min", "sec", "'"]) {
+        WorkoutType::Interval
+    } else if has(&["tempo"]) {
+        WorkoutType::Tempo
+    } else if has(&["recovery", "easy", "shakeout", "spin"]) {
+        WorkoutType::Recovery
+    } else if has(&["test", "time trial"]) {
+        WorkoutType::Test
+    } else if has(&["strength", "gym", "weights"]) {
+        WorkoutType::Strength
+    } else {
+        WorkoutType::Endurance
+    }
+}
+
+/// Typical whole-session intensity factor for a workout type
+fn typical_intensity(workout_type: &WorkoutType) -> Decimal {
+    match workout_type {
+        WorkoutType::Recovery => dec!(0.55),
+        WorkoutType::Endurance => dec!(0.68),
+        WorkoutType::Tempo => dec!(0.80),
+        WorkoutType::Threshold => dec!(0.88),
+        WorkoutType::Interval | WorkoutType::VO2Max => dec!(0.90),
+        WorkoutType::Race | WorkoutType::Test => dec!(0.95),
+        WorkoutType::Strength => dec!(0.60),
+    }
+}
+
+fn week_start(date: NaiveDate) -> NaiveDate {
+    use chrono::Datelike;
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::ics::IcsExporter;
+    use crate::export::planned::tests::athlete;
+
+    #[test]
+    fn test_round_trip_of_exported_plan_and_races() {
+        let plan = crate::export::ics::tests::plan();
+        let race = Race::new("Spring Classic".to_string(), NaiveDate::from_ymd_opt(2025, 4, 6).unwrap(), Sport::Cycling, RacePriority::A);
+        let ics = IcsExporter::generate_ics(Some(&plan), std::slice::from_ref(&race)).unwrap();
+
+        let calendar = IcsImporter::parse(&ics, &athlete()).unwrap();
+        assert_eq!(calendar.name.as_deref(), Some("Spring Build"));
+        assert_eq!(calendar.sessions.len(), 2);
+        assert_eq!(calendar.races.len(), 1);
+        assert_eq!(calendar.races[0].id, race.id);
+        assert_eq!(calendar.races[0].name, "Spring Classic");
+        assert_eq!(calendar.races[0].priority, RacePriority::A);
+
+        let imported = calendar.to_plan().unwrap();
+        assert_eq!(imported.id, plan.id);
+        assert_eq!(imported.weeks.len(), 1);
+        assert_eq!(imported.weeks[0].phase, "Build");
+
+        let original = &plan.weeks[0].workouts;
+        let restored = &imported.weeks[0].workouts;
+        assert_eq!(restored[0].id, original[0].id);
+        assert_eq!(restored[1].id, "group-ride");
+        assert_eq!(restored[0].structure, original[0].structure);
+        assert_eq!(restored[0].description, original[0].description);
+        assert_eq!(restored[0].planned_tss, original[0].planned_tss.round_dp(1));
+        assert_eq!(restored[1].description, "Group ride; stay in the bunch, no attacks");
+        assert_eq!(restored[1].planned_tss, dec!(95));
+        assert_eq!(restored[1].planned_duration_minutes, 120);
+    }
+
+    #[test]
+    fn test_coach_authored_events() {
+        let ics = "BEGIN:VCALENDAR\r\n\
+VERSION:2.0\r\n\
+PRODID:-//Coach//Calendar//EN\r\n\
+BEGIN:VEVENT\r\n\
+UID:abc-1\r\n\
+DTSTART;TZID=Europe/Berlin:20250304T180000\r\n\
+DTEND;TZID=Europe/Berlin:20250304T193000\r\n\
+SUMMARY:Threshold ride (3x12')\r\n\
+DESCRIPTION:Hold 95-100% FTP\\, 5 min easy between\r\n\
+BEGIN:VALARM\r\n\
+ACTION:DISPLAY\r\n\
+DESCRIPTION:Reminder\r\n\
+END:VALARM\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+UID:abc-2\r\n\
+DTSTART;VALUE=DATE:20250306\r\n\
+SUMMARY:Easy run 45 min\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+UID:abc-3\r\n\
+DTSTART;VALUE=DATE:20250309\r\n\
+SUMMARY:Long ride\r\n\
+DURATION:PT3H\r\n\
+STATUS:CANCELLED\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+UID:abc-4\r\n\
+DTSTART;VALUE=DATE:20250420\r\n\
+SUMMARY:City Marathon (Berlin)\r\n\
+CATEGORIES:RACE\r\n\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n";
+
+        let calendar = IcsImporter::parse(ics, &athlete()).unwrap();
+        assert_eq!(calendar.sessions.len(), 2);
+
+        let threshold = &calendar.sessions[0];
+        assert_eq!(threshold.sport, Sport::Cycling);
+        assert_eq!(threshold.workout_type, WorkoutType::Threshold);
+        assert_eq!(threshold.planned_duration_minutes, 90);
+        assert_eq!(threshold.description, "Threshold ride (3x12')");
+        assert_eq!(threshold.notes.as_deref(), Some("Hold 95-100% FTP, 5 min easy between"));
+
+        let run = &calendar.sessions[1];
+        assert_eq!(run.sport, Sport::Running);
+        assert_eq!(run.workout_type, WorkoutType::Recovery);
+        assert_eq!(run.planned_duration_minutes, 45);
+
+        assert_eq!(calendar.races.len(), 1);
+        assert_eq!(calendar.races[0].name, "City Marathon (Berlin)");
+        assert_eq!(calendar.races[0].priority, RacePriority::B);
+
+        let plan = calendar.to_plan().unwrap();
+        assert_eq!(plan.start_date, NaiveDate::from_ymd_opt(2025, 3, 3).unwrap());
+        assert_eq!(plan.weeks[0].phase, "Imported");
+    }
+
+    #[test]
+    fn test_duration_parsing() {
+        assert_eq!(parse_duration("PT1H30M"), Some(90));
+        assert_eq!(parse_duration("P1D"), Some(1440));
+        assert_eq!(minutes_from_text("long ride 2.5h"), Some(150));
+        assert_eq!(minutes_from_text("easy 40 minutes"), Some(40));
+        assert_eq!(minutes_from_text("4x4 vo2"), None);
+        assert!(IcsImporter::parse("not a calendar", &athlete()).is_err());
+    }
+}
@@ -1,21 +1,26 @@
 use crate::models::Workout;
 use anyhow::Result;
 use indicatif::{ProgressBar, ProgressStyle};
+use std::io::Read;
 use std::path::Path;
 
+pub mod archive;
 pub mod csv;
+pub mod csv_profile;
 pub mod developer_registry;
 pub mod fit;
 pub mod fit_cache;
 pub mod fit_recovery;
 pub mod fit_streaming;
 pub mod gpx;
+pub mod ics;
 pub mod logging;
 pub mod parallel;
 pub mod streaming;
 pub mod tcx;
 pub mod validation;
 pub mod validation_rules;
+pub mod watch;
 
 /// Trait for importing workout data from different file formats
 pub trait ImportFormat {
@@ -27,8 +32,21 @@ pub trait ImportFormat {
 
     /// Get the format name for this importer
     fn get_format_name(&self) -> &'static str;
+
+    /// Check whether the leading bytes of a file look like this format
+    ///
+    /// Consulted when no importer claims the file's extension or the one that
+    /// does rejects its content. Formats without a recognisable signature keep
+    /// the default `None`: they are only picked by extension, and a file whose
+    /// extension they claim is never handed to another importer.
+    fn sniff(&self, _header: &[u8]) -> Option<bool> {
+        None
+    }
 }
 
+/// Number of leading bytes handed to [`ImportFormat::sniff`]
+const SNIFF_LEN: usize = 4096;
+
 /// Manager for coordinating different import formats
 pub struct ImportManager {
     importers: Vec<Box<dyn ImportFormat>>,
@@ -42,26 +60,87 @@ impl ImportManager {
             Box::new(tcx::TcxImporter::new()),
             Box::new(gpx::GpxImporter::new()),
             Box::new(fit::FitImporter::new()),
+            Box::new(archive::ArchiveImporter::new()),
         ];
 
         Self { importers }
     }
 
+    /// Register an additional importer
+    ///
+    /// Registered importers are consulted before the built-in ones, so a
+    /// downstream format can also take over an extension such as `.csv`.
+    pub fn register(&mut self, importer: Box<dyn ImportFormat>) {
+        self.importers.insert(0, importer);
+    }
+
+    /// Pick the importer for a file
+    ///
+    /// The extension decides first; when no importer claims it, or the
+    /// claiming importer has a signature the content does not match, the
+    /// file's leading bytes are sniffed instead.
+    pub fn find_importer(&self, file_path: &Path) -> Option<&dyn ImportFormat> {
+        let by_extension = self
+            .importers
+            .iter()
+            .find(|importer| importer.can_import(file_path))
+            .map(|importer| importer.as_ref());
+
+        let Some(header) = Self::read_header(file_path) else {
+            return by_extension;
+        };
+        match by_extension.map(|importer| importer.sniff(&header)) {
+            // The claiming importer recognises the content or has no signature to check
+            Some(Some(true) | None) => by_extension,
+            _ => self
+                .importers
+                .iter()
+                .find(|importer| importer.sniff(&header) == Some(true))
+                .map(|importer| importer.as_ref())
+                .or(by_extension),
+        }
+    }
+
+    /// Read the leading bytes of a file for sniffing
+    fn read_header(file_path: &Path) -> Option<Vec<u8>> {
+        let file = std::fs::File::open(file_path).ok()?;
+        let mut header = Vec::with_capacity(SNIFF_LEN);
+        file.take(SNIFF_LEN as u64).read_to_end(&mut header).ok()?;
+        Some(header)
+    }
+
     /// Import a single file, auto-detecting the format
     pub fn import_file(&self, file_path: &Path) -> Result<Vec<Workout>> {
-        // Find an importer that can handle this file
-        for importer in &self.importers {
-            if importer.can_import(file_path) {
-                println!(
-                    "Importing {} using {} format...",
-                    file_path.display(),
-                    importer.get_format_name()
-                );
-                return importer.import_file(file_path);
-            }
-        }
+        let Some(importer) = self.find_importer(file_path) else {
+            anyhow::bail!("No importer found for file: {}", file_path.display());
+        };
+
+        println!(
+            "Importing {} using {} format...",
+            file_path.display(),
+            importer.get_format_name()
+        );
+        importer.import_file(file_path)
+    }
 
-        anyhow::bail!("No importer found for file: {}", file_path.display());
+    /// Import a single file with an explicitly named format
+    pub fn import_file_as(&self, file_path: &Path, format: &str) -> Result<Vec<Workout>> {
+        let Some(importer) = self
+            .importers
+            .iter()
+            .find(|importer| importer.get_format_name().eq_ignore_ascii_case(format))
+        else {
+            let mut formats: Vec<&str> = self.importers.iter().map(|i| i.get_format_name()).collect();
+            formats.dedup();
+            anyhow::bail!("Unknown import format '{}' (available: {})", format, formats.join(", "));
+        };
+
+        println!(
+            "Importing {} using {} format...",
+            file_path.display(),
+            importer.get_format_name()
+        );
+        importer.import_file(file_path)
     }
 
     /// Import all files from a directory
@@ -131,14 +210,8 @@ impl ImportManager {
             let entry = entry?;
             let path = entry.path();
 
-            if path.is_file() {
-                // Check if any importer can handle this file
-                for importer in &self.importers {
-                    if importer.can_import(&path) {
-                        files.push(path);
-                        break;
-                    }
-                }
+            if path.is_file() && self.find_importer(&path).is_some() {
+                files.push(path);
             }
         }
 
@@ -147,34 +220,31 @@ impl ImportManager {
 
     /// Validate a file without importing
     pub fn validate_file(&self, file_path: &Path) -> Result<()> {
-        // Find an importer that can handle this file
-        for importer in &self.importers {
-            if importer.can_import(file_path) {
-                println!(
-                    "Validating {} using {} format...",
-                    file_path.display(),
-                    importer.get_format_name()
-                );
-
-                // Try to import but don't return the data
-                match importer.import_file(file_path) {
-                    Ok(workouts) => {
-                        println!("✓ File is valid: {} workouts found", workouts.len());
-                        return Ok(());
-                    }
-                    Err(e) => {
-                        anyhow::bail!("Validation failed: {}", e);
-                    }
-                }
+        let Some(importer) = self.find_importer(file_path) else {
+            anyhow::bail!("No importer found for file: {}", file_path.display());
+        };
+
+        println!(
+            "Validating {} using {} format...",
+            file_path.display(),
+            importer.get_format_name()
+        );
+
+        // Try to import but don't return the data
+        match importer.import_file(file_path) {
+            Ok(workouts) => {
+                println!("✓ File is valid: {} workouts found", workouts.len());
+                Ok(())
+            }
+            Err(e) => {
+                anyhow::bail!("Validation failed: {}", e);
             }
         }
-
-        anyhow::bail!("No importer found for file: {}", file_path.display());
     }
 
     /// Check if this manager can import a given file (helper for streaming)
     pub fn can_import_file(&self, file_path: &Path) -> bool {
-        self.importers.iter().any(|importer| importer.can_import(file_path))
+        self.find_importer(file_path).is_some()
     }
 
     /// Get reference to importers for external use
@@ -189,3 +259,116 @@ impl Default for ImportManager {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct RowingImporter;
+
+    impl ImportFormat for RowingImporter {
+        fn can_import(&self, file_path: &Path) -> bool {
+            file_path.extension().is_some_and(|ext| ext == "erg" || ext == "csv")
+        }
+
+        fn import_file(&self, _file_path: &Path) -> Result<Vec<Workout>> {
+            Ok(Vec::new())
+        }
+
+        fn get_format_name(&self) -> &'static str {
+            "erg"
+        }
+    }
+
+    fn format_of(manager: &ImportManager, path: &Path) -> Option<&'static str> {
+        manager.find_importer(path).map(|importer| importer.get_format_name())
+    }
+
+    #[test]
+    fn test_sniffs_misnamed_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = ImportManager::new();
+
+        let tcx = dir.path().join("ride.xml");
+        std::fs::write(&tcx, "<?xml version=\"1.0\"?>\n<TrainingCenterDatabase>").unwrap();
+        assert_eq!(format_of(&manager, &tcx), Some("TCX"));
+
+        let fit = dir.path().join("ride.dat");
+        std::fs::write(&fit, [14, 0x10, 0x6c, 0x08, 0x10, 0, 0, 0, b'.', b'F', b'I', b'T', 0, 0]).unwrap();
+        assert_eq!(format_of(&manager, &fit), Some("FIT"));
+
+        let gpx = dir.path().join("run.csv");
+        std::fs::write(&gpx, "<?xml version=\"1.0\"?>\n<gpx version=\"1.1\">").unwrap();
+        assert_eq!(format_of(&manager, &gpx), Some("GPX"));
+
+        let csv = dir.path().join("export");
+        std::fs::write(&csv, "Time,Heart Rate,Watts\n0,120,200\n").unwrap();
+        assert_eq!(format_of(&manager, &csv), Some("CSV"));
+
+        let notes = dir.path().join("notes.txt");
+        std::fs::write(&notes, "Felt strong today").unwrap();
+        assert_eq!(format_of(&manager, &notes), None);
+    }
+
+    #[test]
+    fn test_registered_importer_takes_precedence() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut manager = ImportManager::new();
+        let erg = dir.path().join("session.erg");
+        let csv = dir.path().join("session.csv");
+        std::fs::write(&erg, "stroke,split\n1,1:52\n").unwrap();
+        std::fs::write(&csv, "stroke,split\n1,1:52\n").unwrap();
+
+        assert_eq!(format_of(&manager, &erg), None);
+        assert_eq!(format_of(&manager, &csv), Some("CSV"));
+
+        manager.register(Box::new(RowingImporter));
+        assert_eq!(format_of(&manager, &erg), Some("erg"));
+        assert_eq!(format_of(&manager, &csv), Some("erg"));
+
+        // Content the built-in CSV importer recognises stays with the importer claiming the extension
+        let recognised = dir.path().join("intervals.csv");
+        std::fs::write(&recognised, "Time,Heart Rate,Watts\n0,120,200\n").unwrap();
+        assert_eq!(format_of(&manager, &recognised), Some("erg"));
+    }
+
+    #[test]
+    fn test_extension_importer_rejecting_content_falls_back_to_sniffing() {
+        struct StrictImporter;
+
+        impl ImportFormat for StrictImporter {
+            fn can_import(&self, file_path: &Path) -> bool {
+                file_path.extension().is_some_and(|ext| ext == "csv")
+            }
+
+            fn import_file(&self, _file_path: &Path) -> Result<Vec<Workout>> {
+                Ok(Vec::new())
+            }
+
+            fn get_format_name(&self) -> &'static str {
+                "strict"
+            }
+
+            fn sniff(&self, header: &[u8]) -> Option<bool> {
+                Some(header.starts_with(b"#strict"))
+            }
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        let mut manager = ImportManager::new();
+        manager.register(Box::new(StrictImporter));
+
+        let own = dir.path().join("own.csv");
+        std::fs::write(&own, "#strict\n1,2\n").unwrap();
+        assert_eq!(format_of(&manager, &own), Some("strict"));
+
+        let gpx = dir.path().join("run.csv");
+        std::fs::write(&gpx, "<?xml version=\"1.0\"?>\n<gpx version=\"1.1\">").unwrap();
+        assert_eq!(format_of(&manager, &gpx), Some("GPX"));
+
+        // Nothing else recognises it either: the extension still decides
+        let other = dir.path().join("other.csv");
+        std::fs::write(&other, "stroke,split\n1,1:52\n").unwrap();
+        assert_eq!(format_of(&manager, &other), Some("strict"));
+    }
+}
@@ -1,16 +1,78 @@
-use anyhow::Result;
+//! TCX (Training Center XML) import
+//!
+//! Reads every `Activity` with its laps and trackpoints. The activity `Id` is
+//! its start time (falling back to the first lap's `StartTime`); the `TPX`
+//! trackpoint extension supplies speed and power, and the `LX` lap extension
+//! the laps' average and maximum power.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, FixedOffset};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use rust_decimal::prelude::*;
 use std::path::Path;
+use uuid::Uuid;
 
+use crate::import::fit::FitImporter;
 use crate::import::ImportFormat;
-use crate::models::Workout;
+use crate::models::{DataPoint, Lap, LapIntensity, LapTrigger, Sport, Workout, WorkoutType};
 
-/// TCX (Training Center XML) importer (stub implementation)
+/// TCX (Training Center XML) importer
 pub struct TcxImporter;
 
+/// Lap summary as recorded in the file
+#[derive(Debug, Default)]
+struct TcxLap {
+    start_time: Option<DateTime<FixedOffset>>,
+    total_time_seconds: f64,
+    distance_meters: Option<f64>,
+    calories: Option<u16>,
+    avg_heart_rate: Option<u16>,
+    max_heart_rate: Option<u16>,
+    avg_power: Option<u16>,
+    max_power: Option<u16>,
+    intensity: Option<String>,
+    trigger_method: Option<String>,
+}
+
+/// One trackpoint with the lap it belongs to
+#[derive(Debug, Default)]
+struct TcxTrackpoint {
+    lap: u16,
+    time: Option<DateTime<FixedOffset>>,
+    altitude: Option<f64>,
+    distance: Option<f64>,
+    heart_rate: Option<u16>,
+    cadence: Option<u16>,
+    speed: Option<f64>,
+    power: Option<u16>,
+}
+
+#[derive(Debug, Default)]
+struct TcxActivity {
+    sport: String,
+    id: Option<DateTime<FixedOffset>>,
+    laps: Vec<TcxLap>,
+    trackpoints: Vec<TcxTrackpoint>,
+}
+
 impl TcxImporter {
     pub fn new() -> Self {
         Self
     }
+
+    /// Parse the activities of a TCX document
+    pub fn parse_str(&self, xml: &str, source: &str) -> Result<Vec<Workout>> {
+        let activities = parse_activities(xml)?;
+        if activities.is_empty() {
+            anyhow::bail!("TCX file contains no activities");
+        }
+
+        activities
+            .into_iter()
+            .map(|activity| activity.into_workout(source))
+            .collect()
+    }
 }
 
 impl ImportFormat for TcxImporter {
@@ -22,13 +84,315 @@ impl ImportFormat for TcxImporter {
             .unwrap_or(false)
     }
 
-    fn import_file(&self, _file_path: &Path) -> Result<Vec<Workout>> {
-        // TODO: Implement TCX file parsing
-        // For now, return an error indicating TCX import is not yet implemented
-        anyhow::bail!("TCX file import is not yet implemented. Please use CSV format for now.")
+    fn import_file(&self, file_path: &Path) -> Result<Vec<Workout>> {
+        let xml = std::fs::read_to_string(file_path)
+            .with_context(|| format!("Failed to read TCX file: {}", file_path.display()))?;
+        self.parse_str(&xml, &file_path.to_string_lossy())
     }
 
     fn get_format_name(&self) -> &'static str {
         "TCX"
     }
+
+    fn sniff(&self, header: &[u8]) -> Option<bool> {
+        Some(header.windows(23).any(|w| w == b"<TrainingCenterDatabase"))
+    }
+}
+
+impl TcxActivity {
+    /// Store the text of the element at the end of `path`
+    fn set(&mut self, path: &[String], text: &str) {
+        let element = path.last().map(String::as_str).unwrap_or_default();
+        let parent = path.len().checked_sub(2).map_or("", |i| path[i].as_str());
+        let in_trackpoint = path.iter().any(|p| p == "Trackpoint");
+
+        match (parent, element) {
+            ("Activity", "Id") => self.id = parse_time(text),
+            ("Lap", name) => {
+                if let Some(lap) = self.laps.last_mut() {
+                    match name {
+                        "TotalTimeSeconds" => lap.total_time_seconds = text.parse().unwrap_or(0.0),
+                        "DistanceMeters" => lap.distance_meters = text.parse().ok(),
+                        "Calories" => lap.calories = text.parse().ok(),
+                        "Intensity" => lap.intensity = Some(text.to_string()),
+                        "TriggerMethod" => lap.trigger_method = Some(text.to_string()),
+                        _ => {}
+                    }
+                }
+            }
+            (_, name) if !in_trackpoint && path.iter().any(|p| p == "Lap") => {
+                if let Some(lap) = self.laps.last_mut() {
+                    match (parent, name) {
+                        ("AverageHeartRateBpm", "Value") => lap.avg_heart_rate = text.parse().ok(),
+                        ("MaximumHeartRateBpm", "Value") => lap.max_heart_rate = text.parse().ok(),
+                        ("LX", "AvgWatts") => lap.avg_power = text.parse().ok(),
+                        ("LX", "MaxWatts") => lap.max_power = text.parse().ok(),
+                        _ => {}
+                    }
+                }
+            }
+            (_, name) if in_trackpoint => {
+                if let Some(point) = self.trackpoints.last_mut() {
+                    match (parent, name) {
+                        ("Trackpoint", "Time") => point.time = parse_time(text),
+                        ("Trackpoint", "AltitudeMeters") => point.altitude = text.parse().ok(),
+                        ("Trackpoint", "DistanceMeters") => point.distance = text.parse().ok(),
+                        ("Trackpoint", "Cadence") | ("TPX", "RunCadence") => point.cadence = text.parse().ok(),
+                        ("HeartRateBpm", "Value") => point.heart_rate = text.parse().ok(),
+                        ("TPX", "Speed") => point.speed = text.parse().ok(),
+                        ("TPX", "Watts") => point.power = text.parse().ok(),
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn into_workout(self, source: &str) -> Result<Workout> {
+        let start_time = self
+            .id
+            .or_else(|| self.laps.iter().find_map(|lap| lap.start_time))
+            .or_else(|| self.trackpoints.iter().find_map(|point| point.time))
+            .context("TCX activity has no start time")?;
+
+        let sport = match self.sport.as_str() {
+            "Biking" => Sport::Cycling,
+            "Running" => Sport::Running,
+            _ => Sport::CrossTraining,
+        };
+
+        let data_points: Vec<DataPoint> = self
+            .trackpoints
+            .iter()
+            .filter_map(|point| {
+                let offset = (point.time? - start_time).num_seconds();
+                Some(DataPoint {
+                    timestamp: offset.max(0) as u32,
+                    heart_rate: point.heart_rate,
+                    power: point.power,
+                    pace: None,
+                    elevation: point.altitude.map(|a| a.round() as i16),
+                    cadence: point.cadence,
+                    speed: point.speed.and_then(Decimal::from_f64).map(|s| s.round_dp(2)),
+                    distance: point.distance.and_then(Decimal::from_f64).map(|d| d.round_dp(1)),
+                    left_power: None,
+                    right_power: None,
+                    ground_contact_time: None,
+                    vertical_oscillation: None,
+                    stride_length: None,
+                    stroke_count: None,
+                    stroke_type: None,
+                    lap_number: Some(point.lap),
+                    sport_transition: None,
+                    temperature: None,
+                    drive_length: None,
+                    stroke_work: None,
+                })
+            })
+            .collect();
+
+        let lap_time: f64 = self.laps.iter().map(|lap| lap.total_time_seconds).sum();
+        let duration_seconds = if lap_time > 0.0 {
+            lap_time.round() as u32
+        } else {
+            data_points.last().map_or(0, |p| p.timestamp)
+        };
+
+        let mut summary = FitImporter::calculate_summary(&data_points, &sport);
+        if summary.total_distance.is_none() {
+            let lap_distance: f64 = self.laps.iter().filter_map(|lap| lap.distance_meters).sum();
+            summary.total_distance = Decimal::from_f64(lap_distance).filter(|d| !d.is_zero()).map(|d| d.round_dp(1));
+        }
+        let calories: u32 = self.laps.iter().filter_map(|lap| lap.calories).map(u32::from).sum();
+        summary.calories = (calories > 0).then(|| calories.min(u32::from(u16::MAX)) as u16);
+
+        let laps = self.laps.iter().map(|lap| lap.to_lap(start_time)).collect();
+
+        Ok(Workout {
+            id: Uuid::new_v4().to_string(),
+            date: start_time.date_naive(),
+            start_time: Some(start_time),
+            sport: sport.clone(),
+            duration_seconds,
+            workout_type: WorkoutType::Endurance,
+            data_source: FitImporter::determine_data_source(&data_points, &sport),
+            raw_data: (!data_points.is_empty()).then_some(data_points),
+            summary,
+            notes: Some(format!("Imported from TCX file: {}", source)),
+            athlete_id: None,
+            source: Some(source.to_string()),
+            swim_lengths: Vec::new(),
+            laps,
+            rpe: None,
+            strength_sets: Vec::new(),
+        })
+    }
+}
+
+impl TcxLap {
+    fn to_lap(&self, activity_start: DateTime<FixedOffset>) -> Lap {
+        Lap {
+            start_offset: self.start_time.map_or(0, |t| (t - activity_start).num_seconds().max(0) as u32),
+            duration_seconds: self.total_time_seconds.round() as u32,
+            distance: self.distance_meters.and_then(Decimal::from_f64).map(|d| d.round_dp(1)),
+            avg_heart_rate: self.avg_heart_rate,
+            max_heart_rate: self.max_heart_rate,
+            avg_power: self.avg_power,
+            max_power: self.max_power,
+            trigger: match self.trigger_method.as_deref() {
+                Some("Manual") => LapTrigger::Manual,
+                Some("Time") => LapTrigger::Time,
+                Some("Distance") => LapTrigger::Distance,
+                Some("Location") => LapTrigger::Position,
+                _ => LapTrigger::Unknown,
+            },
+            intensity: match self.intensity.as_deref() {
+                Some("Resting") => LapIntensity::Rest,
+                _ => LapIntensity::Active,
+            },
+        }
+    }
+}
+
+/// Collect activities, laps and trackpoints from the document
+fn parse_activities(xml: &str) -> Result<Vec<TcxActivity>> {
+    let mut reader = Reader::from_str(xml);
+    reader.trim_text(true);
+
+    let mut path: Vec<String> = Vec::new();
+    let mut activities: Vec<TcxActivity> = Vec::new();
+
+    loop {
+        match reader.read_event().context("Malformed TCX document")? {
+            Event::Start(element) => {
+                let name = String::from_utf8_lossy(element.local_name().as_ref()).into_owned();
+                match name.as_str() {
+                    "Activity" => activities.push(TcxActivity {
+                        sport: attribute(&element, "Sport")?.unwrap_or_default(),
+                        ..TcxActivity::default()
+                    }),
+                    "Lap" => {
+                        if let Some(activity) = activities.last_mut() {
+                            activity.laps.push(TcxLap {
+                                start_time: attribute(&element, "StartTime")?.and_then(|t| parse_time(&t)),
+                                ..TcxLap::default()
+                            });
+                        }
+                    }
+                    "Trackpoint" => {
+                        if let Some(activity) = activities.last_mut() {
+                            let lap = activity.laps.len().max(1) as u16;
+                            activity.trackpoints.push(TcxTrackpoint { lap, ..TcxTrackpoint::default() });
+                        }
+                    }
+                    _ => {}
+                }
+                path.push(name);
+            }
+            Event::End(_) => {
+                path.pop();
+            }
+            Event::Text(text) => {
+                if let Some(activity) = activities.last_mut() {
+                    activity.set(&path, text.unescape()?.trim());
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok(activities)
+}
+
+fn attribute(element: &BytesStart, name: &str) -> Result<Option<String>> {
+    Ok(match element.try_get_attribute(name)? {
+        Some(attribute) => Some(attribute.unescape_value()?.into_owned()),
+        None => None,
+    })
+}
+
+fn parse_time(text: &str) -> Option<DateTime<FixedOffset>> {
+    DateTime::parse_from_rfc3339(text).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RIDE: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<TrainingCenterDatabase xmlns="http://www.garmin.com/xmlschemas/TrainingCenterDatabase/v2"
+    xmlns:ns3="http://www.garmin.com/xmlschemas/ActivityExtension/v2">
+  <Activities>
+    <Activity Sport="Biking">
+      <Id>2024-05-04T06:30:00.000+02:00</Id>
+      <Lap StartTime="2024-05-04T04:30:00Z">
+        <TotalTimeSeconds>20.0</TotalTimeSeconds>
+        <DistanceMeters>180.0</DistanceMeters>
+        <Calories>12</Calories>
+        <AverageHeartRateBpm><Value>125</Value></AverageHeartRateBpm>
+        <MaximumHeartRateBpm><Value>130</Value></MaximumHeartRateBpm>
+        <Intensity>Active</Intensity>
+        <TriggerMethod>Distance</TriggerMethod>
+        <Track>
+          <Trackpoint>
+            <Time>2024-05-04T04:30:00Z</Time>
+            <AltitudeMeters>101.4</AltitudeMeters>
+            <DistanceMeters>0.0</DistanceMeters>
+            <HeartRateBpm><Value>120</Value></HeartRateBpm>
+            <Cadence>85</Cadence>
+            <Extensions><ns3:TPX><ns3:Speed>8.5</ns3:Speed><ns3:Watts>210</ns3:Watts></ns3:TPX></Extensions>
+          </Trackpoint>
+          <Trackpoint>
+            <Time>2024-05-04T04:30:20Z</Time>
+            <AltitudeMeters>103.0</AltitudeMeters>
+            <DistanceMeters>180.0</DistanceMeters>
+            <HeartRateBpm><Value>130</Value></HeartRateBpm>
+            <Cadence>90</Cadence>
+            <Extensions><ns3:TPX><ns3:Speed>9.1</ns3:Speed><ns3:Watts>230</ns3:Watts></ns3:TPX></Extensions>
+          </Trackpoint>
+        </Track>
+        <Extensions><ns3:LX><ns3:AvgWatts>220</ns3:AvgWatts><ns3:MaxWatts>230</ns3:MaxWatts></ns3:LX></Extensions>
+      </Lap>
+      <Lap StartTime="2024-05-04T04:30:20Z">
+        <TotalTimeSeconds>60.0</TotalTimeSeconds>
+        <Intensity>Resting</Intensity>
+        <TriggerMethod>Manual</TriggerMethod>
+      </Lap>
+    </Activity>
+  </Activities>
+</TrainingCenterDatabase>"#;
+
+    #[test]
+    fn test_parses_activity_with_local_start_time() {
+        let workouts = TcxImporter::new().parse_str(RIDE, "ride.tcx").unwrap();
+        assert_eq!(workouts.len(), 1);
+
+        let workout = &workouts[0];
+        assert_eq!(workout.sport, Sport::Cycling);
+        assert_eq!(workout.start_time.unwrap().to_rfc3339(), "2024-05-04T06:30:00+02:00");
+        assert_eq!(workout.date, chrono::NaiveDate::from_ymd_opt(2024, 5, 4).unwrap());
+        assert_eq!(workout.duration_seconds, 80);
+        assert_eq!(workout.summary.calories, Some(12));
+
+        let data = workout.raw_data.as_ref().unwrap();
+        assert_eq!(data.len(), 2);
+        assert_eq!(data[1].timestamp, 20);
+        assert_eq!(data[1].heart_rate, Some(130));
+        assert_eq!(data[1].power, Some(230));
+        assert_eq!(data[1].speed, Some(Decimal::new(91, 1)));
+        assert_eq!(data[1].lap_number, Some(1));
+
+        assert_eq!(workout.laps.len(), 2);
+        let work = &workout.laps[0];
+        assert_eq!((work.start_offset, work.duration_seconds), (0, 20));
+        assert_eq!(work.distance, Some(Decimal::from(180)));
+        assert_eq!((work.avg_heart_rate, work.max_heart_rate), (Some(125), Some(130)));
+        assert_eq!((work.avg_power, work.max_power), (Some(220), Some(230)));
+        assert_eq!((work.trigger, work.intensity), (LapTrigger::Distance, LapIntensity::Active));
+        let rest = &workout.laps[1];
+        assert_eq!((rest.start_offset, rest.duration_seconds), (20, 60));
+        assert_eq!((rest.trigger, rest.intensity), (LapTrigger::Manual, LapIntensity::Rest));
+    }
 }
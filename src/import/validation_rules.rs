@@ -12,6 +12,7 @@ use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
 
+use crate::device_quirks::{QuirkDetector, QuirkSuggestion};
 use crate::models::{DataPoint, Sport, Workout};
 
 /// Validation severity level
@@ -253,6 +254,10 @@ pub struct ValidationReport {
 
     /// Info-level issues
     pub info: Vec<ValidationIssue>,
+
+    /// Device quirks suspected from the data streams
+    #[serde(default)]
+    pub quirk_suggestions: Vec<QuirkSuggestion>,
 }
 
 impl ValidationReport {
@@ -265,6 +270,7 @@ impl ValidationReport {
             errors: Vec::new(),
             warnings: Vec::new(),
             info: Vec::new(),
+            quirk_suggestions: Vec::new(),
         }
     }
 
@@ -553,6 +559,7 @@ impl DataValidator {
             }
         }
 
+        report.quirk_suggestions = QuirkDetector::default().detect(workout);
         report
     }
 
@@ -683,6 +690,9 @@ mod tests {
             stroke_type: None,
             lap_number: Some(1),
             sport_transition: None,
+            temperature: None,
+            drive_length: None,
+            stroke_work: None,
         }
     }
 
@@ -775,4 +785,39 @@ mod tests {
         assert_eq!(report.errors.len(), 1);
         assert!(!report.passed); // Errors fail validation
     }
+
+    #[test]
+    fn test_report_carries_quirk_suggestions() {
+        use crate::models::{DataSource, WorkoutSummary, WorkoutType};
+
+        let points: Vec<DataPoint> = (0..60)
+            .map(|t| DataPoint {
+                timestamp: t,
+                ..create_test_data_point(Some(200 + 2 * t as u16), Some(140), Some(90))
+            })
+            .collect();
+        let workout = Workout {
+            id: "balance".to_string(),
+            date: chrono::NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            sport: Sport::Cycling,
+            duration_seconds: 60,
+            workout_type: WorkoutType::Endurance,
+            data_source: DataSource::Power,
+            raw_data: Some(points),
+            summary: WorkoutSummary::default(),
+            notes: None,
+            athlete_id: None,
+            source: None,
+            swim_lengths: Vec::new(),
+            start_time: None,
+            laps: Vec::new(),
+            rpe: None,
+            strength_sets: Vec::new(),
+        };
+
+        let report = DataValidator::with_defaults().validate_workout(&workout);
+        assert!(report.passed);
+        assert_eq!(report.quirk_suggestions.len(), 1);
+        assert!(report.quirk_suggestions[0].description.contains("50%"));
+    }
 }
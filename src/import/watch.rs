@@ -0,0 +1,362 @@
+//! Watch-folder ingestion for synced device folders
+//!
+//! Polls a directory (a Dropbox or NAS folder devices sync into) for new or
+//! changed FIT files and imports them through [`ParallelImporter`]. Files are
+//! recognised by their [`FileFingerprint`]:
+//! - size and modification time unchanged: skipped without reading the file
+//! - touched but with a known content hash (here or under another name): skipped
+//! - otherwise imported, once the file has stopped changing for the settle time
+//!
+//! A file that cannot be read (removed mid-scan, permissions, a sync client
+//! holding a lock) is reported in the poll's result and retried on the next
+//! one; it does not abort the scan of the other files.
+//!
+//! Storing the workouts and remembering the fingerprints is left to the caller,
+//! so the watcher itself holds no state between polls.
+//!
+//! The watcher deliberately polls instead of subscribing to file-system
+//! events: network shares and many sync clients do not deliver inotify or
+//! FSEvents notifications reliably, and the settle time needs a rescan of the
+//! folder anyway.
+
+use crate::models::Workout;
+use anyhow::Result;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use tracing::{debug, info, warn};
+
+use super::fit_cache::FileFingerprint;
+use super::logging::{ImportLogger, OperationType};
+use super::parallel::{ParallelImportConfig, ParallelImporter};
+
+/// Configuration for a watched folder
+#[derive(Debug, Clone)]
+pub struct WatchConfig {
+    /// Time between directory scans
+    pub poll_interval: Duration,
+    /// Minimum time since a file's last modification before it is imported
+    pub settle_time: Duration,
+    /// Scan subdirectories as well
+    pub recursive: bool,
+}
+
+impl Default for WatchConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval: Duration::from_secs(30),
+            settle_time: Duration::from_secs(10),
+            recursive: true,
+        }
+    }
+}
+
+/// A file imported during a poll
+#[derive(Debug, Clone)]
+pub struct WatchedFile {
+    /// Path to the file
+    pub path: PathBuf,
+    /// Fingerprint of the content that was imported
+    pub fingerprint: FileFingerprint,
+    /// Workouts parsed from the file
+    pub workouts: Vec<Workout>,
+    /// Import time in milliseconds
+    pub duration_ms: u128,
+    /// Error message if the import failed
+    pub error: Option<String>,
+}
+
+/// Result of one poll of the watched folder
+#[derive(Debug, Clone, Default)]
+pub struct WatchCycle {
+    /// New or changed files that were imported (or failed to import)
+    pub imported: Vec<WatchedFile>,
+    /// Files whose modification time changed but whose content is already known
+    pub touched: Vec<(PathBuf, FileFingerprint)>,
+    /// Files still being written, retried on the next poll
+    pub pending: Vec<PathBuf>,
+    /// Files that could not be read, with the reason; retried on the next poll
+    pub unreadable: Vec<(PathBuf, String)>,
+    /// Files skipped as unchanged
+    pub unchanged: usize,
+}
+
+impl WatchCycle {
+    /// Workouts parsed from all imported files
+    pub fn workouts(&self) -> Vec<Workout> {
+        self.imported.iter().flat_map(|f| f.workouts.iter().cloned()).collect()
+    }
+
+    /// Record an audit entry for every processed file
+    pub fn audit(&self, logger: &mut ImportLogger) {
+        for file in &self.imported {
+            logger.begin_operation(OperationType::Import, Some(&file.path));
+            match &file.error {
+                None => logger.log_success(
+                    OperationType::Import,
+                    Some(&file.path),
+                    file.duration_ms as u64,
+                    Some(file.workouts.len() as u64),
+                ),
+                Some(error) => {
+                    logger.log_error(OperationType::Import, Some(&file.path), "IMPORT_FAILED", error, false)
+                }
+            }
+        }
+        for (path, error) in &self.unreadable {
+            logger.begin_operation(OperationType::Import, Some(path));
+            logger.log_error(OperationType::Import, Some(path), "READ_FAILED", error, true);
+        }
+    }
+}
+
+/// Polling watcher for a folder of FIT files
+pub struct FolderWatcher {
+    root: PathBuf,
+    config: WatchConfig,
+    importer: ParallelImporter,
+}
+
+impl FolderWatcher {
+    /// Watch a directory
+    pub fn new(root: &Path, config: WatchConfig) -> Result<Self> {
+        if !root.is_dir() {
+            anyhow::bail!("Path is not a directory: {}", root.display());
+        }
+
+        Ok(Self {
+            root: root.canonicalize()?,
+            config,
+            importer: ParallelImporter::with_config(ParallelImportConfig {
+                show_progress: false,
+                ..ParallelImportConfig::default()
+            }),
+        })
+    }
+
+    /// Watched directory
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Watch configuration
+    pub fn config(&self) -> &WatchConfig {
+        &self.config
+    }
+
+    /// Scan the folder once and import new or changed files
+    ///
+    /// `known` holds the fingerprints of files processed by earlier polls.
+    pub fn poll(&self, known: &HashMap<PathBuf, FileFingerprint>) -> Result<WatchCycle> {
+        let known_hashes: HashSet<&str> = known.values().map(|f| f.hash.as_str()).collect();
+        let now = SystemTime::now();
+        let mut cycle = WatchCycle::default();
+        let mut changed = HashMap::new();
+
+        for path in self.collect_files(&mut cycle)? {
+            let (file_size, modified) = match file_state(&path) {
+                Ok(state) => state,
+                Err(e) => {
+                    warn!("Cannot read {:?}: {}", path, e);
+                    cycle.unreadable.push((path, e.to_string()));
+                    continue;
+                }
+            };
+            let modified_timestamp = modified.duration_since(std::time::UNIX_EPOCH).map_or(0, |d| d.as_secs() as i64);
+
+            if known
+                .get(&path)
+                .is_some_and(|f| f.file_size == file_size && f.modified_timestamp == modified_timestamp)
+            {
+                cycle.unchanged += 1;
+                continue;
+            }
+
+            if now.duration_since(modified).unwrap_or_default() < self.config.settle_time {
+                debug!("Waiting for {:?} to settle", path);
+                cycle.pending.push(path);
+                continue;
+            }
+
+            let fingerprint = match FileFingerprint::generate(&path) {
+                Ok(fingerprint) => fingerprint,
+                Err(e) => {
+                    warn!("Cannot fingerprint {:?}: {}", path, e);
+                    cycle.unreadable.push((path, e.to_string()));
+                    continue;
+                }
+            };
+            let queued = changed.values().any(|f: &FileFingerprint| f.hash == fingerprint.hash);
+            if queued || known_hashes.contains(fingerprint.hash.as_str()) {
+                cycle.touched.push((path, fingerprint));
+            } else {
+                changed.insert(path, fingerprint);
+            }
+        }
+
+        if changed.is_empty() {
+            return Ok(cycle);
+        }
+
+        let paths: Vec<PathBuf> = changed.keys().cloned().collect();
+        info!("Importing {} new or changed files from {:?}", paths.len(), self.root);
+        let (_, summary) = self.importer.import_files(&paths)?;
+
+        cycle.imported = summary
+            .results
+            .into_iter()
+            .filter_map(|result| {
+                let fingerprint = changed.remove(&result.file_path)?;
+                Some(WatchedFile {
+                    path: result.file_path,
+                    fingerprint,
+                    workouts: result.workouts,
+                    duration_ms: result.duration_ms,
+                    error: result.error,
+                })
+            })
+            .collect();
+        cycle.imported.sort_by(|a, b| a.path.cmp(&b.path));
+
+        Ok(cycle)
+    }
+
+    /// FIT files in the watched folder
+    ///
+    /// Only an unreadable root fails the scan; subdirectories and entries that
+    /// cannot be read are recorded in the cycle.
+    fn collect_files(&self, cycle: &mut WatchCycle) -> Result<Vec<PathBuf>> {
+        let mut files = Vec::new();
+        let mut directories = vec![self.root.clone()];
+
+        while let Some(directory) = directories.pop() {
+            let entries = match std::fs::read_dir(&directory) {
+                Ok(entries) => entries,
+                Err(e) if directory != self.root => {
+                    warn!("Cannot read {:?}: {}", directory, e);
+                    cycle.unreadable.push((directory, e.to_string()));
+                    continue;
+                }
+                Err(e) => return Err(e.into()),
+            };
+            for entry in entries {
+                let path = match entry {
+                    Ok(entry) => entry.path(),
+                    Err(e) => {
+                        cycle.unreadable.push((directory.clone(), e.to_string()));
+                        continue;
+                    }
+                };
+                if path.is_dir() {
+                    if self.config.recursive {
+                        directories.push(path);
+                    }
+                } else if path
+                    .extension()
+                    .and_then(|e| e.to_str())
+                    .is_some_and(|e| e.eq_ignore_ascii_case("fit"))
+                {
+                    files.push(path);
+                }
+            }
+        }
+
+        files.sort();
+        Ok(files)
+    }
+}
+
+/// Size and modification time of a file
+fn file_state(path: &Path) -> std::io::Result<(u64, SystemTime)> {
+    let metadata = std::fs::metadata(path)?;
+    Ok((metadata.len(), metadata.modified()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> WatchConfig {
+        WatchConfig {
+            settle_time: Duration::ZERO,
+            ..WatchConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_known_files_are_skipped() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("garmin")).unwrap();
+        std::fs::write(dir.path().join("garmin/ride.fit"), b"not a fit file").unwrap();
+        std::fs::write(dir.path().join("notes.txt"), b"ignored").unwrap();
+
+        let watcher = FolderWatcher::new(dir.path(), config()).unwrap();
+        let path = watcher.root().join("garmin/ride.fit");
+
+        // New file: imported (and failing to parse, which is recorded per file)
+        let cycle = watcher.poll(&HashMap::new()).unwrap();
+        assert_eq!(cycle.imported.len(), 1);
+        assert_eq!(cycle.imported[0].path, path);
+        assert!(cycle.imported[0].error.is_some());
+
+        let mut logger = ImportLogger::new("watch-test");
+        cycle.audit(&mut logger);
+        assert_eq!(logger.get_entries_for_file(&path).len(), 2);
+
+        // Same fingerprint: unchanged
+        let mut known = HashMap::new();
+        known.insert(path.clone(), cycle.imported[0].fingerprint.clone());
+        let cycle = watcher.poll(&known).unwrap();
+        assert!(cycle.imported.is_empty());
+        assert_eq!(cycle.unchanged, 1);
+
+        // Same content under a new name: touched, not imported again
+        std::fs::copy(&path, dir.path().join("copy.fit")).unwrap();
+        let cycle = watcher.poll(&known).unwrap();
+        assert!(cycle.imported.is_empty());
+        assert_eq!(cycle.touched.len(), 1);
+
+        // Copies found in the same scan are imported once
+        let cycle = watcher.poll(&HashMap::new()).unwrap();
+        assert_eq!(cycle.imported.len(), 1);
+        assert_eq!(cycle.touched.len(), 1);
+    }
+
+    #[test]
+    fn test_recently_modified_files_wait_to_settle() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("run.FIT"), b"partial").unwrap();
+
+        let watcher = FolderWatcher::new(
+            dir.path(),
+            WatchConfig {
+                settle_time: Duration::from_secs(3600),
+                ..WatchConfig::default()
+            },
+        )
+        .unwrap();
+
+        let cycle = watcher.poll(&HashMap::new()).unwrap();
+        assert!(cycle.imported.is_empty());
+        assert_eq!(cycle.pending.len(), 1);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_unreadable_files_do_not_abort_the_poll() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("ride.fit"), b"not a fit file").unwrap();
+        std::os::unix::fs::symlink(dir.path().join("gone.fit"), dir.path().join("broken.fit")).unwrap();
+
+        let watcher = FolderWatcher::new(dir.path(), config()).unwrap();
+        let cycle = watcher.poll(&HashMap::new()).unwrap();
+        assert_eq!(cycle.imported.len(), 1);
+        assert_eq!(cycle.imported[0].path, watcher.root().join("ride.fit"));
+        assert_eq!(cycle.unreadable.len(), 1);
+        assert_eq!(cycle.unreadable[0].0, watcher.root().join("broken.fit"));
+
+        let mut logger = ImportLogger::new("watch-test");
+        cycle.audit(&mut logger);
+        assert_eq!(logger.get_entries_for_file(&cycle.unreadable[0].0).len(), 2);
+    }
+}
@@ -0,0 +1,566 @@
+#![allow(dead_code)]
+
+//! Three-zone (Seiler) training intensity distribution
+//!
+//! Collapses time-in-zone into the three physiological intensity domains
+//! bounded by the first and second ventilatory (or lactate) thresholds:
+//!
+//! - **Zone 1**: below VT1/LT1 (easy, conversational)
+//! - **Zone 2**: between VT1 and VT2 (tempo and threshold)
+//! - **Zone 3**: above VT2/LT2 (severe, interval work)
+//!
+//! Distributions are reported per training week or for a whole block and are
+//! classified as polarized, pyramidal or threshold using the polarization index.
+//!
+//! # Sports Science Background
+//!
+//! Seiler & Kjerland (2006) showed that endurance athletes accumulate roughly 80%
+//! of their training below VT1. Treff et al. (2019) defined the polarization index
+//! `PI = log10(Z1 / Z2 × Z3 × 100)` with zone fractions; values above 2.0 indicate
+//! a polarized distribution.
+
+use crate::models::{AthleteProfile, DataPoint, Workout};
+use crate::training_plan::PeriodizationModel;
+use anyhow::Result;
+use chrono::{Datelike, Duration, NaiveDate};
+use rust_decimal::prelude::*;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Longest gap between samples counted as training time; longer gaps are pauses
+const MAX_SAMPLE_GAP_SECONDS: u32 = 60;
+
+/// Reads one intensity channel from a sample
+type SampleValue = fn(&DataPoint) -> Option<u16>;
+
+/// Polarization index above which a distribution counts as polarized
+const POLARIZED_INDEX: f64 = 2.0;
+
+/// VT1 and VT2 in one intensity channel
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ThresholdPair {
+    pub vt1: u16,
+    pub vt2: u16,
+}
+
+impl ThresholdPair {
+    pub fn new(vt1: u16, vt2: u16) -> Result<Self> {
+        if vt1 == 0 || vt1 >= vt2 {
+            anyhow::bail!("VT1 ({}) must be positive and below VT2 ({})", vt1, vt2);
+        }
+        Ok(Self { vt1, vt2 })
+    }
+
+    /// Seiler zone (1-3) of a heart rate or power value
+    pub fn zone(&self, value: u16) -> u8 {
+        if value < self.vt1 {
+            1
+        } else if value < self.vt2 {
+            2
+        } else {
+            3
+        }
+    }
+}
+
+/// Seiler zone boundaries for heart rate and power
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SeilerThresholds {
+    pub heart_rate: Option<ThresholdPair>,
+    pub power: Option<ThresholdPair>,
+}
+
+impl SeilerThresholds {
+    /// Estimate the thresholds from the athlete's profile
+    ///
+    /// Without tested values, VT2 is taken as LTHR (or 89% of max HR) and FTP,
+    /// and VT1 as 90% of LTHR (or 82% of max HR) and 75% of FTP.
+    pub fn from_athlete(athlete: &AthleteProfile) -> Self {
+        let scaled = |value: u16, factor: Decimal| (Decimal::from(value) * factor).round().to_u16().unwrap_or(value);
+
+        let heart_rate = match (athlete.lthr, athlete.max_hr) {
+            (Some(lthr), _) => ThresholdPair::new(scaled(lthr, dec!(0.90)), lthr).ok(),
+            (None, Some(max_hr)) => ThresholdPair::new(scaled(max_hr, dec!(0.82)), scaled(max_hr, dec!(0.89))).ok(),
+            (None, None) => None,
+        };
+        let power = athlete
+            .ftp
+            .and_then(|ftp| ThresholdPair::new(scaled(ftp, dec!(0.75)), ftp).ok());
+
+        Self { heart_rate, power }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heart_rate.is_none() && self.power.is_none()
+    }
+}
+
+/// How a distribution is organised across the three zones
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DistributionClass {
+    /// Mostly zone 1, more zone 3 than zone 2, PI > 2.0
+    Polarized,
+    /// Time decreases from zone 1 to zone 3
+    Pyramidal,
+    /// Zone 2 dominates
+    Threshold,
+    /// Zone 3 dominates
+    HighIntensity,
+}
+
+impl std::fmt::Display for DistributionClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Polarized => "Polarized",
+            Self::Pyramidal => "Pyramidal",
+            Self::Threshold => "Threshold",
+            Self::HighIntensity => "High Intensity",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Time spent in each Seiler zone
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct ThreeZoneDistribution {
+    pub zone1_seconds: u32,
+    pub zone2_seconds: u32,
+    pub zone3_seconds: u32,
+}
+
+impl ThreeZoneDistribution {
+    pub fn total_seconds(&self) -> u32 {
+        self.zone1_seconds + self.zone2_seconds + self.zone3_seconds
+    }
+
+    pub fn add(&mut self, other: &Self) {
+        self.zone1_seconds += other.zone1_seconds;
+        self.zone2_seconds += other.zone2_seconds;
+        self.zone3_seconds += other.zone3_seconds;
+    }
+
+    fn add_seconds(&mut self, zone: u8, seconds: u32) {
+        match zone {
+            1 => self.zone1_seconds += seconds,
+            2 => self.zone2_seconds += seconds,
+            _ => self.zone3_seconds += seconds,
+        }
+    }
+
+    /// Share of time in zones 1-3 as percentages (one decimal)
+    pub fn percentages(&self) -> [Decimal; 3] {
+        let total = self.total_seconds();
+        [self.zone1_seconds, self.zone2_seconds, self.zone3_seconds].map(|seconds| {
+            if total == 0 {
+                Decimal::ZERO
+            } else {
+                (Decimal::from(seconds) * dec!(100) / Decimal::from(total)).round_dp(1)
+            }
+        })
+    }
+
+    /// Polarization index (Treff et al., 2019)
+    ///
+    /// Zero when no time was spent in zone 3. Zone 2 is floored at 1% so an
+    /// empty zone 2 does not divide by zero.
+    pub fn polarization_index(&self) -> Option<Decimal> {
+        let total = self.total_seconds();
+        if total == 0 {
+            return None;
+        }
+        if self.zone3_seconds == 0 {
+            return Some(Decimal::ZERO);
+        }
+        let fraction = |seconds: u32| seconds as f64 / total as f64;
+        let zone2 = fraction(self.zone2_seconds).max(0.01);
+        let index = (fraction(self.zone1_seconds) / zone2 * fraction(self.zone3_seconds) * 100.0).log10();
+        Decimal::from_f64(index).map(|pi| pi.max(Decimal::ZERO).round_dp(2))
+    }
+
+    pub fn classify(&self) -> Option<DistributionClass> {
+        let pi = self.polarization_index()?;
+        let (z1, z2, z3) = (self.zone1_seconds, self.zone2_seconds, self.zone3_seconds);
+
+        Some(if z3 > z1 && z3 > z2 {
+            DistributionClass::HighIntensity
+        } else if z2 >= z1 {
+            DistributionClass::Threshold
+        } else if z3 > z2 && pi > Decimal::from_f64(POLARIZED_INDEX).unwrap_or(dec!(2)) {
+            DistributionClass::Polarized
+        } else {
+            DistributionClass::Pyramidal
+        })
+    }
+}
+
+/// Distribution over a week or training block
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PeriodDistribution {
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub workouts: usize,
+    pub distribution: ThreeZoneDistribution,
+}
+
+impl PeriodDistribution {
+    pub fn polarization_index(&self) -> Option<Decimal> {
+        self.distribution.polarization_index()
+    }
+
+    pub fn classify(&self) -> Option<DistributionClass> {
+        self.distribution.classify()
+    }
+}
+
+/// Target share of time in each Seiler zone
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DistributionTarget {
+    pub name: String,
+    pub zone1_percent: Decimal,
+    pub zone2_percent: Decimal,
+    pub zone3_percent: Decimal,
+    /// Allowed deviation per zone in percentage points
+    pub tolerance: Decimal,
+}
+
+impl DistributionTarget {
+    pub fn new(name: &str, zone1: Decimal, zone2: Decimal, zone3: Decimal) -> Result<Self> {
+        if zone1 + zone2 + zone3 != dec!(100) {
+            anyhow::bail!("Target distribution must add up to 100% (got {})", zone1 + zone2 + zone3);
+        }
+        Ok(Self {
+            name: name.to_string(),
+            zone1_percent: zone1,
+            zone2_percent: zone2,
+            zone3_percent: zone3,
+            tolerance: dec!(5),
+        })
+    }
+
+    /// 80/5/15 polarized model
+    pub fn polarized() -> Self {
+        Self::new("Polarized", dec!(80), dec!(5), dec!(15)).expect("valid preset")
+    }
+
+    /// 75/20/5 pyramidal model
+    pub fn pyramidal() -> Self {
+        Self::new("Pyramidal", dec!(75), dec!(20), dec!(5)).expect("valid preset")
+    }
+
+    /// 55/35/10 threshold-focused model
+    pub fn threshold() -> Self {
+        Self::new("Threshold", dec!(55), dec!(35), dec!(10)).expect("valid preset")
+    }
+
+    /// Default target for a periodization model
+    ///
+    /// Traditional base-build plans accumulate tempo work and end up pyramidal;
+    /// block and reverse plans concentrate intensity into hard sessions.
+    pub fn for_model(model: &PeriodizationModel) -> Self {
+        match model {
+            PeriodizationModel::Traditional => Self::pyramidal(),
+            PeriodizationModel::Block | PeriodizationModel::Reverse => Self::polarized(),
+        }
+    }
+
+    pub fn percentages(&self) -> [Decimal; 3] {
+        [self.zone1_percent, self.zone2_percent, self.zone3_percent]
+    }
+
+    /// Compare an actual distribution with this target
+    pub fn check(&self, actual: &ThreeZoneDistribution) -> DistributionCompliance {
+        let actual_percent = actual.percentages();
+        let target = self.percentages();
+        let deviations = [0, 1, 2].map(|i| actual_percent[i] - target[i]);
+        let total_deviation: Decimal = deviations.iter().map(|d| d.abs()).sum();
+
+        DistributionCompliance {
+            target: self.clone(),
+            actual: actual_percent,
+            deviations,
+            within_tolerance: deviations.iter().all(|d| d.abs() <= self.tolerance),
+            // Each second out of place counts twice in the summed deviation
+            score: (dec!(100) - total_deviation / dec!(2)).max(Decimal::ZERO).round(),
+        }
+    }
+}
+
+impl FromStr for DistributionTarget {
+    type Err = anyhow::Error;
+
+    /// Parse a preset name or explicit "Z1/Z2/Z3" percentages such as "80/5/15"
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "polarized" | "polarised" => Ok(Self::polarized()),
+            "pyramidal" => Ok(Self::pyramidal()),
+            "threshold" => Ok(Self::threshold()),
+            custom => {
+                let parts: Vec<Decimal> = custom
+                    .split('/')
+                    .map(|p| Decimal::from_str(p.trim()))
+                    .collect::<Result<_, _>>()
+                    .map_err(|_| anyhow::anyhow!("Unknown target distribution: {}", s))?;
+                match parts.as_slice() {
+                    [z1, z2, z3] => Self::new("Custom", *z1, *z2, *z3),
+                    _ => anyhow::bail!("Target distribution needs three percentages, e.g. 80/5/15"),
+                }
+            }
+        }
+    }
+}
+
+/// Result of checking a distribution against its target
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DistributionCompliance {
+    pub target: DistributionTarget,
+    /// Actual zone 1-3 percentages
+    pub actual: [Decimal; 3],
+    /// Actual minus target, in percentage points
+    pub deviations: [Decimal; 3],
+    pub within_tolerance: bool,
+    /// 100 when the distributions match, 0 when they share no time
+    pub score: Decimal,
+}
+
+impl DistributionCompliance {
+    /// Advice for the zone furthest from its target
+    pub fn advice(&self) -> Option<String> {
+        if self.within_tolerance {
+            return None;
+        }
+        let (zone, deviation) = self
+            .deviations
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, d)| d.abs())
+            .map(|(i, d)| (i + 1, *d))?;
+        let direction = if deviation > Decimal::ZERO { "more" } else { "less" };
+        Some(format!(
+            "Intensity distribution {:.0}/{:.0}/{:.0} misses the {} target: {:.0} points {} zone {} time than planned",
+            self.actual[0],
+            self.actual[1],
+            self.actual[2],
+            self.target.name.to_lowercase(),
+            deviation.abs(),
+            direction,
+            zone
+        ))
+    }
+}
+
+/// Seiler zone analysis of workouts
+pub struct IntensityDistributionAnalyzer;
+
+impl IntensityDistributionAnalyzer {
+    /// Time in each Seiler zone for one workout
+    ///
+    /// Power is preferred over heart rate when both thresholds and samples are
+    /// available. Workouts without samples are assigned entirely to the zone of
+    /// their average power or heart rate. Returns None when nothing can be zoned.
+    pub fn workout_distribution(workout: &Workout, thresholds: &SeilerThresholds) -> Option<ThreeZoneDistribution> {
+        if let Some(samples) = workout.raw_data.as_deref().filter(|s| !s.is_empty()) {
+            let channels: [(Option<ThresholdPair>, SampleValue); 2] =
+                [(thresholds.power, |p| p.power), (thresholds.heart_rate, |p| p.heart_rate)];
+
+            for (pair, value) in channels {
+                let Some(pair) = pair else { continue };
+                if !samples.iter().any(|p| value(p).is_some()) {
+                    continue;
+                }
+
+                let mut distribution = ThreeZoneDistribution::default();
+                for (i, point) in samples.iter().enumerate() {
+                    let Some(v) = value(point) else { continue };
+                    let gap = match (samples.get(i + 1), i.checked_sub(1).and_then(|j| samples.get(j))) {
+                        (Some(next), _) => next.timestamp.saturating_sub(point.timestamp),
+                        (None, Some(previous)) => point.timestamp.saturating_sub(previous.timestamp),
+                        (None, None) => 1,
+                    };
+                    distribution.add_seconds(pair.zone(v), gap.min(MAX_SAMPLE_GAP_SECONDS));
+                }
+                return Some(distribution);
+            }
+        }
+
+        let zone = match (thresholds.power, workout.summary.avg_power, thresholds.heart_rate, workout.summary.avg_heart_rate) {
+            (Some(pair), Some(power), _, _) => pair.zone(power),
+            (_, _, Some(pair), Some(hr)) => pair.zone(hr),
+            _ => return None,
+        };
+        let mut distribution = ThreeZoneDistribution::default();
+        distribution.add_seconds(zone, workout.duration_seconds);
+        Some(distribution)
+    }
+
+    /// Combined distribution of a set of workouts between two dates (inclusive)
+    pub fn block_distribution(
+        workouts: &[Workout],
+        thresholds: &SeilerThresholds,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> PeriodDistribution {
+        let mut period = PeriodDistribution {
+            start_date,
+            end_date,
+            workouts: 0,
+            distribution: ThreeZoneDistribution::default(),
+        };
+
+        for workout in workouts.iter().filter(|w| start_date <= w.date && w.date <= end_date) {
+            if let Some(distribution) = Self::workout_distribution(workout, thresholds) {
+                period.distribution.add(&distribution);
+                period.workouts += 1;
+            }
+        }
+        period
+    }
+
+    /// Distribution per Monday-based training week, in date order
+    pub fn weekly_distribution(workouts: &[Workout], thresholds: &SeilerThresholds) -> Vec<PeriodDistribution> {
+        let mut weeks: BTreeMap<NaiveDate, PeriodDistribution> = BTreeMap::new();
+
+        for workout in workouts {
+            let Some(distribution) = Self::workout_distribution(workout, thresholds) else {
+                continue;
+            };
+            let start = workout.date - Duration::days(workout.date.weekday().num_days_from_monday() as i64);
+            let week = weeks.entry(start).or_insert_with(|| PeriodDistribution {
+                start_date: start,
+                end_date: start + Duration::days(6),
+                workouts: 0,
+                distribution: ThreeZoneDistribution::default(),
+            });
+            week.distribution.add(&distribution);
+            week.workouts += 1;
+        }
+
+        weeks.into_values().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{DataSource, Sport, WorkoutSummary, WorkoutType};
+
+    fn distribution(z1: u32, z2: u32, z3: u32) -> ThreeZoneDistribution {
+        ThreeZoneDistribution {
+            zone1_seconds: z1,
+            zone2_seconds: z2,
+            zone3_seconds: z3,
+        }
+    }
+
+    fn ride(date: NaiveDate, power: &[u16]) -> Workout {
+        let raw_data = power
+            .iter()
+            .enumerate()
+            .map(|(i, watts)| DataPoint {
+                timestamp: i as u32,
+                heart_rate: None,
+                power: Some(*watts),
+                pace: None,
+                elevation: None,
+                cadence: None,
+                speed: None,
+                distance: None,
+                left_power: None,
+                right_power: None,
+                ground_contact_time: None,
+                vertical_oscillation: None,
+                stride_length: None,
+                stroke_count: None,
+                stroke_type: None,
+                lap_number: None,
+                sport_transition: None,
+                temperature: None,
+                drive_length: None,
+                stroke_work: None,
+            })
+            .collect();
+
+        Workout {
+            id: format!("ride_{}", date),
+            date,
+            sport: Sport::Cycling,
+            duration_seconds: power.len() as u32,
+            workout_type: WorkoutType::Endurance,
+            data_source: DataSource::Power,
+            raw_data: Some(raw_data),
+            summary: WorkoutSummary::default(),
+            notes: None,
+            athlete_id: None,
+            source: None,
+            swim_lengths: Vec::new(),
+            start_time: None,
+            laps: Vec::new(),
+            rpe: None,
+            strength_sets: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_polarization_index_and_classification() {
+        // 80/5/15: PI = log10(0.80 / 0.05 * 0.15 * 100) = 2.38
+        let polarized = distribution(800, 50, 150);
+        assert_eq!(polarized.polarization_index(), Some(dec!(2.38)));
+        assert_eq!(polarized.classify(), Some(DistributionClass::Polarized));
+
+        // 75/20/5: PI = log10(0.75 / 0.20 * 0.05 * 100) = 1.27
+        let pyramidal = distribution(750, 200, 50);
+        assert_eq!(pyramidal.polarization_index(), Some(dec!(1.27)));
+        assert_eq!(pyramidal.classify(), Some(DistributionClass::Pyramidal));
+
+        assert_eq!(distribution(400, 500, 100).classify(), Some(DistributionClass::Threshold));
+        assert_eq!(distribution(900, 100, 0).polarization_index(), Some(Decimal::ZERO));
+        assert_eq!(distribution(0, 0, 0).classify(), None);
+    }
+
+    #[test]
+    fn test_weekly_distribution_from_power_samples() {
+        let thresholds = SeilerThresholds {
+            heart_rate: None,
+            power: Some(ThresholdPair::new(190, 250).unwrap()),
+        };
+        let monday = NaiveDate::from_ymd_opt(2025, 3, 3).unwrap();
+        let mut easy = vec![150u16; 80];
+        easy.extend([300u16; 20]);
+        let workouts = vec![
+            ride(monday, &easy),
+            ride(monday + Duration::days(2), &[220; 50]),
+            ride(monday + Duration::days(7), &[150; 100]),
+        ];
+
+        let weeks = IntensityDistributionAnalyzer::weekly_distribution(&workouts, &thresholds);
+        assert_eq!(weeks.len(), 2);
+        assert_eq!(weeks[0].workouts, 2);
+        assert_eq!(weeks[0].distribution, distribution(80, 50, 20));
+        assert_eq!(weeks[1].distribution.percentages(), [dec!(100), dec!(0), dec!(0)]);
+
+        let block = IntensityDistributionAnalyzer::block_distribution(
+            &workouts,
+            &thresholds,
+            monday,
+            monday + Duration::days(13),
+        );
+        assert_eq!(block.distribution, distribution(180, 50, 20));
+    }
+
+    #[test]
+    fn test_target_compliance() {
+        let target = DistributionTarget::polarized();
+        let on_target = target.check(&distribution(810, 40, 150));
+        assert!(on_target.within_tolerance);
+        assert!(on_target.advice().is_none());
+
+        let drifting = target.check(&distribution(650, 250, 100));
+        assert!(!drifting.within_tolerance);
+        assert_eq!(drifting.deviations, [dec!(-15), dec!(20), dec!(-5)]);
+        assert_eq!(drifting.score, dec!(80));
+        assert!(drifting.advice().unwrap().contains("more zone 2"));
+
+        assert_eq!("70/20/10".parse::<DistributionTarget>().unwrap().zone2_percent, dec!(20));
+        assert!("70/20/20".parse::<DistributionTarget>().is_err());
+    }
+}
@@ -1,6 +1,7 @@
 // Library interface for TrainRS modules
 // This allows integration tests to access the core functionality
 
+pub mod acwr;
 pub mod config;
 pub mod database;
 pub mod data_management;
@@ -9,20 +10,29 @@ pub mod error;
 pub mod export;
 pub mod formulas;
 pub mod import;
+pub mod intensity_distribution;
 pub mod logging;
 pub mod models;
 pub mod multisport;
 pub mod performance;
+pub mod plan_rescheduler;
 pub mod pmc;
 pub mod power;
+pub mod race_calendar;
+pub mod readiness;
 pub mod recovery;
+pub mod rowing;
 pub mod running;
+pub mod strength;
 pub mod stress_testing;
+pub mod structured_workout;
 pub mod swimming;
+pub mod threshold_detection;
 pub mod training_effect;
 pub mod training_plan;
 pub mod tss;
 pub mod vo2max;
+pub mod workout_merge;
 pub mod zones;
 
 // Test utilities have been integrated into individual test files
@@ -1,4 +1,4 @@
-use chrono::NaiveDate;
+use chrono::{DateTime, FixedOffset, NaiveDate};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
@@ -54,7 +54,8 @@ pub struct DataPoint {
     /// Elevation in meters above sea level
     pub elevation: Option<i16>,
 
-    /// Cadence (steps per minute for running, revolutions per minute for cycling)
+    /// Cadence (steps per minute for running, revolutions per minute for cycling,
+    /// strokes per minute for rowing)
     pub cadence: Option<u16>,
 
     /// Speed in meters per second
@@ -91,6 +92,16 @@ pub struct DataPoint {
 
     /// Sport transition indicator (for triathlon/multi-sport activities)
     pub sport_transition: Option<bool>,
+
+    /// Ambient temperature in degrees Celsius
+    pub temperature: Option<i8>,
+
+    /// Rowing-specific metrics
+    /// Drive length in meters (rowing)
+    pub drive_length: Option<Decimal>,
+
+    /// Work per stroke in joules (rowing)
+    pub stroke_work: Option<u16>,
 }
 
 /// Summary metrics calculated from workout data
@@ -139,6 +150,10 @@ pub struct Workout {
     /// Date of the workout
     pub date: NaiveDate,
 
+    /// Start of the workout with the UTC offset it was recorded in
+    #[serde(default)]
+    pub start_time: Option<DateTime<FixedOffset>>,
+
     /// Sport/activity type
     pub sport: Sport,
 
@@ -165,6 +180,186 @@ pub struct Workout {
 
     /// Original file name or source identifier
     pub source: Option<String>,
+
+    /// Pool lengths for lap swimming (empty for other workouts)
+    #[serde(default)]
+    pub swim_lengths: Vec<SwimLength>,
+
+    /// Device laps (empty when the file has none)
+    #[serde(default)]
+    pub laps: Vec<Lap>,
+
+    /// Session rating of perceived exertion (1-10)
+    #[serde(default)]
+    pub rpe: Option<u8>,
+
+    /// Sets of a strength workout (empty for other workouts)
+    #[serde(default)]
+    pub strength_sets: Vec<StrengthSet>,
+}
+
+impl Workout {
+    /// Training day of the workout in the given timezone
+    ///
+    /// Falls back to the recorded local time without a timezone, and to the
+    /// stored date for workouts without a start time.
+    pub fn local_date(&self, timezone: Option<LocalTimezone>) -> NaiveDate {
+        match (self.start_time, timezone) {
+            (Some(start), Some(timezone)) => timezone.date_of(&start),
+            (Some(start), None) => start.date_naive(),
+            (None, _) => self.date,
+        }
+    }
+}
+
+/// Timezone an athlete's training days are counted in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LocalTimezone {
+    /// IANA zone such as "Europe/Berlin", following daylight saving time
+    Named(chrono_tz::Tz),
+    /// Fixed UTC offset such as "+01:00"
+    Fixed(FixedOffset),
+}
+
+impl LocalTimezone {
+    /// Calendar date of an instant in this timezone
+    pub fn date_of(&self, time: &DateTime<FixedOffset>) -> NaiveDate {
+        match self {
+            LocalTimezone::Named(tz) => time.with_timezone(tz).date_naive(),
+            LocalTimezone::Fixed(offset) => time.with_timezone(offset).date_naive(),
+        }
+    }
+
+    /// Current date in this timezone
+    pub fn today(&self) -> NaiveDate {
+        self.date_of(&chrono::Utc::now().fixed_offset())
+    }
+}
+
+impl std::fmt::Display for LocalTimezone {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LocalTimezone::Named(tz) => write!(f, "{}", tz.name()),
+            LocalTimezone::Fixed(offset) => write!(f, "UTC{}", offset),
+        }
+    }
+}
+
+/// Whether a pool length was swum or spent resting at the wall
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SwimLengthType {
+    Active,
+    Idle,
+}
+
+/// One pool length of a swim workout (FIT `length` message)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SwimLength {
+    /// Start of the length in seconds from workout start
+    pub start_offset: u32,
+
+    /// Time for the length in seconds
+    pub duration_seconds: Decimal,
+
+    /// Length distance (pool length) in meters
+    pub distance: Decimal,
+
+    /// Stroke count for the length
+    pub strokes: Option<u16>,
+
+    /// Swimming stroke type, as in `DataPoint::stroke_type`
+    pub stroke_type: Option<u8>,
+
+    /// Active length or rest interval
+    pub length_type: SwimLengthType,
+}
+
+/// What ended a device lap
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LapTrigger {
+    /// Lap button pressed
+    Manual,
+    /// Auto-lap by time
+    Time,
+    /// Auto-lap by distance
+    Distance,
+    /// Auto-lap at a saved position
+    Position,
+    /// Activity stopped
+    SessionEnd,
+    /// Lap sent by a trainer or gym machine
+    FitnessEquipment,
+    Unknown,
+}
+
+/// Whether a lap was work or recovery
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LapIntensity {
+    Active,
+    Rest,
+    Warmup,
+    Cooldown,
+}
+
+/// One lap as recorded by the device (FIT `lap` message, TCX `Lap`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Lap {
+    /// Start of the lap in seconds from workout start
+    pub start_offset: u32,
+
+    /// Timer time of the lap in seconds
+    pub duration_seconds: u32,
+
+    /// Lap distance in meters
+    pub distance: Option<Decimal>,
+
+    pub avg_heart_rate: Option<u16>,
+    pub max_heart_rate: Option<u16>,
+    pub avg_power: Option<u16>,
+    pub max_power: Option<u16>,
+
+    /// What ended the lap
+    pub trigger: LapTrigger,
+
+    /// Work or recovery lap
+    pub intensity: LapIntensity,
+}
+
+impl Lap {
+    /// End of the lap in seconds from workout start
+    pub fn end_offset(&self) -> u32 {
+        self.start_offset + self.duration_seconds
+    }
+}
+
+/// Whether a strength set was lifted or spent resting
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SetType {
+    Active,
+    Rest,
+}
+
+/// One set of a strength workout (FIT `set` message or manual entry)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StrengthSet {
+    /// Start of the set in seconds from workout start
+    pub start_offset: u32,
+
+    /// Duration of the set in seconds
+    pub duration_seconds: u32,
+
+    /// Exercise, named like the FIT exercise categories (e.g. "bench_press");
+    /// none for rest sets
+    pub exercise: Option<String>,
+
+    /// Repetitions completed
+    pub reps: Option<u16>,
+
+    /// Load in kilograms
+    pub weight: Option<Decimal>,
+
+    /// Lifting or resting
+    pub set_type: SetType,
 }
 
 /// Training zones for different sports and metrics
@@ -239,6 +434,15 @@ pub struct AthleteProfile {
     /// Threshold pace for running (minutes per mile or km, depending on units)
     pub threshold_pace: Option<Decimal>,
 
+    /// Functional Threshold Power on the rowing ergometer (watts), separate
+    /// from the cycling FTP
+    #[serde(default)]
+    pub rowing_ftp: Option<u16>,
+
+    /// Critical swim speed pace (minutes per 100m)
+    #[serde(default)]
+    pub css_pace: Option<Decimal>,
+
     /// Maximum Heart Rate
     pub max_hr: Option<u16>,
 
@@ -372,6 +576,9 @@ mod tests {
             stroke_type: None,
             lap_number: None,
             sport_transition: None,
+            temperature: None,
+            drive_length: None,
+            stroke_work: None,
         };
 
         assert_eq!(data_point.timestamp, 60);
@@ -400,6 +607,9 @@ mod tests {
             stroke_type: None,
             lap_number: None,
             sport_transition: None,
+            temperature: None,
+            drive_length: None,
+            stroke_work: None,
         };
 
         let json = serde_json::to_string(&data_point).unwrap();
@@ -456,6 +666,11 @@ mod tests {
             notes: Some("Great morning run".to_string()),
             athlete_id: Some("athlete_456".to_string()),
             source: Some("garmin_connect".to_string()),
+            swim_lengths: Vec::new(),
+            start_time: None,
+            laps: Vec::new(),
+            rpe: None,
+            strength_sets: Vec::new(),
         };
 
         assert_eq!(workout.id, "workout_123");
@@ -485,6 +700,9 @@ mod tests {
                 stroke_type: None,
                 lap_number: None,
                 sport_transition: None,
+                temperature: None,
+                drive_length: None,
+                stroke_work: None,
             },
             DataPoint {
                 timestamp: 60,
@@ -504,6 +722,9 @@ mod tests {
                 stroke_type: None,
                 lap_number: None,
                 sport_transition: None,
+                temperature: None,
+                drive_length: None,
+                stroke_work: None,
             },
         ];
 
@@ -519,6 +740,11 @@ mod tests {
             notes: None,
             athlete_id: Some("athlete_789".to_string()),
             source: None,
+            swim_lengths: Vec::new(),
+            start_time: None,
+            laps: Vec::new(),
+            rpe: None,
+            strength_sets: Vec::new(),
         };
 
         assert_eq!(workout.raw_data.as_ref().unwrap().len(), 2);
@@ -597,6 +823,8 @@ mod tests {
             preferred_units: Units::Metric,
             created_at: now,
             updated_at: now,
+            rowing_ftp: None,
+            css_pace: None,
         };
 
         assert_eq!(profile.id, "athlete_001");
@@ -647,6 +875,8 @@ mod tests {
             preferred_units: Units::Imperial,
             created_at: now,
             updated_at: now,
+            rowing_ftp: None,
+            css_pace: None,
         };
 
         assert_eq!(profile.preferred_units, Units::Imperial);
@@ -691,6 +921,9 @@ mod tests {
             stroke_type: None,
             lap_number: None,
             sport_transition: None,
+            temperature: None,
+            drive_length: None,
+            stroke_work: None,
             }]),
             summary: WorkoutSummary {
                 avg_heart_rate: Some(155),
@@ -708,6 +941,11 @@ mod tests {
             notes: Some("Excellent interval session".to_string()),
             athlete_id: Some("athlete_123".to_string()),
             source: Some("wahoo_elemnt".to_string()),
+            swim_lengths: Vec::new(),
+            start_time: None,
+            laps: Vec::new(),
+            rpe: None,
+            strength_sets: Vec::new(),
         };
 
         // Test serialization
@@ -740,6 +978,8 @@ mod tests {
             preferred_units: Units::Metric,
             created_at: now,
             updated_at: now,
+            rowing_ftp: None,
+            css_pace: None,
         };
 
         // Test serialization
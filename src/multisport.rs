@@ -9,6 +9,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 use crate::models::{Sport, Workout, AthleteProfile};
+use crate::swimming::{CssTest, SwimmingAnalyzer};
 use crate::tss::TssCalculator;
 use crate::pmc::{PmcCalculator, PmcMetrics};
 
@@ -107,6 +108,9 @@ pub struct CssMetrics {
     pub css_pace: Decimal, // Critical Swim Speed in min/100m
     pub css_threshold_power: Option<Decimal>,
     pub recent_performances: Vec<SwimPerformance>,
+    /// 400/200 test sets the CSS was calculated from, when found
+    #[serde(default)]
+    pub test: Option<CssTest>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -389,6 +393,23 @@ fn get_sport_conversion_matrix() -> HashMap<(Sport, Sport), Decimal> {
 
 /// Calculate Critical Swim Speed (CSS)
 pub fn calculate_css(swim_workouts: &[Workout]) -> Option<CssMetrics> {
+    // Prefer a 400/200 test detected from pool lengths
+    if let Some(test) = SwimmingAnalyzer::detect_css_test(swim_workouts) {
+        let recent_performances = [(dec!(400), test.time_400), (dec!(200), test.time_200)]
+            .into_iter()
+            .filter_map(|(distance, time)| {
+                let time = time.round().to_u32()?;
+                Some(SwimPerformance { date: test.date, distance, time, pace: calculate_swim_pace(distance, time) })
+            })
+            .collect();
+        return Some(CssMetrics {
+            css_pace: test.pace_min_per_100m(),
+            css_threshold_power: None,
+            recent_performances,
+            test: Some(test),
+        });
+    }
+
     let swim_performances: Vec<SwimPerformance> = swim_workouts
         .iter()
         .filter(|w| w.sport == Sport::Swimming && w.duration_seconds > 0)
@@ -428,6 +449,7 @@ pub fn calculate_css(swim_workouts: &[Workout]) -> Option<CssMetrics> {
         css_pace,
         css_threshold_power: None, // Would be calculated from power data
         recent_performances: performances.into_iter().take(10).collect(),
+        test: None,
     })
 }
 
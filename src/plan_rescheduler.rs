@@ -0,0 +1,768 @@
+//! Adaptive training plan re-scheduling
+//!
+//! Rebalances the remaining weeks of a plan after missed or over-done sessions:
+//! - Missed key sessions from the last week move onto an easy day in the next
+//!   days, never into a recovery week and never back-to-back with another key
+//!   session. After an extended break only the most important one is kept.
+//! - Low readiness or deep fatigue (TSB) defers the next key sessions by
+//!   swapping them with a later easy day, or eases them in place.
+//! - When the completed load of the last days overshoots the plan, the
+//!   sessions of the coming days are shortened by the excess TSS.
+//! - Future weeks are scaled down so projected CTL rises by no more than the
+//!   configured weekly ramp, starting from the athlete's current CTL.
+//!
+//! Every change is recorded in a `PlanDiff` for review before saving.
+
+use chrono::{Duration, NaiveDate};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use rust_decimal::prelude::ToPrimitive;
+use serde::{Deserialize, Serialize};
+
+use crate::models::{AthleteProfile, WorkoutType};
+use crate::pmc::PmcMetrics;
+use crate::readiness::DailyReadiness;
+use crate::recovery::TrainingRecommendation;
+use crate::training_plan::{PlanMonitoring, PlannedWorkout, TrainingPlan, TrainingPlanGenerator};
+
+/// Re-scheduling thresholds
+#[derive(Debug, Clone)]
+pub struct RescheduleConfig {
+    /// Maximum CTL increase per week
+    pub max_ctl_ramp: Decimal,
+    /// Missed sessions older than this are left alone
+    pub lookback_days: i64,
+    /// How far ahead a missed or deferred session may move
+    pub window_days: i64,
+    /// Missed days within the lookback that count as an extended break
+    pub break_days: usize,
+    /// Composite readiness below this defers upcoming key sessions
+    pub low_readiness: u8,
+    /// TSB below this defers upcoming key sessions
+    pub fatigue_tsb: Decimal,
+    /// Days of key sessions deferred when readiness is low
+    pub deferral_days: i64,
+    /// Smallest factor the ramp cap or an overshoot may scale a session by
+    pub min_scale: Decimal,
+    /// Completed/planned TSS ratio over the lookback above which upcoming sessions are trimmed
+    pub overshoot_ratio: Decimal,
+}
+
+impl Default for RescheduleConfig {
+    fn default() -> Self {
+        Self {
+            max_ctl_ramp: dec!(7),
+            lookback_days: 7,
+            window_days: 10,
+            break_days: 3,
+            low_readiness: 40,
+            fatigue_tsb: dec!(-30),
+            deferral_days: 2,
+            min_scale: dec!(0.5),
+            overshoot_ratio: dec!(1.15),
+        }
+    }
+}
+
+/// A single change to the plan
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum PlanChange {
+    /// Session moved to another day
+    Moved { from: NaiveDate, to: NaiveDate, description: String, reason: String },
+    /// Session taken out of the plan
+    Removed { date: NaiveDate, description: String, reason: String },
+    /// Session put into the plan
+    Added { date: NaiveDate, description: String, reason: String },
+    /// Session shortened or lengthened
+    Scaled {
+        date: NaiveDate,
+        description: String,
+        previous_minutes: u32,
+        new_minutes: u32,
+        previous_tss: Decimal,
+        new_tss: Decimal,
+    },
+    /// Missed session that could not be rescheduled
+    Dropped { date: NaiveDate, description: String, reason: String },
+}
+
+impl PlanChange {
+    /// One diff line: `-` removed, `+` added, `~` moved or scaled, `!` dropped
+    pub fn line(&self) -> String {
+        match self {
+            PlanChange::Moved { from, to, description, reason } => {
+                format!("~ {} → {}  {} ({})", from, to, description, reason)
+            }
+            PlanChange::Removed { date, description, reason } => format!("- {}  {} ({})", date, description, reason),
+            PlanChange::Added { date, description, reason } => format!("+ {}  {} ({})", date, description, reason),
+            PlanChange::Scaled { date, description, previous_minutes, new_minutes, previous_tss, new_tss } => format!(
+                "~ {}  {}: {} → {} min, {:.0} → {:.0} TSS",
+                date, description, previous_minutes, new_minutes, previous_tss, new_tss
+            ),
+            PlanChange::Dropped { date, description, reason } => format!("! {}  {} ({})", date, description, reason),
+        }
+    }
+}
+
+/// Changes made by a re-scheduling run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlanDiff {
+    pub plan_id: String,
+    pub changes: Vec<PlanChange>,
+    pub previous_tss: Decimal,
+    pub new_tss: Decimal,
+    /// Projected CTL at the end of each remaining week after the changes
+    pub projected_ctl: Vec<(u32, Decimal)>,
+}
+
+impl PlanDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Diff lines in plan order
+    pub fn lines(&self) -> Vec<String> {
+        self.changes.iter().map(PlanChange::line).collect()
+    }
+}
+
+/// Rebalances a plan from monitoring results, PMC and readiness
+pub struct PlanRescheduler {
+    config: RescheduleConfig,
+}
+
+impl Default for PlanRescheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PlanRescheduler {
+    pub fn new() -> Self {
+        Self::with_config(RescheduleConfig::default())
+    }
+
+    pub fn with_config(config: RescheduleConfig) -> Self {
+        Self { config }
+    }
+
+    /// Rebalance the plan after `as_of`, returning the changes made
+    pub fn reschedule(
+        &self,
+        plan: &mut TrainingPlan,
+        monitoring: &PlanMonitoring,
+        metrics: Option<&PmcMetrics>,
+        readiness: Option<&DailyReadiness>,
+        athlete: &AthleteProfile,
+        as_of: NaiveDate,
+    ) -> PlanDiff {
+        let previous_tss = plan.total_planned_tss;
+        let week_tss: Vec<Decimal> = plan.weeks.iter().map(TrainingPlanGenerator::workouts_tss).collect();
+        let mut changes = Vec::new();
+
+        let fatigued = self.is_fatigued(metrics, readiness);
+        if fatigued {
+            self.defer_key_sessions(plan, athlete, as_of, &mut changes);
+        }
+
+        let earliest = if fatigued { as_of + Duration::days(self.config.deferral_days + 1) } else { as_of + Duration::days(1) };
+        self.redistribute_missed(plan, monitoring, as_of, earliest, &mut changes);
+        self.absorb_overshoot(plan, monitoring, athlete, as_of, &mut changes);
+
+        let projected_ctl = match metrics {
+            Some(metrics) => self.cap_ctl_ramp(plan, metrics.ctl, athlete, as_of, &mut changes),
+            None => Vec::new(),
+        };
+
+        for (week, previous) in plan.weeks.iter_mut().zip(week_tss) {
+            week.workouts.sort_by_key(|w| w.date);
+            TrainingPlanGenerator::adjust_week_totals(week, previous);
+        }
+        plan.total_planned_tss = plan.weeks.iter().map(|w| w.planned_tss).sum();
+        plan.total_planned_hours = plan.weeks.iter().map(|w| w.planned_hours).sum();
+
+        PlanDiff {
+            plan_id: plan.id.clone(),
+            changes,
+            previous_tss,
+            new_tss: plan.total_planned_tss,
+            projected_ctl,
+        }
+    }
+
+    fn is_fatigued(&self, metrics: Option<&PmcMetrics>, readiness: Option<&DailyReadiness>) -> bool {
+        let deep_fatigue = metrics.is_some_and(|m| m.tsb < self.config.fatigue_tsb);
+        let low_readiness = readiness.is_some_and(|r| {
+            r.composite_score.is_some_and(|score| score < self.config.low_readiness)
+                || r.decision.as_ref().is_some_and(|d| {
+                    matches!(d.recommendation, TrainingRecommendation::RestDay | TrainingRecommendation::LightRecovery)
+                })
+        });
+        deep_fatigue || low_readiness
+    }
+
+    /// Swap key sessions in the next days with a later easy day, or ease them in place
+    fn defer_key_sessions(&self, plan: &mut TrainingPlan, athlete: &AthleteProfile, as_of: NaiveDate, changes: &mut Vec<PlanChange>) {
+        let window_end = as_of + Duration::days(self.config.deferral_days);
+        let upcoming: Vec<(usize, usize)> = locate(plan, |w| w.date > as_of && w.date <= window_end && is_key(w));
+
+        // Work back to front so indices of earlier sessions stay valid
+        for (week_idx, workout_idx) in upcoming.into_iter().rev() {
+            let key = plan.weeks[week_idx].workouts[workout_idx].clone();
+            let slot = self.find_slot(plan, &key, window_end + Duration::days(1), as_of + Duration::days(self.config.window_days), &[key.date]);
+
+            match slot {
+                Some((slot_week, slot_idx)) => {
+                    let easy_date = plan.weeks[slot_week].workouts[slot_idx].date;
+                    let mut easy = plan.weeks[slot_week].workouts[slot_idx].clone();
+                    let mut deferred = key.clone();
+                    deferred.date = easy_date;
+                    deferred.sequence += 1;
+                    deferred.notes = Some(format!("Deferred from {}", key.date));
+                    easy.date = key.date;
+                    easy.sequence += 1;
+
+                    changes.push(PlanChange::Moved {
+                        from: key.date,
+                        to: easy_date,
+                        description: key.label().to_string(),
+                        reason: "low readiness".to_string(),
+                    });
+                    changes.push(PlanChange::Moved {
+                        from: easy_date,
+                        to: key.date,
+                        description: easy.label().to_string(),
+                        reason: "swapped with deferred session".to_string(),
+                    });
+
+                    plan.weeks[slot_week].workouts[slot_idx] = deferred;
+                    move_workout(plan, week_idx, workout_idx, easy);
+                }
+                None => {
+                    let minutes = (key.planned_duration_minutes * 6 / 10).max(20);
+                    let mut easy = TrainingPlanGenerator::easy_workout(key.date, &key.sport, minutes, "Easy recovery session", athlete);
+                    easy.notes = Some(format!("Eased from {}", key.label()));
+
+                    changes.push(PlanChange::Removed {
+                        date: key.date,
+                        description: key.label().to_string(),
+                        reason: "low readiness, no later slot".to_string(),
+                    });
+                    changes.push(PlanChange::Added {
+                        date: key.date,
+                        description: easy.label().to_string(),
+                        reason: "replaces key session".to_string(),
+                    });
+                    plan.weeks[week_idx].workouts[workout_idx] = easy;
+                }
+            }
+        }
+    }
+
+    /// Move recently missed key sessions onto upcoming easy days
+    fn redistribute_missed(
+        &self,
+        plan: &mut TrainingPlan,
+        monitoring: &PlanMonitoring,
+        as_of: NaiveDate,
+        earliest: NaiveDate,
+        changes: &mut Vec<PlanChange>,
+    ) {
+        let lookback_start = as_of - Duration::days(self.config.lookback_days);
+        let recent: Vec<_> = monitoring
+            .missed_sessions
+            .iter()
+            .filter(|key| key.date > lookback_start && key.date <= as_of)
+            .collect();
+
+        let mut missed_days: Vec<NaiveDate> = recent.iter().map(|key| key.date).collect();
+        missed_days.dedup();
+        let extended_break = missed_days.len() >= self.config.break_days;
+
+        // Most demanding first so it gets the earliest slot
+        let sessions = plan.sessions();
+        let mut missed_key: Vec<PlannedWorkout> = recent
+            .iter()
+            .filter_map(|key| sessions.iter().find(|(k, _)| k.id == key.id).map(|(_, w)| (*w).clone()))
+            .filter(is_key)
+            .collect();
+        missed_key.sort_by(|a, b| b.intensity_factor.cmp(&a.intensity_factor).then(b.planned_tss.cmp(&a.planned_tss)));
+
+        let latest = as_of + Duration::days(self.config.window_days);
+        for (rank, missed) in missed_key.into_iter().enumerate() {
+            if extended_break && rank > 0 {
+                changes.push(PlanChange::Dropped {
+                    date: missed.date,
+                    description: missed.label().to_string(),
+                    reason: "extended break, resume the plan instead of catching up".to_string(),
+                });
+                continue;
+            }
+
+            let Some((slot_week, slot_idx)) = self.find_slot(plan, &missed, earliest, latest, &missed_days) else {
+                changes.push(PlanChange::Dropped {
+                    date: missed.date,
+                    description: missed.label().to_string(),
+                    reason: "no easy day available".to_string(),
+                });
+                continue;
+            };
+
+            let Some((week_idx, workout_idx)) = locate(plan, |w| w.date == missed.date && w.description == missed.description)
+                .into_iter()
+                .next()
+            else {
+                continue;
+            };
+
+            let replaced = plan.weeks[slot_week].workouts[slot_idx].clone();
+            let mut moved = missed.clone();
+            moved.date = replaced.date;
+            moved.sequence += 1;
+            moved.notes = Some(format!("Rescheduled from {}", missed.date));
+
+            changes.push(PlanChange::Moved {
+                from: missed.date,
+                to: replaced.date,
+                description: missed.label().to_string(),
+                reason: "missed".to_string(),
+            });
+            changes.push(PlanChange::Removed {
+                date: replaced.date,
+                description: replaced.label().to_string(),
+                reason: "makes room for rescheduled session".to_string(),
+            });
+
+            plan.weeks[slot_week].workouts[slot_idx] = moved;
+            plan.weeks[week_idx].workouts.remove(workout_idx);
+        }
+    }
+
+    /// Shorten the coming days' sessions by the TSS completed beyond the plan
+    ///
+    /// Compares completed and planned TSS over the lookback; above the
+    /// overshoot ratio, the sessions over the same number of days ahead are
+    /// scaled down together so they carry the excess less load. Races are kept.
+    fn absorb_overshoot(
+        &self,
+        plan: &mut TrainingPlan,
+        monitoring: &PlanMonitoring,
+        athlete: &AthleteProfile,
+        as_of: NaiveDate,
+        changes: &mut Vec<PlanChange>,
+    ) {
+        let lookback_start = as_of - Duration::days(self.config.lookback_days);
+        let (actual, planned) = monitoring
+            .actual_vs_planned_tss
+            .iter()
+            .filter(|(date, _)| **date > lookback_start && **date <= as_of)
+            .fold((dec!(0), dec!(0)), |(a, p), (_, (actual, planned))| (a + actual, p + planned));
+        if planned <= dec!(0) || actual <= planned * self.config.overshoot_ratio {
+            return;
+        }
+
+        let window_end = as_of + Duration::days(self.config.lookback_days);
+        let upcoming = |w: &PlannedWorkout| w.date > as_of && w.date <= window_end && w.workout_type != WorkoutType::Race;
+        let upcoming_tss: Decimal = plan.weeks.iter().flat_map(|w| &w.workouts).filter(|w| upcoming(w)).map(|w| w.planned_tss).sum();
+        if upcoming_tss <= dec!(0) {
+            return;
+        }
+
+        let scale = ((upcoming_tss - (actual - planned)) / upcoming_tss).clamp(self.config.min_scale, dec!(1));
+        for workout in plan.weeks.iter_mut().flat_map(|w| w.workouts.iter_mut()).filter(|w| upcoming(w)) {
+            if let Some(change) = scale_workout(workout, scale, athlete) {
+                changes.push(change);
+            }
+        }
+    }
+
+    /// Earliest easy session of the same sport between two dates that can
+    /// take a key session without back-to-back hard days
+    ///
+    /// Recovery weeks and race weeks are skipped so the recovery cycle and
+    /// tapers stay intact. Key sessions on `ignored` dates (missed or being
+    /// moved away) don't block their neighbouring days.
+    fn find_slot(
+        &self,
+        plan: &TrainingPlan,
+        session: &PlannedWorkout,
+        earliest: NaiveDate,
+        latest: NaiveDate,
+        ignored: &[NaiveDate],
+    ) -> Option<(usize, usize)> {
+        let key_dates: Vec<NaiveDate> = plan
+            .weeks
+            .iter()
+            .flat_map(|w| w.workouts.iter())
+            .filter(|w| is_key(w) && !ignored.contains(&w.date))
+            .map(|w| w.date)
+            .collect();
+
+        let mut candidates = Vec::new();
+        for (week_idx, week) in plan.weeks.iter().enumerate() {
+            if week.is_recovery_week || week.workouts.iter().any(|w| w.workout_type == WorkoutType::Race) {
+                continue;
+            }
+            for (workout_idx, workout) in week.workouts.iter().enumerate() {
+                let near_key = key_dates.iter().any(|d| (*d - workout.date).num_days().abs() <= 1);
+                if workout.date >= earliest
+                    && workout.date <= latest
+                    && workout.sport == session.sport
+                    && is_easy(workout)
+                    && !near_key
+                {
+                    candidates.push((workout.date, week_idx, workout_idx));
+                }
+            }
+        }
+
+        candidates.into_iter().min().map(|(_, week_idx, workout_idx)| (week_idx, workout_idx))
+    }
+
+    /// Scale future weeks so projected CTL rises no faster than the cap
+    ///
+    /// CTL is linear in each day's TSS, so the scale factor for a week follows
+    /// from projecting the week once without and once with its scalable load.
+    /// Recovery weeks and races are never scaled.
+    fn cap_ctl_ramp(
+        &self,
+        plan: &mut TrainingPlan,
+        current_ctl: Decimal,
+        athlete: &AthleteProfile,
+        as_of: NaiveDate,
+        changes: &mut Vec<PlanChange>,
+    ) -> Vec<(u32, Decimal)> {
+        let mut ctl = current_ctl;
+        let mut projected = Vec::new();
+
+        for week in plan.weeks.iter_mut().filter(|w| w.end_date > as_of) {
+            let first_day = week.start_date.max(as_of + Duration::days(1));
+            let days: Vec<NaiveDate> = (0..=(week.end_date - first_day).num_days()).map(|d| first_day + Duration::days(d)).collect();
+
+            let daily = |scalable: bool| -> Vec<Decimal> {
+                days.iter()
+                    .map(|day| {
+                        week.workouts
+                            .iter()
+                            .filter(|w| w.date == *day && (w.workout_type != WorkoutType::Race) == scalable)
+                            .map(|w| w.planned_tss)
+                            .sum()
+                    })
+                    .collect()
+            };
+            let fixed = daily(false);
+            let scalable = daily(true);
+            let full: Vec<Decimal> = fixed.iter().zip(&scalable).map(|(f, s)| f + s).collect();
+
+            let base_end = project_ctl(ctl, &fixed);
+            let full_end = project_ctl(ctl, &full);
+            let cap_end = ctl + self.config.max_ctl_ramp * Decimal::from(days.len() as u32) / dec!(7);
+
+            if !week.is_recovery_week && full_end > cap_end && full_end > base_end {
+                let scale = ((cap_end - base_end) / (full_end - base_end)).clamp(self.config.min_scale, dec!(1));
+
+                for workout in week.workouts.iter_mut().filter(|w| w.date >= first_day && w.workout_type != WorkoutType::Race) {
+                    if let Some(change) = scale_workout(workout, scale, athlete) {
+                        changes.push(change);
+                    }
+                }
+            }
+
+            let actual: Vec<Decimal> = days
+                .iter()
+                .map(|day| week.workouts.iter().filter(|w| w.date == *day).map(|w| w.planned_tss).sum())
+                .collect();
+            ctl = project_ctl(ctl, &actual);
+            projected.push((week.week_number, ctl.round_dp(1)));
+        }
+
+        projected
+    }
+}
+
+/// Shorten a session by `scale`, returning the change when its load dropped
+fn scale_workout(workout: &mut PlannedWorkout, scale: Decimal, athlete: &AthleteProfile) -> Option<PlanChange> {
+    let minutes = (Decimal::from(workout.planned_duration_minutes) * scale).round().to_u32().unwrap_or(20).max(20);
+    if minutes >= workout.planned_duration_minutes {
+        return None;
+    }
+    let mut scaled = workout.resized(minutes, athlete);
+    scaled.sequence += 1;
+    // Templates have a minimum length, so short sessions may not shrink
+    if scaled.planned_tss >= workout.planned_tss {
+        return None;
+    }
+
+    let change = PlanChange::Scaled {
+        date: workout.date,
+        description: workout.label().to_string(),
+        previous_minutes: workout.planned_duration_minutes,
+        new_minutes: scaled.planned_duration_minutes,
+        previous_tss: workout.planned_tss,
+        new_tss: scaled.planned_tss,
+    };
+    *workout = scaled;
+    Some(change)
+}
+
+/// Intensity sessions and long sessions carry the training stimulus of a week
+fn is_key(workout: &PlannedWorkout) -> bool {
+    matches!(
+        workout.workout_type,
+        WorkoutType::Interval | WorkoutType::Tempo | WorkoutType::Threshold | WorkoutType::VO2Max | WorkoutType::Test
+    ) || workout.planned_duration_minutes >= 120
+}
+
+fn is_easy(workout: &PlannedWorkout) -> bool {
+    matches!(workout.workout_type, WorkoutType::Recovery | WorkoutType::Endurance) && !is_key(workout)
+}
+
+/// (week, workout) indices of matching sessions in plan order
+fn locate(plan: &TrainingPlan, predicate: impl Fn(&PlannedWorkout) -> bool) -> Vec<(usize, usize)> {
+    plan.weeks
+        .iter()
+        .enumerate()
+        .flat_map(|(week_idx, week)| {
+            week.workouts
+                .iter()
+                .enumerate()
+                .filter(|(_, w)| predicate(w))
+                .map(move |(workout_idx, _)| (week_idx, workout_idx))
+        })
+        .collect::<Vec<_>>()
+}
+
+/// Replace a session, moving it to the week containing its new date if needed
+fn move_workout(plan: &mut TrainingPlan, week_idx: usize, workout_idx: usize, workout: PlannedWorkout) {
+    let week = &plan.weeks[week_idx];
+    if week.start_date <= workout.date && workout.date <= week.end_date {
+        plan.weeks[week_idx].workouts[workout_idx] = workout;
+        return;
+    }
+
+    plan.weeks[week_idx].workouts.remove(workout_idx);
+    if let Some(target) = plan.weeks.iter_mut().find(|w| w.start_date <= workout.date && workout.date <= w.end_date) {
+        target.workouts.push(workout);
+    }
+}
+
+/// CTL after a run of daily TSS values, using the standard 42-day constant
+fn project_ctl(start: Decimal, daily_tss: &[Decimal]) -> Decimal {
+    daily_tss.iter().fold(start, |ctl, tss| ctl + (tss - ctl) / dec!(42))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Sport;
+    use crate::structured_workout::{StepDuration, StepKind, StepTarget, StructuredWorkout, WorkoutStep};
+    use crate::training_plan::{PeriodizationModel, RecoveryPattern, TrainingGoal, TrainingWeek};
+    use std::collections::HashMap;
+
+    fn athlete() -> AthleteProfile {
+        crate::export::planned::tests::athlete()
+    }
+
+    fn threshold(date: NaiveDate, athlete: &AthleteProfile) -> PlannedWorkout {
+        let session = StructuredWorkout::new("Threshold", Sport::Cycling)
+            .step(StepKind::Warmup, StepDuration::minutes(15), StepTarget::PercentFtp { low: 50, high: 65 })
+            .repeat(3, vec![
+                WorkoutStep::new(StepKind::Interval, StepDuration::minutes(10), StepTarget::PercentFtp { low: 95, high: 105 }),
+                WorkoutStep::new(StepKind::Recovery, StepDuration::minutes(5), StepTarget::PercentFtp { low: 50, high: 60 }),
+            ])
+            .step(StepKind::Cooldown, StepDuration::minutes(10), StepTarget::PercentFtp { low: 50, high: 60 });
+        PlannedWorkout::from_structure(date, WorkoutType::Threshold, "Threshold intervals", session, athlete)
+    }
+
+    fn week(number: u32, start: NaiveDate, recovery: bool, athlete: &AthleteProfile) -> TrainingWeek {
+        let day = |d: i64| start + Duration::days(d);
+        let easy = |d: i64| TrainingPlanGenerator::easy_workout(day(d), &Sport::Cycling, 60, "Easy ride", athlete);
+        let workouts = if recovery {
+            vec![easy(1), easy(3), easy(5)]
+        } else {
+            vec![threshold(day(1), athlete), easy(2), threshold(day(3), athlete), easy(4), easy(5)]
+        };
+        let planned_tss = workouts.iter().map(|w| w.planned_tss).sum();
+        TrainingWeek {
+            week_number: number,
+            start_date: start,
+            end_date: day(6),
+            phase: "Build".to_string(),
+            focus: "Threshold".to_string(),
+            planned_tss,
+            planned_hours: dec!(5),
+            is_recovery_week: recovery,
+            workouts,
+        }
+    }
+
+    fn plan(athlete: &AthleteProfile) -> TrainingPlan {
+        let start = NaiveDate::from_ymd_opt(2025, 3, 3).unwrap();
+        let weeks = vec![
+            week(1, start, false, athlete),
+            week(2, start + Duration::days(7), false, athlete),
+            week(3, start + Duration::days(14), true, athlete),
+        ];
+        let total = weeks.iter().map(|w| w.planned_tss).sum();
+        TrainingPlan {
+            id: "plan_test".to_string(),
+            name: "Test".to_string(),
+            goal: TrainingGoal::Century,
+            periodization_model: PeriodizationModel::Traditional,
+            recovery_pattern: RecoveryPattern::ThreeToOne,
+            start_date: start,
+            target_date: None,
+            total_weeks: 3,
+            weeks,
+            total_planned_tss: total,
+            total_planned_hours: dec!(15),
+            target_distribution: None,
+            created_at: chrono::Utc::now(),
+        }
+    }
+
+    fn monitoring(plan: &TrainingPlan, missed: &[NaiveDate]) -> PlanMonitoring {
+        PlanMonitoring {
+            plan_id: "plan_test".to_string(),
+            current_week: 1,
+            actual_vs_planned_tss: HashMap::new(),
+            completion_rate: dec!(0),
+            session_links: Vec::new(),
+            missed_sessions: plan
+                .sessions()
+                .into_iter()
+                .filter(|(key, _)| missed.contains(&key.date))
+                .map(|(key, _)| key)
+                .collect(),
+            average_compliance: None,
+            distribution_compliance: None,
+            adjustments_needed: Vec::new(),
+            recovery_recommendations: Vec::new(),
+        }
+    }
+
+    fn metrics(ctl: Decimal, tsb: Decimal) -> PmcMetrics {
+        PmcMetrics {
+            date: NaiveDate::from_ymd_opt(2025, 3, 4).unwrap(),
+            ctl,
+            atl: ctl - tsb,
+            tsb,
+            daily_tss: dec!(0),
+            ctl_ramp_rate: None,
+            atl_spike: false,
+        }
+    }
+
+    #[test]
+    fn test_missed_key_session_moves_to_easy_day() {
+        let athlete = athlete();
+        let mut plan = plan(&athlete);
+        let tuesday = plan.start_date + Duration::days(1);
+        let session_id = plan.workouts_on(tuesday)[0].id.clone();
+
+        let progress = monitoring(&plan, &[tuesday]);
+        let diff = PlanRescheduler::new().reschedule(&mut plan, &progress, None, None, &athlete, tuesday);
+
+        // Wednesday neighbours Thursday's threshold, so Saturday is the first free easy day
+        let saturday = plan.start_date + Duration::days(5);
+        assert_eq!(diff.changes[0], PlanChange::Moved {
+            from: tuesday,
+            to: saturday,
+            description: "Threshold intervals".to_string(),
+            reason: "missed".to_string(),
+        });
+        assert!(matches!(diff.changes[1], PlanChange::Removed { date, .. } if date == saturday));
+        assert!(plan.workouts_on(tuesday).is_empty());
+        assert_eq!(plan.workouts_on(saturday)[0].workout_type, WorkoutType::Threshold);
+        // Same session, next revision, so calendars move the existing event
+        assert_eq!(plan.workouts_on(saturday)[0].id, session_id);
+        assert_eq!(plan.workouts_on(saturday)[0].sequence, 1);
+        assert_eq!(plan.weeks[0].workouts.len(), 4);
+    }
+
+    #[test]
+    fn test_extended_break_keeps_one_session_and_skips_recovery_week() {
+        let athlete = athlete();
+        let mut plan = plan(&athlete);
+        let start = plan.start_date;
+        let day = |d: i64| start + Duration::days(d);
+        let missed = [day(8), day(9), day(10)];
+
+        let progress = monitoring(&plan, &missed);
+        let diff = PlanRescheduler::new().reschedule(&mut plan, &progress, None, None, &athlete, day(10));
+
+        let moved = diff.changes.iter().filter(|c| matches!(c, PlanChange::Moved { .. })).count();
+        let dropped = diff.changes.iter().filter(|c| matches!(c, PlanChange::Dropped { .. })).count();
+        assert_eq!(moved, 1);
+        assert_eq!(dropped, 1);
+        assert_eq!(plan.workouts_on(day(11))[0].workout_type, WorkoutType::Threshold);
+        // The recovery week keeps only easy sessions
+        assert!(plan.weeks[2].workouts.iter().all(|w| w.workout_type == WorkoutType::Recovery));
+    }
+
+    #[test]
+    fn test_low_readiness_defers_next_key_session() {
+        let athlete = athlete();
+        let mut plan = plan(&athlete);
+        let monday = plan.start_date;
+
+        let progress = monitoring(&plan, &[]);
+        let diff = PlanRescheduler::new().reschedule(&mut plan, &progress, Some(&metrics(dec!(60), dec!(-35))), None, &athlete, monday);
+
+        let tuesday = monday + Duration::days(1);
+        assert!(diff.changes.iter().any(|c| matches!(c, PlanChange::Moved { from, reason, .. } if *from == tuesday && reason == "low readiness")));
+        assert_ne!(plan.workouts_on(tuesday)[0].workout_type, WorkoutType::Threshold);
+    }
+
+    #[test]
+    fn test_ramp_cap_scales_weeks_from_low_ctl() {
+        let athlete = athlete();
+        let mut plan = plan(&athlete);
+        let sunday = plan.start_date - Duration::days(1);
+        let previous_tss = plan.total_planned_tss;
+
+        let rescheduler = PlanRescheduler::with_config(RescheduleConfig { max_ctl_ramp: dec!(2), ..Default::default() });
+        let progress = monitoring(&plan, &[]);
+        let diff = rescheduler.reschedule(&mut plan, &progress, Some(&metrics(dec!(10), dec!(0))), None, &athlete, sunday);
+
+        assert!(diff.changes.iter().any(|c| matches!(c, PlanChange::Scaled { .. })));
+        assert!(diff.new_tss < previous_tss);
+        assert_eq!(plan.total_planned_tss, diff.new_tss);
+        let (week, ctl) = diff.projected_ctl[0];
+        assert_eq!(week, 1);
+        assert!(ctl > dec!(10) && ctl <= dec!(12.5), "projected CTL {}", ctl);
+
+        // A fit athlete keeps the plan untouched
+        let mut fit_plan = self::plan(&athlete);
+        let diff = rescheduler.reschedule(&mut fit_plan, &monitoring(&plan, &[]), Some(&metrics(dec!(80), dec!(0))), None, &athlete, sunday);
+        assert!(diff.is_empty());
+    }
+
+    #[test]
+    fn test_overshoot_trims_the_coming_days() {
+        let athlete = athlete();
+        let mut plan = plan(&athlete);
+        let tuesday = plan.start_date + Duration::days(1);
+        let planned = plan.workouts_on(tuesday)[0].planned_tss;
+        let upcoming = |plan: &TrainingPlan| -> Decimal {
+            (2..=8).flat_map(|d| plan.workouts_on(plan.start_date + Duration::days(d))).map(|w| w.planned_tss).sum()
+        };
+        let before = upcoming(&plan);
+
+        // On plan: nothing changes
+        let mut on_plan = monitoring(&plan, &[]);
+        on_plan.actual_vs_planned_tss.insert(tuesday, (planned * dec!(1.1), planned));
+        let diff = PlanRescheduler::new().reschedule(&mut plan, &on_plan, None, None, &athlete, tuesday);
+        assert!(diff.is_empty());
+
+        // Doubled the session: the next days carry the excess less
+        let mut overdone = monitoring(&plan, &[]);
+        overdone.actual_vs_planned_tss.insert(tuesday, (planned * dec!(2), planned));
+        let diff = PlanRescheduler::new().reschedule(&mut plan, &overdone, None, None, &athlete, tuesday);
+
+        assert!(diff.changes.iter().all(|c| matches!(c, PlanChange::Scaled { date, .. } if *date > tuesday)));
+        let reduction = before - upcoming(&plan);
+        assert!(reduction > planned * dec!(0.7) && reduction < planned * dec!(1.3), "reduced by {} for {} excess", reduction, planned);
+        assert!(plan.workouts_on(tuesday)[0].planned_tss == planned);
+    }
+}
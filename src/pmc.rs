@@ -1,6 +1,6 @@
 #![allow(dead_code)]
 
-use crate::models::Workout;
+use crate::models::{LocalTimezone, Workout};
 use chrono::NaiveDate;
 use rust_decimal::prelude::*;
 use rust_decimal::Decimal;
@@ -198,13 +198,25 @@ impl PmcCalculator {
 
     /// Aggregate daily TSS from a collection of workouts
     pub fn aggregate_daily_tss(&self, workouts: &[Workout]) -> BTreeMap<NaiveDate, DailyTss> {
+        self.aggregate_local_daily_tss(workouts, None)
+    }
+
+    /// Aggregate daily TSS by the local training day in `timezone`
+    ///
+    /// Without a timezone, workouts count on the local day they were recorded.
+    pub fn aggregate_local_daily_tss(
+        &self,
+        workouts: &[Workout],
+        timezone: Option<LocalTimezone>,
+    ) -> BTreeMap<NaiveDate, DailyTss> {
         let mut daily_tss: BTreeMap<NaiveDate, DailyTss> = BTreeMap::new();
 
         for workout in workouts {
             let tss = workout.summary.tss.unwrap_or(Decimal::ZERO);
+            let date = workout.local_date(timezone);
 
             daily_tss
-                .entry(workout.date)
+                .entry(date)
                 .and_modify(|day| {
                     day.total_tss += tss;
                     day.workout_count += 1;
@@ -212,7 +224,7 @@ impl PmcCalculator {
                     day.has_workouts = true;
                 })
                 .or_insert(DailyTss {
-                    date: workout.date,
+                    date,
                     total_tss: tss,
                     workout_count: 1,
                     has_workouts: true,
@@ -236,17 +248,61 @@ impl PmcCalculator {
             ));
         }
 
-        let mut pmc_series = Vec::new();
-        let mut current_ctl = Decimal::ZERO;
-        let mut current_atl = Decimal::ZERO;
-
         // Calculate the date range ensuring we have enough data
         let calculation_start = start_date
             .checked_sub_days(chrono::Days::new(self.config.ctl_time_constant as u64))
             .unwrap_or(start_date);
 
+        Ok(self.extend_series(&[], calculation_start, start_date, end_date, daily_tss))
+    }
+
+    /// Continue a stored PMC series from `start_date` to `end_date`
+    ///
+    /// `history` holds the metrics up to the day before `start_date`; CTL and ATL
+    /// carry on from its last entry so only the new days are calculated. Ramp rate
+    /// and ATL spikes look back into the history as they would in a full series.
+    pub fn continue_pmc_series(
+        &self,
+        history: &[PmcMetrics],
+        daily_tss: &BTreeMap<NaiveDate, DailyTss>,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Result<Vec<PmcMetrics>, PmcError> {
+        let Some(last) = history.last() else {
+            return self.calculate_pmc_series(daily_tss, start_date, end_date);
+        };
+
+        if start_date > end_date {
+            return Err(PmcError::InvalidDateRange(
+                "Start date must be before end date".to_string(),
+            ));
+        }
+        if last.date.succ_opt() != Some(start_date) {
+            return Err(PmcError::InvalidDateRange(format!(
+                "History ends on {} but the series continues from {}",
+                last.date, start_date
+            )));
+        }
+
+        Ok(self.extend_series(history, start_date, start_date, end_date, daily_tss))
+    }
+
+    /// Run the CTL/ATL recursion from `calculation_start`, seeded from `history`,
+    /// returning the metrics from `start_date` on
+    fn extend_series(
+        &self,
+        history: &[PmcMetrics],
+        calculation_start: NaiveDate,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+        daily_tss: &BTreeMap<NaiveDate, DailyTss>,
+    ) -> Vec<PmcMetrics> {
+        let mut pmc_series = history.to_vec();
+        let mut current_ctl = history.last().map_or(Decimal::ZERO, |m| m.ctl);
+        let mut current_atl = history.last().map_or(Decimal::ZERO, |m| m.atl);
+
         let mut current_date = calculation_start;
-        let mut ctl_history = Vec::new();
+        let mut ctl_history: Vec<Decimal> = history.iter().map(|m| m.ctl).collect();
 
         while current_date <= end_date {
             let daily_tss_value = daily_tss
@@ -293,7 +349,7 @@ impl PmcCalculator {
             current_date = current_date.succ_opt().unwrap();
         }
 
-        Ok(pmc_series)
+        pmc_series.split_off(history.len())
     }
 
     /// Calculate CTL ramp rate (change per week)
@@ -462,7 +518,7 @@ impl Default for PmcCalculator {
 mod tests {
     use super::*;
     use crate::models::{DataSource, Sport, WorkoutSummary, WorkoutType};
-    use chrono::NaiveDate;
+    use chrono::{FixedOffset, NaiveDate};
     use rust_decimal_macros::dec;
 
     fn create_test_workout(date: NaiveDate, tss: Decimal) -> Workout {
@@ -481,9 +537,53 @@ mod tests {
             notes: None,
             athlete_id: Some("test_athlete".to_string()),
             source: None,
+            swim_lengths: Vec::new(),
+            start_time: None,
+            laps: Vec::new(),
+            rpe: None,
+            strength_sets: Vec::new(),
         }
     }
 
+    #[test]
+    fn test_daily_tss_uses_local_training_day() {
+        let calculator = PmcCalculator::new();
+        let date = NaiveDate::from_ymd_opt(2024, 9, 23).unwrap();
+
+        // 23:30 in New York is already the next day in UTC
+        let mut late_ride = create_test_workout(date, dec!(60));
+        late_ride.start_time = chrono::DateTime::parse_from_rfc3339("2024-09-23T23:30:00-04:00").ok();
+
+        let recorded = calculator.aggregate_daily_tss(std::slice::from_ref(&late_ride));
+        assert!(recorded.contains_key(&date));
+
+        let utc = calculator.aggregate_local_daily_tss(&[late_ride], FixedOffset::east_opt(0).map(LocalTimezone::Fixed));
+        assert!(utc.contains_key(&date.succ_opt().unwrap()));
+        assert!(!utc.contains_key(&date));
+    }
+
+    #[test]
+    fn test_daily_tss_follows_daylight_saving_time() {
+        let calculator = PmcCalculator::new();
+        let berlin = Some(LocalTimezone::Named(chrono_tz::Europe::Berlin));
+        let winter_offset = FixedOffset::east_opt(3600).map(LocalTimezone::Fixed);
+
+        // 22:30 UTC is 23:30 in Berlin in winter but 00:30 the next day in summer
+        let mut winter = create_test_workout(NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(), dec!(50));
+        winter.start_time = chrono::DateTime::parse_from_rfc3339("2024-01-15T22:30:00Z").ok();
+        let mut summer = create_test_workout(NaiveDate::from_ymd_opt(2024, 7, 15).unwrap(), dec!(50));
+        summer.start_time = chrono::DateTime::parse_from_rfc3339("2024-07-15T22:30:00Z").ok();
+        let workouts = [winter, summer];
+
+        let daily = calculator.aggregate_local_daily_tss(&workouts, berlin);
+        assert!(daily.contains_key(&NaiveDate::from_ymd_opt(2024, 1, 15).unwrap()));
+        assert!(daily.contains_key(&NaiveDate::from_ymd_opt(2024, 7, 16).unwrap()));
+
+        // A fixed offset misses the summer shift
+        let fixed = calculator.aggregate_local_daily_tss(&workouts, winter_offset);
+        assert!(fixed.contains_key(&NaiveDate::from_ymd_opt(2024, 7, 15).unwrap()));
+    }
+
     #[test]
     fn test_daily_tss_aggregation() {
         let calculator = PmcCalculator::new();
@@ -528,6 +628,30 @@ mod tests {
         assert!(pmc_series[pmc_series.len() - 1].ctl > pmc_series[0].ctl);
     }
 
+    #[test]
+    fn test_continued_series_matches_full_calculation() {
+        let calculator = PmcCalculator::new();
+        let start_date = NaiveDate::from_ymd_opt(2024, 9, 1).unwrap();
+        let end_date = NaiveDate::from_ymd_opt(2024, 10, 15).unwrap();
+
+        let workouts: Vec<Workout> = (0..45)
+            .filter(|day| day % 4 != 3)
+            .map(|day| create_test_workout(start_date + chrono::Duration::days(day), Decimal::from(60 + day)))
+            .collect();
+        let daily_tss = calculator.aggregate_daily_tss(&workouts);
+        let full = calculator.calculate_pmc_series(&daily_tss, start_date, end_date).unwrap();
+
+        // Stored history up to Sep 30, then only October is calculated
+        let split = NaiveDate::from_ymd_opt(2024, 10, 1).unwrap();
+        let (history, expected) = full.split_at(30);
+        let continued = calculator.continue_pmc_series(history, &daily_tss, split, end_date).unwrap();
+        assert_eq!(continued, expected);
+
+        // A gap between the history and the new days is rejected
+        let gap = calculator.continue_pmc_series(history, &daily_tss, split.succ_opt().unwrap(), end_date);
+        assert!(matches!(gap, Err(PmcError::InvalidDateRange(_))));
+    }
+
     #[test]
     fn test_atl_calculation() {
         let calculator = PmcCalculator::new();
@@ -759,6 +883,11 @@ mod tests {
             notes: None,
             athlete_id: Some("test_athlete".to_string()),
             source: None,
+            swim_lengths: Vec::new(),
+            start_time: None,
+            laps: Vec::new(),
+            rpe: None,
+            strength_sets: Vec::new(),
         };
 
         let daily_tss = calculator.aggregate_daily_tss(&[workout]);
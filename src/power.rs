@@ -491,6 +491,23 @@ impl PowerAnalyzer {
         }
     }
 
+    /// Fit a 2-parameter CP model from maximal efforts such as time trials or races
+    ///
+    /// Each effort is (duration in seconds, average power); at least two efforts
+    /// of different duration are required.
+    pub fn fit_critical_power_from_efforts(
+        efforts: &[(u32, u16)],
+        test_dates: Vec<NaiveDate>,
+    ) -> Result<CriticalPowerModel> {
+        if efforts.len() < 2 {
+            return Err(PowerError::InsufficientData(
+                "At least two efforts are needed for a CP model".to_string(),
+            ).into());
+        }
+
+        Self::fit_two_parameter_model(efforts, test_dates)
+    }
+
     /// Fit 2-parameter CP model: P = CP + W'/t
     fn fit_two_parameter_model(points: &[(u32, u16)], test_dates: Vec<NaiveDate>) -> Result<CriticalPowerModel> {
         // Using linear regression on P vs 1/t
@@ -513,8 +530,10 @@ impl PowerAnalyzer {
         }
 
         // Calculate CP and W' using least squares
+        // 1/t values are small, so compare against the scale of the inputs
+        // rather than an absolute tolerance
         let denominator = n * sum_xx - sum_x * sum_x;
-        if denominator.abs() < 0.0001 {
+        if denominator.abs() <= f64::EPSILON * n * sum_xx {
             return Err(anyhow!("Cannot fit model: singular matrix"));
         }
 
@@ -917,6 +936,9 @@ mod tests {
                 stroke_type: None,
                 lap_number: None,
                 sport_transition: None,
+                temperature: None,
+                drive_length: None,
+                stroke_work: None,
             });
         }
         data
@@ -1138,6 +1160,9 @@ mod tests {
                     stroke_type: None,
                     lap_number: None,
                     sport_transition: None,
+                    temperature: None,
+                    drive_length: None,
+                    stroke_work: None,
                 }
             }).collect();
 
@@ -1153,6 +1178,11 @@ mod tests {
                 notes: None,
                 athlete_id: None,
                 source: None,
+                swim_lengths: Vec::new(),
+                start_time: None,
+                laps: Vec::new(),
+                rpe: None,
+                strength_sets: Vec::new(),
             });
         }
 
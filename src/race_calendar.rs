@@ -0,0 +1,324 @@
+//! Season race calendar
+//!
+//! Races carry an A/B/C priority that drives season planning:
+//! - A: season goal races, each gets its own build and full taper
+//! - B: important races, trained through with a short mini-taper
+//! - C: training races, no taper
+//!
+//! Completed races are linked to the imported workout so race results can
+//! feed VDOT (running) and Critical Power (cycling) estimation.
+
+use anyhow::Result;
+use chrono::NaiveDate;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+
+use crate::models::{Sport, Workout};
+use crate::power::{CriticalPowerModel, PowerAnalyzer};
+use crate::running::{PerformancePrediction, RunningAnalyzer};
+
+/// Race priority
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum RacePriority {
+    /// Season goal race with a full taper
+    A,
+    /// Important race with a mini-taper
+    B,
+    /// Training race without taper
+    C,
+}
+
+impl std::str::FromStr for RacePriority {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_uppercase().as_str() {
+            "A" => Ok(Self::A),
+            "B" => Ok(Self::B),
+            "C" => Ok(Self::C),
+            _ => anyhow::bail!("Unknown race priority: {} (use A, B or C)", s),
+        }
+    }
+}
+
+impl std::fmt::Display for RacePriority {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::A => "A",
+            Self::B => "B",
+            Self::C => "C",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl RacePriority {
+    /// Days of reduced load before the race
+    pub fn taper_days(&self) -> u32 {
+        match self {
+            Self::A => 14,
+            Self::B => 4,
+            Self::C => 0,
+        }
+    }
+}
+
+/// A race on the season calendar
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Race {
+    pub id: String,
+    pub name: String,
+    pub date: NaiveDate,
+    pub sport: Sport,
+    /// Race distance in meters
+    pub distance_meters: Option<Decimal>,
+    pub priority: RacePriority,
+    /// Goal finishing time in seconds
+    pub goal_time_seconds: Option<u32>,
+    /// Imported workout recording the race, once completed
+    pub workout_id: Option<String>,
+    /// Actual finishing time in seconds, once completed
+    pub result_time_seconds: Option<u32>,
+    pub notes: Option<String>,
+}
+
+impl Race {
+    /// Create a race with a generated ID
+    pub fn new(name: String, date: NaiveDate, sport: Sport, priority: RacePriority) -> Self {
+        Race {
+            id: uuid::Uuid::new_v4().to_string(),
+            name,
+            date,
+            sport,
+            distance_meters: None,
+            priority,
+            goal_time_seconds: None,
+            workout_id: None,
+            result_time_seconds: None,
+            notes: None,
+        }
+    }
+
+    /// Whether the race has been linked to a completed workout
+    pub fn is_completed(&self) -> bool {
+        self.workout_id.is_some()
+    }
+
+    /// Link the workout that recorded this race
+    ///
+    /// The workout duration is used as result time unless one was already entered.
+    pub fn link_workout(&mut self, workout: &Workout) {
+        self.workout_id = Some(workout.id.clone());
+        if self.result_time_seconds.is_none() {
+            self.result_time_seconds = Some(workout.duration_seconds);
+        }
+        if self.distance_meters.is_none() {
+            self.distance_meters = workout.summary.total_distance;
+        }
+    }
+
+    /// Expected race duration in minutes, from the goal time or a typical pace
+    pub fn expected_duration_minutes(&self) -> u32 {
+        if let Some(seconds) = self.goal_time_seconds {
+            return (seconds / 60).max(1);
+        }
+
+        let km = self
+            .distance_meters
+            .and_then(|d| (d / dec!(1000)).to_f64())
+            .unwrap_or(0.0);
+        // Typical age-group speeds in km/h
+        let speed = match self.sport {
+            Sport::Running => 11.0,
+            Sport::Cycling => 32.0,
+            Sport::Swimming => 3.0,
+            Sport::Triathlon => 20.0,
+            Sport::Rowing => 12.0,
+            Sport::CrossTraining => 10.0,
+        };
+
+        if km > 0.0 {
+            ((km / speed) * 60.0).round().max(1.0) as u32
+        } else {
+            60
+        }
+    }
+}
+
+/// Collection of races for a season
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RaceCalendar {
+    pub races: Vec<Race>,
+}
+
+impl RaceCalendar {
+    pub fn new(mut races: Vec<Race>) -> Self {
+        races.sort_by_key(|r| r.date);
+        RaceCalendar { races }
+    }
+
+    /// Add a race, keeping the calendar in date order
+    pub fn add(&mut self, race: Race) {
+        self.races.push(race);
+        self.races.sort_by_key(|r| r.date);
+    }
+
+    /// Races on or after a date
+    pub fn upcoming(&self, from: NaiveDate) -> Vec<&Race> {
+        self.races.iter().filter(|r| r.date >= from).collect()
+    }
+
+    /// Races of a priority on or after a date
+    pub fn with_priority(&self, priority: RacePriority, from: NaiveDate) -> Vec<&Race> {
+        self.races
+            .iter()
+            .filter(|r| r.priority == priority && r.date >= from)
+            .collect()
+    }
+
+    /// Find the imported workout that recorded a race
+    ///
+    /// Candidates are workouts of the race sport on the race date; when the race
+    /// distance is known the closest distance wins, otherwise the longest workout.
+    pub fn find_race_workout<'a>(race: &Race, workouts: &'a [Workout]) -> Option<&'a Workout> {
+        let candidates = workouts
+            .iter()
+            .filter(|w| w.date == race.date && w.sport == race.sport);
+
+        match race.distance_meters {
+            Some(distance) => candidates
+                .filter(|w| w.summary.total_distance.is_some())
+                .min_by_key(|w| (w.summary.total_distance.unwrap() - distance).abs()),
+            None => candidates.max_by_key(|w| w.duration_seconds),
+        }
+    }
+
+    /// VDOT and race predictions from the most recent completed running race
+    pub fn latest_running_performance(&self) -> Option<(&Race, PerformancePrediction)> {
+        self.races
+            .iter()
+            .rev()
+            .filter(|r| r.sport == Sport::Running)
+            .find_map(|race| {
+                let seconds = race.result_time_seconds?;
+                let distance_km = race.distance_meters? / dec!(1000);
+                RunningAnalyzer::predict_performance(Decimal::from(seconds) / dec!(60), distance_km)
+                    .ok()
+                    .map(|prediction| (race, prediction))
+            })
+    }
+
+    /// Critical Power model from completed cycling races
+    ///
+    /// Each linked race contributes its duration and average power; at least two
+    /// races of different duration are needed.
+    pub fn critical_power_from_races(&self, workouts: &[Workout]) -> Option<CriticalPowerModel> {
+        let efforts: Vec<(u32, u16, NaiveDate)> = self
+            .races
+            .iter()
+            .filter(|r| r.sport == Sport::Cycling)
+            .filter_map(|race| {
+                let workout = workouts.iter().find(|w| Some(&w.id) == race.workout_id.as_ref())?;
+                let power = workout.summary.avg_power?;
+                let duration = race.result_time_seconds.unwrap_or(workout.duration_seconds);
+                Some((duration, power, race.date))
+            })
+            .collect();
+
+        let points: Vec<(u32, u16)> = efforts.iter().map(|(t, p, _)| (*t, *p)).collect();
+        let dates = efforts.iter().map(|(_, _, d)| *d).collect();
+        PowerAnalyzer::fit_critical_power_from_efforts(&points, dates).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{DataSource, WorkoutSummary, WorkoutType};
+
+    fn workout(id: &str, date: NaiveDate, sport: Sport, duration: u32, distance: Option<Decimal>, power: Option<u16>) -> Workout {
+        Workout {
+            id: id.to_string(),
+            date,
+            sport,
+            duration_seconds: duration,
+            workout_type: WorkoutType::Race,
+            data_source: DataSource::HeartRate,
+            raw_data: None,
+            summary: WorkoutSummary {
+                total_distance: distance,
+                avg_power: power,
+                ..WorkoutSummary::default()
+            },
+            notes: None,
+            athlete_id: None,
+            source: None,
+            swim_lengths: Vec::new(),
+            start_time: None,
+            laps: Vec::new(),
+            rpe: None,
+            strength_sets: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_priority_parsing() {
+        assert_eq!("a".parse::<RacePriority>().unwrap(), RacePriority::A);
+        assert_eq!("B".parse::<RacePriority>().unwrap(), RacePriority::B);
+        assert!("D".parse::<RacePriority>().is_err());
+        assert!(RacePriority::A.taper_days() > RacePriority::B.taper_days());
+        assert_eq!(RacePriority::C.taper_days(), 0);
+    }
+
+    #[test]
+    fn test_find_and_link_race_workout() {
+        let date = NaiveDate::from_ymd_opt(2024, 4, 21).unwrap();
+        let mut race = Race::new("Spring 10K".to_string(), date, Sport::Running, RacePriority::B);
+        race.distance_meters = Some(dec!(10000));
+
+        let workouts = vec![
+            workout("warmup", date, Sport::Running, 900, Some(dec!(2500)), None),
+            workout("race", date, Sport::Running, 2400, Some(dec!(10050)), None),
+            workout("ride", date, Sport::Cycling, 3600, Some(dec!(30000)), None),
+        ];
+
+        let found = RaceCalendar::find_race_workout(&race, &workouts).unwrap();
+        assert_eq!(found.id, "race");
+
+        race.link_workout(found);
+        assert!(race.is_completed());
+        assert_eq!(race.result_time_seconds, Some(2400));
+        assert_eq!(race.distance_meters, Some(dec!(10000)));
+    }
+
+    #[test]
+    fn test_completed_races_feed_vdot_and_cp() {
+        let mut calendar = RaceCalendar::default();
+
+        let mut run = Race::new("10K".to_string(), NaiveDate::from_ymd_opt(2024, 5, 1).unwrap(), Sport::Running, RacePriority::A);
+        run.distance_meters = Some(dec!(10000));
+        run.result_time_seconds = Some(2400);
+        run.workout_id = Some("run".to_string());
+        calendar.add(run);
+
+        let mut workouts = Vec::new();
+        for (id, day, duration, power) in [("tt1", 2, 1200, 300), ("tt2", 9, 3600, 270)] {
+            let date = NaiveDate::from_ymd_opt(2024, 6, day).unwrap();
+            let mut race = Race::new(id.to_string(), date, Sport::Cycling, RacePriority::C);
+            let w = workout(id, date, Sport::Cycling, duration, None, Some(power));
+            race.link_workout(&w);
+            calendar.add(race);
+            workouts.push(w);
+        }
+
+        let (race, prediction) = calendar.latest_running_performance().unwrap();
+        assert_eq!(race.name, "10K");
+        assert!(prediction.vdot > dec!(40));
+
+        let cp = calendar.critical_power_from_races(&workouts).unwrap();
+        // P = CP + W'/t through (1200 s, 300 W) and (3600 s, 270 W)
+        assert!((cp.critical_power as i32 - 255).abs() <= 1);
+    }
+}
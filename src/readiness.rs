@@ -0,0 +1,629 @@
+#![allow(dead_code)]
+
+//! Daily readiness pipeline
+//!
+//! Joins the objective and subjective inputs that the recovery module scores in
+//! isolation into one readiness assessment per day:
+//!
+//! - **PMC**: Training Stress Balance and load from `PmcMetrics`
+//! - **Recovery**: HRV, sleep, Body Battery and stress from stored `RecoveryMetrics`
+//! - **Wellness**: a short subjective questionnaire (soreness, mood, stress, sleep quality)
+//! - **ACWR**: workload ratio used to qualify the training decision
+//!
+//! The composite score drives `TrainingDecision::assess` for the day's planned
+//! workout from the active `TrainingPlan`.
+//!
+//! # Sports Science Background
+//!
+//! Subjective wellness questionnaires (McLean et al., 2010; Hooper & Mackinnon, 1995)
+//! respond to accumulated fatigue as quickly as HRV and are cheap to collect daily.
+//! Combining them with objective markers reduces the impact of a single noisy reading.
+
+use crate::acwr::{AcwrConfig, AcwrMethod, AcwrMetrics};
+use crate::pmc::PmcMetrics;
+use crate::recovery::{
+    calculate_training_readiness, EnhancedForm, RecoveryMetrics, RecoveryQuality,
+    TrainingDecision,
+};
+use crate::training_plan::TrainingPlan;
+use chrono::NaiveDate;
+use rust_decimal::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use thiserror::Error;
+
+/// Wellness questionnaire validation errors
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum WellnessValidationError {
+    #[error("{field} must be between 1 and 5, got {value}")]
+    OutOfRange { field: &'static str, value: u8 },
+}
+
+/// Daily subjective wellness questionnaire
+///
+/// Each item is rated 1-5 where **5 is always the best state**:
+///
+/// | Item | 1 | 5 |
+/// |------|---|---|
+/// | soreness | very sore | no soreness |
+/// | mood | very low | very positive |
+/// | stress | very stressed | very relaxed |
+/// | sleep_quality | very poor | excellent |
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WellnessQuestionnaire {
+    /// Date the questionnaire refers to (morning of)
+    pub date: NaiveDate,
+    /// Muscle soreness (1 = very sore, 5 = none)
+    pub soreness: u8,
+    /// Mood (1 = very low, 5 = very positive)
+    pub mood: u8,
+    /// Psychological stress (1 = very stressed, 5 = very relaxed)
+    pub stress: u8,
+    /// Perceived sleep quality (1 = very poor, 5 = excellent)
+    pub sleep_quality: u8,
+    /// Optional free-text notes
+    pub notes: Option<String>,
+}
+
+impl WellnessQuestionnaire {
+    /// Create a validated questionnaire entry
+    pub fn new(
+        date: NaiveDate,
+        soreness: u8,
+        mood: u8,
+        stress: u8,
+        sleep_quality: u8,
+    ) -> Result<Self, WellnessValidationError> {
+        let entry = WellnessQuestionnaire {
+            date,
+            soreness,
+            mood,
+            stress,
+            sleep_quality,
+            notes: None,
+        };
+        entry.validate()?;
+        Ok(entry)
+    }
+
+    /// Validate that every item is within 1-5
+    pub fn validate(&self) -> Result<(), WellnessValidationError> {
+        for (field, value) in self.items() {
+            if !(1..=5).contains(&value) {
+                return Err(WellnessValidationError::OutOfRange { field, value });
+            }
+        }
+        Ok(())
+    }
+
+    /// Wellness score normalized to 0-100 (all 1s = 0, all 5s = 100)
+    pub fn score(&self) -> u8 {
+        let total: u32 = self.items().iter().map(|(_, v)| *v as u32).sum();
+        ((total.saturating_sub(4)) as f64 / 16.0 * 100.0).round() as u8
+    }
+
+    /// Items rated 2 or lower, reported as limiting factors
+    pub fn concerns(&self) -> Vec<String> {
+        self.items()
+            .iter()
+            .filter(|(_, v)| *v <= 2)
+            .map(|(field, v)| format!("Low {} rating ({}/5)", field.replace('_', " "), v))
+            .collect()
+    }
+
+    fn items(&self) -> [(&'static str, u8); 4] {
+        [
+            ("soreness", self.soreness),
+            ("mood", self.mood),
+            ("stress", self.stress),
+            ("sleep_quality", self.sleep_quality),
+        ]
+    }
+}
+
+/// Weights used to blend the readiness components
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReadinessConfig {
+    /// Weight of the objective (PMC + recovery) component (default: 0.7)
+    pub objective_weight: f64,
+    /// Weight of the subjective wellness component (default: 0.3)
+    pub wellness_weight: f64,
+    /// Number of prior days used for the resting HR baseline (default: 7)
+    pub rhr_baseline_days: usize,
+    /// ACWR variant qualifying the training decision (default: `AcwrConfig` method)
+    pub acwr_method: AcwrMethod,
+}
+
+impl Default for ReadinessConfig {
+    fn default() -> Self {
+        ReadinessConfig {
+            objective_weight: 0.7,
+            wellness_weight: 0.3,
+            rhr_baseline_days: 7,
+            acwr_method: AcwrConfig::default().method,
+        }
+    }
+}
+
+/// Readiness assessment for a single day
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DailyReadiness {
+    /// Date of the assessment
+    pub date: NaiveDate,
+    /// Training Stress Balance for the day, if PMC data is available
+    pub tsb: Option<Decimal>,
+    /// ACWR used for the training decision
+    pub acwr: Option<f64>,
+    /// HRV deviation from baseline in percent
+    pub hrv_deviation: Option<f64>,
+    /// Readiness from `RecoveryMetrics::calculate_readiness`
+    pub recovery_readiness: Option<u8>,
+    /// Score from `EnhancedForm::calculate` or `calculate_training_readiness`
+    pub objective_score: Option<u8>,
+    /// Normalized wellness questionnaire score (0-100)
+    pub wellness_score: Option<u8>,
+    /// Weighted composite readiness (0-100)
+    pub composite_score: Option<u8>,
+    /// Recovery quality category for the composite score
+    pub recovery_quality: Option<RecoveryQuality>,
+    /// Planned TSS for the day from the active plan
+    pub planned_tss: Option<Decimal>,
+    /// Decision for the planned workout (None on unplanned days)
+    pub decision: Option<TrainingDecision>,
+    /// Factors that pulled the score down
+    pub limiting_factors: Vec<String>,
+}
+
+/// Engine assembling daily readiness from stored data
+pub struct ReadinessEngine {
+    config: ReadinessConfig,
+}
+
+impl ReadinessEngine {
+    /// Create new readiness engine with default weights
+    pub fn new() -> Self {
+        ReadinessEngine {
+            config: ReadinessConfig::default(),
+        }
+    }
+
+    /// Create new readiness engine with custom weights
+    pub fn with_config(config: ReadinessConfig) -> Self {
+        ReadinessEngine { config }
+    }
+
+    /// Assess readiness for every day in `pmc_series`
+    ///
+    /// Recovery rows, wellness entries and ACWR values are joined by date. Days
+    /// missing a source are scored from the sources that are present.
+    pub fn assess_series(
+        &self,
+        pmc_series: &[PmcMetrics],
+        recovery: &[RecoveryMetrics],
+        wellness: &[WellnessQuestionnaire],
+        acwr: &[AcwrMetrics],
+        plan: Option<&TrainingPlan>,
+    ) -> Vec<DailyReadiness> {
+        let recovery_by_date: HashMap<NaiveDate, &RecoveryMetrics> =
+            recovery.iter().map(|r| (r.date, r)).collect();
+        let wellness_by_date: HashMap<NaiveDate, &WellnessQuestionnaire> =
+            wellness.iter().map(|w| (w.date, w)).collect();
+        let acwr_by_date: HashMap<NaiveDate, f64> = acwr
+            .iter()
+            .filter_map(|a| a.ratio(self.config.acwr_method).map(|r| (a.date, r)))
+            .collect();
+
+        pmc_series
+            .iter()
+            .map(|pmc| {
+                let rhr_baseline = self.rhr_baseline(recovery, pmc.date);
+                self.assess_day(
+                    pmc.date,
+                    Some(pmc),
+                    recovery_by_date.get(&pmc.date).copied(),
+                    wellness_by_date.get(&pmc.date).copied(),
+                    acwr_by_date.get(&pmc.date).copied(),
+                    rhr_baseline,
+                    plan,
+                )
+            })
+            .collect()
+    }
+
+    /// Assess readiness for a single day
+    #[allow(clippy::too_many_arguments)]
+    pub fn assess_day(
+        &self,
+        date: NaiveDate,
+        pmc: Option<&PmcMetrics>,
+        recovery: Option<&RecoveryMetrics>,
+        wellness: Option<&WellnessQuestionnaire>,
+        acwr: Option<f64>,
+        rhr_baseline: Option<u8>,
+        plan: Option<&TrainingPlan>,
+    ) -> DailyReadiness {
+        let mut limiting_factors = Vec::new();
+
+        let tsb = pmc.map(|m| m.tsb);
+        let tsb_i16 = tsb.and_then(|t| t.round().to_i16());
+
+        let hrv_deviation = recovery
+            .and_then(|r| r.hrv_metrics.as_ref())
+            .and_then(|h| match (h.rmssd, h.baseline) {
+                (Some(rmssd), Some(baseline)) if baseline > 0.0 => {
+                    Some((rmssd - baseline) / baseline * 100.0)
+                }
+                _ => None,
+            });
+        let sleep_score = recovery
+            .and_then(|r| r.sleep_data.as_ref())
+            .and_then(|s| s.sleep_score);
+        let rhr_current = recovery
+            .and_then(|r| r.physiological.as_ref())
+            .and_then(|p| p.resting_hr);
+
+        // Recovery readiness: stored score, or recompute from the components
+        let recovery_readiness = recovery.and_then(|r| {
+            r.training_readiness.or_else(|| {
+                let mut scored = r.clone();
+                scored.calculate_readiness();
+                scored.training_readiness
+            })
+        });
+        if let Some(factor) = recovery.and_then(|r| r.limiting_factor()) {
+            limiting_factors.push(format!("{} below normal", factor));
+        }
+
+        // Objective score: the richest formula the available inputs support
+        let objective_score = match (tsb_i16, hrv_deviation) {
+            (Some(tsb), Some(deviation)) => Some(
+                EnhancedForm::calculate(tsb, deviation, sleep_score, rhr_current, rhr_baseline)
+                    .score,
+            ),
+            (None, Some(deviation)) => Some(calculate_training_readiness(
+                deviation,
+                sleep_score,
+                None,
+            )),
+            (Some(tsb), None) => Some(match recovery_readiness {
+                Some(readiness) => {
+                    (readiness as f64 * 0.7 + Self::tsb_score(tsb) * 0.3).round() as u8
+                }
+                None => Self::tsb_score(tsb).round() as u8,
+            }),
+            (None, None) => recovery_readiness,
+        };
+
+        if let Some(tsb) = tsb_i16 {
+            if tsb < -30 {
+                limiting_factors.push(format!("Very negative TSB ({})", tsb));
+            }
+        }
+
+        let wellness_score = wellness.map(|w| w.score());
+        if let Some(w) = wellness {
+            limiting_factors.extend(w.concerns());
+        }
+
+        let composite_score = self.blend(objective_score, wellness_score);
+        let recovery_quality = composite_score.map(RecoveryQuality::from_readiness);
+
+        // Decision for the day's planned workout(s)
+        let planned_tss = plan.and_then(|p| {
+            let workouts = p.workouts_on(date);
+            if workouts.is_empty() {
+                None
+            } else {
+                Some(workouts.iter().map(|w| w.planned_tss).sum::<Decimal>())
+            }
+        });
+        let decision = match (composite_score, planned_tss) {
+            (Some(score), Some(tss)) => Some(TrainingDecision::assess(
+                score,
+                tss.round().to_u16().unwrap_or(0),
+                acwr.unwrap_or(1.0),
+            )),
+            _ => None,
+        };
+
+        DailyReadiness {
+            date,
+            tsb,
+            acwr,
+            hrv_deviation,
+            recovery_readiness,
+            objective_score,
+            wellness_score,
+            composite_score,
+            recovery_quality,
+            planned_tss,
+            decision,
+            limiting_factors,
+        }
+    }
+
+    /// Weighted blend of objective and wellness scores, renormalized for missing inputs
+    fn blend(&self, objective: Option<u8>, wellness: Option<u8>) -> Option<u8> {
+        let mut total = 0.0;
+        let mut weight = 0.0;
+
+        if let Some(score) = objective {
+            total += score as f64 * self.config.objective_weight;
+            weight += self.config.objective_weight;
+        }
+        if let Some(score) = wellness {
+            total += score as f64 * self.config.wellness_weight;
+            weight += self.config.wellness_weight;
+        }
+
+        if weight > 0.0 {
+            Some((total / weight).round().clamp(0.0, 100.0) as u8)
+        } else {
+            None
+        }
+    }
+
+    /// Average resting HR over the days before `date`
+    fn rhr_baseline(&self, recovery: &[RecoveryMetrics], date: NaiveDate) -> Option<u8> {
+        let start = date - chrono::Duration::days(self.config.rhr_baseline_days as i64);
+        let values: Vec<u32> = recovery
+            .iter()
+            .filter(|r| r.date >= start && r.date < date)
+            .filter_map(|r| r.physiological.as_ref().and_then(|p| p.resting_hr))
+            .map(|hr| hr as u32)
+            .collect();
+
+        if values.is_empty() {
+            None
+        } else {
+            Some((values.iter().sum::<u32>() / values.len() as u32) as u8)
+        }
+    }
+
+    /// TSB mapped to 20-100 (TSB +30 = 100, 0 = 60, -30 = 20), as in `EnhancedForm`
+    fn tsb_score(tsb: i16) -> f64 {
+        ((tsb as f64 + 30.0) / 60.0 * 80.0 + 20.0).clamp(20.0, 100.0)
+    }
+}
+
+impl Default for ReadinessEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recovery::{HrvMetrics, RecoveryMetrics, TrainingRecommendation};
+    use crate::training_plan::{
+        PeriodizationModel, PlannedWorkout, RecoveryPattern, TrainingGoal, TrainingWeek,
+    };
+    use crate::models::{Sport, WorkoutType};
+    use rust_decimal_macros::dec;
+
+    fn date() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 9, 23).unwrap()
+    }
+
+    fn pmc(tsb: Decimal) -> PmcMetrics {
+        PmcMetrics {
+            date: date(),
+            ctl: dec!(60),
+            atl: dec!(60) - tsb,
+            tsb,
+            daily_tss: dec!(80),
+            ctl_ramp_rate: None,
+            atl_spike: false,
+        }
+    }
+
+    fn recovery(rmssd: f64, baseline: f64) -> RecoveryMetrics {
+        let mut metrics = RecoveryMetrics::new(date());
+        metrics.hrv_metrics = Some(HrvMetrics {
+            rmssd: Some(rmssd),
+            status: None,
+            baseline: Some(baseline),
+            score: Some(70),
+            measurement_time: None,
+            measurement_context: None,
+        });
+        metrics
+    }
+
+    fn plan_with_workout(tss: Decimal) -> TrainingPlan {
+        TrainingPlan {
+            id: "plan".to_string(),
+            name: "Test".to_string(),
+            goal: TrainingGoal::Century,
+            periodization_model: PeriodizationModel::Traditional,
+            recovery_pattern: RecoveryPattern::ThreeToOne,
+            start_date: date(),
+            target_date: None,
+            total_weeks: 1,
+            weeks: vec![TrainingWeek {
+                week_number: 1,
+                start_date: date(),
+                end_date: date() + chrono::Duration::days(6),
+                phase: "Build".to_string(),
+                focus: "Threshold Development".to_string(),
+                planned_tss: tss,
+                planned_hours: dec!(1.5),
+                is_recovery_week: false,
+                workouts: vec![PlannedWorkout {
+                    id: uuid::Uuid::new_v4().to_string(),
+                    sequence: 0,
+                    date: date(),
+                    sport: Sport::Cycling,
+                    workout_type: WorkoutType::Threshold,
+                    planned_duration_minutes: 90,
+                    planned_tss: tss,
+                    description: "Threshold intervals".to_string(),
+                    intensity_factor: dec!(0.90),
+                    notes: None,
+                    structure: None,
+                }],
+            }],
+            total_planned_tss: tss,
+            total_planned_hours: dec!(1.5),
+            target_distribution: None,
+            created_at: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_wellness_score_and_validation() {
+        let best = WellnessQuestionnaire::new(date(), 5, 5, 5, 5).unwrap();
+        let worst = WellnessQuestionnaire::new(date(), 1, 1, 1, 1).unwrap();
+        let mid = WellnessQuestionnaire::new(date(), 3, 3, 3, 3).unwrap();
+
+        assert_eq!(best.score(), 100);
+        assert_eq!(worst.score(), 0);
+        assert_eq!(mid.score(), 50);
+        assert_eq!(worst.concerns().len(), 4);
+
+        assert_eq!(
+            WellnessQuestionnaire::new(date(), 0, 3, 3, 3),
+            Err(WellnessValidationError::OutOfRange {
+                field: "soreness",
+                value: 0
+            })
+        );
+        assert!(WellnessQuestionnaire::new(date(), 3, 3, 6, 3).is_err());
+    }
+
+    #[test]
+    fn test_full_inputs_use_enhanced_form() {
+        let engine = ReadinessEngine::new();
+        let recovery = recovery(45.0, 50.0);
+        let wellness = WellnessQuestionnaire::new(date(), 4, 4, 4, 4).unwrap();
+
+        let result = engine.assess_day(
+            date(),
+            Some(&pmc(dec!(5))),
+            Some(&recovery),
+            Some(&wellness),
+            Some(1.0),
+            None,
+            None,
+        );
+
+        let form = EnhancedForm::calculate(5, -10.0, None, None, None);
+        assert_eq!(result.hrv_deviation, Some(-10.0));
+        assert_eq!(result.objective_score, Some(form.score));
+        assert_eq!(result.wellness_score, Some(75));
+
+        let expected = (form.score as f64 * 0.7 + 75.0 * 0.3).round() as u8;
+        assert_eq!(result.composite_score, Some(expected));
+        assert!(result.decision.is_none()); // No plan supplied
+    }
+
+    #[test]
+    fn test_missing_sources_renormalize() {
+        let engine = ReadinessEngine::new();
+
+        let wellness_only = engine.assess_day(
+            date(),
+            None,
+            None,
+            Some(&WellnessQuestionnaire::new(date(), 2, 2, 2, 2).unwrap()),
+            None,
+            None,
+            None,
+        );
+        assert_eq!(wellness_only.composite_score, Some(25));
+        assert_eq!(wellness_only.limiting_factors.len(), 4);
+
+        let nothing = engine.assess_day(date(), None, None, None, None, None, None);
+        assert!(nothing.composite_score.is_none());
+        assert!(nothing.recovery_quality.is_none());
+    }
+
+    #[test]
+    fn test_decision_for_planned_workout() {
+        let engine = ReadinessEngine::new();
+        let plan = plan_with_workout(dec!(120));
+
+        let fresh = engine.assess_day(
+            date(),
+            Some(&pmc(dec!(10))),
+            Some(&recovery(55.0, 50.0)),
+            Some(&WellnessQuestionnaire::new(date(), 5, 5, 4, 5).unwrap()),
+            Some(1.0),
+            None,
+            Some(&plan),
+        );
+        let decision = fresh.decision.unwrap();
+        assert_eq!(fresh.planned_tss, Some(dec!(120)));
+        assert!(matches!(
+            decision.recommendation,
+            TrainingRecommendation::HardTraining | TrainingRecommendation::PeakPerformance
+        ));
+
+        let wrecked = engine.assess_day(
+            date(),
+            Some(&pmc(dec!(-40))),
+            Some(&recovery(25.0, 50.0)),
+            Some(&WellnessQuestionnaire::new(date(), 1, 2, 1, 1).unwrap()),
+            Some(1.6),
+            None,
+            Some(&plan),
+        );
+        let decision = wrecked.decision.unwrap();
+        assert!(decision.max_tss < 120);
+
+        // Day without a planned session
+        let rest_day = engine.assess_day(
+            date() + chrono::Duration::days(1),
+            Some(&pmc(dec!(10))),
+            None,
+            None,
+            None,
+            None,
+            Some(&plan),
+        );
+        assert!(rest_day.decision.is_none());
+    }
+
+    #[test]
+    fn test_series_joins_by_date() {
+        let engine = ReadinessEngine::new();
+        let mut day_two = pmc(dec!(0));
+        day_two.date = date() + chrono::Duration::days(1);
+        let series = vec![pmc(dec!(0)), day_two];
+
+        let wellness = vec![WellnessQuestionnaire::new(date(), 5, 5, 5, 5).unwrap()];
+        let results = engine.assess_series(&series, &[], &wellness, &[], None);
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].wellness_score, Some(100));
+        assert!(results[1].wellness_score.is_none());
+        assert!(results[0].composite_score > results[1].composite_score);
+    }
+
+    #[test]
+    fn test_series_uses_configured_acwr_method() {
+        let acwr = AcwrMetrics {
+            date: date(),
+            daily_load: 80.0,
+            acute_load: 80.0,
+            chronic_load: 60.0,
+            rolling_coupled: Some(1.2),
+            rolling_uncoupled: Some(1.4),
+            ewma: Some(1.1),
+            monotony: None,
+            strain: None,
+            zone: None,
+        };
+
+        let ewma = ReadinessEngine::new().assess_series(&[pmc(dec!(0))], &[], &[], &[acwr.clone()], None);
+        assert_eq!(ewma[0].acwr, Some(1.1));
+
+        let uncoupled = ReadinessEngine::with_config(ReadinessConfig {
+            acwr_method: AcwrMethod::RollingUncoupled,
+            ..ReadinessConfig::default()
+        })
+        .assess_series(&[pmc(dec!(0))], &[], &[], &[acwr], None);
+        assert_eq!(uncoupled[0].acwr, Some(1.4));
+    }
+}
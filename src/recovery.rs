@@ -2195,8 +2195,7 @@ impl OvertrainingRisk {
     /// Assess overtraining risk from multiple indicators
     ///
     /// # Arguments
-    /// * `ctl` - Chronic Training Load (fitness)
-    /// * `atl` - Acute Training Load (fatigue)
+    /// * `acwr` - Acute:Chronic Workload Ratio from `acwr::AcwrCalculator`
     /// * `recent_hrv` - HRV measurements (last 7 days)
     /// * `sleep_scores` - Sleep quality scores (last 7 days)
     /// * `rhr_elevations` - RHR deviation percentages (last 7 days)
@@ -2210,19 +2209,11 @@ impl OvertrainingRisk {
     /// 3. Multiple risk factors present: ESCALATE
     /// 4. RHR chronically elevated: MODERATE RISK
     pub fn assess(
-        ctl: f64,
-        atl: f64,
+        acwr: f64,
         recent_hrv: &[f64],
         sleep_scores: &[u8],
         rhr_elevations: &[f64],
     ) -> Self {
-        // Calculate ACWR (Acute:Chronic Workload Ratio)
-        let acwr = if ctl > 0.0 {
-            atl / ctl
-        } else {
-            0.0
-        };
-
         // Determine HRV trend
         let hrv_trend = if recent_hrv.len() >= 3 {
             let early_hrv = recent_hrv[..recent_hrv.len() / 2].iter().sum::<f64>()
@@ -2243,13 +2234,21 @@ impl OvertrainingRisk {
         };
 
         // Calculate sleep debt (optimal = 7.5 hours/night)
-        let avg_sleep = sleep_scores.iter().map(|&s| s as f64).sum::<f64>() / sleep_scores.len() as f64;
-        let sleep_debt_hours = ((75.0 - avg_sleep) / 75.0 * 7.5 * sleep_scores.len() as f64).max(0.0);
+        let sleep_debt_hours = if sleep_scores.is_empty() {
+            0.0
+        } else {
+            let avg_sleep =
+                sleep_scores.iter().map(|&s| s as f64).sum::<f64>() / sleep_scores.len() as f64;
+            ((75.0 - avg_sleep) / 75.0 * 7.5 * sleep_scores.len() as f64).max(0.0)
+        };
 
         // Calculate percentage of elevated RHR
         let elevated_rhr_count = rhr_elevations.iter().filter(|&&e| e > 5.0).count();
-        let elevated_rhr_percentage =
-            (elevated_rhr_count as f64 / rhr_elevations.len() as f64) * 100.0;
+        let elevated_rhr_percentage = if rhr_elevations.is_empty() {
+            0.0
+        } else {
+            (elevated_rhr_count as f64 / rhr_elevations.len() as f64) * 100.0
+        };
 
         // Determine risk level and action items
         let (risk_level, action_items, days_to_recovery) = Self::assess_risk_level(
@@ -4528,7 +4527,7 @@ mod tests {
         let sleep_scores = vec![80, 82, 78, 81, 79, 80, 81];
         let rhr_elevations = vec![2.0, 1.5, 2.5, 1.0, 2.0, 1.5, 2.0];
 
-        let risk = OvertrainingRisk::assess(100.0, 70.0, &hrv_data, &sleep_scores, &rhr_elevations);
+        let risk = OvertrainingRisk::assess(0.7, &hrv_data, &sleep_scores, &rhr_elevations);
 
         assert_eq!(risk.risk_level, RiskLevel::Low);
         assert!(risk.acwr < 1.3);
@@ -4540,7 +4539,7 @@ mod tests {
         let sleep_scores = vec![50, 45, 40, 40, 45, 50, 45]; // Poor
         let rhr_elevations = vec![8.0, 10.0, 12.0, 11.0, 13.0, 14.0, 15.0]; // Elevated
 
-        let risk = OvertrainingRisk::assess(50.0, 110.0, &hrv_data, &sleep_scores, &rhr_elevations);
+        let risk = OvertrainingRisk::assess(2.2, &hrv_data, &sleep_scores, &rhr_elevations);
 
         assert!(risk.risk_level == RiskLevel::High || risk.risk_level == RiskLevel::Critical);
         assert!(risk.acwr > 1.3);
@@ -4554,9 +4553,9 @@ mod tests {
         let sleep_scores = vec![50, 50, 50, 50, 50, 50, 50]; // Poor sleep
         let rhr_elevations = vec![8.0, 9.0, 10.0, 11.0, 12.0, 13.0, 14.0]; // Elevated RHR
 
-        let risk = OvertrainingRisk::assess(30.0, 100.0, &hrv_data, &sleep_scores, &rhr_elevations);
+        let risk = OvertrainingRisk::assess(3.33, &hrv_data, &sleep_scores, &rhr_elevations);
 
-        // ACWR = 100/30 = 3.33, very high + multiple other risk factors
+        // ACWR of 3.33, very high + multiple other risk factors
         // With declining HRV, poor sleep, and elevated RHR, should be high or critical
         assert!(
             risk.risk_level == RiskLevel::Critical || risk.risk_level == RiskLevel::High,
@@ -4668,12 +4667,30 @@ mod tests {
         let sleep_scores = vec![40, 40, 40, 40, 40, 40, 40]; // Consistently poor
         let rhr_elevations = vec![2.0; 7];
 
-        let risk = OvertrainingRisk::assess(100.0, 80.0, &hrv_data, &sleep_scores, &rhr_elevations);
+        let risk = OvertrainingRisk::assess(0.8, &hrv_data, &sleep_scores, &rhr_elevations);
 
         assert!(risk.sleep_debt_hours > 0.0);
         assert!(risk.action_items.iter().any(|a| a.contains("Sleep debt")));
     }
 
+    #[test]
+    fn test_overtraining_risk_from_acwr_alone() {
+        let hrv_data = vec![50.0; 7];
+        let sleep_scores = vec![80; 7];
+        let rhr_elevations = vec![1.0; 7];
+
+        let risk = OvertrainingRisk::assess(1.8, &hrv_data, &sleep_scores, &rhr_elevations);
+        assert_eq!(risk.acwr, 1.8);
+        assert_eq!(risk.risk_level, RiskLevel::Moderate);
+        assert!(risk.action_items.iter().any(|a| a.contains(">1.5")));
+
+        // Without recovery data only ACWR contributes
+        let risk = OvertrainingRisk::assess(1.0, &[], &[], &[]);
+        assert_eq!(risk.sleep_debt_hours, 0.0);
+        assert_eq!(risk.elevated_rhr_percentage, 0.0);
+        assert_eq!(risk.risk_level, RiskLevel::Low);
+    }
+
     #[test]
     fn test_recovery_forecast_accuracy_check() {
         let today = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
@@ -0,0 +1,435 @@
+//! Rowing-specific training analysis module
+//!
+//! Splits per 500m, stroke metrics and 2k-based performance predictions.
+//! Pace and power are related by the Concept2 ergometer formula
+//! `watts = 2.80 / (seconds per meter)³`; predictions across distances
+//! follow Paul's law, which adds five seconds to the 500m split for every
+//! doubling of the distance.
+
+use crate::models::{DataPoint, Sport, Workout};
+use anyhow::{anyhow, Result};
+use rust_decimal::prelude::*;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+
+#[derive(Debug, thiserror::Error)]
+pub enum RowingError {
+    #[error("Insufficient data: {0}")]
+    InsufficientData(String),
+    #[error("Invalid piece: {0}")]
+    InvalidPiece(String),
+}
+
+/// Distance of one split in meters
+pub const SPLIT_DISTANCE: Decimal = dec!(500);
+
+/// Seconds added to the 500m split per doubling of the distance (Paul's law)
+const PAULS_LAW_SECONDS: f64 = 5.0;
+
+/// Standard test and race distances in meters
+const STANDARD_PIECES: [(&str, u32); 8] = [
+    ("500m", 500),
+    ("1k", 1000),
+    ("2k", 2000),
+    ("5k", 5000),
+    ("6k", 6000),
+    ("10k", 10000),
+    ("half_marathon", 21097),
+    ("marathon", 42195),
+];
+
+/// Rowing analysis of one workout
+#[derive(Debug, Clone)]
+pub struct RowingAnalysis {
+    /// Total distance in meters
+    pub distance_meters: Decimal,
+    /// Average split in seconds per 500m
+    pub avg_split: Decimal,
+    /// Fastest full 500m split in seconds
+    pub fastest_split: Option<Decimal>,
+    /// Average power in watts
+    pub avg_power: Option<u16>,
+    /// Estimated number of strokes, from the stroke rate
+    pub stroke_count: Option<u32>,
+    /// Stroke metrics over the whole workout
+    pub strokes: StrokeMetrics,
+    /// Splits per 500m; the last one may be shorter
+    pub splits: Vec<RowingSplit>,
+}
+
+/// Stroke metrics over a stretch of rowing
+#[derive(Debug, Clone, Default)]
+pub struct StrokeMetrics {
+    /// Average stroke rate in strokes per minute
+    pub avg_stroke_rate: Option<u16>,
+    /// Meters covered per stroke
+    pub distance_per_stroke: Option<Decimal>,
+    /// Average drive length in meters
+    pub avg_drive_length: Option<Decimal>,
+    /// Average work per stroke in joules
+    pub avg_stroke_work: Option<u16>,
+}
+
+/// One 500m split
+#[derive(Debug, Clone)]
+pub struct RowingSplit {
+    /// Split number, starting at 1
+    pub split_number: u16,
+    /// Distance of the split in meters
+    pub distance_meters: Decimal,
+    /// Duration in seconds
+    pub duration_seconds: u32,
+    /// Pace in seconds per 500m
+    pub split: Decimal,
+    /// Average power in watts
+    pub avg_power: Option<u16>,
+    /// Average heart rate
+    pub avg_heart_rate: Option<u16>,
+    /// Stroke metrics over the split
+    pub strokes: StrokeMetrics,
+}
+
+/// 2k-based rowing performance predictions
+#[derive(Debug, Clone)]
+pub struct RowingPrediction {
+    /// Predicted 2k time in seconds
+    pub two_k_time: Decimal,
+    /// 2k split in seconds per 500m
+    pub two_k_split: Decimal,
+    /// Average power of the 2k in watts
+    pub two_k_watts: Decimal,
+    /// Rowing FTP: power sustainable for 60 minutes
+    pub rowing_ftp: u16,
+    /// Meters covered in 60 minutes at FTP
+    pub hour_distance: Decimal,
+    /// Predicted times for the standard distances
+    pub pieces: Vec<PiecePrediction>,
+}
+
+/// Predicted result over one distance
+#[derive(Debug, Clone)]
+pub struct PiecePrediction {
+    pub name: String,
+    pub distance_meters: u32,
+    /// Time in seconds
+    pub time: Decimal,
+    /// Split in seconds per 500m
+    pub split: Decimal,
+    /// Average power in watts
+    pub watts: Decimal,
+}
+
+/// Main rowing analysis struct
+pub struct RowingAnalyzer;
+
+impl RowingAnalyzer {
+    /// Analyze splits and stroke metrics of a rowing workout
+    pub fn analyze(workout: &Workout) -> Result<RowingAnalysis> {
+        if workout.sport != Sport::Rowing {
+            return Err(anyhow!("Workout must be a rowing activity"));
+        }
+        let raw_data = workout
+            .raw_data
+            .as_ref()
+            .ok_or_else(|| RowingError::InsufficientData("No stroke data available".to_string()))?;
+
+        let splits = Self::analyze_splits(raw_data)?;
+        let distance_meters: Decimal = splits.iter().map(|s| s.distance_meters).sum();
+        let duration: u32 = splits.iter().map(|s| s.duration_seconds).sum();
+        if distance_meters <= Decimal::ZERO || duration == 0 {
+            return Err(RowingError::InsufficientData("No distance covered".to_string()).into());
+        }
+
+        let strokes = Self::stroke_metrics(raw_data, distance_meters, duration);
+        let stroke_count = strokes
+            .avg_stroke_rate
+            .map(|rate| (u32::from(rate) * duration + 30) / 60);
+
+        Ok(RowingAnalysis {
+            distance_meters,
+            avg_split: (Decimal::from(duration) * SPLIT_DISTANCE / distance_meters).round_dp(1),
+            fastest_split: splits
+                .iter()
+                .filter(|s| s.distance_meters >= SPLIT_DISTANCE)
+                .map(|s| s.split)
+                .min(),
+            avg_power: average(raw_data.iter().map(|p| p.power)),
+            stroke_count,
+            strokes,
+            splits,
+        })
+    }
+
+    /// Split the data into 500m splits by the recorded distance
+    ///
+    /// A split closes at the first sample reaching its end distance; the
+    /// remainder after the last full split forms a shorter final split.
+    pub fn analyze_splits(raw_data: &[DataPoint]) -> Result<Vec<RowingSplit>> {
+        let points: Vec<&DataPoint> = raw_data.iter().filter(|p| p.distance.is_some()).collect();
+        if points.len() < 2 {
+            return Err(RowingError::InsufficientData("Distance data required for splits".to_string()).into());
+        }
+
+        let mut splits = Vec::new();
+        let mut start = 0;
+        let mut boundary = points[0].distance.unwrap_or_default() + SPLIT_DISTANCE;
+
+        for (i, point) in points.iter().enumerate().skip(1) {
+            let last = i == points.len() - 1;
+            if point.distance.unwrap_or_default() >= boundary || last {
+                if let Some(split) = Self::split(&points[start..=i], splits.len() as u16 + 1) {
+                    splits.push(split);
+                }
+                start = i;
+                while boundary <= point.distance.unwrap_or_default() {
+                    boundary += SPLIT_DISTANCE;
+                }
+            }
+        }
+
+        Ok(splits)
+    }
+
+    /// Split over a run of samples, the first one marking its start
+    fn split(points: &[&DataPoint], split_number: u16) -> Option<RowingSplit> {
+        let (first, last) = (points.first()?, points.last()?);
+        let distance_meters = last.distance? - first.distance?;
+        let duration_seconds = last.timestamp.saturating_sub(first.timestamp);
+        if distance_meters <= Decimal::ZERO || duration_seconds == 0 {
+            return None;
+        }
+
+        let samples: Vec<DataPoint> = points[1..].iter().map(|p| (*p).clone()).collect();
+        Some(RowingSplit {
+            split_number,
+            distance_meters,
+            duration_seconds,
+            split: (Decimal::from(duration_seconds) * SPLIT_DISTANCE / distance_meters).round_dp(1),
+            avg_power: average(samples.iter().map(|p| p.power)),
+            avg_heart_rate: average(samples.iter().map(|p| p.heart_rate)),
+            strokes: Self::stroke_metrics(&samples, distance_meters, duration_seconds),
+        })
+    }
+
+    /// Stroke rate, distance per stroke, drive length and work per stroke
+    fn stroke_metrics(samples: &[DataPoint], distance_meters: Decimal, duration_seconds: u32) -> StrokeMetrics {
+        let avg_stroke_rate = average(samples.iter().map(|p| p.cadence));
+        let distance_per_stroke = avg_stroke_rate.filter(|rate| *rate > 0).map(|rate| {
+            let strokes = Decimal::from(rate) * Decimal::from(duration_seconds) / dec!(60);
+            (distance_meters / strokes).round_dp(2)
+        });
+        let drive_lengths: Vec<Decimal> = samples.iter().filter_map(|p| p.drive_length).collect();
+
+        StrokeMetrics {
+            avg_stroke_rate,
+            distance_per_stroke,
+            avg_drive_length: (!drive_lengths.is_empty())
+                .then(|| (drive_lengths.iter().sum::<Decimal>() / Decimal::from(drive_lengths.len())).round_dp(2)),
+            avg_stroke_work: average(samples.iter().map(|p| p.stroke_work)),
+        }
+    }
+
+    /// Predict the 2k, rowing FTP and standard distances from a recent piece
+    ///
+    /// The piece time is in seconds.
+    pub fn predict_performance(piece_time: Decimal, distance_meters: Decimal) -> Result<RowingPrediction> {
+        if piece_time <= Decimal::ZERO || distance_meters <= Decimal::ZERO {
+            return Err(RowingError::InvalidPiece("Time and distance must be positive".to_string()).into());
+        }
+        let piece_split = (piece_time * SPLIT_DISTANCE / distance_meters)
+            .to_f64()
+            .ok_or_else(|| RowingError::InvalidPiece("Piece out of range".to_string()))?;
+        let two_k_split = piece_split - PAULS_LAW_SECONDS * (distance_meters.to_f64().unwrap_or(2000.0) / 2000.0).log2();
+        if two_k_split <= 0.0 {
+            return Err(RowingError::InvalidPiece("Piece is faster than any 2k prediction allows".to_string()).into());
+        }
+
+        let hour_distance = Self::hour_distance(two_k_split);
+        let hour_watts = watts_for_split(split_at(two_k_split, hour_distance));
+        let pieces = STANDARD_PIECES
+            .iter()
+            .map(|&(name, meters)| {
+                let split = split_at(two_k_split, f64::from(meters));
+                PiecePrediction {
+                    name: name.to_string(),
+                    distance_meters: meters,
+                    time: to_decimal(split * f64::from(meters) / 500.0),
+                    split: to_decimal(split),
+                    watts: to_decimal(watts_for_split(split)).round(),
+                }
+            })
+            .collect();
+
+        Ok(RowingPrediction {
+            two_k_time: to_decimal(two_k_split * 4.0),
+            two_k_split: to_decimal(two_k_split),
+            two_k_watts: to_decimal(watts_for_split(two_k_split)).round(),
+            rowing_ftp: hour_watts.round() as u16,
+            hour_distance: to_decimal(hour_distance).round(),
+            pieces,
+        })
+    }
+
+    /// Rowing FTP from a 2k test time in seconds
+    pub fn ftp_from_two_k(two_k_time: Decimal) -> Result<u16> {
+        Ok(Self::predict_performance(two_k_time, dec!(2000))?.rowing_ftp)
+    }
+
+    /// Meters covered in 60 minutes, found by bisection on Paul's law
+    fn hour_distance(two_k_split: f64) -> f64 {
+        let (mut low, mut high) = (1.0_f64, 100_000.0_f64);
+        for _ in 0..60 {
+            let mid = (low + high) / 2.0;
+            if split_at(two_k_split, mid) * mid / 500.0 < 3600.0 {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+        low
+    }
+}
+
+/// Ergometer power in watts for a split in seconds per 500m
+pub fn watts_for_split(split: f64) -> f64 {
+    2.80 / (split / 500.0).powi(3)
+}
+
+/// Erg time to the tenth, e.g. "1:52.3" or "1:02:05.0"
+pub fn format_split(seconds: Decimal) -> String {
+    let tenths = (seconds * dec!(10)).round().to_u32().unwrap_or(0);
+    let (hours, minutes) = (tenths / 36000, tenths / 600 % 60);
+    if hours > 0 {
+        format!("{}:{:02}:{:02}.{}", hours, minutes, tenths / 10 % 60, tenths % 10)
+    } else {
+        format!("{}:{:02}.{}", minutes, tenths / 10 % 60, tenths % 10)
+    }
+}
+
+/// Split over a distance predicted from the 2k split
+fn split_at(two_k_split: f64, meters: f64) -> f64 {
+    two_k_split + PAULS_LAW_SECONDS * (meters / 2000.0).log2()
+}
+
+fn to_decimal(value: f64) -> Decimal {
+    Decimal::from_f64(value).unwrap_or_default().round_dp(1)
+}
+
+/// Mean of the present values
+fn average(values: impl Iterator<Item = Option<u16>>) -> Option<u16> {
+    let present: Vec<u32> = values.flatten().map(u32::from).collect();
+    (!present.is_empty()).then(|| (present.iter().sum::<u32>() / present.len() as u32) as u16)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{DataSource, WorkoutSummary, WorkoutType};
+    use chrono::NaiveDate;
+
+    fn stroke(timestamp: u32, distance: Decimal, power: u16) -> DataPoint {
+        DataPoint {
+            timestamp,
+            heart_rate: Some(160),
+            power: Some(power),
+            pace: None,
+            elevation: None,
+            cadence: Some(24),
+            speed: None,
+            distance: Some(distance),
+            left_power: None,
+            right_power: None,
+            ground_contact_time: None,
+            vertical_oscillation: None,
+            stride_length: None,
+            stroke_count: None,
+            stroke_type: None,
+            lap_number: None,
+            sport_transition: None,
+            temperature: None,
+            drive_length: Some(dec!(1.40)),
+            stroke_work: Some(700),
+        }
+    }
+
+    /// 1200m at 5 m/s (1:40/500m), sampled every 10 seconds
+    fn rowing_workout() -> Workout {
+        let raw_data: Vec<DataPoint> = (0..=24)
+            .map(|i| stroke(i * 10, Decimal::from(i * 50), 280))
+            .collect();
+        Workout {
+            id: "row".to_string(),
+            date: NaiveDate::from_ymd_opt(2026, 10, 1).unwrap(),
+            start_time: None,
+            sport: Sport::Rowing,
+            duration_seconds: 240,
+            workout_type: WorkoutType::Threshold,
+            data_source: DataSource::Power,
+            raw_data: Some(raw_data),
+            summary: WorkoutSummary::default(),
+            notes: None,
+            athlete_id: None,
+            source: None,
+            laps: Vec::new(),
+            swim_lengths: Vec::new(),
+            rpe: None,
+            strength_sets: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_splits_per_500m() {
+        let analysis = RowingAnalyzer::analyze(&rowing_workout()).unwrap();
+
+        assert_eq!(analysis.splits.len(), 3);
+        assert_eq!(analysis.splits[0].distance_meters, dec!(500));
+        assert_eq!(analysis.splits[0].split, dec!(100));
+        assert_eq!(analysis.splits[2].distance_meters, dec!(200));
+        assert_eq!(analysis.avg_split, dec!(100));
+        assert_eq!(analysis.fastest_split, Some(dec!(100)));
+        assert_eq!(analysis.avg_power, Some(280));
+
+        // 24 spm for 100 seconds covers 500m in 40 strokes
+        let strokes = &analysis.splits[0].strokes;
+        assert_eq!(strokes.avg_stroke_rate, Some(24));
+        assert_eq!(strokes.distance_per_stroke, Some(dec!(12.5)));
+        assert_eq!(strokes.avg_drive_length, Some(dec!(1.40)));
+        assert_eq!(strokes.avg_stroke_work, Some(700));
+        assert_eq!(analysis.stroke_count, Some(96));
+    }
+
+    #[test]
+    fn test_rejects_other_sports() {
+        let mut workout = rowing_workout();
+        workout.sport = Sport::Running;
+        assert!(RowingAnalyzer::analyze(&workout).is_err());
+    }
+
+    #[test]
+    fn test_pace_power_relationship() {
+        // 2:00/500m is 202.5 W on a Concept2 monitor
+        assert!((watts_for_split(120.0) - 202.5).abs() < 0.1);
+        assert_eq!(format_split(dec!(112.34)), "1:52.3");
+        assert_eq!(format_split(dec!(3725)), "1:02:05.0");
+    }
+
+    #[test]
+    fn test_predictions_follow_pauls_law() {
+        // 7:00 2k, a 1:45.0 split
+        let prediction = RowingAnalyzer::predict_performance(dec!(420), dec!(2000)).unwrap();
+        assert_eq!(prediction.two_k_split, dec!(105));
+        assert_eq!(prediction.two_k_watts, dec!(302));
+
+        let six_k = prediction.pieces.iter().find(|p| p.name == "6k").unwrap();
+        assert_eq!(six_k.split, dec!(112.9));
+
+        // A 6k at that split predicts the same 2k
+        let from_six_k = RowingAnalyzer::predict_performance(six_k.time, dec!(6000)).unwrap();
+        assert!((from_six_k.two_k_time - dec!(420)).abs() <= dec!(0.5));
+
+        assert!(prediction.hour_distance > dec!(14000) && prediction.hour_distance < dec!(16000));
+        assert!(prediction.rowing_ftp > 190 && prediction.rowing_ftp < 220);
+        assert_eq!(RowingAnalyzer::ftp_from_two_k(dec!(420)).unwrap(), prediction.rowing_ftp);
+        assert!(RowingAnalyzer::predict_performance(dec!(0), dec!(2000)).is_err());
+    }
+}
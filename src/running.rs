@@ -5,7 +5,7 @@
 
 #![allow(dead_code)]
 
-use crate::models::{DataPoint, Sport, Workout};
+use crate::models::{DataPoint, Lap, LapIntensity, LapTrigger, Sport, Workout};
 use anyhow::{anyhow, Result};
 use rust_decimal::prelude::*;
 use rust_decimal::Decimal;
@@ -36,6 +36,8 @@ pub struct PaceAnalysis {
     pub pace_distribution: PaceDistribution,
     /// Split analysis for different segments
     pub splits: Vec<SplitAnalysis>,
+    /// Splits of the laps recorded by the device
+    pub laps: Vec<LapSplit>,
     /// Efficiency factor (normalized pace / average heart rate)
     pub efficiency_factor: Option<Decimal>,
 }
@@ -66,6 +68,21 @@ pub struct SplitAnalysis {
     pub avg_gradient: Decimal,
 }
 
+/// Split of one device lap
+#[derive(Debug, Clone)]
+pub struct LapSplit {
+    /// Lap number, starting at 1
+    pub lap_number: u16,
+    /// What ended the lap
+    pub trigger: LapTrigger,
+    /// Work or recovery lap
+    pub intensity: LapIntensity,
+    /// Average heart rate as recorded by the device, or from the samples
+    pub avg_heart_rate: Option<u16>,
+    /// Pace metrics over the lap
+    pub split: SplitAnalysis,
+}
+
 /// Elevation analysis results
 #[derive(Debug, Clone)]
 pub struct ElevationAnalysis {
@@ -194,6 +211,7 @@ impl RunningAnalyzer {
         let gap = Self::calculate_grade_adjusted_pace(raw_data)?;
         let pace_distribution = Self::analyze_pace_distribution(raw_data)?;
         let splits = Self::analyze_splits(raw_data, 1000.0)?; // 1km splits
+        let laps = Self::analyze_device_laps(&workout.laps, raw_data)?;
         let efficiency_factor = Self::calculate_efficiency_factor(raw_data)?;
 
         Ok(PaceAnalysis {
@@ -202,6 +220,7 @@ impl RunningAnalyzer {
             grade_adjusted_pace: gap,
             pace_distribution,
             splits,
+            laps,
             efficiency_factor,
         })
     }
@@ -437,6 +456,48 @@ impl RunningAnalyzer {
         Ok(splits)
     }
 
+    /// Split the run at the device laps
+    ///
+    /// Lap distance and timer time come from the device when recorded, so
+    /// paused time is not counted in the lap pace.
+    fn analyze_device_laps(laps: &[Lap], raw_data: &[DataPoint]) -> Result<Vec<LapSplit>> {
+        let mut lap_splits = Vec::new();
+
+        for (index, lap) in laps.iter().enumerate() {
+            let start = raw_data.partition_point(|dp| dp.timestamp < lap.start_offset);
+            let end = raw_data.partition_point(|dp| dp.timestamp < lap.end_offset());
+            // Include the first sample of the next lap so the lap's distance is covered
+            let data = &raw_data[start..(end + 1).min(raw_data.len())];
+            if data.len() < 2 {
+                continue;
+            }
+
+            let start_dist = data.first().and_then(|dp| dp.distance).unwrap_or(dec!(0));
+            let end_dist = data.last().and_then(|dp| dp.distance).unwrap_or(start_dist);
+            let mut split = Self::calculate_split(data, start_dist, end_dist)?;
+
+            if let Some(distance) = lap.distance {
+                split.distance_meters = distance;
+            }
+            if lap.duration_seconds > 0 {
+                split.duration_seconds = lap.duration_seconds;
+            }
+            if split.distance_meters > dec!(0) && split.duration_seconds > 0 {
+                split.avg_pace = (Decimal::from(split.duration_seconds) / dec!(60)) / (split.distance_meters / dec!(1000));
+            }
+
+            lap_splits.push(LapSplit {
+                lap_number: index as u16 + 1,
+                trigger: lap.trigger,
+                intensity: lap.intensity,
+                avg_heart_rate: lap.avg_heart_rate.or_else(|| Self::calculate_average_hr(&raw_data[start..end])),
+                split,
+            });
+        }
+
+        Ok(lap_splits)
+    }
+
     /// Calculate metrics for a single split
     fn calculate_split(data: &[DataPoint], start_dist: Decimal, end_dist: Decimal) -> Result<SplitAnalysis> {
         let distance_meters = end_dist - start_dist;
@@ -1086,6 +1147,9 @@ mod tests {
                 stroke_type: None,
                 lap_number: None,
                 sport_transition: None,
+                temperature: None,
+                drive_length: None,
+                stroke_work: None,
             });
         }
 
@@ -1106,6 +1170,11 @@ mod tests {
             notes: None,
             athlete_id: None,
             source: None,
+            swim_lengths: Vec::new(),
+            start_time: None,
+            laps: Vec::new(),
+            rpe: None,
+            strength_sets: Vec::new(),
         };
 
         let analysis = RunningAnalyzer::analyze_pace(&workout).unwrap();
@@ -1116,6 +1185,56 @@ mod tests {
         assert!(!analysis.splits.is_empty());
     }
 
+    #[test]
+    fn test_device_lap_splits() {
+        let lap = |start_offset, trigger, intensity| Lap {
+            start_offset,
+            duration_seconds: 900,
+            distance: None,
+            avg_heart_rate: None,
+            max_heart_rate: None,
+            avg_power: None,
+            max_power: None,
+            trigger,
+            intensity,
+        };
+        let workout = Workout {
+            id: "lap_run".to_string(),
+            date: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            sport: Sport::Running,
+            duration_seconds: 1800,
+            workout_type: crate::models::WorkoutType::Interval,
+            data_source: crate::models::DataSource::Pace,
+            raw_data: Some(create_sample_running_data()),
+            summary: Default::default(),
+            notes: None,
+            athlete_id: None,
+            source: None,
+            swim_lengths: Vec::new(),
+            start_time: None,
+            laps: vec![
+                lap(0, LapTrigger::Manual, LapIntensity::Active),
+                lap(900, LapTrigger::SessionEnd, LapIntensity::Rest),
+            ],
+            rpe: None,
+            strength_sets: Vec::new(),
+        };
+
+        let analysis = RunningAnalyzer::analyze_pace(&workout).unwrap();
+        assert_eq!(analysis.laps.len(), 2);
+
+        let first = &analysis.laps[0];
+        assert_eq!(first.lap_number, 1);
+        assert_eq!(first.trigger, LapTrigger::Manual);
+        assert_eq!(first.split.duration_seconds, 900);
+        // 150 samples of 16.67 m
+        assert_eq!(first.split.distance_meters, dec!(2500.50));
+        assert!(first.split.avg_pace > dec!(5.9) && first.split.avg_pace < dec!(6.1));
+        assert!(first.avg_heart_rate.is_some());
+
+        assert_eq!(analysis.laps[1].intensity, LapIntensity::Rest);
+    }
+
     #[test]
     fn test_elevation_analysis() {
         let workout = Workout {
@@ -1130,6 +1249,11 @@ mod tests {
             notes: None,
             athlete_id: None,
             source: None,
+            swim_lengths: Vec::new(),
+            start_time: None,
+            laps: Vec::new(),
+            rpe: None,
+            strength_sets: Vec::new(),
         };
 
         let elevation = RunningAnalyzer::analyze_elevation(&workout).unwrap();
@@ -1245,6 +1369,9 @@ mod tests {
                 stroke_type: None,
                 lap_number: Some(1),
                 sport_transition: None,
+                temperature: None,
+                drive_length: None,
+                stroke_work: None,
             },
             DataPoint {
                 timestamp: 1,
@@ -1264,6 +1391,9 @@ mod tests {
                 stroke_type: None,
                 lap_number: Some(1),
                 sport_transition: None,
+                temperature: None,
+                drive_length: None,
+                stroke_work: None,
             },
         ];
 
@@ -1291,6 +1421,11 @@ mod tests {
             notes: None,
             athlete_id: None,
             source: None,
+            swim_lengths: Vec::new(),
+            start_time: None,
+            laps: Vec::new(),
+            rpe: None,
+            strength_sets: Vec::new(),
         };
 
         let dynamics = RunningAnalyzer::analyze_running_dynamics(&workout).unwrap();
@@ -1368,13 +1503,15 @@ mod tests {
                 timestamp: 0, cadence: Some(180), heart_rate: None, power: None, pace: None,
                 elevation: None, speed: None, distance: None, left_power: None, right_power: None,
                 ground_contact_time: None, vertical_oscillation: None, stride_length: None,
-                stroke_count: None, stroke_type: None, lap_number: None, sport_transition: None,
+                stroke_count: None, stroke_type: None, lap_number: None, sport_transition: None, temperature: None,
+                drive_length: None, stroke_work: None,
             },
             &DataPoint {
                 timestamp: 1, cadence: Some(180), heart_rate: None, power: None, pace: None,
                 elevation: None, speed: None, distance: None, left_power: None, right_power: None,
                 ground_contact_time: None, vertical_oscillation: None, stride_length: None,
-                stroke_count: None, stroke_type: None, lap_number: None, sport_transition: None,
+                stroke_count: None, stroke_type: None, lap_number: None, sport_transition: None, temperature: None,
+                drive_length: None, stroke_work: None,
             },
         ];
 
@@ -0,0 +1,338 @@
+//! Strength training analysis module
+//!
+//! Tonnage, per-exercise summaries and estimated one-rep max progression
+//! from the sets of strength workouts. One-rep maxes use the Epley formula
+//! `weight × (1 + reps / 30)`, which is only trusted for sets of up to
+//! twelve reps. Session load follows Foster's session RPE: RPE × minutes.
+
+use crate::models::{SetType, StrengthSet, Workout};
+use anyhow::{anyhow, Result};
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use std::collections::BTreeMap;
+
+#[derive(Debug, thiserror::Error)]
+pub enum StrengthError {
+    #[error("Insufficient data: {0}")]
+    InsufficientData(String),
+    #[error("Invalid set entry: {0}")]
+    InvalidSet(String),
+}
+
+/// Most reps in a set that still gives a usable one-rep max estimate
+pub const MAX_E1RM_REPS: u16 = 12;
+
+/// Strength analysis of one workout
+#[derive(Debug, Clone)]
+pub struct StrengthAnalysis {
+    /// Total load lifted (reps × weight) in kg
+    pub tonnage: Decimal,
+    /// Number of active sets
+    pub total_sets: u32,
+    /// Number of reps over all active sets
+    pub total_reps: u32,
+    /// Summaries per exercise, in the order first performed
+    pub exercises: Vec<ExerciseSummary>,
+    /// Session RPE load in arbitrary units, when RPE was recorded
+    pub session_load: Option<u32>,
+}
+
+/// Work done on one exercise within a workout
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExerciseSummary {
+    /// Exercise name, e.g. `bench_press`
+    pub exercise: String,
+    /// Number of active sets
+    pub sets: u32,
+    /// Number of reps
+    pub reps: u32,
+    /// Load lifted (reps × weight) in kg
+    pub tonnage: Decimal,
+    /// Heaviest weight lifted in kg
+    pub top_weight: Option<Decimal>,
+    /// Best estimated one-rep max in kg
+    pub best_e1rm: Option<Decimal>,
+}
+
+/// Best estimated one-rep max of an exercise on one day
+#[derive(Debug, Clone, PartialEq)]
+pub struct OneRepMaxPoint {
+    pub date: NaiveDate,
+    /// Estimated one-rep max in kg
+    pub e1rm: Decimal,
+    /// Weight of the set the estimate came from, in kg
+    pub weight: Decimal,
+    /// Reps of the set the estimate came from
+    pub reps: u16,
+}
+
+/// Strength training analyzer
+pub struct StrengthAnalyzer;
+
+impl StrengthAnalyzer {
+    /// Analyze the sets of a strength workout
+    pub fn analyze(workout: &Workout) -> Result<StrengthAnalysis> {
+        let active: Vec<&StrengthSet> =
+            workout.strength_sets.iter().filter(|set| set.set_type == SetType::Active).collect();
+        if active.is_empty() {
+            return Err(anyhow!(StrengthError::InsufficientData(
+                "workout has no strength sets".to_string()
+            )));
+        }
+
+        let mut exercises: Vec<ExerciseSummary> = Vec::new();
+        for set in &active {
+            let name = set.exercise.clone().unwrap_or_else(|| "unknown".to_string());
+            let index = match exercises.iter().position(|e| e.exercise == name) {
+                Some(index) => index,
+                None => {
+                    exercises.push(ExerciseSummary {
+                        exercise: name,
+                        sets: 0,
+                        reps: 0,
+                        tonnage: Decimal::ZERO,
+                        top_weight: None,
+                        best_e1rm: None,
+                    });
+                    exercises.len() - 1
+                }
+            };
+
+            let summary = &mut exercises[index];
+            summary.sets += 1;
+            summary.reps += u32::from(set.reps.unwrap_or(0));
+            summary.tonnage += set_tonnage(set);
+            summary.top_weight = summary.top_weight.max(set.weight);
+            summary.best_e1rm = summary.best_e1rm.max(set_e1rm(set));
+        }
+
+        Ok(StrengthAnalysis {
+            tonnage: exercises.iter().map(|e| e.tonnage).sum(),
+            total_sets: active.len() as u32,
+            total_reps: exercises.iter().map(|e| e.reps).sum(),
+            exercises,
+            session_load: session_rpe_load(workout),
+        })
+    }
+
+    /// Best estimated one-rep max per exercise and day, oldest first
+    pub fn e1rm_progression(workouts: &[Workout]) -> BTreeMap<String, Vec<OneRepMaxPoint>> {
+        let mut progression: BTreeMap<String, Vec<OneRepMaxPoint>> = BTreeMap::new();
+        let mut sorted: Vec<&Workout> = workouts.iter().collect();
+        sorted.sort_by_key(|w| w.date);
+
+        for workout in sorted {
+            for set in &workout.strength_sets {
+                let (Some(exercise), Some(e1rm)) = (&set.exercise, set_e1rm(set)) else {
+                    continue;
+                };
+                let point = OneRepMaxPoint {
+                    date: workout.date,
+                    e1rm,
+                    weight: set.weight.unwrap_or_default(),
+                    reps: set.reps.unwrap_or(0),
+                };
+
+                let points = progression.entry(exercise.clone()).or_default();
+                match points.last_mut() {
+                    Some(last) if last.date == workout.date => {
+                        if point.e1rm > last.e1rm {
+                            *last = point;
+                        }
+                    }
+                    _ => points.push(point),
+                }
+            }
+        }
+
+        progression
+    }
+}
+
+/// Estimated one-rep max by the Epley formula
+///
+/// Returns `None` outside 1–12 reps, where the estimate is unreliable.
+pub fn estimated_one_rep_max(weight: Decimal, reps: u16) -> Option<Decimal> {
+    match reps {
+        0 => None,
+        1 => Some(weight),
+        reps if reps <= MAX_E1RM_REPS => {
+            Some((weight * (Decimal::ONE + Decimal::from(reps) / dec!(30))).round_dp(1))
+        }
+        _ => None,
+    }
+}
+
+/// Session RPE load (RPE × minutes) in arbitrary units
+pub fn session_rpe_load(workout: &Workout) -> Option<u32> {
+    workout.rpe.map(|rpe| u32::from(rpe) * workout.duration_seconds / 60)
+}
+
+/// Normalize an exercise name to the FIT profile style, e.g. `bench_press`
+pub fn normalize_exercise(name: &str) -> String {
+    name.split(|c: char| c.is_whitespace() || c == '-' || c == '_')
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+/// Parse a manually entered exercise such as `squat 5x5@100`
+///
+/// The last word gives the sets and reps (`5x5`, or `8` for a single set)
+/// with an optional `@weight` in kg; everything before it names the
+/// exercise. Sets without a weight are bodyweight sets.
+pub fn parse_set_entry(entry: &str) -> Result<Vec<StrengthSet>> {
+    let invalid = || anyhow!(StrengthError::InvalidSet(format!("'{}', expected e.g. 'squat 5x5@100'", entry)));
+
+    let (name, scheme) = entry.trim().rsplit_once(char::is_whitespace).ok_or_else(invalid)?;
+    let exercise = normalize_exercise(name);
+    if exercise.is_empty() {
+        return Err(invalid());
+    }
+
+    let (volume, weight) = match scheme.split_once('@') {
+        Some((volume, weight)) => {
+            let weight = weight.trim_end_matches("kg").parse::<Decimal>().map_err(|_| invalid())?;
+            if weight < Decimal::ZERO {
+                return Err(invalid());
+            }
+            (volume, Some(weight))
+        }
+        None => (scheme, None),
+    };
+    let (sets, reps) = match volume.split_once(['x', 'X']) {
+        Some((sets, reps)) => (sets.parse::<u16>().map_err(|_| invalid())?, reps.parse::<u16>().map_err(|_| invalid())?),
+        None => (1, volume.parse::<u16>().map_err(|_| invalid())?),
+    };
+    if sets == 0 || reps == 0 {
+        return Err(invalid());
+    }
+
+    Ok((0..sets)
+        .map(|_| StrengthSet {
+            start_offset: 0,
+            duration_seconds: 0,
+            exercise: Some(exercise.clone()),
+            reps: Some(reps),
+            weight,
+            set_type: SetType::Active,
+        })
+        .collect())
+}
+
+fn set_tonnage(set: &StrengthSet) -> Decimal {
+    match (set.reps, set.weight) {
+        (Some(reps), Some(weight)) => Decimal::from(reps) * weight,
+        _ => Decimal::ZERO,
+    }
+}
+
+fn set_e1rm(set: &StrengthSet) -> Option<Decimal> {
+    if set.set_type != SetType::Active {
+        return None;
+    }
+    estimated_one_rep_max(set.weight.filter(|w| *w > Decimal::ZERO)?, set.reps?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{DataSource, Sport, WorkoutSummary, WorkoutType};
+
+    fn strength_workout(date: NaiveDate, sets: Vec<StrengthSet>) -> Workout {
+        Workout {
+            id: format!("gym-{}", date),
+            date,
+            sport: Sport::CrossTraining,
+            duration_seconds: 3600,
+            workout_type: WorkoutType::Strength,
+            data_source: DataSource::Rpe,
+            raw_data: None,
+            summary: WorkoutSummary::default(),
+            notes: None,
+            athlete_id: None,
+            source: None,
+            swim_lengths: Vec::new(),
+            start_time: None,
+            laps: Vec::new(),
+            rpe: Some(7),
+            strength_sets: sets,
+        }
+    }
+
+    #[test]
+    fn test_parse_set_entry() {
+        let sets = parse_set_entry("Bench Press 3x8@60").unwrap();
+        assert_eq!(sets.len(), 3);
+        assert_eq!(sets[0].exercise.as_deref(), Some("bench_press"));
+        assert_eq!(sets[0].reps, Some(8));
+        assert_eq!(sets[0].weight, Some(dec!(60)));
+
+        let single = parse_set_entry("pull-up 10").unwrap();
+        assert_eq!(single.len(), 1);
+        assert_eq!(single[0].exercise.as_deref(), Some("pull_up"));
+        assert_eq!(single[0].weight, None);
+
+        assert!(parse_set_entry("5x5@100").is_err());
+        assert!(parse_set_entry("squat 0x5@100").is_err());
+        assert!(parse_set_entry("squat 5x5@heavy").is_err());
+    }
+
+    #[test]
+    fn test_estimated_one_rep_max() {
+        assert_eq!(estimated_one_rep_max(dec!(100), 1), Some(dec!(100)));
+        assert_eq!(estimated_one_rep_max(dec!(100), 5), Some(dec!(116.7)));
+        assert_eq!(estimated_one_rep_max(dec!(100), 0), None);
+        assert_eq!(estimated_one_rep_max(dec!(100), 20), None);
+    }
+
+    #[test]
+    fn test_analyze_tonnage_and_exercises() {
+        let mut sets = parse_set_entry("squat 5x5@100").unwrap();
+        sets.push(StrengthSet {
+            start_offset: 0,
+            duration_seconds: 120,
+            exercise: None,
+            reps: None,
+            weight: None,
+            set_type: SetType::Rest,
+        });
+        sets.extend(parse_set_entry("pull_up 3x10").unwrap());
+        let workout = strength_workout(NaiveDate::from_ymd_opt(2025, 1, 6).unwrap(), sets);
+
+        let analysis = StrengthAnalyzer::analyze(&workout).unwrap();
+        assert_eq!(analysis.total_sets, 8);
+        assert_eq!(analysis.total_reps, 55);
+        assert_eq!(analysis.tonnage, dec!(2500));
+        assert_eq!(analysis.session_load, Some(420));
+        assert_eq!(analysis.exercises[0].exercise, "squat");
+        assert_eq!(analysis.exercises[0].best_e1rm, Some(dec!(116.7)));
+        assert_eq!(analysis.exercises[1].tonnage, Decimal::ZERO);
+        assert_eq!(analysis.exercises[1].best_e1rm, None);
+
+        let empty = strength_workout(NaiveDate::from_ymd_opt(2025, 1, 6).unwrap(), Vec::new());
+        assert!(StrengthAnalyzer::analyze(&empty).is_err());
+    }
+
+    #[test]
+    fn test_e1rm_progression_keeps_best_per_day() {
+        let monday = NaiveDate::from_ymd_opt(2025, 1, 6).unwrap();
+        let thursday = NaiveDate::from_ymd_opt(2025, 1, 9).unwrap();
+        let mut first = parse_set_entry("squat 3x5@100").unwrap();
+        first.extend(parse_set_entry("squat 1x3@110").unwrap());
+        let workouts = vec![
+            strength_workout(thursday, parse_set_entry("squat 3x5@105").unwrap()),
+            strength_workout(monday, first),
+        ];
+
+        let progression = StrengthAnalyzer::e1rm_progression(&workouts);
+        let squat = &progression["squat"];
+        assert_eq!(squat.len(), 2);
+        assert_eq!(squat[0].date, monday);
+        assert_eq!(squat[0].e1rm, dec!(121.0));
+        assert_eq!(squat[0].reps, 3);
+        assert_eq!(squat[1].e1rm, dec!(122.5));
+    }
+}
@@ -0,0 +1,437 @@
+//! Structured workout definitions
+//!
+//! A structured workout is a sequence of steps (warmup, intervals, recoveries,
+//! cooldown), optionally grouped into repeat blocks. Each step ends after a
+//! duration or a distance and carries a target expressed relative to the
+//! athlete's thresholds (%FTP, %LTHR), as an absolute pace range, or as RPE.
+//!
+//! Planned load is derived from the steps: every step contributes
+//! `hours × IF² × 100` TSS, and the session IF is recovered from the total.
+
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+
+use crate::models::{AthleteProfile, Sport};
+
+/// Role of a step within the session
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StepKind {
+    Warmup,
+    /// Work interval
+    Interval,
+    /// Recovery between work intervals
+    Recovery,
+    /// Continuous effort, e.g. the main part of an endurance session
+    Steady,
+    Cooldown,
+}
+
+/// Condition ending a step
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StepDuration {
+    Time { seconds: u32 },
+    Distance { meters: u32 },
+}
+
+impl StepDuration {
+    pub fn minutes(minutes: u32) -> Self {
+        StepDuration::Time { seconds: minutes * 60 }
+    }
+
+    /// Compact label such as "8min", "90s", "800m" or "5km"
+    pub fn label(&self) -> String {
+        match *self {
+            StepDuration::Time { seconds } if seconds % 60 == 0 => format!("{}min", seconds / 60),
+            StepDuration::Time { seconds } if seconds > 60 => format!("{}min{:02}s", seconds / 60, seconds % 60),
+            StepDuration::Time { seconds } => format!("{}s", seconds),
+            StepDuration::Distance { meters } if meters >= 1000 && meters % 100 == 0 => {
+                format!("{}km", Decimal::from(meters) / dec!(1000))
+            }
+            StepDuration::Distance { meters } => format!("{}m", meters),
+        }
+    }
+}
+
+/// Intensity target for a step
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum StepTarget {
+    /// Power range as percent of FTP
+    PercentFtp { low: u16, high: u16 },
+    /// Heart rate range as percent of LTHR
+    PercentLthr { low: u16, high: u16 },
+    /// Pace range in minutes per km (`fast` < `slow`)
+    Pace { fast: Decimal, slow: Decimal },
+    /// Rating of perceived exertion (1-10)
+    Rpe(u8),
+    /// No specific target
+    Open,
+}
+
+impl StepTarget {
+    /// Build the natural target for a sport from an intensity range
+    ///
+    /// Intensities are fractions of threshold (1.0 = FTP/threshold pace). Cycling
+    /// uses %FTP; running uses a pace range when threshold pace is known and %LTHR
+    /// otherwise; other sports use %LTHR or fall back to RPE.
+    pub fn for_sport(sport: &Sport, athlete: &AthleteProfile, low: Decimal, high: Decimal) -> Self {
+        match sport {
+            Sport::Cycling => StepTarget::PercentFtp {
+                low: Self::percent(low),
+                high: Self::percent(high),
+            },
+            Sport::Running if athlete.threshold_pace.is_some() => {
+                let threshold = athlete.threshold_pace.unwrap();
+                StepTarget::Pace {
+                    fast: (threshold / high).round_dp(2),
+                    slow: (threshold / low).round_dp(2),
+                }
+            }
+            _ if athlete.lthr.is_some() => StepTarget::PercentLthr {
+                low: Self::percent(Self::lthr_fraction(low)),
+                high: Self::percent(Self::lthr_fraction(high)),
+            },
+            _ => StepTarget::Rpe(Self::rpe_for_intensity((low + high) / dec!(2))),
+        }
+    }
+
+    /// Intensity factor implied by the target, relative to threshold
+    pub fn intensity_factor(&self, athlete: &AthleteProfile) -> Decimal {
+        match *self {
+            StepTarget::PercentFtp { low, high } => Decimal::from(low + high) / dec!(200),
+            StepTarget::PercentLthr { low, high } => {
+                Self::intensity_from_lthr_fraction(Decimal::from(low + high) / dec!(200))
+            }
+            StepTarget::Pace { fast, slow } => match athlete.threshold_pace {
+                Some(threshold) if fast + slow > Decimal::ZERO => threshold / ((fast + slow) / dec!(2)),
+                _ => dec!(0.75),
+            },
+            StepTarget::Rpe(rpe) => Self::rpe_intensity(rpe),
+            StepTarget::Open => dec!(0.60),
+        }
+        .round_dp(3)
+    }
+
+    /// Target as written on a workout card, e.g. "95-105% FTP" or "4:17-4:44/km"
+    pub fn label(&self) -> String {
+        match *self {
+            StepTarget::PercentFtp { low, high } if low == high => format!("{}% FTP", low),
+            StepTarget::PercentFtp { low, high } => format!("{}-{}% FTP", low, high),
+            StepTarget::PercentLthr { low, high } if low == high => format!("{}% LTHR", low),
+            StepTarget::PercentLthr { low, high } => format!("{}-{}% LTHR", low, high),
+            StepTarget::Pace { fast, slow } if fast == slow => format!("{}/km", Self::format_pace(fast)),
+            StepTarget::Pace { fast, slow } => format!("{}-{}/km", Self::format_pace(fast), Self::format_pace(slow)),
+            StepTarget::Rpe(rpe) => format!("RPE {}", rpe),
+            StepTarget::Open => "open".to_string(),
+        }
+    }
+
+    fn percent(fraction: Decimal) -> u16 {
+        (fraction * dec!(100)).round().to_u16().unwrap_or(0)
+    }
+
+    /// Heart rate lags and compresses relative to power: 55% FTP ≈ 82% LTHR,
+    /// threshold ≈ 100% LTHR
+    fn lthr_fraction(intensity: Decimal) -> Decimal {
+        dec!(0.6) + dec!(0.4) * intensity
+    }
+
+    fn intensity_from_lthr_fraction(fraction: Decimal) -> Decimal {
+        ((fraction - dec!(0.6)) / dec!(0.4)).max(dec!(0.3))
+    }
+
+    /// Intensity factor of an RPE (1-10); needs no athlete thresholds
+    pub fn rpe_intensity(rpe: u8) -> Decimal {
+        (dec!(0.45) + dec!(0.065) * Decimal::from(rpe.clamp(1, 10))).round_dp(3)
+    }
+
+    fn rpe_for_intensity(intensity: Decimal) -> u8 {
+        ((intensity - dec!(0.45)) / dec!(0.065)).round().to_u8().unwrap_or(1).clamp(1, 10)
+    }
+
+    fn format_pace(minutes_per_km: Decimal) -> String {
+        let total_seconds = (minutes_per_km * dec!(60)).round().to_u32().unwrap_or(0);
+        format!("{}:{:02}", total_seconds / 60, total_seconds % 60)
+    }
+}
+
+/// Single workout step
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorkoutStep {
+    pub kind: StepKind,
+    pub duration: StepDuration,
+    pub target: StepTarget,
+    pub notes: Option<String>,
+}
+
+impl WorkoutStep {
+    pub fn new(kind: StepKind, duration: StepDuration, target: StepTarget) -> Self {
+        WorkoutStep {
+            kind,
+            duration,
+            target,
+            notes: None,
+        }
+    }
+
+    /// Estimated step duration in seconds
+    ///
+    /// Distance steps use the target pace when given, otherwise a speed derived
+    /// from the step intensity and the athlete's threshold pace.
+    pub fn estimated_seconds(&self, sport: &Sport, athlete: &AthleteProfile) -> u32 {
+        match self.duration {
+            StepDuration::Time { seconds } => seconds,
+            StepDuration::Distance { meters } => {
+                let km = Decimal::from(meters) / dec!(1000);
+                let minutes_per_km = match self.target {
+                    StepTarget::Pace { fast, slow } => (fast + slow) / dec!(2),
+                    _ => {
+                        let intensity = self.target.intensity_factor(athlete).max(dec!(0.3));
+                        let threshold_pace = match sport {
+                            Sport::Running => athlete.threshold_pace.unwrap_or(dec!(5.0)),
+                            Sport::Cycling => dec!(1.6),  // ~37 km/h at threshold
+                            Sport::Swimming => dec!(17.0), // ~1:42/100m at threshold
+                            Sport::Rowing => dec!(3.8),   // ~1:54/500m at threshold
+                            _ => dec!(5.0),
+                        };
+                        threshold_pace / intensity
+                    }
+                };
+                (km * minutes_per_km * dec!(60)).round().to_u32().unwrap_or(0)
+            }
+        }
+    }
+
+    /// Step as written on a workout card, e.g. "8min @ 95-105% FTP"
+    pub fn label(&self) -> String {
+        match self.target {
+            StepTarget::Open => self.duration.label(),
+            target => format!("{} @ {}", self.duration.label(), target.label()),
+        }
+    }
+}
+
+/// Step or repeated group of steps
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum WorkoutBlock {
+    Step(WorkoutStep),
+    Repeat { count: u32, steps: Vec<WorkoutStep> },
+}
+
+/// Planned load derived from the steps
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StructuredLoad {
+    pub duration_seconds: u32,
+    pub tss: Decimal,
+    pub intensity_factor: Decimal,
+}
+
+/// Structured workout made of steps and repeat blocks
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StructuredWorkout {
+    pub name: String,
+    pub sport: Sport,
+    pub blocks: Vec<WorkoutBlock>,
+}
+
+impl StructuredWorkout {
+    pub fn new(name: &str, sport: Sport) -> Self {
+        StructuredWorkout {
+            name: name.to_string(),
+            sport,
+            blocks: Vec::new(),
+        }
+    }
+
+    /// Append a single step
+    pub fn step(mut self, kind: StepKind, duration: StepDuration, target: StepTarget) -> Self {
+        self.blocks.push(WorkoutBlock::Step(WorkoutStep::new(kind, duration, target)));
+        self
+    }
+
+    /// Append a repeat block
+    pub fn repeat(mut self, count: u32, steps: Vec<WorkoutStep>) -> Self {
+        if count > 0 && !steps.is_empty() {
+            self.blocks.push(WorkoutBlock::Repeat { count, steps });
+        }
+        self
+    }
+
+    /// All steps in execution order with repeats expanded
+    pub fn flatten(&self) -> Vec<&WorkoutStep> {
+        let mut steps = Vec::new();
+        for block in &self.blocks {
+            match block {
+                WorkoutBlock::Step(step) => steps.push(step),
+                WorkoutBlock::Repeat { count, steps: group } => {
+                    for _ in 0..*count {
+                        steps.extend(group.iter());
+                    }
+                }
+            }
+        }
+        steps
+    }
+
+    /// Duration, TSS and IF computed from the steps
+    pub fn calculate_load(&self, athlete: &AthleteProfile) -> StructuredLoad {
+        let mut duration_seconds = 0u32;
+        let mut tss = Decimal::ZERO;
+
+        for step in self.flatten() {
+            let seconds = step.estimated_seconds(&self.sport, athlete);
+            let intensity = step.target.intensity_factor(athlete);
+            duration_seconds += seconds;
+            tss += Decimal::from(seconds) / dec!(3600) * intensity * intensity * dec!(100);
+        }
+
+        let hours = Decimal::from(duration_seconds) / dec!(3600);
+        let intensity_factor = if hours > Decimal::ZERO {
+            (tss / (hours * dec!(100)))
+                .to_f64()
+                .and_then(|v| Decimal::from_f64(v.sqrt()))
+                .unwrap_or(Decimal::ZERO)
+        } else {
+            Decimal::ZERO
+        };
+
+        StructuredLoad {
+            duration_seconds,
+            tss: tss.round_dp(1),
+            intensity_factor: intensity_factor.round_dp(2),
+        }
+    }
+
+    /// Main set description, e.g. "4×8min @ 95-105% FTP"
+    ///
+    /// Warmup and cooldown are omitted; recoveries inside repeats are shown after
+    /// a slash.
+    pub fn summary(&self) -> String {
+        let parts: Vec<String> = self
+            .blocks
+            .iter()
+            .filter_map(|block| match block {
+                WorkoutBlock::Step(step) if matches!(step.kind, StepKind::Warmup | StepKind::Cooldown) => None,
+                WorkoutBlock::Step(step) => Some(step.label()),
+                WorkoutBlock::Repeat { count, steps } => {
+                    let work: Vec<String> = steps.iter().filter(|s| s.kind != StepKind::Recovery).map(|s| s.label()).collect();
+                    let recovery: Vec<String> = steps
+                        .iter()
+                        .filter(|s| s.kind == StepKind::Recovery)
+                        .map(|s| format!("{} easy", s.duration.label()))
+                        .collect();
+                    let mut text = format!("{}×{}", count, work.join(" + "));
+                    if !recovery.is_empty() {
+                        text.push_str(&format!(" / {}", recovery.join(" + ")));
+                    }
+                    Some(text)
+                }
+            })
+            .collect();
+
+        if parts.is_empty() {
+            self.name.clone()
+        } else {
+            parts.join(", ")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn athlete() -> AthleteProfile {
+        AthleteProfile {
+            id: "test".to_string(),
+            name: "Test".to_string(),
+            date_of_birth: None,
+            weight: Some(dec!(70)),
+            height: None,
+            ftp: Some(250),
+            lthr: Some(165),
+            threshold_pace: Some(dec!(4.5)),
+            max_hr: Some(185),
+            resting_hr: Some(50),
+            training_zones: Default::default(),
+            preferred_units: crate::models::Units::Metric,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            rowing_ftp: None,
+            css_pace: None,
+        }
+    }
+
+    fn threshold_session() -> StructuredWorkout {
+        let ftp = |low, high| StepTarget::PercentFtp { low, high };
+        StructuredWorkout::new("Threshold", Sport::Cycling)
+            .step(StepKind::Warmup, StepDuration::minutes(15), ftp(50, 65))
+            .repeat(
+                4,
+                vec![
+                    WorkoutStep::new(StepKind::Interval, StepDuration::minutes(8), ftp(95, 105)),
+                    WorkoutStep::new(StepKind::Recovery, StepDuration::minutes(4), ftp(50, 60)),
+                ],
+            )
+            .step(StepKind::Cooldown, StepDuration::minutes(10), ftp(50, 60))
+    }
+
+    #[test]
+    fn test_load_from_steps() {
+        let athlete = athlete();
+        let workout = threshold_session();
+
+        assert_eq!(workout.flatten().len(), 10);
+
+        let load = workout.calculate_load(&athlete);
+        assert_eq!(load.duration_seconds, 73 * 60);
+        // 32 min @ 1.00, 16 min @ 0.55, 15 min @ 0.575, 10 min @ 0.55
+        let expected = (dec!(32) * dec!(1.0) + dec!(26) * dec!(0.3025) + dec!(15) * dec!(0.330625)) / dec!(60) * dec!(100);
+        assert!((load.tss - expected).abs() < dec!(0.2));
+        assert!(load.intensity_factor > dec!(0.75) && load.intensity_factor < dec!(0.85));
+    }
+
+    #[test]
+    fn test_summary_and_labels() {
+        assert_eq!(threshold_session().summary(), "4×8min @ 95-105% FTP / 4min easy");
+        assert_eq!(StepDuration::Distance { meters: 800 }.label(), "800m");
+        assert_eq!(StepDuration::Distance { meters: 5000 }.label(), "5km");
+        assert_eq!(StepDuration::Time { seconds: 90 }.label(), "1min30s");
+    }
+
+    #[test]
+    fn test_targets_per_sport() {
+        let mut athlete = athlete();
+
+        let run = StepTarget::for_sport(&Sport::Running, &athlete, dec!(0.95), dec!(1.05));
+        assert_eq!(run.label(), "4:17-4:44/km");
+        assert!((run.intensity_factor(&athlete) - dec!(1.0)).abs() < dec!(0.01));
+
+        let swim = StepTarget::for_sport(&Sport::Swimming, &athlete, dec!(0.95), dec!(1.05));
+        assert_eq!(swim, StepTarget::PercentLthr { low: 98, high: 102 });
+        assert!((swim.intensity_factor(&athlete) - dec!(1.0)).abs() < dec!(0.01));
+
+        athlete.lthr = None;
+        let rpe = StepTarget::for_sport(&Sport::Rowing, &athlete, dec!(0.95), dec!(1.05));
+        assert_eq!(rpe, StepTarget::Rpe(8));
+    }
+
+    #[test]
+    fn test_distance_steps_use_pace() {
+        let athlete = athlete();
+        let target = StepTarget::Pace { fast: dec!(4.0), slow: dec!(4.0) };
+        let workout = StructuredWorkout::new("Track", Sport::Running).repeat(
+            6,
+            vec![
+                WorkoutStep::new(StepKind::Interval, StepDuration::Distance { meters: 800 }, target),
+                WorkoutStep::new(StepKind::Recovery, StepDuration::Time { seconds: 90 }, StepTarget::Open),
+            ],
+        );
+
+        let load = workout.calculate_load(&athlete);
+        // 6 × (192 s + 90 s)
+        assert_eq!(load.duration_seconds, 6 * 282);
+        assert_eq!(workout.summary(), "6×800m @ 4:00/km / 1min30s easy");
+    }
+}
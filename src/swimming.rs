@@ -2,10 +2,17 @@
 //!
 //! This module provides comprehensive swimming analysis including stroke analysis,
 //! SWOLF calculation, pool lap detection, and swimming efficiency metrics.
+//!
+//! Pool swims imported with per-length data are split into sets at rest
+//! intervals. Critical Swim Speed (CSS) comes from 400 m and 200 m test sets
+//! found in those: `CSS = (400 - 200) / (T400 - T200)`. Drill and kick lengths
+//! count towards training load but not towards pace metrics.
 
 #![allow(dead_code)]
 
-use crate::models::{DataPoint, Sport, Workout};
+use crate::models::{DataPoint, Lap, LapIntensity, Sport, SwimLength, SwimLengthType, Workout};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
 use anyhow::{anyhow, Result};
 use rust_decimal::prelude::*;
 use rust_decimal::Decimal;
@@ -39,6 +46,8 @@ pub struct SwimmingAnalysis {
     pub pace_analysis: SwimmingPaceAnalysis,
     /// Efficiency metrics
     pub efficiency_metrics: SwimmingEfficiencyMetrics,
+    /// Continuous swims between rests (from pool lengths)
+    pub sets: Vec<SwimSet>,
 }
 
 /// SWOLF (Swimming Golf) analysis - measures efficiency
@@ -78,6 +87,8 @@ pub struct LapAnalysis {
     pub stroke_type: Option<SwimStrokeType>,
     /// Distance covered in this lap (meters)
     pub distance_meters: Option<Decimal>,
+    /// Work or rest, when the device recorded the lap
+    pub intensity: Option<LapIntensity>,
 }
 
 /// Swimming pace analysis
@@ -91,6 +102,68 @@ pub struct SwimmingPaceAnalysis {
     pub pace_consistency: Option<u8>,
 }
 
+/// Continuous swim between two rests
+#[derive(Debug, Clone, PartialEq)]
+pub struct SwimSet {
+    /// Index of the first length in the workout
+    pub first_length: usize,
+    /// Number of lengths
+    pub lengths: usize,
+    /// Distance in meters
+    pub distance: Decimal,
+    /// Swim time in seconds
+    pub duration_seconds: Decimal,
+    /// Stroke when every length used the same one
+    pub stroke_type: Option<SwimStrokeType>,
+    /// Set contains drill or kick lengths
+    pub drill_or_kick: bool,
+}
+
+impl SwimSet {
+    /// Pace in seconds per 100m
+    pub fn pace_per_100m(&self) -> Option<Decimal> {
+        (self.distance > Decimal::ZERO).then(|| self.duration_seconds / self.distance * dec!(100))
+    }
+}
+
+/// Critical Swim Speed from a 400 m and a 200 m test set
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CssTest {
+    /// 400 m time in seconds
+    pub time_400: Decimal,
+    /// 200 m time in seconds
+    pub time_200: Decimal,
+    /// Workouts the 400 m and 200 m sets were swum in
+    pub workout_400: String,
+    pub workout_200: String,
+    /// Date of the later of the two sets
+    pub date: NaiveDate,
+    /// Critical swim speed in m/s
+    pub css_speed: Decimal,
+    /// CSS pace in seconds per 100m
+    pub css_pace_per_100m: Decimal,
+}
+
+impl CssTest {
+    /// Calculate CSS from test times; the 400 must be paced slower than the 200
+    pub fn from_times(time_400: Decimal, time_200: Decimal) -> Result<(Decimal, Decimal)> {
+        if time_200 <= Decimal::ZERO || time_400 <= time_200 * dec!(2) {
+            return Err(SwimmingError::CalculationError(format!(
+                "400 m time ({}s) must be more than twice the 200 m time ({}s)",
+                time_400, time_200
+            ))
+            .into());
+        }
+        let difference = time_400 - time_200;
+        Ok(((dec!(200) / difference).round_dp(3), (difference / dec!(2)).round_dp(1)))
+    }
+
+    /// CSS pace in minutes per 100m
+    pub fn pace_min_per_100m(&self) -> Decimal {
+        (self.css_pace_per_100m / dec!(60)).round_dp(2)
+    }
+}
+
 /// Swimming efficiency metrics
 #[derive(Debug, Clone)]
 pub struct SwimmingEfficiencyMetrics {
@@ -153,10 +226,8 @@ impl SwimmingAnalyzer {
             return Err(anyhow!("Workout must be a swimming activity"));
         }
 
-        let raw_data = workout.raw_data.as_ref()
-            .ok_or_else(|| SwimmingError::InsufficientData("No raw data available".to_string()))?;
-
-        if raw_data.is_empty() {
+        let raw_data = workout.raw_data.as_deref().unwrap_or(&[]);
+        if raw_data.is_empty() && workout.swim_lengths.is_empty() {
             return Err(SwimmingError::InsufficientData("No data points available".to_string()).into());
         }
 
@@ -169,8 +240,12 @@ impl SwimmingAnalyzer {
         let avg_strokes_per_lap = Self::calculate_avg_strokes_per_lap(&swimming_data);
         let swolf_analysis = Self::analyze_swolf(&swimming_data, raw_data)?;
         let stroke_distribution = Self::analyze_stroke_distribution(&swimming_data, workout.duration_seconds);
-        let lap_analysis = Self::analyze_laps(&swimming_data, raw_data)?;
-        let pace_analysis = Self::analyze_pace(&swimming_data, raw_data)?;
+        let lap_analysis = Self::analyze_laps(&swimming_data, raw_data, &workout.laps)?;
+        let pace_analysis = if workout.swim_lengths.is_empty() {
+            Self::analyze_pace(&swimming_data, raw_data)?
+        } else {
+            Self::analyze_length_pace(&workout.swim_lengths)
+        };
         let efficiency_metrics = Self::calculate_efficiency_metrics(&swimming_data, &lap_analysis)?;
 
         Ok(SwimmingAnalysis {
@@ -181,9 +256,122 @@ impl SwimmingAnalyzer {
             lap_analysis,
             pace_analysis,
             efficiency_metrics,
+            sets: Self::detect_sets(&workout.swim_lengths),
         })
     }
 
+    /// Whether a length is drill or kick: drill stroke, or swum without arm strokes
+    pub fn is_drill_or_kick(length: &SwimLength) -> bool {
+        length.length_type == SwimLengthType::Active
+            && (length.stroke_type == Some(SwimStrokeType::Drill as u8) || length.strokes == Some(0))
+    }
+
+    /// Split lengths into continuous sets at idle (rest) lengths
+    pub fn detect_sets(lengths: &[SwimLength]) -> Vec<SwimSet> {
+        let mut sets: Vec<SwimSet> = Vec::new();
+        let mut current: Option<SwimSet> = None;
+
+        for (index, length) in lengths.iter().enumerate() {
+            if length.length_type == SwimLengthType::Idle {
+                sets.extend(current.take());
+                continue;
+            }
+
+            let stroke = length.stroke_type.map(SwimStrokeType::from);
+            let set = current.get_or_insert_with(|| SwimSet {
+                first_length: index,
+                lengths: 0,
+                distance: Decimal::ZERO,
+                duration_seconds: Decimal::ZERO,
+                stroke_type: stroke.clone(),
+                drill_or_kick: false,
+            });
+            if set.stroke_type != stroke {
+                set.stroke_type = None;
+            }
+            set.lengths += 1;
+            set.distance += length.distance;
+            set.duration_seconds += length.duration_seconds;
+            set.drill_or_kick |= Self::is_drill_or_kick(length);
+        }
+        sets.extend(current);
+        sets
+    }
+
+    /// Fastest swum (not drill or kick) set of the given distance
+    ///
+    /// A set matches when it is within half a pool length of the distance.
+    fn fastest_set(lengths: &[SwimLength], distance: Decimal) -> Option<SwimSet> {
+        Self::detect_sets(lengths)
+            .into_iter()
+            .filter(|set| !set.drill_or_kick && set.lengths > 0)
+            .filter(|set| {
+                let pool_length = set.distance / Decimal::from(set.lengths as u32);
+                (set.distance - distance).abs() < pool_length / dec!(2)
+            })
+            .min_by_key(|set| set.duration_seconds)
+    }
+
+    /// Find a CSS test: a 400 m and a 200 m set swum as hard efforts
+    ///
+    /// The most recent workout containing both sets is used. Otherwise the
+    /// fastest 400 m and 200 m sets across the workouts are combined.
+    pub fn detect_css_test(workouts: &[Workout]) -> Option<CssTest> {
+        let mut swims: Vec<&Workout> = workouts
+            .iter()
+            .filter(|w| w.sport == Sport::Swimming && !w.swim_lengths.is_empty())
+            .collect();
+        swims.sort_by_key(|w| std::cmp::Reverse(w.date));
+
+        let test = |set_400: (&SwimSet, &Workout), set_200: (&SwimSet, &Workout)| -> Option<CssTest> {
+            let (css_speed, css_pace_per_100m) =
+                CssTest::from_times(set_400.0.duration_seconds, set_200.0.duration_seconds).ok()?;
+            Some(CssTest {
+                time_400: set_400.0.duration_seconds,
+                time_200: set_200.0.duration_seconds,
+                workout_400: set_400.1.id.clone(),
+                workout_200: set_200.1.id.clone(),
+                date: set_400.1.date.max(set_200.1.date),
+                css_speed,
+                css_pace_per_100m,
+            })
+        };
+
+        let mut best_400: Option<(SwimSet, &Workout)> = None;
+        let mut best_200: Option<(SwimSet, &Workout)> = None;
+        for workout in &swims {
+            let set_400 = Self::fastest_set(&workout.swim_lengths, dec!(400));
+            let set_200 = Self::fastest_set(&workout.swim_lengths, dec!(200));
+            if let (Some(s400), Some(s200)) = (&set_400, &set_200) {
+                if let Some(css) = test((s400, workout), (s200, workout)) {
+                    return Some(css);
+                }
+            }
+            if let Some(set) = set_400.filter(|s| best_400.as_ref().is_none_or(|b| s.duration_seconds < b.0.duration_seconds)) {
+                best_400 = Some((set, workout));
+            }
+            if let Some(set) = set_200.filter(|s| best_200.as_ref().is_none_or(|b| s.duration_seconds < b.0.duration_seconds)) {
+                best_200 = Some((set, workout));
+            }
+        }
+
+        let (set_400, workout_400) = best_400?;
+        let (set_200, workout_200) = best_200?;
+        test((&set_400, workout_400), (&set_200, workout_200))
+    }
+
+    /// Pace metrics from active lengths, leaving out drill and kick
+    fn analyze_length_pace(lengths: &[SwimLength]) -> SwimmingPaceAnalysis {
+        let pace_values: Vec<Decimal> = lengths
+            .iter()
+            .filter(|l| l.length_type == SwimLengthType::Active && !Self::is_drill_or_kick(l))
+            .filter(|l| l.distance > Decimal::ZERO && l.duration_seconds > Decimal::ZERO)
+            .map(|l| l.duration_seconds / l.distance * dec!(100))
+            .collect();
+
+        Self::pace_statistics(&pace_values)
+    }
+
     /// Calculate total stroke count for the workout
     fn calculate_total_strokes(swimming_data: &[&DataPoint]) -> u32 {
         swimming_data.iter()
@@ -337,7 +525,10 @@ impl SwimmingAnalyzer {
     }
 
     /// Analyze individual lap performance
-    fn analyze_laps(swimming_data: &[&DataPoint], all_data: &[DataPoint]) -> Result<Vec<LapAnalysis>> {
+    ///
+    /// Device laps, when recorded, supply the lap time and distance and mark
+    /// rest intervals, which are listed even without strokes.
+    fn analyze_laps(swimming_data: &[&DataPoint], all_data: &[DataPoint], device_laps: &[Lap]) -> Result<Vec<LapAnalysis>> {
         let mut lap_analysis = Vec::new();
         let mut lap_data: HashMap<u16, Vec<&DataPoint>> = HashMap::new();
 
@@ -347,6 +538,11 @@ impl SwimmingAnalyzer {
                 lap_data.entry(lap).or_insert_with(Vec::new).push(dp);
             }
         }
+        for (index, lap) in device_laps.iter().enumerate() {
+            if lap.intensity == LapIntensity::Rest {
+                lap_data.entry(index as u16 + 1).or_default();
+            }
+        }
 
         for (lap_num, lap_points) in lap_data {
             // Calculate lap metrics
@@ -379,6 +575,13 @@ impl SwimmingAnalyzer {
                 .filter_map(|dp| dp.distance)
                 .max(); // Take the maximum distance value in the lap
 
+            let device_lap = device_laps.get(usize::from(lap_num).wrapping_sub(1));
+            let duration_seconds = device_lap
+                .map(|lap| lap.duration_seconds)
+                .filter(|seconds| *seconds > 0)
+                .unwrap_or(duration_seconds);
+            let distance_meters = device_lap.and_then(|lap| lap.distance).or(distance_meters);
+
             // Calculate SWOLF score
             let swolf_score = if stroke_count > 0 && duration_seconds > 0 {
                 Decimal::from(stroke_count) + Decimal::from(duration_seconds)
@@ -406,6 +609,7 @@ impl SwimmingAnalyzer {
                 pace,
                 stroke_type,
                 distance_meters,
+                intensity: device_lap.map(|lap| lap.intensity),
             });
         }
 
@@ -417,8 +621,9 @@ impl SwimmingAnalyzer {
 
     /// Analyze swimming pace metrics
     fn analyze_pace(_swimming_data: &[&DataPoint], all_data: &[DataPoint]) -> Result<SwimmingPaceAnalysis> {
-        // Calculate pace per 100m from speed and distance data
+        // Calculate pace per 100m from speed and distance data, leaving out drills
         let pace_values: Vec<Decimal> = all_data.iter()
+            .filter(|dp| dp.stroke_type != Some(SwimStrokeType::Drill as u8))
             .filter_map(|dp| {
                 if let Some(speed) = dp.speed {
                     if speed > Decimal::ZERO {
@@ -433,6 +638,11 @@ impl SwimmingAnalyzer {
             })
             .collect();
 
+        Ok(Self::pace_statistics(&pace_values))
+    }
+
+    /// Average, best and consistency of per-100m paces
+    fn pace_statistics(pace_values: &[Decimal]) -> SwimmingPaceAnalysis {
         let avg_pace_per_100m = if !pace_values.is_empty() {
             Some(pace_values.iter().sum::<Decimal>() / Decimal::from(pace_values.len()))
         } else {
@@ -462,11 +672,11 @@ impl SwimmingAnalyzer {
             None
         };
 
-        Ok(SwimmingPaceAnalysis {
+        SwimmingPaceAnalysis {
             avg_pace_per_100m,
             best_pace_per_100m,
             pace_consistency,
-        })
+        }
     }
 
     /// Calculate swimming efficiency metrics
@@ -579,6 +789,9 @@ mod tests {
                 stroke_type: Some(0), // Freestyle
                 lap_number: Some(1),
                 sport_transition: None,
+                temperature: None,
+                drive_length: None,
+                stroke_work: None,
             },
             // End of lap 1 / 25m mark
             DataPoint {
@@ -599,6 +812,9 @@ mod tests {
                 stroke_type: Some(0), // Freestyle
                 lap_number: Some(1),
                 sport_transition: None,
+                temperature: None,
+                drive_length: None,
+                stroke_work: None,
             },
             // Start of lap 2
             DataPoint {
@@ -619,6 +835,9 @@ mod tests {
                 stroke_type: Some(0), // Freestyle
                 lap_number: Some(2),
                 sport_transition: None,
+                temperature: None,
+                drive_length: None,
+                stroke_work: None,
             },
             // End of lap 2 / 50m mark
             DataPoint {
@@ -639,6 +858,9 @@ mod tests {
                 stroke_type: Some(0), // Freestyle
                 lap_number: Some(2),
                 sport_transition: None,
+                temperature: None,
+                drive_length: None,
+                stroke_work: None,
             },
         ]
     }
@@ -671,6 +893,11 @@ mod tests {
             notes: None,
             athlete_id: None,
             source: None,
+            swim_lengths: Vec::new(),
+            start_time: None,
+            laps: Vec::new(),
+            rpe: None,
+            strength_sets: Vec::new(),
         };
 
         let analysis = SwimmingAnalyzer::analyze_swimming(&workout).unwrap();
@@ -691,6 +918,38 @@ mod tests {
         assert!(analysis.efficiency_metrics.stroke_rate.is_some());
     }
 
+    #[test]
+    fn test_device_laps_supply_distance_and_rest() {
+        let lap = |start_offset, duration_seconds, distance, intensity| Lap {
+            start_offset,
+            duration_seconds,
+            distance,
+            avg_heart_rate: None,
+            max_heart_rate: None,
+            avg_power: None,
+            max_power: None,
+            trigger: crate::models::LapTrigger::Manual,
+            intensity,
+        };
+        let device_laps = vec![
+            lap(0, 20, Some(dec!(25)), LapIntensity::Active),
+            lap(20, 22, Some(dec!(25)), LapIntensity::Active),
+            lap(42, 30, None, LapIntensity::Rest),
+        ];
+        let data = create_sample_swimming_data();
+        let swimming_data: Vec<&DataPoint> = data.iter().collect();
+
+        let laps = SwimmingAnalyzer::analyze_laps(&swimming_data, &data, &device_laps).unwrap();
+        assert_eq!(laps.len(), 3);
+        // Lap distance from the device rather than the cumulative distance
+        assert_eq!(laps[1].distance_meters, Some(dec!(25)));
+        assert_eq!(laps[1].duration_seconds, 22);
+        assert_eq!(laps[1].pace, Some(dec!(88)));
+        assert_eq!(laps[2].intensity, Some(LapIntensity::Rest));
+        assert_eq!(laps[2].stroke_count, 0);
+        assert_eq!(laps[2].duration_seconds, 30);
+    }
+
     #[test]
     fn test_swolf_calculation() {
         let swimming_data = create_sample_swimming_data();
@@ -726,4 +985,75 @@ mod tests {
         assert!(metrics.distance_per_stroke.is_none());
         assert!(metrics.stroke_rate.is_none());
     }
-}
\ No newline at end of file
+
+    fn length(seconds: Decimal, stroke_type: u8, length_type: SwimLengthType) -> SwimLength {
+        let active = length_type == SwimLengthType::Active;
+        SwimLength {
+            start_offset: 0,
+            duration_seconds: seconds,
+            distance: if active { dec!(25) } else { Decimal::ZERO },
+            strokes: active.then_some(16),
+            stroke_type: Some(stroke_type),
+            length_type,
+        }
+    }
+
+    fn css_test_workout() -> Workout {
+        let mut lengths = Vec::new();
+        // Drill warm-up, then a 400 m and a 200 m effort with rests in between
+        lengths.extend((0..4).map(|_| length(dec!(40), 4, SwimLengthType::Active)));
+        lengths.push(length(dec!(60), 0, SwimLengthType::Idle));
+        lengths.extend((0..16).map(|_| length(dec!(22.5), 0, SwimLengthType::Active)));
+        lengths.push(length(dec!(300), 0, SwimLengthType::Idle));
+        lengths.extend((0..8).map(|_| length(dec!(20), 0, SwimLengthType::Active)));
+
+        Workout {
+            id: "css-test".to_string(),
+            date: NaiveDate::from_ymd_opt(2024, 3, 1).unwrap(),
+            sport: Sport::Swimming,
+            duration_seconds: 1080,
+            workout_type: WorkoutType::Test,
+            data_source: DataSource::Pace,
+            raw_data: None,
+            summary: WorkoutSummary::default(),
+            notes: None,
+            athlete_id: None,
+            source: None,
+            swim_lengths: lengths,
+            start_time: None,
+            laps: Vec::new(),
+            rpe: None,
+            strength_sets: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_css_from_400_200_test_sets() {
+        let workout = css_test_workout();
+
+        let sets = SwimmingAnalyzer::detect_sets(&workout.swim_lengths);
+        assert_eq!(sets.len(), 3);
+        assert!(sets[0].drill_or_kick);
+        assert_eq!(sets[1].distance, dec!(400));
+        assert_eq!(sets[2].stroke_type, Some(SwimStrokeType::Freestyle));
+
+        let test = SwimmingAnalyzer::detect_css_test(&[workout]).unwrap();
+        assert_eq!(test.time_400, dec!(360));
+        assert_eq!(test.time_200, dec!(160));
+        assert_eq!(test.css_speed, dec!(1));
+        assert_eq!(test.css_pace_per_100m, dec!(100));
+        assert_eq!(test.pace_min_per_100m(), dec!(1.67));
+    }
+
+    #[test]
+    fn test_length_pace_excludes_drill_and_kick() {
+        let mut workout = css_test_workout();
+        // A kick length (no strokes) slower than everything else
+        workout.swim_lengths.push(SwimLength { strokes: Some(0), ..length(dec!(50), 0, SwimLengthType::Active) });
+
+        let analysis = SwimmingAnalyzer::analyze_swimming(&workout).unwrap();
+        assert_eq!(analysis.pace_analysis.best_pace_per_100m, Some(dec!(80)));
+        // 16 lengths at 90 s/100m and 8 at 80 s/100m
+        assert_eq!(analysis.pace_analysis.avg_pace_per_100m.unwrap().round_dp(2), dec!(86.67));
+    }
+}
@@ -0,0 +1,741 @@
+#![allow(dead_code)]
+
+//! Automatic threshold detection from stored workouts
+//!
+//! Scans workouts for efforts that imply higher thresholds than the athlete's
+//! profile and proposes `ThresholdChange` records for confirmation:
+//!
+//! - **eFTP** from the best mean-maximal powers, using the configured
+//!   `FtpMethod`s (20-minute, 8-minute or Critical Power model)
+//! - **LTHR** from heart-rate plateaus held for a sustained hard effort
+//! - **Max HR** from the highest heart rate sustained for a few seconds
+//! - **Threshold pace** from the critical speed of running best efforts
+//!
+//! Nothing is changed automatically: each `ThresholdProposal` carries the
+//! efforts it was derived from (workout, duration, value) and can be applied
+//! with `AthleteConfig::apply_thresholds`.
+//!
+//! # Sports Science Background
+//!
+//! Efforts found in training are rarely maximal, so estimates from them are
+//! lower bounds. A proposal is only made when it exceeds the current threshold
+//! by a minimum margin. Friel's field test takes LTHR as the average heart rate
+//! of a steady 20-minute effort at race intensity. Critical speed is the slope
+//! of the distance-time relationship `d = CS × t + D'` (Hill, 1993), and sits
+//! close to the pace sustainable for about an hour.
+
+use crate::config::{AthleteConfig, ThresholdChange, ThresholdSource, ThresholdType, ThresholdValues};
+use crate::formulas::FtpMethod;
+use crate::models::{DataPoint, Sport, Workout};
+use crate::power::PowerAnalyzer;
+use chrono::NaiveDate;
+use rust_decimal::prelude::*;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// Samples further apart than this split a recording into separate efforts
+const MAX_SAMPLE_GAP_SECONDS: u32 = 30;
+
+/// Durations fitted by the Critical Power method
+const CP_DURATIONS: [u32; 4] = [180, 300, 720, 1200];
+
+/// Detection settings
+#[derive(Debug, Clone)]
+pub struct DetectionConfig {
+    /// eFTP methods to run; the highest estimate is proposed
+    pub ftp_methods: Vec<FtpMethod>,
+    /// Minimum improvement over the current threshold (fraction)
+    pub min_improvement: Decimal,
+    /// Length of a heart-rate plateau used for LTHR
+    pub lthr_window_seconds: u32,
+    /// Maximum heart-rate standard deviation within a plateau (bpm)
+    pub lthr_max_std_dev: Decimal,
+    /// Maximum difference between the two halves of a plateau (bpm)
+    pub lthr_max_drift: Decimal,
+    /// Plateau must reach this fraction of the current LTHR to count as hard
+    pub lthr_min_intensity: Decimal,
+    /// Heart rate must be held this long to count as a new max HR
+    pub max_hr_window_seconds: u32,
+    /// Heart rates above this are treated as sensor artefacts
+    pub max_plausible_hr: u16,
+    /// Best-effort durations used for critical speed
+    pub cs_durations: Vec<u32>,
+    /// Minimum fit quality for Critical Power and critical speed models
+    pub min_r_squared: Decimal,
+}
+
+impl Default for DetectionConfig {
+    fn default() -> Self {
+        Self {
+            ftp_methods: vec![FtpMethod::default(), FtpMethod::CriticalPower],
+            min_improvement: dec!(0.01),
+            lthr_window_seconds: 1200,
+            lthr_max_std_dev: dec!(5),
+            lthr_max_drift: dec!(5),
+            lthr_min_intensity: dec!(0.95),
+            max_hr_window_seconds: 5,
+            max_plausible_hr: 220,
+            cs_durations: vec![180, 360, 720, 1200],
+            min_r_squared: dec!(0.95),
+        }
+    }
+}
+
+/// What an evidence value measures
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EvidenceMetric {
+    /// Average power in watts
+    Power,
+    /// Average heart rate in bpm
+    HeartRate,
+    /// Distance covered in meters
+    Distance,
+}
+
+impl EvidenceMetric {
+    pub fn unit(&self) -> &'static str {
+        match self {
+            EvidenceMetric::Power => "W",
+            EvidenceMetric::HeartRate => "bpm",
+            EvidenceMetric::Distance => "m",
+        }
+    }
+}
+
+/// A best effort supporting a proposal
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ThresholdEvidence {
+    pub workout_id: String,
+    pub date: NaiveDate,
+    pub duration_seconds: u32,
+    pub value: Decimal,
+    pub metric: EvidenceMetric,
+}
+
+/// A proposed threshold change awaiting confirmation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThresholdProposal {
+    /// Sport profile the value belongs to; `None` for the athlete-wide value
+    pub scope: Option<Sport>,
+    /// The change that would be recorded
+    pub change: ThresholdChange,
+    /// Detection method
+    pub method: String,
+    /// Efforts the estimate was derived from
+    pub evidence: Vec<ThresholdEvidence>,
+}
+
+impl ThresholdProposal {
+    /// Threshold values for `AthleteConfig::apply_thresholds`
+    pub fn values(&self) -> ThresholdValues {
+        let value = self.change.new_value;
+        let mut values = ThresholdValues::default();
+        match self.change.threshold_type {
+            ThresholdType::Ftp => values.ftp = value.to_u16(),
+            ThresholdType::Lthr => values.lthr = value.to_u16(),
+            ThresholdType::MaxHr => values.max_hr = value.to_u16(),
+            ThresholdType::ThresholdPace => values.threshold_pace = Some(value),
+            ThresholdType::ThresholdSwimPace => values.threshold_swim_pace = Some(value),
+            ThresholdType::CriticalPower | ThresholdType::Awc => {}
+        }
+        values
+    }
+
+    /// Apply to the athlete, returning the recorded changes
+    pub fn apply(&self, athlete: &mut AthleteConfig) -> Vec<ThresholdChange> {
+        athlete.apply_thresholds(
+            self.scope.as_ref(),
+            &self.values(),
+            self.change.date,
+            ThresholdSource::Estimated,
+            self.change.notes.clone(),
+        )
+    }
+}
+
+/// Time series of one channel with prefix sums for window statistics
+struct Series {
+    times: Vec<u32>,
+    values: Vec<f64>,
+    sum: Vec<f64>,
+    sum_sq: Vec<f64>,
+    /// Prefix count of gaps longer than `MAX_SAMPLE_GAP_SECONDS`
+    gaps: Vec<u32>,
+}
+
+impl Series {
+    fn new(samples: impl Iterator<Item = (u32, f64)>) -> Self {
+        let mut series = Series { times: Vec::new(), values: Vec::new(), sum: vec![0.0], sum_sq: vec![0.0], gaps: vec![0] };
+        for (time, value) in samples {
+            let gap = series.times.last().is_some_and(|&last| time.saturating_sub(last) > MAX_SAMPLE_GAP_SECONDS);
+            series.times.push(time);
+            series.values.push(value);
+            series.sum.push(series.sum.last().unwrap() + value);
+            series.sum_sq.push(series.sum_sq.last().unwrap() + value * value);
+            series.gaps.push(series.gaps.last().unwrap() + gap as u32);
+        }
+        series
+    }
+
+    /// Sample ranges `start..end` spanning `duration` seconds without gaps
+    fn windows(&self, duration: u32) -> Vec<(usize, usize)> {
+        let mut windows = Vec::new();
+        let mut end = 0;
+        for start in 0..self.times.len() {
+            end = end.max(start);
+            while end < self.times.len() && self.times[end].saturating_sub(self.times[start]) < duration {
+                end += 1;
+            }
+            if end == self.times.len() {
+                break;
+            }
+            // gaps[i] counts gaps before sample i; none may fall inside the window
+            if self.gaps[end + 1] == self.gaps[start + 1] {
+                windows.push((start, end));
+            }
+        }
+        windows
+    }
+
+    fn mean(&self, start: usize, end: usize) -> f64 {
+        (self.sum[end] - self.sum[start]) / (end - start) as f64
+    }
+
+    fn std_dev(&self, start: usize, end: usize) -> f64 {
+        let n = (end - start) as f64;
+        let mean = self.mean(start, end);
+        ((self.sum_sq[end] - self.sum_sq[start]) / n - mean * mean).max(0.0).sqrt()
+    }
+
+    /// Highest mean over any window of `duration` seconds
+    fn best_mean(&self, duration: u32) -> Option<f64> {
+        self.windows(duration).into_iter().map(|(s, e)| self.mean(s, e)).max_by(f64::total_cmp)
+    }
+}
+
+/// Best effort at one duration with the workout it came from
+#[derive(Debug, Clone, Copy)]
+struct Best<'a> {
+    value: f64,
+    workout: &'a Workout,
+}
+
+/// Best efforts per duration across workouts
+#[derive(Default)]
+struct BestEfforts<'a> {
+    by_duration: BTreeMap<u32, Best<'a>>,
+}
+
+impl<'a> BestEfforts<'a> {
+    fn offer(&mut self, duration: u32, value: f64, workout: &'a Workout) {
+        let best = self.by_duration.entry(duration).or_insert(Best { value, workout });
+        if value > best.value {
+            *best = Best { value, workout };
+        }
+    }
+
+    fn get(&self, duration: u32) -> Option<Best<'a>> {
+        self.by_duration.get(&duration).copied()
+    }
+
+    fn evidence(&self, duration: u32, metric: EvidenceMetric, decimals: u32) -> Option<ThresholdEvidence> {
+        self.get(duration).map(|best| evidence(best.workout, duration, best.value, metric, decimals))
+    }
+}
+
+fn evidence(workout: &Workout, duration: u32, value: f64, metric: EvidenceMetric, decimals: u32) -> ThresholdEvidence {
+    ThresholdEvidence {
+        workout_id: workout.id.clone(),
+        date: workout.date,
+        duration_seconds: duration,
+        value: Decimal::from_f64(value).unwrap_or_default().round_dp(decimals),
+        metric,
+    }
+}
+
+fn samples(workout: &Workout) -> &[DataPoint] {
+    workout.raw_data.as_deref().unwrap_or(&[])
+}
+
+fn power_series(workout: &Workout) -> Series {
+    Series::new(samples(workout).iter().filter_map(|dp| dp.power.map(|p| (dp.timestamp, p as f64))))
+}
+
+fn heart_rate_series(workout: &Workout) -> Series {
+    Series::new(samples(workout).iter().filter_map(|dp| dp.heart_rate.map(|hr| (dp.timestamp, hr as f64))))
+}
+
+/// Cumulative distance in meters, integrated from speed where distance is missing
+fn distance_series(workout: &Workout) -> Series {
+    let mut cumulative = 0.0;
+    let mut last_time: Option<u32> = None;
+    let points = samples(workout).iter().filter_map(move |dp| {
+        let step = last_time.map(|t| dp.timestamp.saturating_sub(t)).unwrap_or(0);
+        last_time = Some(dp.timestamp);
+        match (dp.distance, dp.speed) {
+            (Some(distance), _) => cumulative = distance.to_f64()?,
+            (None, Some(speed)) if step <= MAX_SAMPLE_GAP_SECONDS => cumulative += speed.to_f64()? * step as f64,
+            _ => return None,
+        }
+        Some((dp.timestamp, cumulative))
+    });
+    Series::new(points)
+}
+
+/// Greatest distance covered in `duration` seconds, scaled to exactly that duration
+fn best_distance(series: &Series, duration: u32) -> Option<f64> {
+    series
+        .windows(duration)
+        .into_iter()
+        .map(|(start, end)| {
+            let elapsed = (series.times[end] - series.times[start]) as f64;
+            let distance = series.values[end] - series.values[start];
+            distance * duration as f64 / elapsed
+        })
+        .max_by(f64::total_cmp)
+}
+
+/// Least-squares line `y = intercept + slope × x` with its R²
+fn linear_fit(points: &[(f64, f64)]) -> Option<(f64, f64, f64)> {
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|p| p.0).sum::<f64>() / n;
+    let mean_y = points.iter().map(|p| p.1).sum::<f64>() / n;
+    let sxx: f64 = points.iter().map(|p| (p.0 - mean_x).powi(2)).sum();
+    let sxy: f64 = points.iter().map(|p| (p.0 - mean_x) * (p.1 - mean_y)).sum();
+    let syy: f64 = points.iter().map(|p| (p.1 - mean_y).powi(2)).sum();
+    if sxx <= 0.0 || syy <= 0.0 {
+        return None;
+    }
+    let slope = sxy / sxx;
+    let intercept = mean_y - slope * mean_x;
+    Some((intercept, slope, sxy * sxy / (sxx * syy)))
+}
+
+fn minutes(seconds: u32) -> String {
+    if seconds.is_multiple_of(60) {
+        format!("{}-min", seconds / 60)
+    } else {
+        format!("{}-s", seconds)
+    }
+}
+
+/// Scans workouts for efforts implying higher thresholds
+pub struct ThresholdDetector {
+    config: DetectionConfig,
+}
+
+impl ThresholdDetector {
+    pub fn new(config: DetectionConfig) -> Self {
+        Self { config }
+    }
+
+    /// Propose threshold changes for the athlete from these workouts
+    ///
+    /// Power and LTHR are detected per sport; max HR is athlete-wide and
+    /// threshold pace comes from running workouts only.
+    pub fn detect(&self, athlete: &AthleteConfig, workouts: &[Workout]) -> Vec<ThresholdProposal> {
+        let mut by_sport: HashMap<&Sport, Vec<&Workout>> = HashMap::new();
+        for workout in workouts.iter().filter(|w| w.raw_data.as_ref().is_some_and(|d| !d.is_empty())) {
+            by_sport.entry(&workout.sport).or_default().push(workout);
+        }
+        let mut sports: Vec<&Sport> = by_sport.keys().copied().collect();
+        sports.sort_by_key(|s| format!("{:?}", s));
+
+        let mut proposals = Vec::new();
+        let max_hr = self.detect_max_hr(athlete, workouts);
+        let detected_max_hr = max_hr.as_ref().map(|p| p.change.new_value);
+        proposals.extend(max_hr);
+
+        for sport in sports {
+            let sport_workouts = &by_sport[sport];
+            proposals.extend(self.detect_ftp(athlete, sport, sport_workouts));
+            proposals.extend(self.detect_lthr(athlete, sport, sport_workouts, detected_max_hr));
+            if *sport == Sport::Running {
+                proposals.extend(self.detect_threshold_pace(athlete, sport_workouts));
+            }
+        }
+        proposals
+    }
+
+    /// eFTP from the best efforts with each configured `FtpMethod`
+    fn detect_ftp(&self, athlete: &AthleteConfig, sport: &Sport, workouts: &[&Workout]) -> Option<ThresholdProposal> {
+        let mut durations: Vec<u32> = Vec::new();
+        for method in &self.config.ftp_methods {
+            match method {
+                FtpMethod::TwentyMinute { .. } => durations.push(1200),
+                FtpMethod::EightMinute { .. } => durations.push(480),
+                FtpMethod::CriticalPower => durations.extend(CP_DURATIONS),
+                FtpMethod::Custom(_) => {}
+            }
+        }
+        durations.sort_unstable();
+        durations.dedup();
+
+        let mut bests = BestEfforts::default();
+        for &workout in workouts {
+            let series = power_series(workout);
+            for &duration in &durations {
+                if let Some(power) = series.best_mean(duration) {
+                    bests.offer(duration, power, workout);
+                }
+            }
+        }
+
+        let mut candidates: Vec<(f64, String, Vec<ThresholdEvidence>)> = Vec::new();
+        for method in &self.config.ftp_methods {
+            match method {
+                FtpMethod::TwentyMinute { factor } | FtpMethod::EightMinute { factor } => {
+                    let duration = if matches!(method, FtpMethod::TwentyMinute { .. }) { 1200 } else { 480 };
+                    if let (Some(best), Some(evidence)) = (bests.get(duration), bests.evidence(duration, EvidenceMetric::Power, 0)) {
+                        let label = format!("{:.0}% of {} power", factor * 100.0, minutes(duration));
+                        candidates.push((best.value * factor, label, vec![evidence]));
+                    }
+                }
+                FtpMethod::CriticalPower => {
+                    let efforts: Vec<(u32, u16)> = CP_DURATIONS
+                        .iter()
+                        .filter_map(|&d| bests.get(d).map(|b| (d, b.value.round() as u16)))
+                        .collect();
+                    if efforts.len() < 3 {
+                        continue;
+                    }
+                    let Ok(model) = PowerAnalyzer::fit_critical_power_from_efforts(&efforts, Vec::new()) else { continue };
+                    if model.r_squared < self.config.min_r_squared {
+                        continue;
+                    }
+                    let evidence = CP_DURATIONS
+                        .iter()
+                        .filter_map(|&d| bests.evidence(d, EvidenceMetric::Power, 0))
+                        .collect();
+                    let label = format!("Critical Power model (CP {} W)", model.critical_power);
+                    candidates.push((model.estimated_ftp as f64, label, evidence));
+                }
+                FtpMethod::Custom(_) => {}
+            }
+        }
+
+        let (estimate, method, evidence) = candidates.into_iter().max_by(|a, b| a.0.total_cmp(&b.0))?;
+        let new_value = Decimal::from_f64(estimate)?.round();
+        if !(dec!(50)..=dec!(800)).contains(&new_value) {
+            return None;
+        }
+        self.propose(athlete, Some(sport), ThresholdType::Ftp, new_value, true, format!("eFTP: {}", method), evidence)
+    }
+
+    /// LTHR from the highest steady heart-rate plateau at a hard effort
+    fn detect_lthr(
+        &self,
+        athlete: &AthleteConfig,
+        sport: &Sport,
+        workouts: &[&Workout],
+        detected_max_hr: Option<Decimal>,
+    ) -> Option<ThresholdProposal> {
+        let thresholds = athlete.zone_thresholds(sport);
+        let max_hr = detected_max_hr.or(thresholds.max_hr.map(Decimal::from));
+        // Without an LTHR a plateau above 85% of max HR counts as hard
+        let floor = match (thresholds.lthr, max_hr) {
+            (Some(lthr), _) => Decimal::from(lthr) * self.config.lthr_min_intensity,
+            (None, Some(max_hr)) => max_hr * dec!(0.85),
+            (None, None) => return None,
+        }
+        .to_f64()?;
+        let max_std_dev = self.config.lthr_max_std_dev.to_f64()?;
+        let max_drift = self.config.lthr_max_drift.to_f64()?;
+        let duration = self.config.lthr_window_seconds;
+
+        let mut best: Option<(f64, &Workout)> = None;
+        for &workout in workouts {
+            let series = heart_rate_series(workout);
+            for (start, end) in series.windows(duration) {
+                let mean = series.mean(start, end);
+                if mean < floor || best.is_some_and(|(b, _)| mean <= b) {
+                    continue;
+                }
+                let middle = start + (end - start) / 2;
+                let drift = (series.mean(middle, end) - series.mean(start, middle)).abs();
+                if drift <= max_drift && series.std_dev(start, end) <= max_std_dev {
+                    best = Some((mean, workout));
+                }
+            }
+        }
+
+        let (mean, workout) = best?;
+        let new_value = Decimal::from_f64(mean)?.round();
+        if max_hr.is_some_and(|max| new_value >= max) {
+            return None;
+        }
+        let evidence = vec![evidence(workout, duration, mean, EvidenceMetric::HeartRate, 0)];
+        let method = format!("LTHR: {} heart-rate plateau", minutes(duration));
+        self.propose(athlete, Some(sport), ThresholdType::Lthr, new_value, true, method, evidence)
+    }
+
+    /// New max HR from the highest heart rate held for a few seconds
+    fn detect_max_hr(&self, athlete: &AthleteConfig, workouts: &[Workout]) -> Option<ThresholdProposal> {
+        let duration = self.config.max_hr_window_seconds;
+        let mut best: Option<(f64, &Workout)> = None;
+        for workout in workouts {
+            let series = heart_rate_series(workout);
+            for (start, end) in series.windows(duration) {
+                // Every sample must stay plausible, so single spikes cannot pass
+                let mean = series.mean(start, end);
+                let plausible = series.values[start..end].iter().all(|&hr| hr <= self.config.max_plausible_hr as f64);
+                if plausible && best.is_none_or(|(b, _)| mean > b) {
+                    best = Some((mean, workout));
+                }
+            }
+        }
+
+        let (mean, workout) = best?;
+        let new_value = Decimal::from_f64(mean)?.floor();
+        let evidence = vec![evidence(workout, duration, mean, EvidenceMetric::HeartRate, 0)];
+        let method = format!("Max HR held for {} s", duration);
+        // Any increase counts: max HR does not improve with training
+        let current = athlete.threshold_value(None, &ThresholdType::MaxHr);
+        if current.is_some_and(|c| new_value <= c) {
+            return None;
+        }
+        Some(self.proposal(None, ThresholdType::MaxHr, current, new_value, method, evidence))
+    }
+
+    /// Running threshold pace from the critical speed of best efforts
+    fn detect_threshold_pace(&self, athlete: &AthleteConfig, workouts: &[&Workout]) -> Option<ThresholdProposal> {
+        let mut bests = BestEfforts::default();
+        for &workout in workouts {
+            let series = distance_series(workout);
+            for &duration in &self.config.cs_durations {
+                if let Some(distance) = best_distance(&series, duration) {
+                    bests.offer(duration, distance, workout);
+                }
+            }
+        }
+
+        let points: Vec<(f64, f64)> = bests.by_duration.iter().map(|(&d, b)| (d as f64, b.value)).collect();
+        if points.len() < 2 {
+            return None;
+        }
+        let (d_prime, critical_speed, r_squared) = linear_fit(&points)?;
+        if Decimal::from_f64(r_squared)? < self.config.min_r_squared || !(1.5..=7.0).contains(&critical_speed) {
+            return None;
+        }
+
+        // Pace in min/km
+        let new_value = Decimal::from_f64(1000.0 / (critical_speed * 60.0))?.round_dp(2);
+        let evidence = bests
+            .by_duration
+            .keys()
+            .filter_map(|&d| bests.evidence(d, EvidenceMetric::Distance, 0))
+            .collect();
+        let method = format!("Critical speed {:.2} m/s (D' {:.0} m)", critical_speed, d_prime);
+        self.propose(athlete, Some(&Sport::Running), ThresholdType::ThresholdPace, new_value, false, method, evidence)
+    }
+
+    /// Propose the value when it beats the current threshold by the minimum margin
+    #[allow(clippy::too_many_arguments)]
+    fn propose(
+        &self,
+        athlete: &AthleteConfig,
+        sport: Option<&Sport>,
+        threshold_type: ThresholdType,
+        new_value: Decimal,
+        higher_is_better: bool,
+        method: String,
+        evidence: Vec<ThresholdEvidence>,
+    ) -> Option<ThresholdProposal> {
+        let scope = sport.and_then(|s| Self::scope(athlete, s, &threshold_type));
+        let current = athlete.threshold_value(scope.as_ref(), &threshold_type);
+        if let Some(current) = current {
+            let improved = if higher_is_better {
+                new_value >= current * (Decimal::ONE + self.config.min_improvement)
+            } else {
+                new_value <= current * (Decimal::ONE - self.config.min_improvement)
+            };
+            if !improved {
+                return None;
+            }
+        }
+        Some(self.proposal(scope, threshold_type, current, new_value, method, evidence))
+    }
+
+    /// Where a sport's threshold lives: the primary sport uses the athlete-wide
+    /// value unless its profile overrides it, other sports their own profile
+    fn scope(athlete: &AthleteConfig, sport: &Sport, threshold_type: &ThresholdType) -> Option<Sport> {
+        let overridden = athlete.threshold_value(Some(sport), threshold_type).is_some();
+        if *sport == athlete.primary_sport && !overridden {
+            None
+        } else {
+            Some(sport.clone())
+        }
+    }
+
+    fn proposal(
+        &self,
+        scope: Option<Sport>,
+        threshold_type: ThresholdType,
+        old_value: Option<Decimal>,
+        new_value: Decimal,
+        method: String,
+        evidence: Vec<ThresholdEvidence>,
+    ) -> ThresholdProposal {
+        let date = evidence.iter().map(|e| e.date).max().unwrap_or_else(|| chrono::Local::now().date_naive());
+        ThresholdProposal {
+            scope: scope.clone(),
+            change: ThresholdChange {
+                date,
+                sport: scope,
+                threshold_type,
+                old_value,
+                new_value,
+                source: ThresholdSource::Estimated,
+                notes: Some(method.clone()),
+            },
+            method,
+            evidence,
+        }
+    }
+}
+
+impl Default for ThresholdDetector {
+    fn default() -> Self {
+        Self::new(DetectionConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{DataSource, WorkoutSummary, WorkoutType};
+
+    fn workout(id: &str, sport: Sport, samples: Vec<(Option<u16>, Option<u16>, Option<f64>)>) -> Workout {
+        let raw_data: Vec<DataPoint> = samples
+            .into_iter()
+            .enumerate()
+            .map(|(i, (power, heart_rate, distance))| DataPoint {
+                timestamp: i as u32,
+                heart_rate,
+                power,
+                pace: None,
+                elevation: None,
+                cadence: None,
+                speed: None,
+                distance: distance.and_then(Decimal::from_f64),
+                left_power: None,
+                right_power: None,
+                ground_contact_time: None,
+                vertical_oscillation: None,
+                stride_length: None,
+                stroke_count: None,
+                stroke_type: None,
+                lap_number: None,
+                sport_transition: None,
+                temperature: None,
+                drive_length: None,
+                stroke_work: None,
+            })
+            .collect();
+
+        Workout {
+            id: id.to_string(),
+            date: NaiveDate::from_ymd_opt(2025, 6, 1).unwrap(),
+            sport,
+            duration_seconds: raw_data.len() as u32,
+            workout_type: WorkoutType::Threshold,
+            data_source: DataSource::Power,
+            raw_data: Some(raw_data),
+            summary: WorkoutSummary::default(),
+            notes: None,
+            athlete_id: None,
+            source: None,
+            swim_lengths: Vec::new(),
+            start_time: None,
+            laps: Vec::new(),
+            rpe: None,
+            strength_sets: Vec::new(),
+        }
+    }
+
+    fn athlete() -> AthleteConfig {
+        let mut athlete = AthleteConfig::new("Test Athlete".to_string(), None);
+        athlete.profile.ftp = Some(250);
+        athlete.profile.lthr = Some(165);
+        athlete.profile.max_hr = Some(185);
+        athlete
+    }
+
+    #[test]
+    fn test_detects_eftp_lthr_and_max_hr_from_ride() {
+        // Warm-up with a short sprint to 190 bpm, then 20 min at 300 W with HR settling at 170-172
+        let mut samples = vec![(Some(150), Some(130), None); 300];
+        samples.extend(vec![(Some(600), Some(190), None); 8]);
+        samples.extend(vec![(Some(150), Some(140), None); 300]);
+        samples.extend((0..1200).map(|i| (Some(300), Some(170 + (i % 3) as u16), None)));
+        samples.extend(vec![(Some(100), Some(150), None); 300]);
+        let ride = workout("ride-1", Sport::Cycling, samples);
+
+        let mut athlete = athlete();
+        let proposals = ThresholdDetector::default().detect(&athlete, &[ride]);
+        let find = |t: ThresholdType| proposals.iter().find(|p| p.change.threshold_type == t).unwrap();
+
+        // 95% of 300 W, evidence points at the 20-min effort
+        let ftp = find(ThresholdType::Ftp);
+        assert_eq!(ftp.change.new_value, dec!(285));
+        assert_eq!(ftp.change.old_value, Some(dec!(250)));
+        assert_eq!(ftp.scope, None);
+        assert_eq!(ftp.evidence[0].workout_id, "ride-1");
+        assert_eq!(ftp.evidence[0].duration_seconds, 1200);
+        assert_eq!(ftp.evidence[0].value, dec!(300));
+
+        assert_eq!(find(ThresholdType::Lthr).change.new_value, dec!(171));
+        assert_eq!(find(ThresholdType::MaxHr).change.new_value, dec!(190));
+
+        let changes = ftp.apply(&mut athlete);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(athlete.profile.ftp, Some(285));
+        assert!(matches!(athlete.threshold_history[0].source, ThresholdSource::Estimated));
+
+        // Nothing new once the thresholds are up to date
+        athlete.profile.lthr = Some(171);
+        athlete.profile.max_hr = Some(190);
+        let ride = workout("ride-1", Sport::Cycling, vec![(Some(280), Some(160), None); 1500]);
+        assert!(ThresholdDetector::default().detect(&athlete, &[ride]).is_empty());
+    }
+
+    #[test]
+    fn test_heart_rate_spikes_and_drift_are_ignored() {
+        // Steady climb from 150 to 180 bpm is not a plateau; one 240 bpm spike is an artefact
+        let mut samples: Vec<_> = (0..1800).map(|i| (None, Some(150 + (i / 60) as u16), None)).collect();
+        samples[900].1 = Some(240);
+        let run = workout("run-1", Sport::Running, samples);
+
+        let athlete = athlete();
+        let proposals = ThresholdDetector::default().detect(&athlete, &[run]);
+        assert!(proposals.iter().all(|p| p.change.threshold_type != ThresholdType::Lthr));
+        assert!(proposals.iter().all(|p| p.change.new_value < dec!(200)));
+    }
+
+    #[test]
+    fn test_threshold_pace_from_critical_speed() {
+        // Best efforts on a line with CS = 4.5 m/s and D' = 200 m
+        let cs = 4.5;
+        let efforts = [(180u32, "run-a"), (360, "run-b"), (720, "run-c"), (1200, "run-d")];
+        let runs: Vec<Workout> = efforts
+            .iter()
+            .map(|&(duration, id)| {
+                let speed = cs + 200.0 / duration as f64;
+                let samples = (0..=duration).map(|t| (None, None, Some(speed * t as f64))).collect();
+                workout(id, Sport::Running, samples)
+            })
+            .collect();
+
+        let mut athlete = athlete();
+        athlete.profile.threshold_pace = Some(dec!(4.0));
+        let proposals = ThresholdDetector::default().detect(&athlete, &runs);
+        let pace = proposals.iter().find(|p| p.change.threshold_type == ThresholdType::ThresholdPace).unwrap();
+
+        // 1000 / (4.5 × 60) = 3.70 min/km, kept in the running profile
+        assert_eq!(pace.change.new_value, dec!(3.70));
+        assert_eq!(pace.scope, Some(Sport::Running));
+        assert_eq!(pace.evidence.len(), 4);
+        assert_eq!(pace.evidence[0].workout_id, "run-a");
+    }
+}
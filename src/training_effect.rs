@@ -14,8 +14,9 @@
 
 use crate::models::{AthleteProfile, DataPoint, Workout};
 use anyhow::Result;
-use chrono::NaiveDate;
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use thiserror::Error;
 
 /// Training Effect calculation errors
@@ -32,7 +33,7 @@ pub enum TrainingEffectError {
 }
 
 /// Training Effect level classification
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum TrainingEffectLevel {
     None,           // 0.0-1.0
     Minor,          // 1.0-2.0
@@ -94,6 +95,10 @@ pub struct TrainingEffect {
 
     /// Recommended recovery time in hours
     pub recovery_time_hours: u32,
+
+    /// Local time the workout ended, when its start time was recorded
+    #[serde(default)]
+    pub end_time: Option<NaiveDateTime>,
 }
 
 impl TrainingEffect {
@@ -117,6 +122,56 @@ impl TrainingEffect {
     pub fn recovery_days(&self) -> u8 {
         (self.recovery_time_hours / 24) as u8
     }
+
+    /// Rebuild a training effect from stored values, deriving the level classifications
+    pub fn from_stored(
+        workout_id: String,
+        date: NaiveDate,
+        aerobic_te: f64,
+        anaerobic_te: f64,
+        epoc: f64,
+        recovery_time_hours: u32,
+    ) -> Self {
+        TrainingEffect {
+            aerobic_te,
+            anaerobic_te,
+            epoc,
+            workout_id,
+            date,
+            aerobic_level: TrainingEffectLevel::from_value(aerobic_te),
+            anaerobic_level: TrainingEffectLevel::from_value(anaerobic_te),
+            recovery_time_hours,
+            end_time: None,
+        }
+    }
+
+    /// Set the local time the workout ended
+    pub fn with_end_time(mut self, end_time: Option<NaiveDateTime>) -> Self {
+        self.end_time = end_time;
+        self
+    }
+}
+
+/// Training effect totals for one Monday-to-Sunday week
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WeeklyTrainingEffectSummary {
+    /// Monday of the week
+    pub week_start: NaiveDate,
+
+    /// Number of sessions with a training effect
+    pub sessions: u32,
+
+    /// Session count per aerobic TE level
+    pub aerobic_levels: BTreeMap<TrainingEffectLevel, u32>,
+
+    /// Session count per anaerobic TE level
+    pub anaerobic_levels: BTreeMap<TrainingEffectLevel, u32>,
+
+    /// Sum of recommended recovery hours
+    pub total_recovery_hours: u32,
+
+    /// Sum of EPOC in ml O2/kg
+    pub total_epoc: f64,
 }
 
 /// Heart rate zone distribution for EPOC calculation
@@ -204,6 +259,7 @@ impl TrainingEffectAnalyzer {
             aerobic_level: TrainingEffectLevel::from_value(aerobic_te),
             anaerobic_level: TrainingEffectLevel::from_value(anaerobic_te),
             recovery_time_hours: 0, // Placeholder
+            end_time: None,
         }, athlete.lthr.unwrap_or(max_hr - 20));
 
         Ok(TrainingEffect {
@@ -215,6 +271,9 @@ impl TrainingEffectAnalyzer {
             aerobic_level: TrainingEffectLevel::from_value(aerobic_te),
             anaerobic_level: TrainingEffectLevel::from_value(anaerobic_te),
             recovery_time_hours,
+            end_time: workout
+                .start_time
+                .map(|start| start.naive_local() + Duration::seconds(i64::from(workout.duration_seconds))),
         })
     }
 
@@ -473,6 +532,76 @@ impl TrainingEffectAnalyzer {
 
         (base_recovery as f64 * fitness_factor) as u32 + anaerobic_penalty
     }
+
+    /// Group training effects into Monday-based weeks, oldest first
+    pub fn weekly_summary(effects: &[TrainingEffect]) -> Vec<WeeklyTrainingEffectSummary> {
+        let mut weeks: BTreeMap<NaiveDate, WeeklyTrainingEffectSummary> = BTreeMap::new();
+
+        for te in effects {
+            let week_start = te.date - Duration::days(te.date.weekday().num_days_from_monday() as i64);
+            let week = weeks.entry(week_start).or_insert_with(|| WeeklyTrainingEffectSummary {
+                week_start,
+                sessions: 0,
+                aerobic_levels: BTreeMap::new(),
+                anaerobic_levels: BTreeMap::new(),
+                total_recovery_hours: 0,
+                total_epoc: 0.0,
+            });
+
+            week.sessions += 1;
+            *week.aerobic_levels.entry(te.aerobic_level).or_insert(0) += 1;
+            *week.anaerobic_levels.entry(te.anaerobic_level).or_insert(0) += 1;
+            week.total_recovery_hours += te.recovery_time_hours;
+            week.total_epoc += te.epoc;
+        }
+
+        weeks.into_values().collect()
+    }
+
+    /// Calculate recovery hours still outstanding at `as_of`
+    ///
+    /// Recovery from consecutive workouts stacks: each workout adds its recovery
+    /// time to whatever is left from the previous ones. The recovery clock starts
+    /// when the workout ended, computed from its recorded start time and duration;
+    /// without a start time it conservatively starts at the end of the workout
+    /// day. Workouts that had not ended by `as_of` are ignored.
+    pub fn recovery_time_remaining(effects: &[TrainingEffect], as_of: NaiveDateTime) -> u32 {
+        let mut clocks: Vec<(NaiveDateTime, u32)> = effects
+            .iter()
+            .filter_map(|te| match te.end_time {
+                Some(end) if end <= as_of => Some((end, te.recovery_time_hours)),
+                Some(_) => None,
+                None if te.date <= as_of.date() => {
+                    let day_end = te.date.succ_opt()?.and_hms_opt(0, 0, 0)?;
+                    Some((day_end, te.recovery_time_hours))
+                }
+                None => None,
+            })
+            .collect();
+        clocks.sort_by_key(|(start, _)| *start);
+
+        let mut outstanding_hours = 0.0_f64;
+        let mut clock: Option<NaiveDateTime> = None;
+
+        for (start, recovery_time_hours) in clocks {
+            if let Some(previous) = clock {
+                let elapsed = (start - previous).num_minutes() as f64 / 60.0;
+                outstanding_hours = (outstanding_hours - elapsed).max(0.0);
+            }
+            outstanding_hours += recovery_time_hours as f64;
+            clock = Some(start);
+        }
+
+        match clock {
+            // Recovery has not started counting down yet when the workout day is still in progress
+            Some(start) if as_of < start => outstanding_hours.ceil() as u32,
+            Some(start) => {
+                let elapsed = (as_of - start).num_minutes() as f64 / 60.0;
+                (outstanding_hours - elapsed).max(0.0).ceil() as u32
+            }
+            None => 0,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -498,6 +627,8 @@ mod tests {
             preferred_units: crate::models::Units::Metric,
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
+            rowing_ftp: None,
+            css_pace: None,
         }
     }
 
@@ -522,6 +653,9 @@ mod tests {
                 stroke_type: None,
                 lap_number: None,
                 sport_transition: None,
+                temperature: None,
+                drive_length: None,
+                stroke_work: None,
             })
             .collect()
     }
@@ -549,6 +683,9 @@ mod tests {
                 stroke_type: None,
                 lap_number: None,
                 sport_transition: None,
+                temperature: None,
+                drive_length: None,
+                stroke_work: None,
             });
         }
 
@@ -576,6 +713,9 @@ mod tests {
                     stroke_type: None,
                     lap_number: None,
                     sport_transition: None,
+                    temperature: None,
+                    drive_length: None,
+                    stroke_work: None,
                 });
             }
 
@@ -599,6 +739,9 @@ mod tests {
                     stroke_type: None,
                     lap_number: None,
                     sport_transition: None,
+                    temperature: None,
+                    drive_length: None,
+                    stroke_work: None,
                 });
             }
         }
@@ -624,6 +767,9 @@ mod tests {
                 stroke_type: None,
                 lap_number: None,
                 sport_transition: None,
+                temperature: None,
+                drive_length: None,
+                stroke_work: None,
             });
         }
 
@@ -688,6 +834,11 @@ mod tests {
             notes: None,
             athlete_id: Some("test_athlete".to_string()),
             source: None,
+            swim_lengths: Vec::new(),
+            start_time: None,
+            laps: Vec::new(),
+            rpe: None,
+            strength_sets: Vec::new(),
         };
 
         let te = TrainingEffectAnalyzer::calculate_training_effect(&workout, &athlete).unwrap();
@@ -716,6 +867,11 @@ mod tests {
             notes: None,
             athlete_id: Some("test_athlete".to_string()),
             source: None,
+            swim_lengths: Vec::new(),
+            start_time: None,
+            laps: Vec::new(),
+            rpe: None,
+            strength_sets: Vec::new(),
         };
 
         let te = TrainingEffectAnalyzer::calculate_training_effect(&workout, &athlete).unwrap();
@@ -737,6 +893,7 @@ mod tests {
             aerobic_level: TrainingEffectLevel::Improving,
             anaerobic_level: TrainingEffectLevel::Maintaining,
             recovery_time_hours: 0,
+            end_time: None,
         };
 
         let recovery = TrainingEffectAnalyzer::calculate_recovery_time(&te, 165);
@@ -761,6 +918,11 @@ mod tests {
             notes: None,
             athlete_id: Some("test_athlete".to_string()),
             source: None,
+            swim_lengths: Vec::new(),
+            start_time: None,
+            laps: Vec::new(),
+            rpe: None,
+            strength_sets: Vec::new(),
         };
 
         let result = TrainingEffectAnalyzer::calculate_training_effect(&workout, &athlete);
@@ -785,9 +947,72 @@ mod tests {
             notes: None,
             athlete_id: Some("test_athlete".to_string()),
             source: None,
+            swim_lengths: Vec::new(),
+            start_time: None,
+            laps: Vec::new(),
+            rpe: None,
+            strength_sets: Vec::new(),
         };
 
         let result = TrainingEffectAnalyzer::calculate_training_effect(&workout, &athlete);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_weekly_summary_counts_levels() {
+        let effects = vec![
+            // Monday and Wednesday of the same week
+            TrainingEffect::from_stored("a".to_string(), NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(), 3.2, 1.1, 70.0, 36),
+            TrainingEffect::from_stored("b".to_string(), NaiveDate::from_ymd_opt(2024, 1, 3).unwrap(), 3.8, 2.4, 90.0, 48),
+            // Following Monday
+            TrainingEffect::from_stored("c".to_string(), NaiveDate::from_ymd_opt(2024, 1, 8).unwrap(), 1.5, 0.2, 25.0, 24),
+        ];
+
+        let weeks = TrainingEffectAnalyzer::weekly_summary(&effects);
+        assert_eq!(weeks.len(), 2);
+
+        assert_eq!(weeks[0].week_start, NaiveDate::from_ymd_opt(2024, 1, 1).unwrap());
+        assert_eq!(weeks[0].sessions, 2);
+        assert_eq!(weeks[0].aerobic_levels.get(&TrainingEffectLevel::Improving), Some(&2));
+        assert_eq!(weeks[0].anaerobic_levels.get(&TrainingEffectLevel::Minor), Some(&1));
+        assert_eq!(weeks[0].anaerobic_levels.get(&TrainingEffectLevel::Maintaining), Some(&1));
+        assert_eq!(weeks[0].total_recovery_hours, 84);
+
+        assert_eq!(weeks[1].week_start, NaiveDate::from_ymd_opt(2024, 1, 8).unwrap());
+        assert_eq!(weeks[1].aerobic_levels.get(&TrainingEffectLevel::Minor), Some(&1));
+    }
+
+    #[test]
+    fn test_recovery_time_remaining_stacks_and_decays() {
+        let effects = vec![
+            TrainingEffect::from_stored("a".to_string(), NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(), 3.5, 1.0, 70.0, 48),
+            TrainingEffect::from_stored("b".to_string(), NaiveDate::from_ymd_opt(2024, 1, 2).unwrap(), 3.0, 2.5, 60.0, 36),
+        ];
+        let at = |day: u32, hour: u32| NaiveDate::from_ymd_opt(2024, 1, day).unwrap().and_hms_opt(hour, 0, 0).unwrap();
+
+        // Before the second workout day ends: 48h less the 24h between clock starts, plus 36h
+        assert_eq!(TrainingEffectAnalyzer::recovery_time_remaining(&effects, at(2, 18)), 60);
+        // Twelve hours after the end of the second day
+        assert_eq!(TrainingEffectAnalyzer::recovery_time_remaining(&effects, at(3, 12)), 48);
+        // Fully recovered
+        assert_eq!(TrainingEffectAnalyzer::recovery_time_remaining(&effects, at(10, 0)), 0);
+        // Workouts after the reference time are ignored
+        assert_eq!(TrainingEffectAnalyzer::recovery_time_remaining(&effects, at(1, 12)), 48);
+        assert_eq!(TrainingEffectAnalyzer::recovery_time_remaining(&[], at(1, 12)), 0);
+    }
+
+    #[test]
+    fn test_recovery_time_counts_from_workout_end() {
+        let at = |day: u32, hour: u32| NaiveDate::from_ymd_opt(2024, 1, day).unwrap().and_hms_opt(hour, 0, 0).unwrap();
+        let effects = vec![
+            TrainingEffect::from_stored("a".to_string(), NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(), 3.5, 1.0, 70.0, 48)
+                .with_end_time(Some(at(1, 8))),
+        ];
+
+        // Not finished yet
+        assert_eq!(TrainingEffectAnalyzer::recovery_time_remaining(&effects, at(1, 7)), 0);
+        // Four hours after the workout ended
+        assert_eq!(TrainingEffectAnalyzer::recovery_time_remaining(&effects, at(1, 12)), 44);
+        assert_eq!(TrainingEffectAnalyzer::recovery_time_remaining(&effects, at(3, 8)), 0);
+    }
 }
@@ -7,8 +7,13 @@ use rust_decimal_macros::dec;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use crate::intensity_distribution::{
+    DistributionCompliance, DistributionTarget, IntensityDistributionAnalyzer, SeilerThresholds,
+};
 use crate::models::{Sport, WorkoutType, AthleteProfile};
 use crate::pmc::PmcMetrics;
+use crate::race_calendar::{Race, RaceCalendar, RacePriority};
+use crate::structured_workout::{StepDuration, StepKind, StepTarget, StructuredWorkout, WorkoutStep};
 
 #[derive(Debug, Clone, Subcommand)]
 pub enum TrainingPlanCommands {
@@ -29,6 +34,45 @@ pub enum TrainingPlanCommands {
         /// Recovery pattern (3:1, 4:1, 2:1)
         #[arg(long, default_value = "3:1")]
         recovery: String,
+        /// Target intensity distribution (polarized, pyramidal, threshold or Z1/Z2/Z3 like 80/5/15)
+        #[arg(long)]
+        distribution: Option<String>,
+        /// Write the generated plan as JSON
+        #[arg(long)]
+        output: Option<std::path::PathBuf>,
+    },
+    /// Generate a multi-peak season plan from the race calendar
+    Season {
+        /// Season start date (YYYY-MM-DD), defaults to today
+        #[arg(long)]
+        start: Option<NaiveDate>,
+        /// Periodization model (traditional, block, reverse)
+        #[arg(long, default_value = "traditional")]
+        model: String,
+        /// Recovery pattern (3:1, 4:1, 2:1)
+        #[arg(long, default_value = "3:1")]
+        recovery: String,
+        /// Target intensity distribution (polarized, pyramidal, threshold or Z1/Z2/Z3 like 80/5/15)
+        #[arg(long)]
+        distribution: Option<String>,
+        /// Write the generated plan as JSON
+        #[arg(long)]
+        output: Option<std::path::PathBuf>,
+    },
+    /// Export planned workouts to trainer/device workout files
+    Export {
+        /// Plan JSON written by `generate --output` or `season --output`
+        #[arg(long)]
+        plan: std::path::PathBuf,
+        /// File format (zwo, erg, mrc, fit)
+        #[arg(long, default_value = "zwo")]
+        format: String,
+        /// Output directory; each training week gets its own folder
+        #[arg(long)]
+        output: std::path::PathBuf,
+        /// Only export this week number
+        #[arg(long)]
+        week: Option<u32>,
     },
     /// Monitor and analyze plan progress
     Monitor {
@@ -51,6 +95,41 @@ pub enum TrainingPlanCommands {
         #[arg(long, default_value_t = 10)]
         percentage: u32,
     },
+    /// Export a plan and the race calendar as an iCalendar (.ics) file
+    CalendarExport {
+        /// Plan ID or name (defaults to the newest plan)
+        #[arg(long)]
+        plan: Option<String>,
+        /// Output .ics file
+        #[arg(short, long)]
+        output: std::path::PathBuf,
+        /// Leave races out
+        #[arg(long)]
+        no_races: bool,
+        /// Export only the race calendar
+        #[arg(long, conflicts_with = "no_races")]
+        races_only: bool,
+    },
+    /// Import planned sessions and races from an iCalendar (.ics) file
+    CalendarImport {
+        /// Input .ics file
+        file: std::path::PathBuf,
+        /// Show what would be imported without saving
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Rebalance the rest of a plan after missed or over-done sessions
+    Reschedule {
+        /// Plan ID or name (defaults to the newest plan)
+        #[arg(long)]
+        plan: Option<String>,
+        /// Maximum CTL increase per week
+        #[arg(long, default_value = "7")]
+        max_ramp: Decimal,
+        /// Show the changes without saving them
+        #[arg(long)]
+        dry_run: bool,
+    },
 }
 
 /// Periodization model for training plans
@@ -112,6 +191,23 @@ impl TrainingGoal {
         }
     }
 
+    /// Pick the training goal closest to a race
+    pub fn from_race(sport: &Sport, distance_meters: Option<Decimal>) -> Self {
+        let meters = distance_meters.unwrap_or(dec!(0));
+        match sport {
+            Sport::Running if meters <= dec!(5500) => Self::FiveK,
+            Sport::Running if meters <= dec!(12000) => Self::TenK,
+            Sport::Running if meters <= dec!(25000) => Self::HalfMarathon,
+            Sport::Running => Self::Marathon,
+            Sport::Cycling if meters <= dec!(120000) => Self::Metric,
+            Sport::Cycling => Self::Century,
+            Sport::Triathlon if meters <= dec!(60000) => Self::Triathlon,
+            Sport::Triathlon if meters <= dec!(120000) => Self::Ironman70_3,
+            Sport::Triathlon => Self::IronmanFull,
+            _ => Self::BaseBuilding,
+        }
+    }
+
     /// Get primary sport for this goal
     pub fn primary_sport(&self) -> Sport {
         match self {
@@ -179,6 +275,12 @@ impl RecoveryPattern {
 /// Individual planned workout
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PlannedWorkout {
+    /// Identifies the session across reschedules; empty for plans saved before IDs existed
+    #[serde(default)]
+    pub id: String,
+    /// Revision counter, bumped whenever the session is moved or resized after planning
+    #[serde(default)]
+    pub sequence: u32,
     pub date: NaiveDate,
     pub sport: Sport,
     pub workout_type: WorkoutType,
@@ -187,6 +289,62 @@ pub struct PlannedWorkout {
     pub description: String,
     pub intensity_factor: Decimal,
     pub notes: Option<String>,
+    /// Step-by-step session; duration, TSS and IF are derived from it when present
+    #[serde(default)]
+    pub structure: Option<StructuredWorkout>,
+}
+
+impl PlannedWorkout {
+    /// Planned workout built from a structured session
+    pub fn from_structure(
+        date: NaiveDate,
+        workout_type: WorkoutType,
+        label: &str,
+        structure: StructuredWorkout,
+        athlete: &AthleteProfile,
+    ) -> Self {
+        let load = structure.calculate_load(athlete);
+        PlannedWorkout {
+            id: uuid::Uuid::new_v4().to_string(),
+            sequence: 0,
+            date,
+            sport: structure.sport.clone(),
+            workout_type,
+            planned_duration_minutes: (load.duration_seconds + 30) / 60,
+            planned_tss: load.tss,
+            description: format!("{}: {}", label, structure.summary()),
+            intensity_factor: load.intensity_factor,
+            notes: None,
+            structure: Some(structure),
+        }
+    }
+
+    /// Short label before the step summary, e.g. "Threshold intervals"
+    pub fn label(&self) -> &str {
+        self.description.split(": ").next().unwrap_or_default()
+    }
+
+    /// The same session resized to a new duration
+    ///
+    /// Structured sessions are rebuilt from their template so the interval
+    /// count follows the duration; others scale TSS proportionally.
+    pub fn resized(&self, minutes: u32, athlete: &AthleteProfile) -> PlannedWorkout {
+        if self.structure.is_some() {
+            let session = TrainingPlanGenerator::build_session(&self.workout_type, &self.sport, minutes, athlete);
+            let mut workout = PlannedWorkout::from_structure(self.date, self.workout_type.clone(), self.label(), session, athlete);
+            workout.id = self.id.clone();
+            workout.sequence = self.sequence;
+            workout.notes = self.notes.clone();
+            workout
+        } else {
+            let factor = Decimal::from(minutes) / Decimal::from(self.planned_duration_minutes.max(1));
+            PlannedWorkout {
+                planned_duration_minutes: minutes,
+                planned_tss: (self.planned_tss * factor).round_dp(1),
+                ..self.clone()
+            }
+        }
+    }
 }
 
 /// Weekly training structure
@@ -217,9 +375,43 @@ pub struct TrainingPlan {
     pub weeks: Vec<TrainingWeek>,
     pub total_planned_tss: Decimal,
     pub total_planned_hours: Decimal,
+    /// Intended Seiler three-zone intensity distribution
+    #[serde(default)]
+    pub target_distribution: Option<DistributionTarget>,
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
 
+impl TrainingPlan {
+    /// Every planned session with its key, in plan order
+    pub fn sessions(&self) -> Vec<(PlannedSessionKey, &PlannedWorkout)> {
+        let mut per_day: HashMap<NaiveDate, u32> = HashMap::new();
+        self.weeks
+            .iter()
+            .flat_map(|w| w.workouts.iter())
+            .map(|workout| {
+                let index = per_day.entry(workout.date).or_insert(0);
+                let id = if workout.id.is_empty() {
+                    format!("{}-{}", workout.date.format("%Y%m%d"), index)
+                } else {
+                    workout.id.clone()
+                };
+                *index += 1;
+                (PlannedSessionKey { id, date: workout.date }, workout)
+            })
+            .collect()
+    }
+
+    /// Planned workouts scheduled on a specific date
+    pub fn workouts_on(&self, date: NaiveDate) -> Vec<&PlannedWorkout> {
+        self.weeks
+            .iter()
+            .filter(|w| w.start_date <= date && date <= w.end_date)
+            .flat_map(|w| w.workouts.iter())
+            .filter(|w| w.date == date)
+            .collect()
+    }
+}
+
 /// Plan monitoring data
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PlanMonitoring {
@@ -227,10 +419,44 @@ pub struct PlanMonitoring {
     pub current_week: u32,
     pub actual_vs_planned_tss: HashMap<NaiveDate, (Decimal, Decimal)>, // (actual, planned)
     pub completion_rate: Decimal,
+    pub session_links: Vec<PlannedSessionLink>,
+    pub missed_sessions: Vec<PlannedSessionKey>,
+    pub average_compliance: Option<Decimal>,
+    /// Completed intensity distribution checked against the plan's target
+    #[serde(default)]
+    pub distribution_compliance: Option<DistributionCompliance>,
     pub adjustments_needed: Vec<String>,
     pub recovery_recommendations: Vec<String>,
 }
 
+/// Identifies a planned session across reschedules, with the date it is scheduled on
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PlannedSessionKey {
+    /// The planned workout's id; plans saved before IDs existed fall back to date and position
+    pub id: String,
+    pub date: NaiveDate,
+}
+
+/// How closely a completed workout followed its planned session (0-100 each)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionCompliance {
+    pub duration_score: Decimal,
+    /// None when the completed workout has no TSS
+    pub tss_score: Option<Decimal>,
+    /// Share of time spent inside the step targets; None without steps or samples
+    pub zone_score: Option<Decimal>,
+    pub overall: Decimal,
+}
+
+/// Link between a planned session and the workout that completed it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlannedSessionLink {
+    pub plan_id: String,
+    pub session: PlannedSessionKey,
+    pub workout_id: String,
+    pub compliance: SessionCompliance,
+}
+
 /// Training plan generator
 pub struct TrainingPlanGenerator;
 
@@ -264,6 +490,7 @@ impl TrainingPlanGenerator {
             weeks: Vec::new(),
             total_planned_tss: dec!(0),
             total_planned_hours: dec!(0),
+            target_distribution: Some(DistributionTarget::for_model(&model)),
             created_at: chrono::Utc::now(),
         };
 
@@ -310,6 +537,256 @@ impl TrainingPlanGenerator {
         Ok(plan)
     }
 
+    /// Generate a multi-peak season plan from a race calendar
+    ///
+    /// Each A race gets its own periodized build ending in a full taper, followed
+    /// by a race week. B races get a mini-taper inside the surrounding week and
+    /// C races simply replace the session scheduled on race day. Races after
+    /// the last A race are reached with maintenance weeks.
+    pub fn generate_season(
+        calendar: &RaceCalendar,
+        start_date: NaiveDate,
+        model: PeriodizationModel,
+        recovery: RecoveryPattern,
+        athlete: &AthleteProfile,
+        current_metrics: Option<&PmcMetrics>,
+    ) -> Result<TrainingPlan> {
+        let a_races = calendar.with_priority(RacePriority::A, start_date);
+        if a_races.is_empty() {
+            anyhow::bail!("Season planning needs at least one A race after {}", start_date);
+        }
+
+        let mut weeks: Vec<TrainingWeek> = Vec::new();
+        let mut cursor = start_date;
+
+        for race in &a_races {
+            let goal = TrainingGoal::from_race(&race.sport, race.distance_meters);
+            let build_weeks = ((race.date - cursor).num_days().max(0) / 7) as u32;
+
+            if build_weeks > 0 {
+                let segment = Self::generate_plan(
+                    goal,
+                    model.clone(),
+                    recovery.clone(),
+                    build_weeks,
+                    Some(race.date),
+                    athlete,
+                    current_metrics,
+                )?;
+
+                // Segments are aligned to race day; absorb any leftover days into
+                // the previous week so the season has no gaps
+                if let (Some(previous), Some(first)) = (weeks.last_mut(), segment.weeks.first()) {
+                    previous.end_date = first.start_date - Duration::days(1);
+                }
+                weeks.extend(segment.weeks);
+            } else if let Some(previous) = weeks.last_mut() {
+                // Back-to-back A races: the previous race week ends early
+                previous.end_date = race.date - Duration::days(1);
+                let end_date = previous.end_date;
+                let previous_tss = Self::workouts_tss(previous);
+                previous.workouts.retain(|w| w.date <= end_date);
+                Self::adjust_week_totals(previous, previous_tss);
+            }
+
+            weeks.push(Self::create_race_week(race, athlete));
+            cursor = race.date + Duration::weeks(1);
+        }
+
+        // B and C races after the last A race extend the season with maintenance weeks
+        let last_race = calendar.upcoming(start_date).iter().map(|r| r.date).max().unwrap_or(cursor);
+        let last_a_race = a_races[a_races.len() - 1];
+        let maintenance_goal = TrainingGoal::from_race(&last_a_race.sport, last_a_race.distance_meters);
+        let maintenance_tss = current_metrics.map(|m| m.ctl * dec!(7)).unwrap_or(dec!(200));
+        let mut maintenance_weeks = 0;
+        while let Some(last) = weeks.last().filter(|w| w.end_date < last_race) {
+            maintenance_weeks += 1;
+            weeks.push(Self::create_week(
+                0,
+                last.end_date + Duration::days(1),
+                "Maintenance",
+                "Maintain fitness between races",
+                maintenance_tss,
+                maintenance_weeks % recovery.cycle_length() == 0,
+                &maintenance_goal,
+                athlete,
+            )?);
+        }
+
+        for race in calendar.upcoming(start_date) {
+            match race.priority {
+                RacePriority::A => {}
+                RacePriority::B => Self::apply_mini_taper(&mut weeks, race, athlete),
+                RacePriority::C => Self::apply_training_race(&mut weeks, race),
+            }
+        }
+
+        for (i, week) in weeks.iter_mut().enumerate() {
+            week.week_number = i as u32 + 1;
+        }
+
+        let first_goal = TrainingGoal::from_race(&a_races[0].sport, a_races[0].distance_meters);
+        let total_planned_tss = weeks.iter().map(|w| w.planned_tss).sum();
+        let total_planned_hours = weeks.iter().map(|w| w.planned_hours).sum();
+
+        Ok(TrainingPlan {
+            id: format!("season_{}", chrono::Utc::now().timestamp()),
+            name: format!("Season Plan ({} A races)", a_races.len()),
+            goal: first_goal,
+            target_distribution: Some(DistributionTarget::for_model(&model)),
+            periodization_model: model,
+            recovery_pattern: recovery,
+            start_date: weeks.first().map(|w| w.start_date).unwrap_or(start_date),
+            target_date: a_races.last().map(|r| r.date),
+            total_weeks: weeks.len() as u32,
+            weeks,
+            total_planned_tss,
+            total_planned_hours,
+            created_at: chrono::Utc::now(),
+        })
+    }
+
+    /// Planned session representing the race itself
+    fn race_workout(race: &Race) -> PlannedWorkout {
+        let minutes = race.expected_duration_minutes();
+        // Sustainable intensity falls with race duration
+        let intensity_factor = match minutes {
+            0..=30 => dec!(1.05),
+            31..=60 => dec!(1.00),
+            61..=120 => dec!(0.92),
+            121..=240 => dec!(0.85),
+            _ => dec!(0.75),
+        };
+        let planned_tss = Decimal::from(minutes) / dec!(60) * intensity_factor * intensity_factor * dec!(100);
+
+        PlannedWorkout {
+            id: uuid::Uuid::new_v4().to_string(),
+            sequence: 0,
+            date: race.date,
+            sport: race.sport.clone(),
+            workout_type: WorkoutType::Race,
+            planned_duration_minutes: minutes,
+            planned_tss: planned_tss.round_dp(1),
+            description: format!("{} ({:?} race)", race.name, race.priority),
+            intensity_factor,
+            notes: race.notes.clone(),
+            structure: None,
+        }
+    }
+
+    pub(crate) fn easy_workout(date: NaiveDate, sport: &Sport, minutes: u32, description: &str, athlete: &AthleteProfile) -> PlannedWorkout {
+        let session = Self::build_session(&WorkoutType::Recovery, sport, minutes, athlete);
+        PlannedWorkout::from_structure(date, WorkoutType::Recovery, description, session, athlete)
+    }
+
+    /// Short session with a few race-pace efforts the day before a race
+    fn openers_workout(date: NaiveDate, sport: &Sport, athlete: &AthleteProfile) -> PlannedWorkout {
+        let easy = StepTarget::for_sport(sport, athlete, dec!(0.50), dec!(0.65));
+        let session = StructuredWorkout::new("Openers", sport.clone())
+            .step(StepKind::Warmup, StepDuration::minutes(20), easy)
+            .repeat(
+                4,
+                vec![
+                    WorkoutStep::new(StepKind::Interval, StepDuration::Time { seconds: 30 }, StepTarget::for_sport(sport, athlete, dec!(1.00), dec!(1.10))),
+                    WorkoutStep::new(StepKind::Recovery, StepDuration::Time { seconds: 150 }, easy),
+                ],
+            )
+            .step(StepKind::Cooldown, StepDuration::minutes(8), easy);
+        PlannedWorkout::from_structure(date, WorkoutType::Endurance, "Openers", session, athlete)
+    }
+
+    /// Race week following an A race: race day then easy recovery sessions
+    fn create_race_week(race: &Race, athlete: &AthleteProfile) -> TrainingWeek {
+        let workouts = vec![
+            Self::race_workout(race),
+            Self::easy_workout(race.date + Duration::days(2), &race.sport, 30, "Post-race recovery", athlete),
+            Self::easy_workout(race.date + Duration::days(4), &race.sport, 45, "Easy aerobic session", athlete),
+            Self::easy_workout(race.date + Duration::days(6), &race.sport, 60, "Easy aerobic session", athlete),
+        ];
+
+        let mut week = TrainingWeek {
+            week_number: 0,
+            start_date: race.date,
+            end_date: race.date + Duration::days(6),
+            phase: "Race".to_string(),
+            focus: format!("{} and recovery", race.name),
+            planned_tss: dec!(0),
+            planned_hours: dec!(0),
+            is_recovery_week: true,
+            workouts,
+        };
+        Self::adjust_week_totals(&mut week, dec!(0));
+        week
+    }
+
+    /// Reduce load in the days around a B race
+    ///
+    /// Sessions in the taper window are cut to 60%, the day before becomes an
+    /// openers session and the day after an easy recovery session.
+    fn apply_mini_taper(weeks: &mut [TrainingWeek], race: &Race, athlete: &AthleteProfile) {
+        if !weeks.iter().any(|w| w.start_date <= race.date && race.date <= w.end_date) {
+            return;
+        }
+
+        let taper_start = race.date - Duration::days(race.priority.taper_days() as i64);
+        let day_before = race.date - Duration::days(1);
+        let day_after = race.date + Duration::days(1);
+
+        for week in weeks.iter_mut() {
+            if week.end_date < taper_start || week.start_date > day_after {
+                continue;
+            }
+
+            let previous_tss = Self::workouts_tss(week);
+            week.workouts.retain(|w| w.date != race.date && w.date != day_before && w.date != day_after);
+            for workout in week.workouts.iter_mut().filter(|w| w.date >= taper_start && w.date < race.date) {
+                *workout = workout.resized(workout.planned_duration_minutes * 6 / 10, athlete);
+                workout.notes = Some(format!("Mini-taper for {}", race.name));
+            }
+
+            if week.start_date <= day_before && day_before <= week.end_date {
+                week.workouts.push(Self::openers_workout(day_before, &race.sport, athlete));
+            }
+            if week.start_date <= race.date && race.date <= week.end_date {
+                week.workouts.push(Self::race_workout(race));
+            }
+            if week.start_date <= day_after && day_after <= week.end_date {
+                week.workouts.push(Self::easy_workout(day_after, &race.sport, 30, "Post-race recovery", athlete));
+            }
+
+            week.workouts.sort_by_key(|w| w.date);
+            Self::adjust_week_totals(week, previous_tss);
+        }
+    }
+
+    /// Replace the session on a C race day with the race
+    fn apply_training_race(weeks: &mut [TrainingWeek], race: &Race) {
+        if let Some(week) = weeks.iter_mut().find(|w| w.start_date <= race.date && race.date <= w.end_date) {
+            let previous_tss = Self::workouts_tss(week);
+            week.workouts.retain(|w| w.date != race.date);
+            week.workouts.push(Self::race_workout(race));
+            week.workouts.sort_by_key(|w| w.date);
+            Self::adjust_week_totals(week, previous_tss);
+        }
+    }
+
+    pub(crate) fn workouts_tss(week: &TrainingWeek) -> Decimal {
+        week.workouts.iter().map(|w| w.planned_tss).sum()
+    }
+
+    /// Total duration of the scheduled workouts in hours
+    pub(crate) fn workouts_hours(week: &TrainingWeek) -> Decimal {
+        let minutes: u32 = week.workouts.iter().map(|w| w.planned_duration_minutes).sum();
+        (Decimal::from(minutes) / dec!(60)).round_dp(2)
+    }
+
+    /// Shift week TSS by the change in scheduled workout TSS and total the session hours
+    pub(crate) fn adjust_week_totals(week: &mut TrainingWeek, previous_workouts_tss: Decimal) {
+        let delta = Self::workouts_tss(week) - previous_workouts_tss;
+        week.planned_tss = (week.planned_tss + delta).max(dec!(0));
+        week.planned_hours = Self::workouts_hours(week);
+    }
+
     /// Generate traditional periodization weeks (Base → Build → Peak → Taper)
     fn generate_traditional_weeks(
         goal: &TrainingGoal,
@@ -583,9 +1060,6 @@ impl TrainingPlanGenerator {
             planned_tss
         };
 
-        // Calculate hours from TSS (rough estimate: 100 TSS ≈ 1.5 hours)
-        let planned_hours = tss / dec!(100) * dec!(1.5);
-
         let mut week = TrainingWeek {
             week_number,
             start_date,
@@ -593,7 +1067,7 @@ impl TrainingPlanGenerator {
             phase: phase.to_string(),
             focus: focus.to_string(),
             planned_tss: tss,
-            planned_hours,
+            planned_hours: dec!(0),
             is_recovery_week: is_recovery,
             workouts: Vec::new(),
         };
@@ -605,6 +1079,10 @@ impl TrainingPlanGenerator {
             athlete,
         )?;
 
+        // Week totals follow the sessions actually scheduled
+        week.planned_tss = Self::workouts_tss(&week);
+        week.planned_hours = Self::workouts_hours(&week);
+
         Ok(week)
     }
 
@@ -612,7 +1090,7 @@ impl TrainingPlanGenerator {
     fn generate_week_workouts(
         week: &TrainingWeek,
         goal: &TrainingGoal,
-        _athlete: &AthleteProfile,
+        athlete: &AthleteProfile,
     ) -> Result<Vec<PlannedWorkout>> {
         let mut workouts = Vec::new();
         let sport = goal.primary_sport();
@@ -650,24 +1128,95 @@ impl TrainingPlanGenerator {
                     goal,
                 );
 
-                let duration_minutes = (workout_tss / intensity_factor / dec!(100) * dec!(60)).to_u32().unwrap_or(60);
+                // Size the session so its TSS (hours × IF² × 100) matches the budget
+                let duration_minutes = (workout_tss * dec!(60) / (intensity_factor * intensity_factor * dec!(100)))
+                    .to_u32()
+                    .unwrap_or(60)
+                    .max(20);
 
-                workouts.push(PlannedWorkout {
-                    date: workout_date,
-                    sport: sport.clone(),
+                let session = Self::build_session(&workout_type, &sport, duration_minutes, athlete);
+                let mut workout = PlannedWorkout::from_structure(
+                    workout_date,
                     workout_type,
-                    planned_duration_minutes: duration_minutes,
-                    planned_tss: workout_tss,
-                    description,
-                    intensity_factor,
-                    notes: None,
-                });
+                    &description,
+                    session,
+                    athlete,
+                );
+
+                // The steps set the real IF; resize once so the step-derived TSS meets the budget
+                if workout.planned_tss > dec!(0) {
+                    let minutes = (Decimal::from(workout.planned_duration_minutes) * workout_tss / workout.planned_tss)
+                        .to_u32()
+                        .unwrap_or(duration_minutes)
+                        .max(20);
+                    if minutes != workout.planned_duration_minutes {
+                        workout = workout.resized(minutes, athlete);
+                    }
+                }
+                workouts.push(workout);
             }
         }
 
         Ok(workouts)
     }
 
+    /// Build a structured session of roughly the given length for a workout type
+    ///
+    /// Quality sessions get a warmup, a repeated main set sized to fit and a
+    /// cooldown; endurance and recovery sessions are steady efforts.
+    fn build_session(
+        workout_type: &WorkoutType,
+        sport: &Sport,
+        minutes: u32,
+        athlete: &AthleteProfile,
+    ) -> StructuredWorkout {
+        let target = |low: Decimal, high: Decimal| StepTarget::for_sport(sport, athlete, low, high);
+        let easy = target(dec!(0.50), dec!(0.60));
+
+        // (name, work minutes, work intensity, rest minutes, max repeats)
+        let main_set = match workout_type {
+            WorkoutType::Tempo => Some(("Tempo", 10, (dec!(0.76), dec!(0.90)), 3, 4)),
+            WorkoutType::Threshold => Some(("Threshold", 8, (dec!(0.95), dec!(1.05)), 4, 6)),
+            WorkoutType::Race => Some(("Race pace", 5, (dec!(1.00), dec!(1.05)), 3, 8)),
+            WorkoutType::VO2Max | WorkoutType::Interval => Some(("VO2max", 4, (dec!(1.06), dec!(1.20)), 3, 6)),
+            _ => None,
+        };
+
+        match main_set {
+            Some((name, work, (low, high), rest, max_repeats)) => {
+                let warmup = (minutes / 5).clamp(10, 15);
+                let cooldown = (minutes / 8).clamp(5, 10);
+                let main = minutes.saturating_sub(warmup + cooldown);
+                let repeats = (main / (work + rest)).clamp(1, max_repeats);
+
+                StructuredWorkout::new(name, sport.clone())
+                    .step(StepKind::Warmup, StepDuration::minutes(warmup), target(dec!(0.50), dec!(0.65)))
+                    .repeat(
+                        repeats,
+                        vec![
+                            WorkoutStep::new(StepKind::Interval, StepDuration::minutes(work), target(low, high)),
+                            WorkoutStep::new(StepKind::Recovery, StepDuration::minutes(rest), easy),
+                        ],
+                    )
+                    .step(StepKind::Cooldown, StepDuration::minutes(cooldown), easy)
+            }
+            None if *workout_type == WorkoutType::Recovery => StructuredWorkout::new("Recovery", sport.clone())
+                .step(StepKind::Steady, StepDuration::minutes(minutes), target(dec!(0.45), dec!(0.55))),
+            None => {
+                let warmup = 10.min(minutes / 4);
+                let cooldown = 5.min(minutes / 6);
+                StructuredWorkout::new("Endurance", sport.clone())
+                    .step(StepKind::Warmup, StepDuration::minutes(warmup), easy)
+                    .step(
+                        StepKind::Steady,
+                        StepDuration::minutes(minutes - warmup - cooldown),
+                        target(dec!(0.56), dec!(0.75)),
+                    )
+                    .step(StepKind::Cooldown, StepDuration::minutes(cooldown), easy)
+            }
+        }
+    }
+
     /// Determine workout type based on day and phase
     fn determine_workout_type(
         day: usize,
@@ -754,41 +1303,88 @@ impl TrainingPlanGenerator {
     }
 }
 
+/// |actual - planned| / planned
+fn relative_difference(actual: Decimal, planned: Decimal) -> Decimal {
+    ((actual - planned) / planned).abs()
+}
+
+/// 100 for an exact match, falling linearly to 0 at a 100% difference
+fn closeness_score(actual: Decimal, planned: Decimal) -> Decimal {
+    ((dec!(1) - relative_difference(actual, planned)) * dec!(100)).max(dec!(0)).round()
+}
+
+/// Whether a sample lies inside a step target, or None when it can't be judged
+fn sample_in_target(sample: &crate::models::DataPoint, target: &StepTarget, athlete: &AthleteProfile) -> Option<bool> {
+    match *target {
+        StepTarget::PercentFtp { low, high } => {
+            let ftp = u32::from(athlete.ftp?);
+            let percent = u32::from(sample.power?) * 100 / ftp.max(1);
+            Some(u32::from(low) <= percent && percent <= u32::from(high))
+        }
+        StepTarget::PercentLthr { low, high } => {
+            let lthr = u32::from(athlete.lthr?);
+            let percent = u32::from(sample.heart_rate?) * 100 / lthr.max(1);
+            Some(u32::from(low) <= percent && percent <= u32::from(high))
+        }
+        StepTarget::Pace { fast, slow } => {
+            let speed = sample.speed.filter(|s| *s > dec!(0))?;
+            let minutes_per_km = dec!(1000) / speed / dec!(60);
+            Some(fast <= minutes_per_km && minutes_per_km <= slow)
+        }
+        StepTarget::Rpe(_) | StepTarget::Open => None,
+    }
+}
+
 /// Plan monitor for tracking and adjustments
 pub struct PlanMonitor;
 
 #[allow(dead_code)]
 impl PlanMonitor {
-    /// Monitor plan progress
+    /// Monitor plan progress for the sessions scheduled up to `as_of`
     pub fn monitor_progress(
         plan: &TrainingPlan,
         actual_workouts: &[crate::models::Workout],
+        athlete: &AthleteProfile,
+        as_of: NaiveDate,
     ) -> Result<PlanMonitoring> {
+        let session_links = Self::match_workouts(plan, actual_workouts, athlete);
+
         let mut monitoring = PlanMonitoring {
             plan_id: plan.id.clone(),
-            current_week: Self::calculate_current_week(plan),
+            current_week: Self::calculate_current_week(plan, as_of),
             actual_vs_planned_tss: HashMap::new(),
             completion_rate: dec!(0),
+            session_links: Vec::new(),
+            missed_sessions: Vec::new(),
+            average_compliance: None,
+            distribution_compliance: None,
             adjustments_needed: Vec::new(),
             recovery_recommendations: Vec::new(),
         };
 
-        // Calculate actual vs planned TSS
-        for week in &plan.weeks {
-            for workout in &week.workouts {
-                let actual_tss = actual_workouts
-                    .iter()
-                    .filter(|w| w.date == workout.date)
-                    .map(|w| w.summary.tss.unwrap_or(dec!(0)))
-                    .sum::<Decimal>();
-
-                monitoring.actual_vs_planned_tss.insert(
-                    workout.date,
-                    (actual_tss, workout.planned_tss),
-                );
+        // Calculate actual vs planned TSS from the linked workouts
+        for (key, planned) in plan.sessions().into_iter().filter(|(key, _)| key.date <= as_of) {
+            let linked = session_links.iter().find(|link| link.session.id == key.id);
+            let actual_tss = linked
+                .and_then(|link| actual_workouts.iter().find(|w| w.id == link.workout_id))
+                .map(|w| w.summary.tss.unwrap_or(dec!(0)))
+                .unwrap_or(dec!(0));
+
+            let entry = monitoring.actual_vs_planned_tss.entry(key.date).or_insert((dec!(0), dec!(0)));
+            entry.0 += actual_tss;
+            entry.1 += planned.planned_tss;
+
+            if linked.is_none() {
+                monitoring.missed_sessions.push(key);
             }
         }
 
+        monitoring.session_links = session_links.into_iter().filter(|link| link.session.date <= as_of).collect();
+        if !monitoring.session_links.is_empty() {
+            let total: Decimal = monitoring.session_links.iter().map(|link| link.compliance.overall).sum();
+            monitoring.average_compliance = Some((total / Decimal::from(monitoring.session_links.len())).round());
+        }
+
         // Calculate completion rate
         let total_planned = monitoring.actual_vs_planned_tss
             .values()
@@ -805,6 +1401,14 @@ impl PlanMonitor {
             dec!(0)
         };
 
+        if let Some(target) = &plan.target_distribution {
+            let thresholds = SeilerThresholds::from_athlete(athlete);
+            let block = IntensityDistributionAnalyzer::block_distribution(actual_workouts, &thresholds, plan.start_date, as_of);
+            if block.distribution.total_seconds() > 0 {
+                monitoring.distribution_compliance = Some(target.check(&block.distribution));
+            }
+        }
+
         // Generate adjustments and recommendations
         monitoring.adjustments_needed = Self::generate_adjustments(&monitoring);
         monitoring.recovery_recommendations = Self::generate_recovery_recommendations(&monitoring);
@@ -812,14 +1416,126 @@ impl PlanMonitor {
         Ok(monitoring)
     }
 
-    fn calculate_current_week(plan: &TrainingPlan) -> u32 {
-        let today = chrono::Local::now().date_naive();
-        let days_since_start = (today - plan.start_date).num_days();
+    /// Link completed workouts to planned sessions
+    ///
+    /// Candidates must share the date and sport. Pairs are assigned cheapest
+    /// first, where the cost is the relative difference in duration plus the
+    /// relative difference in TSS, so each workout completes at most one session.
+    pub fn match_workouts(
+        plan: &TrainingPlan,
+        workouts: &[crate::models::Workout],
+        athlete: &AthleteProfile,
+    ) -> Vec<PlannedSessionLink> {
+        let sessions = plan.sessions();
+
+        let mut candidates = Vec::new();
+        for (session_idx, (key, planned)) in sessions.iter().enumerate() {
+            for (workout_idx, workout) in workouts.iter().enumerate() {
+                if workout.date != key.date || workout.sport != planned.sport {
+                    continue;
+                }
+                let planned_minutes = Decimal::from(planned.planned_duration_minutes.max(1));
+                let actual_minutes = Decimal::from(workout.duration_seconds) / dec!(60);
+                let mut cost = relative_difference(actual_minutes, planned_minutes);
+                if let Some(tss) = workout.summary.tss {
+                    cost += relative_difference(tss, planned.planned_tss.max(dec!(1)));
+                }
+                candidates.push((cost, session_idx, workout_idx));
+            }
+        }
+        candidates.sort_by_key(|candidate| candidate.0);
+
+        let mut used_sessions = vec![false; sessions.len()];
+        let mut used_workouts = vec![false; workouts.len()];
+        let mut links = Vec::new();
+        for (_, session_idx, workout_idx) in candidates {
+            if used_sessions[session_idx] || used_workouts[workout_idx] {
+                continue;
+            }
+            used_sessions[session_idx] = true;
+            used_workouts[workout_idx] = true;
+
+            let (key, planned) = &sessions[session_idx];
+            let workout = &workouts[workout_idx];
+            links.push(PlannedSessionLink {
+                plan_id: plan.id.clone(),
+                session: key.clone(),
+                workout_id: workout.id.clone(),
+                compliance: Self::score_session(planned, workout, athlete),
+            });
+        }
+
+        links.sort_by_key(|link| link.session.date);
+        links
+    }
+
+    /// Score how closely a workout followed a planned session
+    pub fn score_session(
+        planned: &PlannedWorkout,
+        workout: &crate::models::Workout,
+        athlete: &AthleteProfile,
+    ) -> SessionCompliance {
+        let actual_minutes = Decimal::from(workout.duration_seconds) / dec!(60);
+        let duration_score = closeness_score(actual_minutes, Decimal::from(planned.planned_duration_minutes.max(1)));
+        let tss_score = workout.summary.tss.map(|tss| closeness_score(tss, planned.planned_tss.max(dec!(1))));
+        let zone_score = planned
+            .structure
+            .as_ref()
+            .and_then(|structure| Self::time_in_target_zone(structure, workout, athlete));
+
+        let scores: Vec<Decimal> = [Some(duration_score), tss_score, zone_score].into_iter().flatten().collect();
+        let overall = (scores.iter().sum::<Decimal>() / Decimal::from(scores.len())).round();
+
+        SessionCompliance { duration_score, tss_score, zone_score, overall }
+    }
+
+    /// Percentage of scored samples that fall inside the target of the step
+    /// scheduled at that point of the workout
+    ///
+    /// Steps with open or RPE targets, and samples missing the targeted
+    /// channel, are not scored.
+    fn time_in_target_zone(
+        structure: &StructuredWorkout,
+        workout: &crate::models::Workout,
+        athlete: &AthleteProfile,
+    ) -> Option<Decimal> {
+        let samples = workout.raw_data.as_ref()?;
+
+        let mut windows = Vec::new();
+        let mut elapsed = 0u32;
+        for step in structure.flatten() {
+            let seconds = step.estimated_seconds(&structure.sport, athlete);
+            windows.push((elapsed, elapsed + seconds, step.target));
+            elapsed += seconds;
+        }
+
+        let mut scored = 0u32;
+        let mut in_zone = 0u32;
+        for sample in samples {
+            let Some((_, _, target)) = windows.iter().find(|(start, end, _)| *start <= sample.timestamp && sample.timestamp < *end) else {
+                continue;
+            };
+            if let Some(inside) = sample_in_target(sample, target, athlete) {
+                scored += 1;
+                if inside {
+                    in_zone += 1;
+                }
+            }
+        }
+
+        (scored > 0).then(|| (Decimal::from(in_zone) * dec!(100) / Decimal::from(scored)).round())
+    }
+
+    fn calculate_current_week(plan: &TrainingPlan, as_of: NaiveDate) -> u32 {
+        let days_since_start = (as_of - plan.start_date).num_days();
         ((days_since_start / 7) + 1).max(1) as u32
     }
 
     fn generate_adjustments(monitoring: &PlanMonitoring) -> Vec<String> {
         let mut adjustments = Vec::new();
+        if monitoring.actual_vs_planned_tss.is_empty() {
+            return adjustments;
+        }
 
         if monitoring.completion_rate < dec!(80) {
             adjustments.push("Consider reducing planned TSS by 10-15% for upcoming weeks".to_string());
@@ -830,19 +1546,31 @@ impl PlanMonitor {
         }
 
         // Check for consistent under/over performance
-        let recent_performance: Vec<_> = monitoring.actual_vs_planned_tss
-            .values()
-            .take(7) // Last week
-            .collect();
+        let recent_performance = Self::recent_days(monitoring);
 
-        if recent_performance.iter().all(|(a, p)| a < &(p * dec!(0.9))) {
+        if !recent_performance.is_empty() && recent_performance.iter().all(|(a, p)| a < &(p * dec!(0.9))) {
             adjustments.push("Consistently under target - consider recovery or reduced volume".to_string());
         }
 
-        if recent_performance.iter().all(|(a, p)| a > &(p * dec!(1.1))) {
+        if !recent_performance.is_empty() && recent_performance.iter().all(|(a, p)| a > &(p * dec!(1.1))) {
             adjustments.push("Consistently over target - great progress but watch for overtraining".to_string());
         }
 
+        if monitoring.average_compliance.is_some_and(|c| c < dec!(70)) {
+            adjustments.push("Completed sessions drift from their targets - review zones or session difficulty".to_string());
+        }
+
+        if let Some(advice) = monitoring.distribution_compliance.as_ref().and_then(DistributionCompliance::advice) {
+            adjustments.push(advice);
+        }
+
+        if monitoring.missed_sessions.len() >= 3 {
+            adjustments.push(format!(
+                "{} planned sessions missed - consider rescheduling key workouts",
+                monitoring.missed_sessions.len()
+            ));
+        }
+
         adjustments
     }
 
@@ -850,9 +1578,8 @@ impl PlanMonitor {
         let mut recommendations = Vec::new();
 
         // Check recent load
-        let recent_actual: Decimal = monitoring.actual_vs_planned_tss
-            .values()
-            .take(7)
+        let recent_actual: Decimal = Self::recent_days(monitoring)
+            .iter()
             .map(|(a, _)| a)
             .sum();
 
@@ -867,6 +1594,13 @@ impl PlanMonitor {
         recommendations
     }
 
+    /// (actual, planned) TSS for the last 7 monitored days
+    fn recent_days(monitoring: &PlanMonitoring) -> Vec<(Decimal, Decimal)> {
+        let mut days: Vec<_> = monitoring.actual_vs_planned_tss.iter().collect();
+        days.sort_by(|a, b| b.0.cmp(a.0));
+        days.into_iter().take(7).map(|(_, tss)| *tss).collect()
+    }
+
     /// Adjust existing plan based on progress
     pub fn adjust_plan(
         plan: &mut TrainingPlan,
@@ -885,7 +1619,6 @@ impl PlanMonitor {
         for week in &mut plan.weeks {
             if week.start_date >= today {
                 week.planned_tss *= adjustment_factor;
-                week.planned_hours *= adjustment_factor;
 
                 // Adjust individual workouts
                 for workout in &mut week.workouts {
@@ -895,6 +1628,7 @@ impl PlanMonitor {
                             .to_u32()
                             .unwrap_or(30);
                 }
+                week.planned_hours = TrainingPlanGenerator::workouts_hours(week);
             }
         }
 
@@ -942,9 +1676,8 @@ mod tests {
         assert_eq!(RecoveryPattern::from_str("3:1").unwrap().build_weeks(), 3);
     }
 
-    #[test]
-    fn test_plan_generation() {
-        let athlete = crate::models::AthleteProfile {
+    fn test_athlete() -> AthleteProfile {
+        crate::models::AthleteProfile {
             id: "test".to_string(),
             name: "Test Athlete".to_string(),
             date_of_birth: None,
@@ -959,8 +1692,14 @@ mod tests {
             preferred_units: crate::models::Units::Metric,
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
-        };
+            rowing_ftp: None,
+            css_pace: None,
+        }
+    }
 
+    #[test]
+    fn test_plan_generation() {
+        let athlete = test_athlete();
         let plan = TrainingPlanGenerator::generate_plan(
             TrainingGoal::Marathon,
             PeriodizationModel::Traditional,
@@ -975,4 +1714,378 @@ mod tests {
         assert_eq!(plan.weeks.len(), 12);
         assert!(plan.total_planned_tss > dec!(0));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_week_totals_match_sessions() {
+        use crate::race_calendar::Race;
+
+        let athlete = test_athlete();
+        let mut plans = Vec::new();
+        for model in [PeriodizationModel::Traditional, PeriodizationModel::Block, PeriodizationModel::Reverse] {
+            plans.push(TrainingPlanGenerator::generate_plan(
+                TrainingGoal::Marathon,
+                model,
+                RecoveryPattern::ThreeToOne,
+                12,
+                None,
+                &athlete,
+                None,
+            ).unwrap());
+        }
+
+        let date = |m, d| NaiveDate::from_ymd_opt(2025, m, d).unwrap();
+        let mut calendar = RaceCalendar::default();
+        calendar.add(Race::new("Spring Half".to_string(), date(4, 13), Sport::Running, RacePriority::A));
+        calendar.add(Race::new("Summer 10K".to_string(), date(6, 8), Sport::Running, RacePriority::B));
+        calendar.add(Race::new("Autumn Marathon".to_string(), date(10, 12), Sport::Running, RacePriority::A));
+        plans.push(TrainingPlanGenerator::generate_season(
+            &calendar,
+            date(1, 6),
+            PeriodizationModel::Traditional,
+            RecoveryPattern::ThreeToOne,
+            &athlete,
+            None,
+        ).unwrap());
+
+        for plan in &plans {
+            for week in &plan.weeks {
+                assert_eq!(week.planned_tss, TrainingPlanGenerator::workouts_tss(week), "week {}", week.week_number);
+                let minutes: u32 = week.workouts.iter().map(|w| w.planned_duration_minutes).sum();
+                assert_eq!(week.planned_hours, (Decimal::from(minutes) / dec!(60)).round_dp(2), "week {}", week.week_number);
+            }
+            let week_sum: Decimal = plan.weeks.iter().map(|w| w.planned_tss).sum();
+            assert_eq!(plan.total_planned_tss, week_sum);
+            let hours_sum: Decimal = plan.weeks.iter().map(|w| w.planned_hours).sum();
+            assert_eq!(plan.total_planned_hours, hours_sum);
+        }
+
+        // Sessions are sized from their steps to the week's budget (200 TSS in the first base week)
+        let first = &plans[0].weeks[0];
+        assert!((first.planned_tss - dec!(200)).abs() <= dec!(20), "{}", first.planned_tss);
+    }
+
+    #[test]
+    fn test_generated_sessions_are_structured() {
+        let athlete = test_athlete();
+        let plan = TrainingPlanGenerator::generate_plan(
+            TrainingGoal::Century,
+            PeriodizationModel::Traditional,
+            RecoveryPattern::ThreeToOne,
+            10,
+            None,
+            &athlete,
+            None,
+        ).unwrap();
+
+        for workout in plan.weeks.iter().flat_map(|w| w.workouts.iter()) {
+            let structure = workout.structure.as_ref().expect("generated sessions have steps");
+            let load = structure.calculate_load(&athlete);
+            assert_eq!(workout.planned_tss, load.tss);
+            assert_eq!(workout.intensity_factor, load.intensity_factor);
+        }
+
+        let threshold = plan
+            .weeks
+            .iter()
+            .flat_map(|w| w.workouts.iter())
+            .find(|w| w.workout_type == WorkoutType::Threshold)
+            .unwrap();
+        assert!(threshold.description.contains("×8min @ 95-105% FTP"), "{}", threshold.description);
+    }
+
+    #[test]
+    fn test_season_plan_with_multiple_peaks() {
+        use crate::race_calendar::Race;
+
+        let date = |m, d| NaiveDate::from_ymd_opt(2025, m, d).unwrap();
+        let mut calendar = RaceCalendar::default();
+
+        let mut spring = Race::new("Spring Half".to_string(), date(4, 13), Sport::Running, RacePriority::A);
+        spring.distance_meters = Some(dec!(21097));
+        spring.goal_time_seconds = Some(5400);
+        calendar.add(spring);
+
+        let mut autumn = Race::new("Autumn Marathon".to_string(), date(10, 12), Sport::Running, RacePriority::A);
+        autumn.distance_meters = Some(dec!(42195));
+        calendar.add(autumn);
+
+        let mut tune_up = Race::new("Summer 10K".to_string(), date(8, 17), Sport::Running, RacePriority::B);
+        tune_up.distance_meters = Some(dec!(10000));
+        calendar.add(tune_up);
+
+        calendar.add(Race::new("Parkrun".to_string(), date(6, 7), Sport::Running, RacePriority::C));
+
+        let plan = TrainingPlanGenerator::generate_season(
+            &calendar,
+            date(1, 6),
+            PeriodizationModel::Traditional,
+            RecoveryPattern::ThreeToOne,
+            &test_athlete(),
+            None,
+        ).unwrap();
+
+        assert_eq!(plan.target_date, Some(date(10, 12)));
+        assert_eq!(plan.total_weeks as usize, plan.weeks.len());
+        assert!(plan.weeks.iter().enumerate().all(|(i, w)| w.week_number == i as u32 + 1));
+
+        // Weeks are contiguous across both peaks
+        for pair in plan.weeks.windows(2) {
+            assert_eq!(pair[0].end_date + Duration::days(1), pair[1].start_date);
+        }
+
+        // Both A races get a taper followed by a race week
+        let race_weeks: Vec<_> = plan.weeks.iter().filter(|w| w.phase == "Race").collect();
+        assert_eq!(race_weeks.len(), 2);
+        for race_week in race_weeks {
+            let previous = plan.weeks.iter().find(|w| w.week_number == race_week.week_number - 1).unwrap();
+            assert_eq!(previous.phase, "Taper");
+        }
+
+        // Every race is scheduled on its date
+        for race in &calendar.races {
+            let on_day = plan.workouts_on(race.date);
+            assert_eq!(on_day.len(), 1, "{}", race.name);
+            assert_eq!(on_day[0].workout_type, WorkoutType::Race);
+        }
+
+        // B race: reduced load before, openers the day before, recovery after
+        let day_before = plan.workouts_on(date(8, 16));
+        assert!(day_before[0].description.starts_with("Openers"));
+        let day_after = plan.workouts_on(date(8, 18));
+        assert_eq!(day_after[0].workout_type, WorkoutType::Recovery);
+        assert!(plan
+            .workouts_on(date(8, 14))
+            .iter()
+            .all(|w| w.notes.as_deref() == Some("Mini-taper for Summer 10K")));
+
+        let week_sum: Decimal = plan.weeks.iter().map(|w| w.planned_tss).sum();
+        assert_eq!(plan.total_planned_tss, week_sum);
+    }
+
+    #[test]
+    fn test_season_extends_to_races_after_last_a_race() {
+        use crate::race_calendar::Race;
+
+        let date = |m, d| NaiveDate::from_ymd_opt(2025, m, d).unwrap();
+        let mut calendar = RaceCalendar::default();
+        calendar.add(Race::new("Spring Half".to_string(), date(4, 13), Sport::Running, RacePriority::A));
+        calendar.add(Race::new("Summer 10K".to_string(), date(6, 8), Sport::Running, RacePriority::B));
+        calendar.add(Race::new("Parkrun".to_string(), date(6, 21), Sport::Running, RacePriority::C));
+
+        let plan = TrainingPlanGenerator::generate_season(
+            &calendar,
+            date(1, 6),
+            PeriodizationModel::Traditional,
+            RecoveryPattern::ThreeToOne,
+            &test_athlete(),
+            None,
+        ).unwrap();
+
+        assert!(plan.weeks.last().unwrap().end_date >= date(6, 21));
+        assert!(plan.weeks.iter().any(|w| w.phase == "Maintenance"));
+        for pair in plan.weeks.windows(2) {
+            assert_eq!(pair[0].end_date + Duration::days(1), pair[1].start_date);
+        }
+        for race in &calendar.races {
+            let on_day = plan.workouts_on(race.date);
+            assert_eq!(on_day.len(), 1, "{}", race.name);
+            assert_eq!(on_day[0].workout_type, WorkoutType::Race);
+        }
+        assert!(plan.workouts_on(date(6, 7))[0].description.starts_with("Openers"));
+    }
+
+    #[test]
+    fn test_season_requires_a_race() {
+        let mut calendar = RaceCalendar::default();
+        calendar.add(Race::new(
+            "Club TT".to_string(),
+            NaiveDate::from_ymd_opt(2025, 5, 1).unwrap(),
+            Sport::Cycling,
+            RacePriority::C,
+        ));
+
+        let result = TrainingPlanGenerator::generate_season(
+            &calendar,
+            NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+            PeriodizationModel::Block,
+            RecoveryPattern::ThreeToOne,
+            &test_athlete(),
+            None,
+        );
+        assert!(result.is_err());
+    }
+
+    fn completed(id: &str, date: NaiveDate, sport: Sport, minutes: u32, tss: Decimal, power: &[u16]) -> crate::models::Workout {
+        let raw_data = power
+            .iter()
+            .enumerate()
+            .map(|(i, watts)| crate::models::DataPoint {
+                timestamp: i as u32 * 60,
+                heart_rate: None,
+                power: Some(*watts),
+                pace: None,
+                elevation: None,
+                cadence: None,
+                speed: None,
+                distance: None,
+                left_power: None,
+                right_power: None,
+                ground_contact_time: None,
+                vertical_oscillation: None,
+                stride_length: None,
+                stroke_count: None,
+                stroke_type: None,
+                lap_number: None,
+                sport_transition: None,
+                temperature: None,
+                drive_length: None,
+                stroke_work: None,
+            })
+            .collect::<Vec<_>>();
+
+        crate::models::Workout {
+            id: id.to_string(),
+            date,
+            sport,
+            duration_seconds: minutes * 60,
+            workout_type: WorkoutType::Endurance,
+            data_source: crate::models::DataSource::Power,
+            raw_data: (!raw_data.is_empty()).then_some(raw_data),
+            summary: crate::models::WorkoutSummary {
+                tss: Some(tss),
+                ..Default::default()
+            },
+            notes: None,
+            athlete_id: None,
+            source: None,
+            swim_lengths: Vec::new(),
+            start_time: None,
+            laps: Vec::new(),
+            rpe: None,
+            strength_sets: Vec::new(),
+        }
+    }
+
+    fn monitored_plan(athlete: &AthleteProfile) -> TrainingPlan {
+        let start = NaiveDate::from_ymd_opt(2025, 3, 3).unwrap();
+        let threshold = StructuredWorkout::new("Threshold", Sport::Cycling).step(
+            StepKind::Steady,
+            StepDuration::minutes(20),
+            StepTarget::PercentFtp { low: 95, high: 105 },
+        );
+        let workouts = vec![
+            PlannedWorkout::from_structure(start, WorkoutType::Threshold, "Threshold", threshold, athlete),
+            TrainingPlanGenerator::easy_workout(start, &Sport::Cycling, 90, "Endurance ride", athlete),
+            TrainingPlanGenerator::easy_workout(start + Duration::days(1), &Sport::Running, 45, "Easy run", athlete),
+        ];
+
+        TrainingPlan {
+            id: "plan_test".to_string(),
+            name: "Test Plan".to_string(),
+            goal: TrainingGoal::Maintenance,
+            periodization_model: PeriodizationModel::Traditional,
+            recovery_pattern: RecoveryPattern::ThreeToOne,
+            start_date: start,
+            target_date: None,
+            total_weeks: 1,
+            weeks: vec![TrainingWeek {
+                week_number: 1,
+                start_date: start,
+                end_date: start + Duration::days(6),
+                phase: "Base".to_string(),
+                focus: "Aerobic".to_string(),
+                planned_tss: workouts.iter().map(|w| w.planned_tss).sum(),
+                planned_hours: dec!(2.6),
+                is_recovery_week: false,
+                workouts,
+            }],
+            total_planned_tss: dec!(0),
+            total_planned_hours: dec!(0),
+            target_distribution: None,
+            created_at: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_match_workouts_by_sport_and_closeness() {
+        let athlete = test_athlete();
+        let plan = monitored_plan(&athlete);
+        let day = plan.start_date;
+
+        // 15 of 20 minutes inside 95-105% FTP
+        let mut power = vec![250u16; 15];
+        power.extend([200u16; 5]);
+        let workouts = vec![
+            completed("long", day, Sport::Cycling, 85, dec!(36), &[]),
+            completed("intervals", day, Sport::Cycling, 20, dec!(33), &power),
+            completed("ride", day + Duration::days(1), Sport::Cycling, 45, dec!(40), &[]),
+        ];
+
+        let links = PlanMonitor::match_workouts(&plan, &workouts, &athlete);
+        assert_eq!(links.len(), 2);
+
+        let planned = &plan.weeks[0].workouts;
+        let threshold = links.iter().find(|l| l.session.id == planned[0].id).unwrap();
+        assert_eq!(threshold.workout_id, "intervals");
+        assert_eq!(threshold.compliance.duration_score, dec!(100));
+        assert_eq!(threshold.compliance.zone_score, Some(dec!(75)));
+
+        let endurance = links.iter().find(|l| l.session.id == planned[1].id).unwrap();
+        assert_eq!(endurance.workout_id, "long");
+        assert_eq!(endurance.compliance.zone_score, None);
+        assert!(endurance.compliance.overall > dec!(80));
+    }
+
+    #[test]
+    fn test_monitor_progress_reports_missed_sessions() {
+        let athlete = test_athlete();
+        let plan = monitored_plan(&athlete);
+        let day = plan.start_date;
+        let workouts = vec![completed("long", day, Sport::Cycling, 90, dec!(38), &[])];
+
+        let monitoring = PlanMonitor::monitor_progress(&plan, &workouts, &athlete, day + Duration::days(1)).unwrap();
+        assert_eq!(monitoring.current_week, 1);
+        assert_eq!(monitoring.session_links.len(), 1);
+        assert_eq!(
+            monitoring.missed_sessions,
+            vec![
+                PlannedSessionKey { id: plan.weeks[0].workouts[0].id.clone(), date: day },
+                PlannedSessionKey { id: plan.weeks[0].workouts[2].id.clone(), date: day + Duration::days(1) },
+            ]
+        );
+        assert!(monitoring.completion_rate < dec!(100));
+
+        // Sessions after the as-of date are neither missed nor counted
+        let early = PlanMonitor::monitor_progress(&plan, &workouts, &athlete, day).unwrap();
+        assert_eq!(early.missed_sessions.len(), 1);
+    }
+
+    #[test]
+    fn test_moved_session_keeps_its_link() {
+        let athlete = test_athlete();
+        let mut plan = monitored_plan(&athlete);
+        let day = plan.start_date;
+        let endurance_id = plan.weeks[0].workouts[1].id.clone();
+
+        // Swap the order of the two sessions on the first day, then move the ride
+        plan.weeks[0].workouts.swap(0, 1);
+        plan.weeks[0].workouts[0].date = day + Duration::days(2);
+        let workouts = vec![completed("long", day + Duration::days(2), Sport::Cycling, 90, dec!(38), &[])];
+
+        let links = PlanMonitor::match_workouts(&plan, &workouts, &athlete);
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].session, PlannedSessionKey { id: endurance_id, date: day + Duration::days(2) });
+    }
+
+    #[test]
+    fn test_sessions_without_ids_fall_back_to_position() {
+        let athlete = test_athlete();
+        let mut plan = monitored_plan(&athlete);
+        for workout in &mut plan.weeks[0].workouts {
+            workout.id.clear();
+        }
+
+        let ids: Vec<String> = plan.sessions().into_iter().map(|(key, _)| key.id).collect();
+        assert_eq!(ids, vec!["20250303-0", "20250303-1", "20250304-0"]);
+    }
+}
@@ -1,4 +1,5 @@
-use crate::models::{AthleteProfile, DataPoint, Sport, Workout, WorkoutSummary};
+use crate::models::{AthleteProfile, DataPoint, Sport, SwimLength, SwimLengthType, Workout, WorkoutSummary, WorkoutType};
+use crate::structured_workout::StepTarget;
 use anyhow::Result;
 use rust_decimal::prelude::*;
 use rust_decimal::Decimal;
@@ -32,6 +33,7 @@ pub enum TssMethod {
     PowerBased,     // Cycling with power data
     HeartRateBased, // hrTSS from heart rate zones
     PaceBased,      // rTSS for running, sTSS for swimming
+    SessionRpe,     // Strength sessions scored from session RPE
     Estimated,      // Fallback estimation from available data
 }
 
@@ -51,6 +53,13 @@ impl TssCalculator {
             }
         }
 
+        // Rowing power is scored against the rowing FTP, never the cycling one
+        if workout.sport == Sport::Rowing {
+            if let Ok(result) = Self::calculate_rowing_power_tss(workout, athlete) {
+                return Ok(result);
+            }
+        }
+
         // Try pace-based TSS for running and swimming
         if matches!(workout.sport, Sport::Running | Sport::Swimming) {
             if let Ok(result) = Self::calculate_pace_tss(workout, athlete) {
@@ -58,6 +67,13 @@ impl TssCalculator {
             }
         }
 
+        // Heart rate lags behind lifting effort, so gym work is scored from session RPE
+        if workout.workout_type == WorkoutType::Strength || !workout.strength_sets.is_empty() {
+            if let Ok(result) = Self::calculate_session_rpe_tss(workout) {
+                return Ok(result);
+            }
+        }
+
         // Try heart rate TSS as fallback
         if let Ok(result) = Self::calculate_heart_rate_tss(workout, athlete) {
             return Ok(result);
@@ -77,6 +93,26 @@ impl TssCalculator {
             .ftp
             .ok_or_else(|| TssError::MissingThreshold("FTP required for power-based TSS".to_string()))?;
 
+        Self::power_tss(workout, ftp)
+    }
+
+    /// Calculate power-based TSS for rowing against the rowing FTP
+    ///
+    /// Erg power runs well below cycling power at the same effort, so the
+    /// cycling FTP would understate rowing intensity.
+    pub fn calculate_rowing_power_tss(
+        workout: &Workout,
+        athlete: &AthleteProfile,
+    ) -> Result<TssResult, TssError> {
+        let ftp = athlete
+            .rowing_ftp
+            .ok_or_else(|| TssError::MissingThreshold("Rowing FTP required for rowing TSS".to_string()))?;
+
+        Self::power_tss(workout, ftp)
+    }
+
+    /// TSS = (duration_hours × IF²) × 100, with IF = NP / FTP
+    fn power_tss(workout: &Workout, ftp: u16) -> Result<TssResult, TssError> {
         let raw_data = workout
             .raw_data
             .as_ref()
@@ -135,10 +171,27 @@ impl TssCalculator {
     }
 
     /// Calculate pace-based TSS for running (rTSS) and swimming (sTSS)
+    ///
+    /// Swims are scored against the athlete's CSS pace (min/100m), never the
+    /// running threshold pace.
     pub fn calculate_pace_tss(
         workout: &Workout,
         athlete: &AthleteProfile,
     ) -> Result<TssResult, TssError> {
+        if workout.sport == Sport::Swimming {
+            let css_pace = athlete
+                .css_pace
+                .ok_or_else(|| TssError::MissingThreshold("CSS pace required for swim TSS".to_string()))?;
+            if !workout.swim_lengths.is_empty() {
+                return Self::calculate_swim_length_tss(&workout.swim_lengths, css_pace);
+            }
+            let raw_data = workout
+                .raw_data
+                .as_ref()
+                .ok_or_else(|| TssError::InvalidData("Raw pace data required".to_string()))?;
+            return Self::calculate_swimming_tss(raw_data, css_pace, workout.duration_seconds);
+        }
+
         let threshold_pace = athlete
             .threshold_pace
             .ok_or_else(|| TssError::MissingThreshold("Threshold pace required for pace-based TSS".to_string()))?;
@@ -150,7 +203,6 @@ impl TssCalculator {
 
         match workout.sport {
             Sport::Running => Self::calculate_running_tss(raw_data, threshold_pace, workout.duration_seconds),
-            Sport::Swimming => Self::calculate_swimming_tss(raw_data, threshold_pace, workout.duration_seconds),
             _ => Err(TssError::UnsupportedSport(workout.sport.clone())),
         }
     }
@@ -250,11 +302,87 @@ impl TssCalculator {
         })
     }
 
+    /// Calculate swimming TSS per pool length
+    ///
+    /// Each active length scores `IF³ × hours × 100` with `IF = CSS pace / length pace`
+    /// (`css_pace` in min/100m). The cube follows water resistance rising with the
+    /// cube of speed. Rest (idle) lengths add no load; drill and kick lengths count
+    /// at their own pace.
+    pub fn calculate_swim_length_tss(
+        lengths: &[SwimLength],
+        css_pace: Decimal,
+    ) -> Result<TssResult, TssError> {
+        if css_pace <= Decimal::ZERO {
+            return Err(TssError::MissingThreshold("CSS pace must be positive".to_string()));
+        }
+
+        let mut tss = Decimal::ZERO;
+        let mut active_seconds = Decimal::ZERO;
+
+        for length in lengths {
+            if length.length_type == SwimLengthType::Idle
+                || length.distance <= Decimal::ZERO
+                || length.duration_seconds <= Decimal::ZERO
+            {
+                continue;
+            }
+
+            // Length pace in min/100m
+            let pace = length.duration_seconds / length.distance * Decimal::from(100) / Decimal::from(60);
+            let intensity_factor = css_pace / pace;
+            let hours = length.duration_seconds / Decimal::from(3600);
+            tss += intensity_factor * intensity_factor * intensity_factor * hours * Decimal::from(100);
+            active_seconds += length.duration_seconds;
+        }
+
+        if active_seconds.is_zero() {
+            return Err(TssError::InvalidData("No active swim lengths".to_string()));
+        }
+
+        // Overall IF is the cube root of the mean cubed intensity
+        let mean_cubed = tss / (active_seconds / Decimal::from(3600) * Decimal::from(100));
+        let intensity_factor = mean_cubed
+            .to_f64()
+            .and_then(|value| Decimal::from_f64(value.cbrt()))
+            .ok_or_else(|| TssError::CalculationError("Invalid swim intensity".to_string()))?;
+
+        Ok(TssResult {
+            tss,
+            method: TssMethod::PaceBased,
+            intensity_factor: Some(intensity_factor),
+            normalized_power: None,
+        })
+    }
+
+    /// Calculate TSS from the session RPE
+    /// RPE maps to an intensity factor; TSS = (duration_hours × IF²) × 100
+    pub fn calculate_session_rpe_tss(workout: &Workout) -> Result<TssResult, TssError> {
+        let rpe = workout
+            .rpe
+            .ok_or_else(|| TssError::InvalidData("Session RPE required for sRPE TSS".to_string()))?;
+
+        let duration_hours = Decimal::from(workout.duration_seconds) / Decimal::from(3600);
+        let intensity_factor = StepTarget::rpe_intensity(rpe);
+
+        Ok(TssResult {
+            tss: duration_hours * intensity_factor * intensity_factor * Decimal::from(100),
+            method: TssMethod::SessionRpe,
+            intensity_factor: Some(intensity_factor),
+            normalized_power: None,
+        })
+    }
+
     /// Estimate TSS when primary metrics are unavailable
+    ///
+    /// A session RPE sets the intensity on the same scale as RPE step targets.
     fn estimate_tss(
         workout: &Workout,
         _athlete: &AthleteProfile,
     ) -> Result<TssResult, TssError> {
+        if let Ok(result) = Self::calculate_session_rpe_tss(workout) {
+            return Ok(TssResult { method: TssMethod::Estimated, ..result });
+        }
+
         // Simple estimation based on duration and sport
         let duration_hours = Decimal::from(workout.duration_seconds) / Decimal::from(3600);
 
@@ -425,6 +553,8 @@ mod tests {
             preferred_units: Units::Metric,
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            rowing_ftp: None,
+            css_pace: None,
         }
     }
 
@@ -448,6 +578,9 @@ mod tests {
                 stroke_type: None,
                 lap_number: None,
                 sport_transition: None,
+                temperature: None,
+                drive_length: None,
+                stroke_work: None,
             },
             DataPoint {
                 timestamp: 30,
@@ -467,6 +600,9 @@ mod tests {
                 stroke_type: None,
                 lap_number: None,
                 sport_transition: None,
+                temperature: None,
+                drive_length: None,
+                stroke_work: None,
             },
             DataPoint {
                 timestamp: 60,
@@ -486,6 +622,9 @@ mod tests {
                 stroke_type: None,
                 lap_number: None,
                 sport_transition: None,
+                temperature: None,
+                drive_length: None,
+                stroke_work: None,
             },
         ];
 
@@ -501,6 +640,11 @@ mod tests {
             notes: None,
             athlete_id: Some("test_athlete".to_string()),
             source: None,
+            swim_lengths: Vec::new(),
+            start_time: None,
+            laps: Vec::new(),
+            rpe: None,
+            strength_sets: Vec::new(),
         }
     }
 
@@ -524,6 +668,9 @@ mod tests {
                 stroke_type: None,
                 lap_number: None,
                 sport_transition: None,
+                temperature: None,
+                drive_length: None,
+                stroke_work: None,
             },
             DataPoint {
                 timestamp: 300,
@@ -543,6 +690,9 @@ mod tests {
                 stroke_type: None,
                 lap_number: None,
                 sport_transition: None,
+                temperature: None,
+                drive_length: None,
+                stroke_work: None,
             },
             DataPoint {
                 timestamp: 600,
@@ -562,6 +712,9 @@ mod tests {
                 stroke_type: None,
                 lap_number: None,
                 sport_transition: None,
+                temperature: None,
+                drive_length: None,
+                stroke_work: None,
             },
         ];
 
@@ -577,6 +730,11 @@ mod tests {
             notes: None,
             athlete_id: Some("test_athlete".to_string()),
             source: None,
+            swim_lengths: Vec::new(),
+            start_time: None,
+            laps: Vec::new(),
+            rpe: None,
+            strength_sets: Vec::new(),
         }
     }
 
@@ -594,6 +752,23 @@ mod tests {
         assert!(result.normalized_power.is_some());
     }
 
+    #[test]
+    fn test_rowing_tss_uses_rowing_ftp() {
+        let mut workout = create_test_power_workout(200, 3600);
+        workout.sport = Sport::Rowing;
+
+        let mut athlete = create_test_athlete_with_ftp(300);
+        let result = TssCalculator::calculate_tss(&workout, &athlete).unwrap();
+        assert_ne!(result.method, TssMethod::PowerBased);
+
+        athlete.rowing_ftp = Some(200);
+        let result = TssCalculator::calculate_tss(&workout, &athlete).unwrap();
+        assert_eq!(result.method, TssMethod::PowerBased);
+        let intensity_factor = result.intensity_factor.unwrap();
+        assert!(intensity_factor > dec!(0.99) && intensity_factor < dec!(1.02));
+        assert!(result.tss > dec!(98) && result.tss < dec!(104));
+    }
+
     #[test]
     fn test_running_tss_with_elevation() {
         let athlete = create_test_athlete();
@@ -641,6 +816,11 @@ mod tests {
             notes: None,
             athlete_id: Some("test_athlete".to_string()),
             source: None,
+            swim_lengths: Vec::new(),
+            start_time: None,
+            laps: Vec::new(),
+            rpe: None,
+            strength_sets: Vec::new(),
         };
 
         let result = TssCalculator::estimate_tss(&workout, &athlete).unwrap();
@@ -649,6 +829,39 @@ mod tests {
         assert_eq!(result.tss, dec!(70)); // 1 hour × 70 TSS/hour for running
         assert!(result.intensity_factor.is_none());
         assert!(result.normalized_power.is_none());
+
+        // A session RPE of 7 is an IF of 0.905
+        let rated = Workout { rpe: Some(7), ..workout };
+        let result = TssCalculator::estimate_tss(&rated, &athlete).unwrap();
+        assert_eq!(result.intensity_factor, Some(dec!(0.905)));
+        assert_eq!(result.tss.round_dp(1), dec!(81.9));
+    }
+
+    #[test]
+    fn test_strength_session_scored_from_rpe() {
+        let athlete = create_test_athlete();
+        let mut workout = create_cycling_workout_with_power();
+        for point in workout.raw_data.as_mut().unwrap() {
+            point.power = None;
+        }
+        workout.sport = Sport::CrossTraining;
+        workout.workout_type = WorkoutType::Strength;
+        workout.data_source = DataSource::HeartRate;
+        workout.duration_seconds = 3600;
+        workout.rpe = Some(7);
+
+        // Session RPE wins over heart rate for gym work
+        let result = TssCalculator::calculate_tss(&workout, &athlete).unwrap();
+        assert_eq!(result.method, TssMethod::SessionRpe);
+        assert_eq!(result.tss.round_dp(1), dec!(81.9));
+
+        // The RPE alone is enough, no athlete thresholds involved
+        assert_eq!(TssCalculator::calculate_session_rpe_tss(&workout).unwrap().tss, result.tss);
+
+        // Without an RPE the usual fallbacks apply
+        let unrated = Workout { rpe: None, ..workout };
+        let result = TssCalculator::calculate_tss(&unrated, &athlete).unwrap();
+        assert_ne!(result.method, TssMethod::SessionRpe);
     }
 
     #[test]
@@ -672,6 +885,9 @@ mod tests {
                 stroke_type: None,
                 lap_number: None,
                 sport_transition: None,
+                temperature: None,
+                drive_length: None,
+                stroke_work: None,
             },
             DataPoint {
                 timestamp: 30,
@@ -691,6 +907,9 @@ mod tests {
                 stroke_type: None,
                 lap_number: None,
                 sport_transition: None,
+                temperature: None,
+                drive_length: None,
+                stroke_work: None,
             },
             DataPoint {
                 timestamp: 60,
@@ -710,6 +929,9 @@ mod tests {
                 stroke_type: None,
                 lap_number: None,
                 sport_transition: None,
+                temperature: None,
+                drive_length: None,
+                stroke_work: None,
             },
         ];
 
@@ -764,7 +986,8 @@ mod tests {
 
     #[test]
     fn test_swimming_tss_calculation() {
-        let athlete = create_test_athlete();
+        let mut athlete = create_test_athlete();
+        athlete.css_pace = Some(dec!(2.0));
 
         let pace_data = vec![
             DataPoint {
@@ -785,6 +1008,9 @@ mod tests {
                 stroke_type: None,
                 lap_number: None,
                 sport_transition: None,
+                temperature: None,
+                drive_length: None,
+                stroke_work: None,
             },
             DataPoint {
                 timestamp: 120,
@@ -804,6 +1030,9 @@ mod tests {
                 stroke_type: None,
                 lap_number: None,
                 sport_transition: None,
+                temperature: None,
+                drive_length: None,
+                stroke_work: None,
             },
         ];
 
@@ -819,6 +1048,11 @@ mod tests {
             notes: None,
             athlete_id: Some("test_athlete".to_string()),
             source: None,
+            swim_lengths: Vec::new(),
+            start_time: None,
+            laps: Vec::new(),
+            rpe: None,
+            strength_sets: Vec::new(),
         };
 
         let result = TssCalculator::calculate_pace_tss(&workout, &athlete).unwrap();
@@ -826,6 +1060,78 @@ mod tests {
         assert_eq!(result.method, TssMethod::PaceBased);
         assert!(result.tss > dec!(0));
         assert!(result.intensity_factor.is_some());
+
+        // The running threshold pace is no substitute for CSS
+        athlete.css_pace = None;
+        assert!(TssCalculator::calculate_pace_tss(&workout, &athlete).is_err());
+    }
+
+    #[test]
+    fn test_swim_tss_scores_lengths_against_css() {
+        let mut athlete = create_test_athlete();
+        // CSS 1:40/100m is 25 s per 25 m length
+        athlete.css_pace = Some(dec!(100) / dec!(60));
+
+        let length = SwimLength {
+            start_offset: 0,
+            duration_seconds: dec!(25),
+            distance: dec!(25),
+            strokes: Some(14),
+            stroke_type: Some(0),
+            length_type: SwimLengthType::Active,
+        };
+        let workout = Workout {
+            id: "pool_swim".to_string(),
+            date: NaiveDate::from_ymd_opt(2024, 9, 23).unwrap(),
+            sport: Sport::Swimming,
+            duration_seconds: 3600,
+            workout_type: WorkoutType::Endurance,
+            data_source: DataSource::Pace,
+            raw_data: None,
+            summary: WorkoutSummary::default(),
+            notes: None,
+            athlete_id: Some("test_athlete".to_string()),
+            source: None,
+            swim_lengths: vec![length; 144],
+            start_time: None,
+            laps: Vec::new(),
+            rpe: None,
+            strength_sets: Vec::new(),
+        };
+
+        // An hour at CSS scores 100 whatever the running threshold pace is
+        let result = TssCalculator::calculate_pace_tss(&workout, &athlete).unwrap();
+        assert_eq!(result.tss.round_dp(1), dec!(100.0));
+        assert_eq!(result.intensity_factor.unwrap().round_dp(3), dec!(1));
+    }
+
+    #[test]
+    fn test_swim_length_tss_uses_cubic_intensity() {
+        let swim_length = |seconds: Decimal, length_type: SwimLengthType| SwimLength {
+            start_offset: 0,
+            duration_seconds: seconds,
+            distance: dec!(25),
+            strokes: Some(14),
+            stroke_type: Some(0),
+            length_type,
+        };
+        // CSS 1:40/100m is 25 s per 25 m length
+        let css_pace = dec!(100) / dec!(60);
+
+        let at_css = vec![swim_length(dec!(25), SwimLengthType::Active); 4];
+        let result = TssCalculator::calculate_swim_length_tss(&at_css, css_pace).unwrap();
+        assert_eq!(result.tss.round_dp(3), dec!(2.778)); // 100 s at IF 1.0
+        assert_eq!(result.intensity_factor.unwrap().round_dp(3), dec!(1));
+
+        // Twice CSS speed scores IF³ = 8 per unit time; rest lengths add nothing
+        let mixed = vec![
+            swim_length(dec!(25), SwimLengthType::Active),
+            swim_length(dec!(12.5), SwimLengthType::Active),
+            swim_length(dec!(60), SwimLengthType::Idle),
+        ];
+        let result = TssCalculator::calculate_swim_length_tss(&mixed, css_pace).unwrap();
+        let expected = (dec!(25) + dec!(8) * dec!(12.5)) / dec!(3600) * dec!(100);
+        assert_eq!(result.tss.round_dp(6), expected.round_dp(6));
     }
 
     // Property-based tests using proptest
@@ -949,6 +1255,9 @@ mod tests {
                 stroke_type: None,
                 lap_number: None,
                 sport_transition: None,
+                temperature: None,
+                drive_length: None,
+                stroke_work: None,
             }).collect();
 
             let np = TssCalculator::calculate_normalized_power(&data_points).unwrap();
@@ -981,6 +1290,8 @@ mod tests {
             preferred_units: Units::Metric,
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            rowing_ftp: None,
+            css_pace: None,
         }
     }
 
@@ -1000,6 +1311,8 @@ mod tests {
             preferred_units: Units::Metric,
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            rowing_ftp: None,
+            css_pace: None,
         }
     }
 
@@ -1019,6 +1332,8 @@ mod tests {
             preferred_units: Units::Metric,
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            rowing_ftp: None,
+            css_pace: None,
         }
     }
 
@@ -1049,6 +1364,9 @@ mod tests {
                 stroke_type: None,
                 lap_number: None,
                 sport_transition: None,
+                temperature: None,
+                drive_length: None,
+                stroke_work: None,
                 }
             })
             .collect();
@@ -1069,6 +1387,11 @@ mod tests {
             notes: None,
             source: None,
             raw_data: Some(data_points),
+            swim_lengths: Vec::new(),
+            start_time: None,
+            laps: Vec::new(),
+            rpe: None,
+            strength_sets: Vec::new(),
         }
     }
 
@@ -1106,7 +1429,15 @@ mod tests {
                 stroke_type: None,
                 lap_number: None,
                 sport_transition: None,
+                temperature: None,
+                drive_length: None,
+                stroke_work: None,
             }]),
+            swim_lengths: Vec::new(),
+            start_time: None,
+            laps: Vec::new(),
+            rpe: None,
+            strength_sets: Vec::new(),
         }
     }
 
@@ -1145,7 +1476,15 @@ mod tests {
                 stroke_type: None,
                 lap_number: None,
                 sport_transition: None,
+                temperature: None,
+                drive_length: None,
+                stroke_work: None,
             }]),
+            swim_lengths: Vec::new(),
+            start_time: None,
+            laps: Vec::new(),
+            rpe: None,
+            strength_sets: Vec::new(),
         }
     }
 
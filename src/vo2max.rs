@@ -8,10 +8,13 @@
 
 use anyhow::{anyhow, Result};
 use chrono::NaiveDate;
+use rust_decimal::prelude::ToPrimitive;
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
 use serde::{Deserialize, Serialize};
 
+use crate::models::{AthleteProfile, DataPoint, Sport, Workout};
+
 /// VO2max estimation methods
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Vo2MaxMethod {
@@ -59,6 +62,137 @@ pub struct Vo2MaxTrend {
     pub date_range: (NaiveDate, NaiveDate),
 }
 
+/// Ambient conditions during an effort
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EnvironmentalConditions {
+    /// Mean ambient temperature in °C
+    pub temperature_c: Option<f64>,
+    /// Mean altitude in meters above sea level
+    pub altitude_m: Option<f64>,
+}
+
+impl EnvironmentalConditions {
+    /// Temperature above which heart rate drifts upward at a given workload
+    pub const REFERENCE_TEMPERATURE_C: f64 = 20.0;
+
+    /// Altitude above which VO2max measurably declines (Wehrlin & Hallén, 2006)
+    pub const REFERENCE_ALTITUDE_M: f64 = 300.0;
+
+    /// Average the temperature and elevation channels of a set of data points
+    pub fn from_data_points(data: &[DataPoint]) -> Self {
+        let mean = |values: Vec<f64>| {
+            if values.is_empty() {
+                None
+            } else {
+                Some(values.iter().sum::<f64>() / values.len() as f64)
+            }
+        };
+
+        EnvironmentalConditions {
+            temperature_c: mean(data.iter().filter_map(|p| p.temperature).map(f64::from).collect()),
+            altitude_m: mean(data.iter().filter_map(|p| p.elevation).map(f64::from).collect()),
+        }
+    }
+
+    /// Heat correction factor
+    ///
+    /// Cardiovascular drift raises heart rate by roughly 1% per °C above 20°C,
+    /// so HR-anchored estimates read low in the heat. Capped at +15%.
+    pub fn heat_factor(&self) -> f64 {
+        match self.temperature_c {
+            Some(t) if t > Self::REFERENCE_TEMPERATURE_C => {
+                (1.0 + 0.01 * (t - Self::REFERENCE_TEMPERATURE_C)).min(1.15)
+            }
+            _ => 1.0,
+        }
+    }
+
+    /// Altitude correction factor
+    ///
+    /// VO2max falls about 6.3% per 1000 m above 300 m, so sea-level equivalent
+    /// values are obtained by dividing out that loss. Capped at 4500 m.
+    pub fn altitude_factor(&self) -> f64 {
+        match self.altitude_m {
+            Some(a) if a > Self::REFERENCE_ALTITUDE_M => {
+                let km_above = (a.min(4500.0) - Self::REFERENCE_ALTITUDE_M) / 1000.0;
+                1.0 / (1.0 - 0.063 * km_above)
+            }
+            _ => 1.0,
+        }
+    }
+
+    /// Combined correction to sea-level, thermoneutral conditions
+    pub fn correction_factor(&self) -> f64 {
+        self.heat_factor() * self.altitude_factor()
+    }
+}
+
+/// Settings for automatic per-workout estimation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Vo2MaxConfig {
+    /// Minimum length of a qualifying steady effort in seconds
+    pub min_effort_seconds: u32,
+    /// Minimum mean heart rate of the effort as a fraction of max HR
+    pub min_hr_fraction: f64,
+    /// Maximum coefficient of variation of power/speed within the effort
+    pub max_variation: f64,
+    /// Maximum absolute grade for running efforts (ACSM equation assumes flat ground)
+    pub max_grade: f64,
+    /// Time constant of the trend smoothing in days
+    pub smoothing_days: f64,
+}
+
+impl Default for Vo2MaxConfig {
+    fn default() -> Self {
+        Self {
+            min_effort_seconds: 600,
+            min_hr_fraction: 0.75,
+            max_variation: 0.10,
+            max_grade: 0.02,
+            smoothing_days: 14.0,
+        }
+    }
+}
+
+/// Steady effort selected from a workout for estimation
+#[derive(Debug, Clone, PartialEq)]
+struct SteadyEffort {
+    duration_seconds: u32,
+    avg_hr: f64,
+    /// Mean power in watts (cycling) or speed in m/s (running)
+    avg_output: f64,
+    conditions: EnvironmentalConditions,
+}
+
+/// VO2max estimate for a single workout, corrected for ambient conditions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkoutVo2MaxEstimate {
+    /// Workout the estimate was derived from
+    pub workout_id: String,
+    /// Corrected estimate
+    pub estimate: Vo2MaxEstimate,
+    /// Estimate before heat/altitude correction
+    pub uncorrected_vo2max: f64,
+    /// Conditions during the qualifying effort
+    pub conditions: EnvironmentalConditions,
+    /// Length of the qualifying effort in seconds
+    pub effort_duration_seconds: u32,
+}
+
+/// Point on the smoothed VO2max timeline
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Vo2MaxTimelinePoint {
+    pub date: NaiveDate,
+    /// Confidence-weighted mean of the estimates on this date
+    pub vo2max: f64,
+    /// Smoothed trend value
+    pub smoothed: f64,
+    /// Mean confidence of the estimates on this date
+    pub confidence: f64,
+    /// Number of estimates on this date
+    pub estimate_count: usize,
+}
+
 /// VO2max estimation errors
 #[derive(Debug, thiserror::Error)]
 pub enum Vo2MaxError {
@@ -378,6 +512,193 @@ impl Vo2MaxAnalyzer {
             date_range: (first_date, last_date),
         })
     }
+
+    /// Estimate VO2max from the best steady effort in a workout
+    ///
+    /// Cycling uses the ACSM cycling equation on power (needs body mass),
+    /// running the ACSM running equation on flat-ground speed. The result is
+    /// corrected to sea-level, thermoneutral conditions when the workout has
+    /// temperature or elevation data.
+    pub fn estimate_from_workout(
+        workout: &Workout,
+        athlete: &AthleteProfile,
+        config: &Vo2MaxConfig,
+    ) -> Result<WorkoutVo2MaxEstimate> {
+        let max_hr = athlete.max_hr.ok_or_else(|| {
+            anyhow!(Vo2MaxError::InvalidParameter("Max heart rate required".to_string()))
+        })?;
+
+        let data = workout.raw_data.as_deref().unwrap_or_default();
+        if data.is_empty() {
+            return Err(anyhow!(Vo2MaxError::InsufficientData(
+                "Workout has no time-series data".to_string()
+            )));
+        }
+
+        let effort = Self::find_steady_effort(data, &workout.sport, max_hr, config).ok_or_else(|| {
+            anyhow!(Vo2MaxError::InsufficientData(format!(
+                "No steady effort of at least {} minutes above {:.0}% max HR",
+                config.min_effort_seconds / 60,
+                config.min_hr_fraction * 100.0
+            )))
+        })?;
+
+        let hr = effort.avg_hr.round() as u16;
+        let mut estimate = match workout.sport {
+            Sport::Cycling => {
+                let body_mass = athlete.weight.and_then(|w| w.to_f64()).ok_or_else(|| {
+                    anyhow!(Vo2MaxError::InvalidParameter("Body mass required for cycling".to_string()))
+                })?;
+                Self::estimate_from_cycling(effort.avg_output.round() as u16, hr, max_hr, body_mass)?
+            }
+            Sport::Running => {
+                let pace_min_per_km = 1000.0 / (effort.avg_output * 60.0);
+                let pace = Decimal::from_f64_retain(pace_min_per_km).unwrap_or_default();
+                Self::estimate_from_running(pace, hr, max_hr)?
+            }
+            _ => {
+                return Err(anyhow!(Vo2MaxError::InsufficientData(format!(
+                    "No VO2max model for {:?}",
+                    workout.sport
+                ))))
+            }
+        };
+
+        let uncorrected_vo2max = estimate.vo2max_ml_kg_min;
+        let factor = effort.conditions.correction_factor();
+        estimate.vo2max_ml_kg_min *= factor;
+        // Large corrections add model uncertainty
+        estimate.confidence -= ((factor - 1.0).abs() * 0.5).min(0.2);
+        estimate.date = workout.date;
+        estimate.contributing_workouts = vec![workout.id.clone()];
+
+        Ok(WorkoutVo2MaxEstimate {
+            workout_id: workout.id.clone(),
+            estimate,
+            uncorrected_vo2max,
+            conditions: effort.conditions,
+            effort_duration_seconds: effort.duration_seconds,
+        })
+    }
+
+    /// Find the highest-HR steady effort meeting the configured thresholds
+    fn find_steady_effort(
+        data: &[DataPoint],
+        sport: &Sport,
+        max_hr: u16,
+        config: &Vo2MaxConfig,
+    ) -> Option<SteadyEffort> {
+        let output = |p: &DataPoint| -> Option<f64> {
+            match sport {
+                Sport::Cycling => p.power.filter(|&w| w > 0).map(f64::from),
+                Sport::Running => p.speed.and_then(|s| s.to_f64()).filter(|&s| s > 0.0),
+                _ => None,
+            }
+        };
+
+        let mut best: Option<SteadyEffort> = None;
+        let mut last_start_time: Option<u32> = None;
+
+        for start in 0..data.len() {
+            // Slide the window start in 30 second steps
+            if let Some(t) = last_start_time {
+                if data[start].timestamp < t + 30 {
+                    continue;
+                }
+            }
+            last_start_time = Some(data[start].timestamp);
+
+            let Some(end) = data[start..]
+                .iter()
+                .position(|p| p.timestamp >= data[start].timestamp + config.min_effort_seconds)
+                .map(|offset| start + offset)
+            else {
+                break;
+            };
+
+            let window = &data[start..=end];
+            let samples: Vec<(f64, f64)> = window
+                .iter()
+                .filter_map(|p| Some((f64::from(p.heart_rate?), output(p)?)))
+                .collect();
+            // Require signal coverage over most of the window
+            if samples.len() * 10 < window.len() * 8 {
+                continue;
+            }
+
+            let n = samples.len() as f64;
+            let avg_hr = samples.iter().map(|(hr, _)| hr).sum::<f64>() / n;
+            let avg_output = samples.iter().map(|(_, o)| o).sum::<f64>() / n;
+            let variance = samples.iter().map(|(_, o)| (o - avg_output).powi(2)).sum::<f64>() / n;
+            let variation = variance.sqrt() / avg_output;
+
+            if avg_hr < max_hr as f64 * config.min_hr_fraction || variation > config.max_variation {
+                continue;
+            }
+
+            if *sport == Sport::Running {
+                let climb = match (window.first().and_then(|p| p.elevation), window.last().and_then(|p| p.elevation)) {
+                    (Some(a), Some(b)) => f64::from(b) - f64::from(a),
+                    _ => 0.0,
+                };
+                let distance = avg_output * (window[window.len() - 1].timestamp - window[0].timestamp) as f64;
+                if distance <= 0.0 || (climb / distance).abs() > config.max_grade {
+                    continue;
+                }
+            }
+
+            if best.as_ref().is_none_or(|b| avg_hr > b.avg_hr) {
+                best = Some(SteadyEffort {
+                    duration_seconds: window[window.len() - 1].timestamp - window[0].timestamp,
+                    avg_hr,
+                    avg_output,
+                    conditions: EnvironmentalConditions::from_data_points(window),
+                });
+            }
+        }
+
+        best
+    }
+
+    /// Build a smoothed VO2max timeline from per-workout estimates
+    ///
+    /// Estimates on the same date are combined by confidence-weighted mean. The
+    /// trend is an exponential moving average whose step weight grows with the
+    /// gap between dates (time constant `smoothing_days`) and with confidence,
+    /// so a single low-confidence outlier moves it little.
+    pub fn smoothed_timeline(estimates: &[Vo2MaxEstimate], smoothing_days: f64) -> Vec<Vo2MaxTimelinePoint> {
+        let mut by_date: std::collections::BTreeMap<NaiveDate, Vec<&Vo2MaxEstimate>> = std::collections::BTreeMap::new();
+        for estimate in estimates {
+            by_date.entry(estimate.date).or_default().push(estimate);
+        }
+
+        let mut timeline: Vec<Vo2MaxTimelinePoint> = Vec::with_capacity(by_date.len());
+
+        for (date, day) in by_date {
+            let weight_sum: f64 = day.iter().map(|e| e.confidence.max(0.01)).sum();
+            let vo2max = day.iter().map(|e| e.vo2max_ml_kg_min * e.confidence.max(0.01)).sum::<f64>() / weight_sum;
+            let confidence = day.iter().map(|e| e.confidence).sum::<f64>() / day.len() as f64;
+
+            let smoothed = match timeline.last() {
+                Some(previous) => {
+                    let gap_days = (date - previous.date).num_days().max(1) as f64;
+                    let alpha = (1.0 - (-gap_days / smoothing_days.max(1.0)).exp()) * confidence.clamp(0.0, 1.0);
+                    previous.smoothed + alpha * (vo2max - previous.smoothed)
+                }
+                None => vo2max,
+            };
+
+            timeline.push(Vo2MaxTimelinePoint {
+                date,
+                vo2max,
+                smoothed,
+                confidence,
+                estimate_count: day.len(),
+            });
+        }
+
+        timeline
+    }
 }
 
 #[cfg(test)]
@@ -530,4 +851,156 @@ mod tests {
         assert_eq!(deserialized.vo2max_ml_kg_min, 55.0);
         assert_eq!(deserialized.estimation_method, Vo2MaxMethod::Combined);
     }
+
+    fn steady_workout(sport: Sport, temperature: Option<i8>, elevation: Option<i16>) -> Workout {
+        let data = (0..1800)
+            .map(|t| DataPoint {
+                timestamp: t,
+                heart_rate: Some(165),
+                power: Some(if t % 2 == 0 { 245 } else { 255 }),
+                pace: None,
+                elevation,
+                cadence: None,
+                speed: Some(dec!(4.0)),
+                distance: None,
+                left_power: None,
+                right_power: None,
+                ground_contact_time: None,
+                vertical_oscillation: None,
+                stride_length: None,
+                stroke_count: None,
+                stroke_type: None,
+                lap_number: None,
+                sport_transition: None,
+                temperature,
+                drive_length: None,
+                stroke_work: None,
+            })
+            .collect();
+
+        Workout {
+            id: "steady".to_string(),
+            date: NaiveDate::from_ymd_opt(2024, 6, 1).unwrap(),
+            sport,
+            duration_seconds: 1800,
+            workout_type: crate::models::WorkoutType::Tempo,
+            data_source: crate::models::DataSource::Power,
+            raw_data: Some(data),
+            summary: crate::models::WorkoutSummary::default(),
+            notes: None,
+            athlete_id: None,
+            source: None,
+            swim_lengths: Vec::new(),
+            start_time: None,
+            laps: Vec::new(),
+            rpe: None,
+            strength_sets: Vec::new(),
+        }
+    }
+
+    fn test_athlete() -> AthleteProfile {
+        AthleteProfile {
+            id: "athlete".to_string(),
+            name: "Athlete".to_string(),
+            date_of_birth: None,
+            weight: Some(dec!(75.0)),
+            height: None,
+            ftp: Some(280),
+            lthr: Some(170),
+            threshold_pace: None,
+            max_hr: Some(190),
+            resting_hr: Some(50),
+            training_zones: Default::default(),
+            preferred_units: crate::models::Units::Metric,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            rowing_ftp: None,
+            css_pace: None,
+        }
+    }
+
+    #[test]
+    fn test_environmental_correction_factors() {
+        let neutral = EnvironmentalConditions { temperature_c: Some(15.0), altitude_m: Some(100.0) };
+        assert_eq!(neutral.correction_factor(), 1.0);
+
+        let hot = EnvironmentalConditions { temperature_c: Some(30.0), altitude_m: None };
+        assert!((hot.heat_factor() - 1.10).abs() < 1e-9);
+
+        let high = EnvironmentalConditions { temperature_c: None, altitude_m: Some(1300.0) };
+        assert!((high.altitude_factor() - 1.0 / 0.937).abs() < 1e-9);
+
+        // Both corrections combine multiplicatively
+        let both = EnvironmentalConditions { temperature_c: Some(30.0), altitude_m: Some(1300.0) };
+        assert!((both.correction_factor() - 1.10 / 0.937).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_estimate_from_steady_cycling_workout() {
+        let athlete = test_athlete();
+        let config = Vo2MaxConfig::default();
+
+        let neutral = Vo2MaxAnalyzer::estimate_from_workout(&steady_workout(Sport::Cycling, None, None), &athlete, &config).unwrap();
+        // VO2 at 250 W = 12×250/75 + 3.5 = 43.5; HR% = 165/190
+        assert!((neutral.estimate.vo2max_ml_kg_min - 43.5 / (165.0 / 190.0)).abs() < 0.5);
+        assert_eq!(neutral.estimate.estimation_method, Vo2MaxMethod::AcsmCycling);
+        assert_eq!(neutral.estimate.contributing_workouts, vec!["steady".to_string()]);
+        assert_eq!(neutral.estimate.date, NaiveDate::from_ymd_opt(2024, 6, 1).unwrap());
+        assert!(neutral.effort_duration_seconds >= config.min_effort_seconds);
+
+        let hot = Vo2MaxAnalyzer::estimate_from_workout(&steady_workout(Sport::Cycling, Some(30), None), &athlete, &config).unwrap();
+        assert!((hot.uncorrected_vo2max - neutral.uncorrected_vo2max).abs() < 1e-9);
+        assert!((hot.estimate.vo2max_ml_kg_min / hot.uncorrected_vo2max - 1.10).abs() < 1e-6);
+        assert!(hot.estimate.confidence < neutral.estimate.confidence);
+    }
+
+    #[test]
+    fn test_estimate_from_workout_requires_qualifying_effort() {
+        let athlete = test_athlete();
+        let config = Vo2MaxConfig::default();
+
+        // Highly variable power is not a steady effort
+        let mut intervals = steady_workout(Sport::Cycling, None, None);
+        for point in intervals.raw_data.as_mut().unwrap() {
+            point.power = Some(if (point.timestamp / 60) % 2 == 0 { 350 } else { 120 });
+        }
+        assert!(Vo2MaxAnalyzer::estimate_from_workout(&intervals, &athlete, &config).is_err());
+
+        // Easy running below the heart rate floor does not qualify
+        let mut easy = steady_workout(Sport::Running, None, None);
+        for point in easy.raw_data.as_mut().unwrap() {
+            point.heart_rate = Some(120);
+        }
+        assert!(Vo2MaxAnalyzer::estimate_from_workout(&easy, &athlete, &config).is_err());
+
+        // Steady running at 4 m/s qualifies
+        let run = Vo2MaxAnalyzer::estimate_from_workout(&steady_workout(Sport::Running, None, Some(50)), &athlete, &config).unwrap();
+        assert_eq!(run.estimate.estimation_method, Vo2MaxMethod::AcsmRunning);
+    }
+
+    #[test]
+    fn test_smoothed_timeline_damps_outliers() {
+        let estimate = |day: u32, vo2max: f64, confidence: f64| Vo2MaxEstimate {
+            vo2max_ml_kg_min: vo2max,
+            estimation_method: Vo2MaxMethod::AcsmRunning,
+            confidence,
+            date: NaiveDate::from_ymd_opt(2024, 1, day).unwrap(),
+            contributing_workouts: vec![],
+        };
+        let estimates = vec![
+            estimate(1, 50.0, 0.8),
+            estimate(3, 50.0, 0.8),
+            estimate(3, 52.0, 0.8),
+            estimate(5, 60.0, 0.5),
+        ];
+
+        let timeline = Vo2MaxAnalyzer::smoothed_timeline(&estimates, 14.0);
+        assert_eq!(timeline.len(), 3);
+        assert_eq!(timeline[0].smoothed, 50.0);
+        assert_eq!(timeline[1].estimate_count, 2);
+        assert!((timeline[1].vo2max - 51.0).abs() < 1e-9);
+        // The low-confidence jump moves the trend only slightly
+        assert!(timeline[2].smoothed > timeline[1].smoothed);
+        assert!(timeline[2].smoothed < 52.0);
+    }
 }
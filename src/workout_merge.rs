@@ -0,0 +1,808 @@
+#![allow(dead_code)]
+
+//! Duplicate detection and merging of workouts recorded by several devices
+//!
+//! One session is often recorded twice, e.g. by a bike computer and a watch.
+//! The recordings start at different moments and differ in duration, so they
+//! pass the exact date/duration/sport check in `Database::store_workout`.
+//!
+//! Detection aligns the time series by cross-correlating heart rate, power,
+//! speed and cadence over a range of start offsets. Two workouts record the
+//! same session when, at the best offset, they overlap for most of the shorter
+//! recording, their streams correlate and their distances agree.
+//!
+//! Merging puts the recordings on a common timeline and takes each channel from
+//! the source that records it best (power from the head unit, heart rate from
+//! the chest strap, running dynamics from the watch), keeping the provenance of
+//! every channel.
+
+use crate::models::{DataPoint, Workout};
+use rust_decimal::prelude::*;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Samples are held for this long when resampling onto a common timeline
+const HOLD_SECONDS: u32 = 5;
+
+/// Minimum aligned samples for a channel correlation
+const MIN_CORRELATION_SAMPLES: usize = 60;
+
+/// Device clocks can disagree by this much around recorded start times
+const MAX_CLOCK_DRIFT_SECONDS: i32 = 60;
+
+/// Merge errors
+#[derive(Error, Debug)]
+pub enum MergeError {
+    #[error("At least two workouts are needed to merge")]
+    NotEnoughWorkouts,
+    #[error("Workouts {0} and {1} are not recordings of the same session")]
+    NotDuplicates(String, String),
+    #[error("Workout {0} has no time-series data")]
+    MissingData(String),
+}
+
+/// Data channels that can be taken from different sources
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Channel {
+    /// Heart rate
+    HeartRate,
+    /// Power and left/right balance
+    Power,
+    /// Cadence
+    Cadence,
+    /// Speed, pace and distance
+    Speed,
+    /// Elevation
+    Elevation,
+    /// Ground contact time, vertical oscillation and stride length
+    RunningDynamics,
+    /// Ambient temperature
+    Temperature,
+}
+
+impl Channel {
+    /// All channels, in merge order
+    pub const ALL: [Channel; 7] = [
+        Channel::HeartRate,
+        Channel::Power,
+        Channel::Cadence,
+        Channel::Speed,
+        Channel::Elevation,
+        Channel::RunningDynamics,
+        Channel::Temperature,
+    ];
+
+    /// Channels compared when aligning recordings
+    const ALIGNMENT: [Channel; 4] = [Channel::HeartRate, Channel::Power, Channel::Speed, Channel::Cadence];
+
+    /// Name used for display and storage
+    pub fn name(&self) -> &'static str {
+        match self {
+            Channel::HeartRate => "heart_rate",
+            Channel::Power => "power",
+            Channel::Cadence => "cadence",
+            Channel::Speed => "speed",
+            Channel::Elevation => "elevation",
+            Channel::RunningDynamics => "running_dynamics",
+            Channel::Temperature => "temperature",
+        }
+    }
+
+    /// Parse a channel name
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|c| c.name() == name)
+    }
+
+    /// Whether the data point records this channel
+    fn is_present(&self, point: &DataPoint) -> bool {
+        match self {
+            Channel::HeartRate => point.heart_rate.is_some(),
+            Channel::Power => point.power.is_some(),
+            Channel::Cadence => point.cadence.is_some(),
+            Channel::Speed => point.speed.is_some() || point.distance.is_some(),
+            Channel::Elevation => point.elevation.is_some(),
+            Channel::RunningDynamics => {
+                point.ground_contact_time.is_some()
+                    || point.vertical_oscillation.is_some()
+                    || point.stride_length.is_some()
+            }
+            Channel::Temperature => point.temperature.is_some(),
+        }
+    }
+
+    /// Value used for alignment
+    fn value(&self, point: &DataPoint) -> Option<f64> {
+        match self {
+            Channel::HeartRate => point.heart_rate.map(f64::from),
+            Channel::Power => point.power.map(f64::from),
+            Channel::Cadence => point.cadence.map(f64::from),
+            Channel::Speed => point.speed.and_then(|s| s.to_f64()),
+            Channel::Elevation => point.elevation.map(f64::from),
+            Channel::RunningDynamics => point.ground_contact_time.map(f64::from),
+            Channel::Temperature => point.temperature.map(f64::from),
+        }
+    }
+
+    /// Whether a reading is physiologically or technically implausible
+    fn is_artifact(&self, point: &DataPoint, previous: Option<&DataPoint>) -> bool {
+        match self {
+            Channel::HeartRate => {
+                let Some(hr) = point.heart_rate else { return false };
+                let jump = previous
+                    .and_then(|p| p.heart_rate.map(|prev| (p.timestamp, prev)))
+                    .is_some_and(|(t, prev)| {
+                        let seconds = point.timestamp.saturating_sub(t).max(1) as f64;
+                        (f64::from(hr) - f64::from(prev)).abs() / seconds > 15.0
+                    });
+                !(30..=230).contains(&hr) || jump
+            }
+            Channel::Power => point.power.is_some_and(|p| p > 2500),
+            Channel::Cadence => point.cadence.is_some_and(|c| c > 250),
+            _ => false,
+        }
+    }
+
+    /// Copy this channel's fields between data points
+    fn copy(&self, from: &DataPoint, to: &mut DataPoint) {
+        match self {
+            Channel::HeartRate => to.heart_rate = from.heart_rate,
+            Channel::Power => {
+                to.power = from.power;
+                to.left_power = from.left_power;
+                to.right_power = from.right_power;
+            }
+            Channel::Cadence => to.cadence = from.cadence,
+            Channel::Speed => {
+                to.speed = from.speed;
+                to.pace = from.pace;
+                to.distance = from.distance;
+            }
+            Channel::Elevation => to.elevation = from.elevation,
+            Channel::RunningDynamics => {
+                to.ground_contact_time = from.ground_contact_time;
+                to.vertical_oscillation = from.vertical_oscillation;
+                to.stride_length = from.stride_length;
+            }
+            Channel::Temperature => to.temperature = from.temperature,
+        }
+    }
+}
+
+/// Duplicate detection thresholds
+#[derive(Debug, Clone)]
+pub struct DuplicateConfig {
+    /// Largest start offset searched between two recordings, in seconds
+    pub max_offset_seconds: u32,
+    /// Minimum share of the shorter recording covered by the other
+    pub min_overlap: f64,
+    /// Minimum mean correlation of the aligned streams
+    pub min_correlation: f64,
+    /// Largest relative difference in total distance
+    pub max_distance_difference: f64,
+}
+
+impl Default for DuplicateConfig {
+    fn default() -> Self {
+        Self {
+            max_offset_seconds: 900,
+            min_overlap: 0.8,
+            min_correlation: 0.8,
+            max_distance_difference: 0.05,
+        }
+    }
+}
+
+/// Two workouts recording the same session
+#[derive(Debug, Clone, PartialEq)]
+pub struct DuplicateMatch {
+    /// Reference workout
+    pub workout_id: String,
+    /// Workout recording the same session
+    pub duplicate_id: String,
+    /// Start of the duplicate on the reference workout's timeline, in seconds
+    pub offset_seconds: i32,
+    /// Share of the shorter recording covered by both
+    pub overlap: f64,
+    /// Mean correlation of the aligned streams (None when no stream is shared)
+    pub correlation: Option<f64>,
+    /// Correlation per shared channel
+    pub channel_correlations: Vec<(Channel, f64)>,
+    /// Relative difference in total distance
+    pub distance_difference: Option<f64>,
+    /// Overall similarity from 0 to 1
+    pub score: f64,
+}
+
+/// Similarity-based duplicate detector
+pub struct DuplicateDetector {
+    config: DuplicateConfig,
+}
+
+impl Default for DuplicateDetector {
+    fn default() -> Self {
+        Self::new(DuplicateConfig::default())
+    }
+}
+
+impl DuplicateDetector {
+    /// Create a detector with custom thresholds
+    pub fn new(config: DuplicateConfig) -> Self {
+        Self { config }
+    }
+
+    /// Find all pairs of duplicates among the workouts
+    pub fn find_duplicates(&self, workouts: &[Workout]) -> Vec<DuplicateMatch> {
+        let mut matches = Vec::new();
+        for (i, a) in workouts.iter().enumerate() {
+            for b in &workouts[i + 1..] {
+                matches.extend(self.compare(a, b));
+            }
+        }
+        matches.sort_by(|a, b| b.score.total_cmp(&a.score));
+        matches
+    }
+
+    /// Compare two workouts, returning a match when they record the same session
+    pub fn compare(&self, a: &Workout, b: &Workout) -> Option<DuplicateMatch> {
+        if a.id == b.id || a.sport != b.sport || a.athlete_id != b.athlete_id {
+            return None;
+        }
+
+        // Recorded start times place `b` on `a`'s timeline up to clock drift
+        let recorded_offset = start_offset(a, b);
+        match recorded_offset {
+            Some(offset) if offset.unsigned_abs() > self.config.max_offset_seconds => return None,
+            None if a.date != b.date => return None,
+            _ => {}
+        }
+
+        let shorter = a.duration_seconds.min(b.duration_seconds);
+        let longer = a.duration_seconds.max(b.duration_seconds);
+        if shorter == 0 || f64::from(shorter) / f64::from(longer) < 0.5 {
+            return None;
+        }
+
+        let distance_difference = match (total_distance(a), total_distance(b)) {
+            (Some(da), Some(db)) if da.max(db) > 0.0 => Some((da - db).abs() / da.max(db)),
+            _ => None,
+        };
+        if distance_difference.is_some_and(|d| d > self.config.max_distance_difference) {
+            return None;
+        }
+
+        let (offset_seconds, channel_correlations) =
+            self.align(a, b, recorded_offset).unwrap_or((recorded_offset.unwrap_or(0), Vec::new()));
+        let correlation = (!channel_correlations.is_empty())
+            .then(|| channel_correlations.iter().map(|(_, r)| r).sum::<f64>() / channel_correlations.len() as f64);
+
+        let overlap = overlap_seconds(a.duration_seconds, b.duration_seconds, offset_seconds) / f64::from(shorter);
+        if overlap < self.config.min_overlap {
+            return None;
+        }
+
+        // Without shared streams only the summary can confirm the match
+        match correlation {
+            Some(r) if r < self.config.min_correlation => return None,
+            None if distance_difference.is_none() => return None,
+            _ => {}
+        }
+
+        let distance_score = distance_difference.map_or(0.5, |d| 1.0 - d / self.config.max_distance_difference);
+        let score = 0.5 * correlation.unwrap_or(0.5) + 0.3 * overlap.min(1.0) + 0.2 * distance_score;
+
+        Some(DuplicateMatch {
+            workout_id: a.id.clone(),
+            duplicate_id: b.id.clone(),
+            offset_seconds,
+            overlap,
+            correlation,
+            channel_correlations,
+            distance_difference,
+            score,
+        })
+    }
+
+    /// Start offset of `b` on `a`'s timeline that best correlates their streams
+    ///
+    /// Searches around the recorded offset when both start times are known.
+    fn align(&self, a: &Workout, b: &Workout, recorded_offset: Option<i32>) -> Option<(i32, Vec<(Channel, f64)>)> {
+        let (data_a, data_b) = (a.raw_data.as_deref()?, b.raw_data.as_deref()?);
+
+        let streams: Vec<(Channel, Stream, Stream)> = Channel::ALIGNMENT
+            .into_iter()
+            .map(|channel| (channel, resample(data_a, channel), resample(data_b, channel)))
+            .filter(|(_, sa, sb)| {
+                sa.iter().flatten().count() >= MIN_CORRELATION_SAMPLES
+                    && sb.iter().flatten().count() >= MIN_CORRELATION_SAMPLES
+            })
+            .collect();
+        if streams.is_empty() {
+            return None;
+        }
+
+        let correlate = |offset: i32| -> Vec<(Channel, f64)> {
+            streams
+                .iter()
+                .filter_map(|(channel, sa, sb)| pearson_at(sa, sb, offset).map(|r| (*channel, r)))
+                .collect()
+        };
+        let mean = |correlations: &[(Channel, f64)]| {
+            if correlations.is_empty() {
+                f64::MIN
+            } else {
+                correlations.iter().map(|(_, r)| r).sum::<f64>() / correlations.len() as f64
+            }
+        };
+
+        // Coarse search, then refine around the best offset
+        let (min, max) = match recorded_offset {
+            Some(offset) => (offset - MAX_CLOCK_DRIFT_SECONDS, offset + MAX_CLOCK_DRIFT_SECONDS),
+            None => (-(self.config.max_offset_seconds as i32), self.config.max_offset_seconds as i32),
+        };
+        let best_coarse = (min..=max)
+            .step_by(5)
+            .max_by(|x, y| mean(&correlate(*x)).total_cmp(&mean(&correlate(*y))))?;
+        let best = (best_coarse - 4..=best_coarse + 4)
+            .filter(|offset| (min..=max).contains(offset))
+            .max_by(|x, y| mean(&correlate(*x)).total_cmp(&mean(&correlate(*y))))?;
+
+        let correlations = correlate(best);
+        (!correlations.is_empty()).then_some((best, correlations))
+    }
+}
+
+/// Which workout a merged channel was taken from
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChannelProvenance {
+    /// Merged channel
+    pub channel: Channel,
+    /// Workout the channel was taken from
+    pub workout_id: String,
+    /// Source of that workout (file or device)
+    pub source: Option<String>,
+}
+
+/// Result of merging duplicate recordings
+#[derive(Debug, Clone)]
+pub struct MergedWorkout {
+    /// Combined workout on the common timeline
+    pub workout: Workout,
+    /// Source of each merged channel
+    pub provenance: Vec<ChannelProvenance>,
+}
+
+/// Merge preferences
+#[derive(Debug, Clone)]
+pub struct MergeConfig {
+    /// Source name fragments preferred per channel, matched against `Workout::source`
+    pub preferred_sources: Vec<(Channel, Vec<String>)>,
+    /// Score bonus for a preferred source
+    pub preference_bonus: f64,
+}
+
+impl Default for MergeConfig {
+    fn default() -> Self {
+        let fragments = |names: &[&str]| names.iter().map(|n| n.to_string()).collect();
+        Self {
+            preferred_sources: vec![
+                (Channel::Power, fragments(&["edge", "elemnt", "karoo", "hammerhead", "head unit", "bike computer"])),
+                (Channel::HeartRate, fragments(&["hrm", "strap", "chest"])),
+                (Channel::RunningDynamics, fragments(&["forerunner", "fenix", "epix", "watch", "coros", "suunto"])),
+            ],
+            preference_bonus: 0.2,
+        }
+    }
+}
+
+/// Builds one workout from several recordings of a session
+pub struct WorkoutMerger {
+    config: MergeConfig,
+}
+
+impl Default for WorkoutMerger {
+    fn default() -> Self {
+        Self::new(MergeConfig::default())
+    }
+}
+
+impl WorkoutMerger {
+    /// Create a merger with custom preferences
+    pub fn new(config: MergeConfig) -> Self {
+        Self { config }
+    }
+
+    /// Merge recordings, aligning each to the first with the detector
+    pub fn merge_duplicates(&self, workouts: &[Workout], detector: &DuplicateDetector) -> Result<MergedWorkout, MergeError> {
+        let (base, others) = workouts.split_first().ok_or(MergeError::NotEnoughWorkouts)?;
+        if others.is_empty() {
+            return Err(MergeError::NotEnoughWorkouts);
+        }
+
+        let aligned = others
+            .iter()
+            .map(|other| {
+                detector
+                    .compare(base, other)
+                    .map(|m| (other, m.offset_seconds))
+                    .ok_or_else(|| MergeError::NotDuplicates(base.id.clone(), other.id.clone()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        self.merge(base, &aligned)
+    }
+
+    /// Merge recordings with known start offsets on the base workout's timeline
+    pub fn merge(&self, base: &Workout, others: &[(&Workout, i32)]) -> Result<MergedWorkout, MergeError> {
+        let sources: Vec<(&Workout, i64)> = std::iter::once((base, 0))
+            .chain(others.iter().map(|(w, offset)| (*w, i64::from(*offset))))
+            .collect();
+        for (workout, _) in &sources {
+            if workout.raw_data.as_ref().is_none_or(|d| d.is_empty()) {
+                return Err(MergeError::MissingData(workout.id.clone()));
+            }
+        }
+
+        // Common timeline starting at the earliest recording
+        let start = sources.iter().map(|(_, offset)| *offset).min().unwrap_or(0);
+        let shifted: Vec<Vec<DataPoint>> = sources
+            .iter()
+            .map(|(workout, offset)| {
+                let shift = (offset - start) as u32;
+                workout.raw_data.iter().flatten().map(|p| DataPoint { timestamp: p.timestamp + shift, ..p.clone() }).collect()
+            })
+            .collect();
+
+        let mut timeline: Vec<u32> = shifted.iter().flatten().map(|p| p.timestamp).collect();
+        timeline.sort_unstable();
+        timeline.dedup();
+
+        // Pick the best source per channel
+        let choices: Vec<(Channel, usize)> = Channel::ALL
+            .into_iter()
+            .filter_map(|channel| {
+                sources
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(i, (workout, _))| self.channel_score(workout, channel).map(|score| (i, score)))
+                    .fold(None, |best: Option<(usize, f64)>, (i, score)| match best {
+                        Some((_, best_score)) if best_score >= score => best,
+                        _ => Some((i, score)),
+                    })
+                    .map(|(i, _)| (channel, i))
+            })
+            .collect();
+
+        let mut cursors = vec![0usize; shifted.len()];
+        let raw_data: Vec<DataPoint> = timeline
+            .iter()
+            .map(|&t| {
+                let samples: Vec<Option<&DataPoint>> = shifted
+                    .iter()
+                    .zip(cursors.iter_mut())
+                    .map(|(points, cursor)| sample_at(points, cursor, t))
+                    .collect();
+
+                // Laps, transitions and swim fields follow the base recording
+                let mut point = samples[0].cloned().unwrap_or_else(|| empty_point(t));
+                point.timestamp = t - timeline[0];
+                let empty = empty_point(t);
+                for channel in Channel::ALL {
+                    let sample = choices.iter().find(|(c, _)| *c == channel).and_then(|(_, i)| samples[*i]);
+                    channel.copy(sample.unwrap_or(&empty), &mut point);
+                }
+                point
+            })
+            .collect();
+
+        let provenance: Vec<ChannelProvenance> = choices
+            .iter()
+            .map(|(channel, i)| ChannelProvenance {
+                channel: *channel,
+                workout_id: sources[*i].0.id.clone(),
+                source: sources[*i].0.source.clone(),
+            })
+            .collect();
+
+        let mut workout = base.clone();
+        workout.id = uuid::Uuid::new_v4().to_string();
+        workout.start_time = base.start_time.map(|t| t + chrono::Duration::seconds(start));
+        workout.date = workout.start_time.map_or(base.date, |t| t.date_naive());
+        workout.duration_seconds = timeline.last().unwrap_or(&0) - timeline.first().unwrap_or(&0);
+        workout.source = Some(sources.iter().map(|(w, _)| w.source.clone().unwrap_or_else(|| w.id.clone())).collect::<Vec<_>>().join(" + "));
+
+        // Summary fields follow the source of their channel
+        let source_of = |channel: Channel| choices.iter().find(|(c, _)| *c == channel).map(|(_, i)| sources[*i].0);
+        if let Some(hr) = source_of(Channel::HeartRate) {
+            workout.summary.avg_heart_rate = hr.summary.avg_heart_rate;
+            workout.summary.max_heart_rate = hr.summary.max_heart_rate;
+        }
+        if let Some(power) = source_of(Channel::Power) {
+            workout.summary.avg_power = power.summary.avg_power;
+            workout.summary.normalized_power = power.summary.normalized_power;
+            workout.summary.intensity_factor = power.summary.intensity_factor;
+            workout.data_source = power.data_source.clone();
+        }
+        if let Some(speed) = source_of(Channel::Speed) {
+            workout.summary.total_distance = speed.summary.total_distance;
+            workout.summary.avg_pace = speed.summary.avg_pace;
+        }
+        if let Some(elevation) = source_of(Channel::Elevation) {
+            workout.summary.elevation_gain = elevation.summary.elevation_gain;
+        }
+        if let Some(cadence) = source_of(Channel::Cadence) {
+            workout.summary.avg_cadence = cadence.summary.avg_cadence;
+        }
+        // Load depends on the merged channels; recalculate it from the merged data
+        workout.summary.tss = None;
+        workout.raw_data = Some(raw_data);
+
+        Ok(MergedWorkout { workout, provenance })
+    }
+
+    /// Quality of a channel in a recording, or None when it isn't recorded
+    ///
+    /// Coverage times the share of plausible readings, plus a bonus for a
+    /// preferred source.
+    fn channel_score(&self, workout: &Workout, channel: Channel) -> Option<f64> {
+        let data = workout.raw_data.as_deref()?;
+        let present: Vec<(usize, &DataPoint)> = data.iter().enumerate().filter(|(_, p)| channel.is_present(p)).collect();
+        let coverage = present.len() as f64 / data.len().max(1) as f64;
+        if coverage < 0.05 {
+            return None;
+        }
+
+        let artifacts = present
+            .iter()
+            .filter(|(i, p)| channel.is_artifact(p, i.checked_sub(1).map(|j| &data[j])))
+            .count();
+        let plausible = 1.0 - artifacts as f64 / present.len() as f64;
+
+        let source = workout.source.as_deref().unwrap_or_default().to_lowercase();
+        let preferred = self
+            .config
+            .preferred_sources
+            .iter()
+            .filter(|(c, _)| *c == channel)
+            .any(|(_, fragments)| fragments.iter().any(|f| source.contains(f.as_str())));
+
+        Some(coverage * plausible + if preferred { self.config.preference_bonus } else { 0.0 })
+    }
+}
+
+/// Total distance in meters from the summary or the last recorded distance
+fn total_distance(workout: &Workout) -> Option<f64> {
+    workout
+        .summary
+        .total_distance
+        .or_else(|| workout.raw_data.as_ref()?.iter().rev().find_map(|p| p.distance))
+        .and_then(|d| d.to_f64())
+}
+
+/// Seconds both recordings cover when `b` starts `offset` seconds into `a`
+fn overlap_seconds(duration_a: u32, duration_b: u32, offset: i32) -> f64 {
+    let start = i64::from(offset).max(0);
+    let end = i64::from(duration_a).min(i64::from(offset) + i64::from(duration_b));
+    (end - start).max(0) as f64
+}
+
+/// Channel values at one-second resolution
+type Stream = Vec<Option<f64>>;
+
+/// Start of `b` relative to `a` in seconds, when both start times are known
+fn start_offset(a: &Workout, b: &Workout) -> Option<i32> {
+    let seconds = (b.start_time? - a.start_time?).num_seconds();
+    i32::try_from(seconds).ok()
+}
+
+/// One value per second, holding each sample for a few seconds
+fn resample(data: &[DataPoint], channel: Channel) -> Stream {
+    let Some(last) = data.iter().map(|p| p.timestamp).max() else {
+        return Vec::new();
+    };
+
+    let mut series = vec![None; last as usize + 1];
+    let mut held: Option<(u32, f64)> = None;
+    let mut points = data.iter().peekable();
+    for (t, slot) in series.iter_mut().enumerate() {
+        let t = t as u32;
+        while let Some(point) = points.next_if(|p| p.timestamp <= t) {
+            if let Some(value) = channel.value(point) {
+                held = Some((point.timestamp, value));
+            }
+        }
+        *slot = held.filter(|(at, _)| t - at <= HOLD_SECONDS).map(|(_, v)| v);
+    }
+    series
+}
+
+/// Pearson correlation of `a` and `b` with `b` starting `offset` seconds into `a`
+fn pearson_at(a: &[Option<f64>], b: &[Option<f64>], offset: i32) -> Option<f64> {
+    let (mut n, mut sum_a, mut sum_b, mut sum_aa, mut sum_bb, mut sum_ab) = (0usize, 0.0, 0.0, 0.0, 0.0, 0.0);
+
+    for (i, value_a) in a.iter().enumerate() {
+        let j = i as i64 - i64::from(offset);
+        if j < 0 || j >= b.len() as i64 {
+            continue;
+        }
+        if let (Some(x), Some(y)) = (value_a, b[j as usize]) {
+            n += 1;
+            sum_a += x;
+            sum_b += y;
+            sum_aa += x * x;
+            sum_bb += y * y;
+            sum_ab += x * y;
+        }
+    }
+
+    if n < MIN_CORRELATION_SAMPLES {
+        return None;
+    }
+    let n = n as f64;
+    let covariance = sum_ab - sum_a * sum_b / n;
+    let variance_a = sum_aa - sum_a * sum_a / n;
+    let variance_b = sum_bb - sum_b * sum_b / n;
+    if variance_a <= f64::EPSILON || variance_b <= f64::EPSILON {
+        return None;
+    }
+    Some(covariance / (variance_a * variance_b).sqrt())
+}
+
+/// Latest sample at or before `t` within the hold time, advancing `cursor`
+fn sample_at<'a>(points: &'a [DataPoint], cursor: &mut usize, t: u32) -> Option<&'a DataPoint> {
+    while *cursor + 1 < points.len() && points[*cursor + 1].timestamp <= t {
+        *cursor += 1;
+    }
+    points
+        .get(*cursor)
+        .filter(|p| p.timestamp <= t && t - p.timestamp <= HOLD_SECONDS)
+}
+
+/// Data point with no channels recorded
+fn empty_point(timestamp: u32) -> DataPoint {
+    DataPoint {
+        timestamp,
+        heart_rate: None,
+        power: None,
+        pace: None,
+        elevation: None,
+        cadence: None,
+        speed: None,
+        distance: None,
+        left_power: None,
+        right_power: None,
+        ground_contact_time: None,
+        vertical_oscillation: None,
+        stride_length: None,
+        stroke_count: None,
+        stroke_type: None,
+        lap_number: None,
+        sport_transition: None,
+        temperature: None,
+        drive_length: None,
+        stroke_work: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{DataSource, Sport, WorkoutSummary, WorkoutType};
+    use chrono::NaiveDate;
+    use rust_decimal_macros::dec;
+
+    /// Heart rate and speed of one ride at `t` seconds since the session started
+    fn session(t: u32) -> (u16, Decimal) {
+        let t = f64::from(t);
+        let hr = 140.0 + 15.0 * (t / 90.0).sin() + 6.0 * (t / 23.0).sin();
+        let speed = 9.0 + 1.5 * (t / 60.0).sin() + 0.5 * (t / 13.0).cos();
+        (hr.round() as u16, Decimal::from_f64(speed).unwrap().round_dp(2))
+    }
+
+    /// A recording of the session from `start` for `duration` seconds
+    fn recording(id: &str, source: &str, start: u32, duration: u32, with_power: bool, hr_every: u32) -> Workout {
+        let raw_data: Vec<DataPoint> = (0..duration)
+            .map(|t| {
+                let (hr, speed) = session(start + t);
+                DataPoint {
+                    heart_rate: (t % hr_every == 0).then_some(hr),
+                    power: with_power.then(|| (speed * dec!(25)).to_u16().unwrap()),
+                    speed: Some(speed),
+                    distance: Some(Decimal::from(t) * dec!(9)),
+                    ..empty_point(t)
+                }
+            })
+            .collect();
+
+        Workout {
+            id: id.to_string(),
+            date: NaiveDate::from_ymd_opt(2025, 5, 4).unwrap(),
+            sport: Sport::Cycling,
+            duration_seconds: duration,
+            workout_type: WorkoutType::Endurance,
+            data_source: if with_power { DataSource::Power } else { DataSource::HeartRate },
+            raw_data: Some(raw_data),
+            summary: WorkoutSummary {
+                // Both devices measure the same ride distance
+                total_distance: Some(dec!(16200)),
+                avg_power: with_power.then_some(225),
+                ..WorkoutSummary::default()
+            },
+            notes: None,
+            athlete_id: Some("athlete".to_string()),
+            source: Some(source.to_string()),
+            swim_lengths: Vec::new(),
+            start_time: None,
+            laps: Vec::new(),
+            rpe: None,
+            strength_sets: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_detects_offset_recordings_of_one_session() {
+        // The watch starts a minute before the head unit and stops 40 s later
+        let head_unit = recording("edge", "Edge 530", 60, 1800, true, 4);
+        let watch = recording("watch", "Forerunner 955", 0, 1900, false, 1);
+
+        let found = DuplicateDetector::default().compare(&head_unit, &watch).unwrap();
+        assert_eq!(found.offset_seconds, -60);
+        assert!(found.correlation.unwrap() > 0.95);
+        assert!(found.overlap > 0.99);
+
+        // A different ride on the same day doesn't match
+        let mut other = recording("other", "Forerunner 955", 0, 1900, false, 1);
+        for point in other.raw_data.as_mut().unwrap() {
+            let (hr, speed) = session(point.timestamp * 3 + 5000);
+            point.heart_rate = Some(hr);
+            point.speed = Some(speed);
+        }
+        assert!(DuplicateDetector::default().compare(&head_unit, &other).is_none());
+    }
+
+    #[test]
+    fn test_recorded_start_times_bound_the_offset() {
+        let start = chrono::DateTime::parse_from_rfc3339("2025-05-04T23:50:00+02:00").unwrap();
+        let mut head_unit = recording("edge", "Edge 530", 60, 1800, true, 4);
+        let mut watch = recording("watch", "Forerunner 955", 0, 1900, false, 1);
+        head_unit.start_time = Some(start + chrono::Duration::seconds(62));
+        watch.start_time = Some(start);
+        // Stored dates may differ when one device saves after midnight
+        watch.date = watch.date.succ_opt().unwrap();
+
+        let found = DuplicateDetector::default().compare(&head_unit, &watch).unwrap();
+        assert_eq!(found.offset_seconds, -60);
+
+        let merged = WorkoutMerger::default().merge(&head_unit, &[(&watch, found.offset_seconds)]).unwrap();
+        assert_eq!(merged.workout.start_time, Some(start + chrono::Duration::seconds(2)));
+
+        // The same streams recorded hours apart are separate sessions
+        watch.start_time = Some(start - chrono::Duration::hours(3));
+        assert!(DuplicateDetector::default().compare(&head_unit, &watch).is_none());
+    }
+
+    #[test]
+    fn test_merge_takes_best_channels_with_provenance() {
+        let head_unit = recording("edge", "Edge 530", 60, 1800, true, 4);
+        let watch = recording("watch", "Forerunner 955", 0, 1900, false, 1);
+
+        let merged = WorkoutMerger::default()
+            .merge_duplicates(&[head_unit, watch], &DuplicateDetector::default())
+            .unwrap();
+
+        let source = |channel| merged.provenance.iter().find(|p| p.channel == channel).map(|p| p.workout_id.as_str());
+        assert_eq!(source(Channel::Power), Some("edge"));
+        // The watch records heart rate every second, the head unit every fourth
+        assert_eq!(source(Channel::HeartRate), Some("watch"));
+
+        let workout = &merged.workout;
+        assert_eq!(workout.duration_seconds, 1899);
+        assert_eq!(workout.summary.avg_power, Some(225));
+        assert!(workout.summary.tss.is_none());
+
+        // Power starts when the head unit does, heart rate from the first second
+        let data = workout.raw_data.as_ref().unwrap();
+        assert_eq!(data[0].heart_rate, Some(session(0).0));
+        assert!(data[0].power.is_none());
+        assert!(data[60].power.is_some());
+    }
+}
@@ -1,10 +1,17 @@
 #![allow(dead_code)]
 
-use crate::models::{AthleteProfile, HeartRateZones, PaceZones, PowerZones, TrainingZones};
+use crate::config::{
+    HRZoneMethod as HRZoneSetting, PaceZoneMethod as PaceZoneSetting, PowerZoneMethod as PowerZoneSetting,
+    ZoneBoundary, ZoneSettings,
+};
+use crate::models::{
+    AthleteProfile, DataPoint, HeartRateZones, PaceZones, PowerZones, Sport, TrainingZones,
+};
 use anyhow::{anyhow, Result};
 use rust_decimal::prelude::ToPrimitive;
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
 
 /// Errors that can occur during zone calculations
 #[derive(Debug, thiserror::Error)]
@@ -231,6 +238,78 @@ impl ZoneCalculator {
         }
     }
 
+    /// Resolve a zone model against the athlete's thresholds
+    pub fn calculate_zones(thresholds: &ZoneThresholds, model: &ZoneModel) -> Result<ResolvedZones> {
+        model.validate()?;
+        let anchor = thresholds.value(model.anchor).ok_or_else(|| {
+            ZoneError::MissingThreshold(format!("{} required for {} zones", model.anchor.label(), model.name))
+        })?;
+
+        match model.anchor {
+            ZoneAnchor::Ftp => Self::validate_power(anchor.to_u16().unwrap_or(0))?,
+            ZoneAnchor::Lthr => Self::validate_heart_rate(anchor.to_u16().unwrap_or(0), "LTHR")?,
+            ZoneAnchor::MaxHr => Self::validate_heart_rate(anchor.to_u16().unwrap_or(0), "Max HR")?,
+            ZoneAnchor::ThresholdPace => Self::validate_pace(anchor)?,
+        }
+
+        // Heart rate and power boundaries are whole numbers; pace keeps two decimals
+        let scale = |fraction: Decimal| match model.metric {
+            ZoneMetric::Pace => (anchor * fraction).round_dp(2),
+            ZoneMetric::HeartRate | ZoneMetric::Power => (anchor * fraction).round(),
+        };
+
+        Ok(ResolvedZones {
+            model_id: model.id.clone(),
+            model_name: model.name.clone(),
+            metric: model.metric,
+            boundaries: model
+                .zones
+                .iter()
+                .enumerate()
+                .map(|(i, zone)| ZoneBoundary {
+                    zone: i as u8 + 1,
+                    name: zone.name.clone(),
+                    min: scale(zone.min),
+                    max: zone.max.map(scale),
+                    color: None,
+                })
+                .collect(),
+        })
+    }
+
+    /// Zones for one metric of a sport
+    ///
+    /// `selection` is the sport's comma-separated list of model IDs (the
+    /// `zone_method` of its sport profile); the first model for `metric` wins.
+    /// Without a match the sport's default model for the configured zone
+    /// method is used.
+    pub fn zones_for_sport(
+        thresholds: &ZoneThresholds,
+        sport: &Sport,
+        metric: ZoneMetric,
+        selection: Option<&str>,
+        settings: &ZoneSettings,
+    ) -> Result<ResolvedZones> {
+        let selected = selection
+            .into_iter()
+            .flat_map(|s| s.split(','))
+            .map(str::trim)
+            .filter(|id| !id.is_empty())
+            .map(|id| ZoneModel::find(id, &settings.custom_models).ok_or_else(|| anyhow!("Unknown zone model: {}", id)))
+            .collect::<Result<Vec<_>>>()?;
+
+        let model = match selected.into_iter().find(|m| m.metric == metric) {
+            Some(model) => model,
+            None => ZoneModel::default_for(sport, metric, thresholds, settings),
+        };
+        Self::calculate_zones(thresholds, &model)
+    }
+
+    /// Determine the zone number of a value in resolved zones
+    pub fn get_zone(value: Decimal, zones: &ResolvedZones) -> Option<u8> {
+        zones.zone_of(value).map(|boundary| boundary.zone)
+    }
+
     // Helper methods for calculations and validation
 
     fn calculate_percentage(value: Decimal, percentage: Decimal) -> Result<u16> {
@@ -258,7 +337,7 @@ impl ZoneCalculator {
     }
 
     fn validate_heart_rate(hr: u16, field_name: &str) -> Result<()> {
-        if hr < 30 || hr > 220 {
+        if !(30..=220).contains(&hr) {
             return Err(anyhow!(ZoneError::InvalidThreshold(format!(
                 "{} must be between 30 and 220 bpm, got {}",
                 field_name, hr
@@ -268,7 +347,7 @@ impl ZoneCalculator {
     }
 
     fn validate_power(power: u16) -> Result<()> {
-        if power < 50 || power > 800 {
+        if !(50..=800).contains(&power) {
             return Err(anyhow!(ZoneError::InvalidThreshold(format!(
                 "FTP must be between 50 and 800 watts, got {}",
                 power
@@ -314,7 +393,7 @@ impl ThresholdEstimator {
 
     /// Estimate max heart rate from age (220 - age formula)
     pub fn estimate_max_hr_from_age(age: u8) -> Result<u16> {
-        if age < 10 || age > 100 {
+        if !(10..=100).contains(&age) {
             return Err(anyhow!(ZoneError::InvalidThreshold(format!(
                 "Age must be between 10 and 100, got {}",
                 age
@@ -358,23 +437,17 @@ pub struct ZoneAnalyzer;
 impl ZoneAnalyzer {
     /// Calculate zone distribution from heart rate data
     pub fn analyze_hr_distribution(hr_data: &[u16], zones: &HeartRateZones) -> ZoneDistribution {
-        let mut zone_counts = [0u32; 5];
-        let total_points = hr_data.len() as u32;
-
-        for &hr in hr_data {
-            let zone = ZoneCalculator::get_heart_rate_zone(hr, zones);
-            if zone >= 1 && zone <= 5 {
-                zone_counts[(zone - 1) as usize] += 1;
-            }
-        }
+        let values: Vec<Decimal> = hr_data.iter().map(|&hr| Decimal::from(hr)).collect();
+        let distribution = Self::analyze_distribution(&values, &ResolvedZones::from(zones));
+        let percent = |zone: usize| distribution.zones[zone].percent;
 
         ZoneDistribution {
-            zone1_percent: calculate_percentage(zone_counts[0], total_points),
-            zone2_percent: calculate_percentage(zone_counts[1], total_points),
-            zone3_percent: calculate_percentage(zone_counts[2], total_points),
-            zone4_percent: calculate_percentage(zone_counts[3], total_points),
-            zone5_percent: calculate_percentage(zone_counts[4], total_points),
-            total_points,
+            zone1_percent: percent(0),
+            zone2_percent: percent(1),
+            zone3_percent: percent(2),
+            zone4_percent: percent(3),
+            zone5_percent: percent(4),
+            total_points: distribution.total_points,
         }
     }
 
@@ -383,27 +456,63 @@ impl ZoneAnalyzer {
         power_data: &[u16],
         zones: &PowerZones,
     ) -> PowerZoneDistribution {
-        let mut zone_counts = [0u32; 7];
-        let total_points = power_data.len() as u32;
+        let values: Vec<Decimal> = power_data.iter().map(|&power| Decimal::from(power)).collect();
+        let distribution = Self::analyze_distribution(&values, &ResolvedZones::from(zones));
+        let percent = |zone: usize| distribution.zones[zone].percent;
 
-        for &power in power_data {
-            let zone = ZoneCalculator::get_power_zone(power, zones);
-            if zone >= 1 && zone <= 7 {
-                zone_counts[(zone - 1) as usize] += 1;
+        PowerZoneDistribution {
+            zone1_percent: percent(0),
+            zone2_percent: percent(1),
+            zone3_percent: percent(2),
+            zone4_percent: percent(3),
+            zone5_percent: percent(4),
+            zone6_percent: percent(5),
+            zone7_percent: percent(6),
+            total_points: distribution.total_points,
+        }
+    }
+
+    /// Calculate the distribution of values over any zone model
+    ///
+    /// Values outside every zone still count towards the total.
+    pub fn analyze_distribution(values: &[Decimal], zones: &ResolvedZones) -> ModelZoneDistribution {
+        let mut counts = vec![0u32; zones.boundaries.len()];
+        for value in values {
+            if let Some(zone) = ZoneCalculator::get_zone(*value, zones) {
+                counts[(zone - 1) as usize] += 1;
             }
         }
 
-        PowerZoneDistribution {
-            zone1_percent: calculate_percentage(zone_counts[0], total_points),
-            zone2_percent: calculate_percentage(zone_counts[1], total_points),
-            zone3_percent: calculate_percentage(zone_counts[2], total_points),
-            zone4_percent: calculate_percentage(zone_counts[3], total_points),
-            zone5_percent: calculate_percentage(zone_counts[4], total_points),
-            zone6_percent: calculate_percentage(zone_counts[5], total_points),
-            zone7_percent: calculate_percentage(zone_counts[6], total_points),
+        let total_points = values.len() as u32;
+        ModelZoneDistribution {
+            model_id: zones.model_id.clone(),
+            zones: zones
+                .boundaries
+                .iter()
+                .zip(counts)
+                .map(|(boundary, points)| ZoneShare {
+                    zone: boundary.zone,
+                    name: boundary.name.clone(),
+                    points,
+                    percent: calculate_percentage(points, total_points),
+                })
+                .collect(),
             total_points,
         }
     }
+
+    /// Distribution of a workout's samples over any zone model
+    pub fn analyze_workout(samples: &[DataPoint], zones: &ResolvedZones) -> ModelZoneDistribution {
+        let values: Vec<Decimal> = samples
+            .iter()
+            .filter_map(|point| match zones.metric {
+                ZoneMetric::HeartRate => point.heart_rate.map(Decimal::from),
+                ZoneMetric::Power => point.power.map(Decimal::from),
+                ZoneMetric::Pace => point.pace,
+            })
+            .collect();
+        Self::analyze_distribution(&values, zones)
+    }
 }
 
 /// Zone distribution results for heart rate
@@ -430,6 +539,454 @@ pub struct PowerZoneDistribution {
     pub total_points: u32,
 }
 
+/// Zone distribution results for any zone model
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelZoneDistribution {
+    pub model_id: String,
+    pub zones: Vec<ZoneShare>,
+    pub total_points: u32,
+}
+
+/// Points and share of one zone
+#[derive(Debug, Clone, PartialEq)]
+pub struct ZoneShare {
+    pub zone: u8,
+    pub name: String,
+    pub points: u32,
+    pub percent: Decimal,
+}
+
+/// Quantity a zone model divides into zones
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ZoneMetric {
+    HeartRate,
+    Power,
+    /// Minutes per km or mile; lower values are harder
+    Pace,
+}
+
+impl std::str::FromStr for ZoneMetric {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "heart-rate" | "hr" => Ok(Self::HeartRate),
+            "power" => Ok(Self::Power),
+            "pace" => Ok(Self::Pace),
+            _ => Err(anyhow!("Unknown zone type: {} (use heart-rate, power or pace)", s)),
+        }
+    }
+}
+
+/// Threshold that a zone model's boundaries are relative to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ZoneAnchor {
+    Ftp,
+    Lthr,
+    MaxHr,
+    ThresholdPace,
+}
+
+impl ZoneAnchor {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Ftp => "FTP",
+            Self::Lthr => "LTHR",
+            Self::MaxHr => "Max HR",
+            Self::ThresholdPace => "Threshold pace",
+        }
+    }
+}
+
+/// One zone as fractions of the anchor threshold
+///
+/// `min` is inclusive and `max` exclusive. For pace the slowest zone comes
+/// first and has no upper bound.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ZoneDefinition {
+    pub name: String,
+    pub min: Decimal,
+    pub max: Option<Decimal>,
+}
+
+/// Named zone model with any number of zones, ordered from easiest to hardest
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ZoneModel {
+    pub id: String,
+    pub name: String,
+    pub metric: ZoneMetric,
+    pub anchor: ZoneAnchor,
+    pub zones: Vec<ZoneDefinition>,
+}
+
+impl ZoneModel {
+    fn build(id: &str, name: &str, metric: ZoneMetric, anchor: ZoneAnchor, zones: &[(&str, Decimal, Option<Decimal>)]) -> Self {
+        Self {
+            id: id.to_string(),
+            name: name.to_string(),
+            metric,
+            anchor,
+            zones: zones
+                .iter()
+                .map(|(name, min, max)| ZoneDefinition {
+                    name: name.to_string(),
+                    min: *min,
+                    max: *max,
+                })
+                .collect(),
+        }
+    }
+
+    /// Coggan 7-zone power levels (% FTP)
+    pub fn coggan_power() -> Self {
+        Self::build("coggan-power", "Coggan Power", ZoneMetric::Power, ZoneAnchor::Ftp, &[
+            ("Active Recovery", dec!(0), Some(dec!(0.55))),
+            ("Endurance", dec!(0.55), Some(dec!(0.75))),
+            ("Tempo", dec!(0.75), Some(dec!(0.90))),
+            ("Lactate Threshold", dec!(0.90), Some(dec!(1.05))),
+            ("VO2 Max", dec!(1.05), Some(dec!(1.20))),
+            ("Anaerobic Capacity", dec!(1.20), Some(dec!(1.50))),
+            ("Neuromuscular Power", dec!(1.50), None),
+        ])
+    }
+
+    /// Coggan 5-zone heart rate levels (% LTHR)
+    pub fn coggan_hr() -> Self {
+        Self::build("coggan-hr", "Coggan Heart Rate", ZoneMetric::HeartRate, ZoneAnchor::Lthr, &[
+            ("Active Recovery", dec!(0), Some(dec!(0.69))),
+            ("Endurance", dec!(0.69), Some(dec!(0.84))),
+            ("Tempo", dec!(0.84), Some(dec!(0.95))),
+            ("Lactate Threshold", dec!(0.95), Some(dec!(1.06))),
+            ("VO2 Max", dec!(1.06), None),
+        ])
+    }
+
+    /// Friel 7-zone heart rate (% LTHR)
+    pub fn friel_hr() -> Self {
+        Self::build("friel-hr", "Friel Heart Rate", ZoneMetric::HeartRate, ZoneAnchor::Lthr, &[
+            ("Recovery", dec!(0), Some(dec!(0.81))),
+            ("Aerobic", dec!(0.81), Some(dec!(0.90))),
+            ("Tempo", dec!(0.90), Some(dec!(0.94))),
+            ("Sub-Threshold", dec!(0.94), Some(dec!(1.00))),
+            ("Super-Threshold", dec!(1.00), Some(dec!(1.03))),
+            ("Aerobic Capacity", dec!(1.03), Some(dec!(1.07))),
+            ("Anaerobic Capacity", dec!(1.07), None),
+        ])
+    }
+
+    /// Friel 7-zone run pace (% threshold pace, slowest first)
+    pub fn friel_run_pace() -> Self {
+        Self::build("friel-run-pace", "Friel Run Pace", ZoneMetric::Pace, ZoneAnchor::ThresholdPace, &[
+            ("Recovery", dec!(1.29), None),
+            ("Aerobic", dec!(1.14), Some(dec!(1.29))),
+            ("Tempo", dec!(1.06), Some(dec!(1.14))),
+            ("Sub-Threshold", dec!(1.01), Some(dec!(1.06))),
+            ("Super-Threshold", dec!(0.97), Some(dec!(1.01))),
+            ("Aerobic Capacity", dec!(0.90), Some(dec!(0.97))),
+            ("Anaerobic Capacity", dec!(0), Some(dec!(0.90))),
+        ])
+    }
+
+    /// Seiler 3-zone heart rate, VT1 at 90% and VT2 at 100% LTHR
+    pub fn seiler_hr() -> Self {
+        Self::build("seiler-hr", "Seiler 3-Zone Heart Rate", ZoneMetric::HeartRate, ZoneAnchor::Lthr, &[
+            ("Below VT1", dec!(0), Some(dec!(0.90))),
+            ("VT1 to VT2", dec!(0.90), Some(dec!(1.00))),
+            ("Above VT2", dec!(1.00), None),
+        ])
+    }
+
+    /// Seiler 3-zone power, VT1 at 75% and VT2 at 100% FTP
+    pub fn seiler_power() -> Self {
+        Self::build("seiler-power", "Seiler 3-Zone Power", ZoneMetric::Power, ZoneAnchor::Ftp, &[
+            ("Below VT1", dec!(0), Some(dec!(0.75))),
+            ("VT1 to VT2", dec!(0.75), Some(dec!(1.00))),
+            ("Above VT2", dec!(1.00), None),
+        ])
+    }
+
+    /// iLevels-style power levels (% FTP)
+    ///
+    /// WKO individualises the upper levels from the power-duration model;
+    /// without one they are approximated with fixed fractions of FTP.
+    pub fn ilevels() -> Self {
+        Self::build("ilevels", "iLevels", ZoneMetric::Power, ZoneAnchor::Ftp, &[
+            ("Recovery", dec!(0), Some(dec!(0.56))),
+            ("Endurance", dec!(0.56), Some(dec!(0.76))),
+            ("Tempo", dec!(0.76), Some(dec!(0.88))),
+            ("Sweet Spot", dec!(0.88), Some(dec!(0.95))),
+            ("FTP", dec!(0.95), Some(dec!(1.06))),
+            ("FRC / VO2 Max", dec!(1.06), Some(dec!(1.21))),
+            ("Anaerobic", dec!(1.21), Some(dec!(1.50))),
+            ("Pmax", dec!(1.50), None),
+        ])
+    }
+
+    /// 5-zone heart rate (% max HR)
+    pub fn max_hr() -> Self {
+        Self::build("max-hr", "Max Heart Rate", ZoneMetric::HeartRate, ZoneAnchor::MaxHr, &[
+            ("Very Light", dec!(0), Some(dec!(0.60))),
+            ("Light", dec!(0.60), Some(dec!(0.70))),
+            ("Moderate", dec!(0.70), Some(dec!(0.80))),
+            ("Hard", dec!(0.80), Some(dec!(0.90))),
+            ("Maximum", dec!(0.90), None),
+        ])
+    }
+
+    /// 5-zone threshold pace (% threshold pace, slowest first)
+    pub fn threshold_pace() -> Self {
+        Self::build("threshold-pace", "Threshold Pace", ZoneMetric::Pace, ZoneAnchor::ThresholdPace, &[
+            ("Easy", dec!(1.29), None),
+            ("Aerobic", dec!(1.14), Some(dec!(1.29))),
+            ("Tempo", dec!(1.06), Some(dec!(1.14))),
+            ("Threshold", dec!(1.00), Some(dec!(1.06))),
+            ("VO2 Max", dec!(0), Some(dec!(1.00))),
+        ])
+    }
+
+    /// All built-in models
+    pub fn builtins() -> Vec<Self> {
+        vec![
+            Self::coggan_power(),
+            Self::coggan_hr(),
+            Self::friel_hr(),
+            Self::friel_run_pace(),
+            Self::seiler_hr(),
+            Self::seiler_power(),
+            Self::ilevels(),
+            Self::max_hr(),
+            Self::threshold_pace(),
+        ]
+    }
+
+    /// Find a model by ID; custom models shadow built-ins
+    pub fn find(id: &str, custom_models: &[ZoneModel]) -> Option<Self> {
+        let id = id.to_lowercase();
+        custom_models
+            .iter()
+            .find(|m| m.id.to_lowercase() == id)
+            .cloned()
+            .or_else(|| Self::builtins().into_iter().find(|m| m.id == id))
+    }
+
+    /// Default model for a sport and metric under the configured zone methods
+    ///
+    /// LTHR heart rate zones fall back to max HR when LTHR is unknown, and
+    /// custom methods fall back to the standard model without usable
+    /// percentages.
+    pub fn default_for(sport: &Sport, metric: ZoneMetric, thresholds: &ZoneThresholds, settings: &ZoneSettings) -> Self {
+        let custom = match metric {
+            ZoneMetric::HeartRate if matches!(settings.hr_zone_method, HRZoneSetting::Custom) => {
+                settings.custom_hr_zones.as_deref().and_then(|p| Self::from_percentages(metric, p))
+            }
+            ZoneMetric::Power if matches!(settings.power_zone_method, PowerZoneSetting::Custom) => {
+                settings.custom_power_zones.as_deref().and_then(|p| Self::from_percentages(metric, p))
+            }
+            ZoneMetric::Pace if matches!(settings.pace_zone_method, PaceZoneSetting::Custom) => {
+                settings.custom_pace_zones.as_deref().and_then(|p| Self::from_percentages(metric, p))
+            }
+            _ => None,
+        };
+        if let Some(model) = custom {
+            return model;
+        }
+
+        match metric {
+            ZoneMetric::Power => Self::coggan_power(),
+            ZoneMetric::HeartRate if matches!(settings.hr_zone_method, HRZoneSetting::MaxHR) => Self::max_hr(),
+            ZoneMetric::HeartRate if thresholds.lthr.is_none() && thresholds.max_hr.is_some() => Self::max_hr(),
+            ZoneMetric::HeartRate => Self::friel_hr(),
+            ZoneMetric::Pace if *sport == Sport::Running => Self::friel_run_pace(),
+            ZoneMetric::Pace => Self::threshold_pace(),
+        }
+    }
+
+    /// Model from the custom zone percentages of the zone settings
+    ///
+    /// Each percentage is a boundary between two zones, relative to LTHR,
+    /// FTP or threshold pace. Returns `None` when the boundaries do not form
+    /// a valid model.
+    fn from_percentages(metric: ZoneMetric, percentages: &[Decimal]) -> Option<Self> {
+        let (id, name, anchor) = match metric {
+            ZoneMetric::HeartRate => ("custom-hr", "Custom Heart Rate", ZoneAnchor::Lthr),
+            ZoneMetric::Power => ("custom-power", "Custom Power", ZoneAnchor::Ftp),
+            ZoneMetric::Pace => ("custom-pace", "Custom Pace", ZoneAnchor::ThresholdPace),
+        };
+
+        let mut bounds: Vec<Decimal> = percentages.iter().map(|p| p / dec!(100)).collect();
+        bounds.sort();
+        bounds.dedup();
+        if bounds.is_empty() || bounds[0] <= Decimal::ZERO {
+            return None;
+        }
+
+        // Zone n spans the n-1th to nth boundary; pace zones run slowest first
+        let mut edges = vec![Decimal::ZERO];
+        edges.extend(bounds);
+        let mut zones: Vec<ZoneDefinition> = (0..edges.len())
+            .map(|i| ZoneDefinition {
+                name: format!("Zone {}", i + 1),
+                min: edges[i],
+                max: edges.get(i + 1).copied(),
+            })
+            .collect();
+        if metric == ZoneMetric::Pace {
+            zones.reverse();
+            for (i, zone) in zones.iter_mut().enumerate() {
+                zone.name = format!("Zone {}", i + 1);
+            }
+        }
+
+        let model = Self {
+            id: id.to_string(),
+            name: name.to_string(),
+            metric,
+            anchor,
+            zones,
+        };
+        model.validate().ok().map(|_| model)
+    }
+
+    /// Check that zones exist, are ordered easiest to hardest and leave no gaps
+    pub fn validate(&self) -> Result<()> {
+        if self.zones.is_empty() {
+            return Err(anyhow!(ZoneError::CalculationError(format!("Zone model {} has no zones", self.id))));
+        }
+        if self.zones.len() > u8::MAX as usize {
+            return Err(anyhow!(ZoneError::CalculationError(format!("Zone model {} has too many zones", self.id))));
+        }
+
+        // Power and heart rate zones climb upwards; pace zones get faster downwards
+        let descending = self.metric == ZoneMetric::Pace;
+        for pair in self.zones.windows(2) {
+            let (easier, harder) = (&pair[0], &pair[1]);
+            let contiguous = if descending {
+                harder.max == Some(easier.min)
+            } else {
+                easier.max == Some(harder.min)
+            };
+            if !contiguous {
+                return Err(anyhow!(ZoneError::CalculationError(format!(
+                    "Zones \"{}\" and \"{}\" of {} are not contiguous",
+                    easier.name, harder.name, self.id
+                ))));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Thresholds zone models are resolved against
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ZoneThresholds {
+    pub ftp: Option<u16>,
+    pub lthr: Option<u16>,
+    pub max_hr: Option<u16>,
+    pub threshold_pace: Option<Decimal>,
+}
+
+impl ZoneThresholds {
+    pub fn from_profile(profile: &AthleteProfile) -> Self {
+        Self {
+            ftp: profile.ftp,
+            lthr: profile.lthr,
+            max_hr: profile.max_hr,
+            threshold_pace: profile.threshold_pace,
+        }
+    }
+
+    pub fn value(&self, anchor: ZoneAnchor) -> Option<Decimal> {
+        match anchor {
+            ZoneAnchor::Ftp => self.ftp.map(Decimal::from),
+            ZoneAnchor::Lthr => self.lthr.map(Decimal::from),
+            ZoneAnchor::MaxHr => self.max_hr.map(Decimal::from),
+            ZoneAnchor::ThresholdPace => self.threshold_pace,
+        }
+    }
+}
+
+/// Zone model resolved to absolute boundaries
+#[derive(Debug, Clone)]
+pub struct ResolvedZones {
+    pub model_id: String,
+    pub model_name: String,
+    pub metric: ZoneMetric,
+    pub boundaries: Vec<ZoneBoundary>,
+}
+
+impl ResolvedZones {
+    pub fn len(&self) -> usize {
+        self.boundaries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.boundaries.is_empty()
+    }
+
+    /// Zone containing a value (`min` inclusive, `max` exclusive)
+    pub fn zone_of(&self, value: Decimal) -> Option<&ZoneBoundary> {
+        self.boundaries
+            .iter()
+            .find(|b| value >= b.min && b.max.is_none_or(|max| value < max))
+    }
+
+    fn from_maxima(model_id: &str, metric: ZoneMetric, maxima: &[u16]) -> Self {
+        // The fixed structs store inclusive whole-number maxima; the last zone is open
+        let mut min = Decimal::ZERO;
+        let boundaries = maxima
+            .iter()
+            .enumerate()
+            .map(|(i, &max)| {
+                let upper = (i + 1 < maxima.len()).then(|| Decimal::from(max) + Decimal::ONE);
+                let boundary = ZoneBoundary {
+                    zone: i as u8 + 1,
+                    name: format!("Zone {}", i + 1),
+                    min,
+                    max: upper,
+                    color: None,
+                };
+                min = upper.unwrap_or(min);
+                boundary
+            })
+            .collect();
+
+        Self {
+            model_id: model_id.to_string(),
+            model_name: model_id.to_string(),
+            metric,
+            boundaries,
+        }
+    }
+}
+
+impl From<&HeartRateZones> for ResolvedZones {
+    fn from(zones: &HeartRateZones) -> Self {
+        Self::from_maxima(
+            "heart-rate-5",
+            ZoneMetric::HeartRate,
+            &[zones.zone1_max, zones.zone2_max, zones.zone3_max, zones.zone4_max, zones.zone5_max],
+        )
+    }
+}
+
+impl From<&PowerZones> for ResolvedZones {
+    fn from(zones: &PowerZones) -> Self {
+        Self::from_maxima(
+            "power-7",
+            ZoneMetric::Power,
+            &[
+                zones.zone1_max,
+                zones.zone2_max,
+                zones.zone3_max,
+                zones.zone4_max,
+                zones.zone5_max,
+                zones.zone6_max,
+                zones.zone7_max,
+            ],
+        )
+    }
+}
+
 fn calculate_percentage(count: u32, total: u32) -> Decimal {
     if total == 0 {
         Decimal::ZERO
@@ -462,6 +1019,8 @@ mod tests {
             preferred_units: Units::Metric,
             created_at: now,
             updated_at: now,
+            rowing_ftp: None,
+            css_pace: None,
         }
     }
 
@@ -955,4 +1514,129 @@ mod tests {
         assert!(distribution.zone1_percent + distribution.zone2_percent + distribution.zone3_percent
                 + distribution.zone4_percent + distribution.zone5_percent >= dec!(0.0));
     }
+
+    #[test]
+    fn test_builtin_zone_models_resolve() {
+        let thresholds = ZoneThresholds::from_profile(&create_test_profile());
+
+        for model in ZoneModel::builtins() {
+            model.validate().unwrap();
+            let zones = ZoneCalculator::calculate_zones(&thresholds, &model).unwrap();
+            assert_eq!(zones.len(), model.zones.len());
+        }
+
+        let coggan = ZoneCalculator::calculate_zones(&thresholds, &ZoneModel::coggan_power()).unwrap();
+        assert_eq!(ZoneCalculator::get_zone(dec!(137), &coggan), Some(1));
+        assert_eq!(ZoneCalculator::get_zone(dec!(138), &coggan), Some(2)); // 55% of 250 rounds to 138
+        assert_eq!(ZoneCalculator::get_zone(dec!(250), &coggan), Some(4));
+        assert_eq!(ZoneCalculator::get_zone(dec!(1200), &coggan), Some(7));
+
+        let seiler = ZoneCalculator::calculate_zones(&thresholds, &ZoneModel::seiler_hr()).unwrap();
+        assert_eq!(seiler.boundaries[1].min, dec!(148)); // 90% of 165 LTHR, rounded half to even
+        assert_eq!(ZoneCalculator::get_zone(dec!(165), &seiler), Some(3));
+
+        // Pace zones get faster towards the hardest zone
+        let pace = ZoneCalculator::calculate_zones(&thresholds, &ZoneModel::friel_run_pace()).unwrap();
+        assert_eq!(ZoneCalculator::get_zone(dec!(8.0), &pace), Some(1));
+        assert_eq!(ZoneCalculator::get_zone(dec!(6.0), &pace), Some(5));
+        assert_eq!(ZoneCalculator::get_zone(dec!(5.0), &pace), Some(7));
+    }
+
+    #[test]
+    fn test_zone_model_selection_per_sport() {
+        let mut thresholds = ZoneThresholds::from_profile(&create_test_profile());
+        let custom = ZoneModel {
+            id: "two-zone".to_string(),
+            name: "Easy/Hard".to_string(),
+            metric: ZoneMetric::HeartRate,
+            anchor: ZoneAnchor::Lthr,
+            zones: vec![
+                ZoneDefinition { name: "Easy".to_string(), min: dec!(0), max: Some(dec!(0.9)) },
+                ZoneDefinition { name: "Hard".to_string(), min: dec!(0.9), max: None },
+            ],
+        };
+
+        let selected = ZoneCalculator::zones_for_sport(
+            &thresholds,
+            &Sport::Running,
+            ZoneMetric::HeartRate,
+            Some("friel-run-pace, two-zone"),
+            &ZoneSettings { custom_models: vec![custom.clone()], ..ZoneSettings::default() },
+        )
+        .unwrap();
+        assert_eq!(selected.model_id, "two-zone");
+
+        let pace = ZoneCalculator::zones_for_sport(&thresholds, &Sport::Running, ZoneMetric::Pace, None, &ZoneSettings::default()).unwrap();
+        assert_eq!(pace.model_id, "friel-run-pace");
+
+        thresholds.lthr = None;
+        let hr = ZoneCalculator::zones_for_sport(&thresholds, &Sport::Cycling, ZoneMetric::HeartRate, None, &ZoneSettings::default()).unwrap();
+        assert_eq!(hr.model_id, "max-hr");
+
+        assert!(ZoneCalculator::zones_for_sport(&thresholds, &Sport::Cycling, ZoneMetric::Power, Some("nope"), &ZoneSettings::default()).is_err());
+
+        let mut gap = custom;
+        gap.zones[1].min = dec!(0.95);
+        assert!(gap.validate().is_err());
+    }
+
+    #[test]
+    fn test_default_model_follows_zone_settings() {
+        let thresholds = ZoneThresholds::from_profile(&create_test_profile());
+        let settings = ZoneSettings {
+            hr_zone_method: HRZoneSetting::MaxHR,
+            power_zone_method: PowerZoneSetting::Custom,
+            custom_power_zones: Some(vec![dec!(60), dec!(85), dec!(105)]),
+            pace_zone_method: PaceZoneSetting::Custom,
+            custom_pace_zones: Some(vec![dec!(110), dec!(100)]),
+            ..ZoneSettings::default()
+        };
+
+        let hr = ZoneCalculator::zones_for_sport(&thresholds, &Sport::Cycling, ZoneMetric::HeartRate, None, &settings).unwrap();
+        assert_eq!(hr.model_id, "max-hr");
+
+        let power = ZoneCalculator::zones_for_sport(&thresholds, &Sport::Cycling, ZoneMetric::Power, None, &settings).unwrap();
+        assert_eq!(power.model_id, "custom-power");
+        assert_eq!(power.len(), 4);
+        assert_eq!(power.boundaries[1].min, dec!(150));
+        assert_eq!(power.boundaries[3].max, None);
+
+        let pace = ZoneCalculator::zones_for_sport(&thresholds, &Sport::Running, ZoneMetric::Pace, None, &settings).unwrap();
+        assert_eq!(pace.model_id, "custom-pace");
+        assert_eq!(pace.len(), 3);
+        assert_eq!(pace.boundaries[0].max, None);
+
+        // Selected models still win over the configured method
+        let selected =
+            ZoneCalculator::zones_for_sport(&thresholds, &Sport::Cycling, ZoneMetric::HeartRate, Some("seiler-hr"), &settings).unwrap();
+        assert_eq!(selected.model_id, "seiler-hr");
+
+        // Custom method without usable percentages keeps the standard model
+        let unusable = ZoneSettings {
+            power_zone_method: PowerZoneSetting::Custom,
+            custom_power_zones: Some(vec![dec!(0)]),
+            ..ZoneSettings::default()
+        };
+        let power = ZoneCalculator::zones_for_sport(&thresholds, &Sport::Cycling, ZoneMetric::Power, None, &unusable).unwrap();
+        assert_eq!(power.model_id, "coggan-power");
+    }
+
+    #[test]
+    fn test_generic_distribution_matches_fixed_zones() {
+        let profile = create_test_profile();
+        let hr_zones = ZoneCalculator::hr_zones_from_lthr(&profile).unwrap();
+        let hr_data = vec![120, 134, 135, 147, 150, 160, 180, 190];
+
+        let fixed = ZoneAnalyzer::analyze_hr_distribution(&hr_data, &hr_zones);
+        assert_eq!(fixed.zone1_percent, dec!(25)); // 120 and 134 (inclusive max)
+        assert_eq!(fixed.zone5_percent, dec!(25));
+
+        let model = ZoneCalculator::calculate_zones(&ZoneThresholds::from_profile(&profile), &ZoneModel::friel_hr()).unwrap();
+        let values: Vec<Decimal> = hr_data.iter().map(|&hr| Decimal::from(hr)).collect();
+        let generic = ZoneAnalyzer::analyze_distribution(&values, &model);
+        assert_eq!(generic.zones.len(), 7);
+        assert_eq!(generic.zones.iter().map(|z| z.points).sum::<u32>(), 8);
+        assert_eq!(generic.zones[6].name, "Anaerobic Capacity");
+        assert_eq!(generic.zones[6].points, 2); // 180 and 190 above 107% LTHR
+    }
 }
@@ -38,6 +38,11 @@ fn create_test_workout(id: &str, date: NaiveDate) -> Workout {
         notes: Some("Test workout".to_string()),
         source: None,
         raw_data: None,
+        swim_lengths: Vec::new(),
+        start_time: None,
+        laps: Vec::new(),
+        rpe: None,
+        strength_sets: Vec::new(),
     }
 }
 
@@ -414,6 +414,11 @@ fn test_validation_accuracy_with_mocked_data() {
         notes: None,
         source: None,
         raw_data: None,
+        swim_lengths: Vec::new(),
+        start_time: None,
+        laps: Vec::new(),
+        rpe: None,
+        strength_sets: Vec::new(),
     };
 
     // Validate it passes checks
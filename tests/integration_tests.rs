@@ -25,6 +25,8 @@ mod integration_tests {
             preferred_units: Units::Metric,
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            rowing_ftp: None,
+            css_pace: None,
         }
     }
 
@@ -50,6 +52,9 @@ mod integration_tests {
                 stroke_type: None,
                 lap_number: None,
                 sport_transition: None,
+                temperature: None,
+                drive_length: None,
+                stroke_work: None,
             });
         }
 
@@ -77,6 +82,11 @@ mod integration_tests {
             notes: Some("Test workout".to_string()),
             source: None,
             raw_data: Some(raw_data),
+            swim_lengths: Vec::new(),
+            start_time: None,
+            laps: Vec::new(),
+            rpe: None,
+            strength_sets: Vec::new(),
         }
     }
 
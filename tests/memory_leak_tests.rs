@@ -82,6 +82,11 @@ fn create_test_workout(seed: usize) -> Workout {
         notes: Some(format!("Memory leak test workout {}", seed)),
         source: None,
         raw_data: None,
+        swim_lengths: Vec::new(),
+        start_time: None,
+        laps: Vec::new(),
+        rpe: None,
+        strength_sets: Vec::new(),
     }
 }
 
@@ -103,6 +108,8 @@ fn create_test_athlete() -> AthleteProfile {
         preferred_units: trainrs::models::Units::Metric,
         created_at: Utc::now(),
         updated_at: Utc::now(),
+        rowing_ftp: None,
+        css_pace: None,
     }
 }
 
@@ -297,6 +304,9 @@ fn test_large_workout_data_memory() {
             stroke_type: None,
             lap_number: None,
             sport_transition: None,
+            temperature: None,
+            drive_length: None,
+            stroke_work: None,
         });
     }
 
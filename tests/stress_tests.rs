@@ -40,6 +40,9 @@ fn create_workout_with_datapoints(id: usize, data_point_count: usize) -> Workout
             stroke_type: None,
             lap_number: None,
             sport_transition: None,
+            temperature: None,
+            drive_length: None,
+            stroke_work: None,
         });
     }
 
@@ -67,6 +70,11 @@ fn create_workout_with_datapoints(id: usize, data_point_count: usize) -> Workout
         notes: Some(format!("Stress test workout {} with {} data points", id, data_point_count)),
         source: None,
         raw_data: Some(data_points),
+        swim_lengths: Vec::new(),
+        start_time: None,
+        laps: Vec::new(),
+        rpe: None,
+        strength_sets: Vec::new(),
     }
 }
 
@@ -229,6 +237,11 @@ fn test_workout_with_missing_fields() {
         notes: None,
         source: None,
         raw_data: None,
+        swim_lengths: Vec::new(),
+        start_time: None,
+        laps: Vec::new(),
+        rpe: None,
+        strength_sets: Vec::new(),
     };
 
     // Should not panic with missing fields
@@ -266,6 +279,11 @@ fn test_empty_workout() {
         notes: None,
         source: None,
         raw_data: Some(vec![]), // Empty data
+        swim_lengths: Vec::new(),
+        start_time: None,
+        laps: Vec::new(),
+        rpe: None,
+        strength_sets: Vec::new(),
     };
 
     assert_eq!(workout.duration_seconds, 0);
@@ -301,6 +319,11 @@ fn test_invalid_string_data() {
         notes: Some("Notes with émojis 🚴 and spëcial çharacters".to_string()),
         source: None,
         raw_data: None,
+        swim_lengths: Vec::new(),
+        start_time: None,
+        laps: Vec::new(),
+        rpe: None,
+        strength_sets: Vec::new(),
     };
 
     // Should not panic with special characters